@@ -0,0 +1,146 @@
+use super::{BinRead, VecArgs};
+use crate::{
+    __private::Required,
+    io::{self, Cursor, Seek},
+    meta::ReadEndian,
+    BinResult, Endian, Error,
+};
+#[cfg(not(feature = "std"))]
+use alloc::boxed::Box;
+
+/// The `BinReadBorrowed` trait reads objects which borrow data directly from
+/// an in-memory byte slice instead of copying it.
+///
+/// Every [`BinRead`] type also implements `BinReadBorrowed`, so borrowing
+/// types can contain any other readable type. Borrowed byte slices and
+/// strings are read using the [`count`] directive to give their length in
+/// bytes.
+///
+/// [`count`]: crate::docs::attribute#count
+///
+/// # Derivable
+///
+/// This trait is derived instead of [`BinRead`] when the [`borrow`] directive
+/// is used on a struct.
+///
+/// [`borrow`]: crate::docs::attribute#borrow
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, BinReadBorrowed};
+/// #[derive(BinRead)]
+/// #[br(big, borrow = 'a)]
+/// struct Packet<'a> {
+///     len: u8,
+///     #[br(count = len)]
+///     payload: &'a [u8],
+/// }
+///
+/// let data = b"\x02\x01\x02";
+/// let packet = Packet::read_borrowed(data).unwrap();
+/// assert_eq!(packet.payload, [1, 2]);
+/// ```
+pub trait BinReadBorrowed<'data>: Sized {
+    /// The type used for the `args` parameter of [`read_borrowed_args()`] and
+    /// [`read_borrowed_options()`].
+    ///
+    /// [`read_borrowed_args()`]: Self::read_borrowed_args
+    /// [`read_borrowed_options()`]: Self::read_borrowed_options
+    type Args<'a>;
+
+    /// Read `Self` from the byte slice using default arguments.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    #[inline]
+    fn read_borrowed(data: &'data [u8]) -> BinResult<Self>
+    where
+        Self: ReadEndian,
+        for<'a> Self::Args<'a>: Required,
+    {
+        Self::read_borrowed_args(data, Self::Args::args())
+    }
+
+    /// Read `Self` from the byte slice using the given arguments.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    #[inline]
+    fn read_borrowed_args(data: &'data [u8], args: Self::Args<'_>) -> BinResult<Self>
+    where
+        Self: ReadEndian,
+    {
+        Self::read_borrowed_options(&mut Cursor::new(data), Endian::Little, args)
+    }
+
+    /// Read `Self` from the cursor using the given [`Endian`] and arguments.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    fn read_borrowed_options(
+        reader: &mut Cursor<&'data [u8]>,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self>;
+}
+
+impl<'data, T: BinRead> BinReadBorrowed<'data> for T {
+    type Args<'a> = T::Args<'a>;
+
+    #[inline]
+    fn read_borrowed_options(
+        reader: &mut Cursor<&'data [u8]>,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        T::read_options(reader, endian, args)
+    }
+}
+
+impl<'data> BinReadBorrowed<'data> for &'data [u8] {
+    type Args<'a> = VecArgs<()>;
+
+    fn read_borrowed_options(
+        reader: &mut Cursor<&'data [u8]>,
+        _: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let data = *reader.get_ref();
+        let start = usize::try_from(reader.position()).unwrap_or(usize::MAX);
+        let bytes = start
+            .checked_add(args.count)
+            .and_then(|end| data.get(start..end))
+            .ok_or_else(|| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    "not enough bytes in reader",
+                ))
+            })?;
+        reader.set_position(reader.position() + bytes.len() as u64);
+        Ok(bytes)
+    }
+}
+
+impl<'data> BinReadBorrowed<'data> for &'data str {
+    type Args<'a> = VecArgs<()>;
+
+    fn read_borrowed_options(
+        reader: &mut Cursor<&'data [u8]>,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let pos = reader.stream_position()?;
+        let bytes = <&[u8]>::read_borrowed_options(reader, endian, args)?;
+        core::str::from_utf8(bytes).map_err(|err| {
+            reader.set_position(pos);
+            Error::Custom {
+                pos,
+                err: Box::new(err),
+            }
+        })
+    }
+}
@@ -1,11 +1,15 @@
+mod borrowed;
 mod impls;
 
 use crate::{
-    io::{Read, Seek},
+    io::{Read, Seek, SeekFrom},
     BinResult, Endian,
-    __private::Required,
+    __private::{restore_position, Required},
     meta::ReadEndian,
 };
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+pub use borrowed::BinReadBorrowed;
 pub use impls::VecArgs;
 
 /// The `BinRead` trait reads data from streams and converts it into objects.
@@ -318,6 +322,146 @@ pub trait BinReaderExt: Read + Seek + Sized {
     {
         self.read_type_args(Endian::NATIVE, args)
     }
+
+    /// Read `T` from the reader using default arguments, then restore the
+    /// position of the reader.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned
+    /// and the position of the reader is also restored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binrw::{BinRead, BinReaderExt, io::Cursor};
+    ///
+    /// #[derive(BinRead)]
+    /// #[br(big, magic = b"PNG")]
+    /// struct Png;
+    ///
+    /// let mut reader = Cursor::new(b"PNG\x01");
+    /// if reader.peek::<Png>().is_ok() {
+    ///     // The magic is still there
+    ///     assert_eq!(reader.read_be::<[u8; 3]>().unwrap(), *b"PNG");
+    /// }
+    /// ```
+    #[inline]
+    fn peek<'a, T>(&mut self) -> BinResult<T>
+    where
+        T: BinRead + ReadEndian,
+        T::Args<'a>: Required,
+    {
+        self.peek_type(Endian::Little)
+    }
+
+    /// Read `T` from the reader with the given byte order, then restore the
+    /// position of the reader.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned
+    /// and the position of the reader is also restored.
+    #[inline]
+    fn peek_type<'a, T>(&mut self, endian: Endian) -> BinResult<T>
+    where
+        T: BinRead,
+        T::Args<'a>: Required,
+    {
+        self.peek_type_args(endian, T::Args::args())
+    }
+
+    /// Read `T` from the reader assuming big-endian byte order, then restore
+    /// the position of the reader.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned
+    /// and the position of the reader is also restored.
+    #[inline]
+    fn peek_be<'a, T>(&mut self) -> BinResult<T>
+    where
+        T: BinRead,
+        T::Args<'a>: Required,
+    {
+        self.peek_type(Endian::Big)
+    }
+
+    /// Read `T` from the reader assuming little-endian byte order, then
+    /// restore the position of the reader.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned
+    /// and the position of the reader is also restored.
+    #[inline]
+    fn peek_le<'a, T>(&mut self) -> BinResult<T>
+    where
+        T: BinRead,
+        T::Args<'a>: Required,
+    {
+        self.peek_type(Endian::Little)
+    }
+
+    /// Read `T` from the reader assuming native-endian byte order, then
+    /// restore the position of the reader.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned
+    /// and the position of the reader is also restored.
+    #[inline]
+    fn peek_ne<'a, T>(&mut self) -> BinResult<T>
+    where
+        T: BinRead,
+        T::Args<'a>: Required,
+    {
+        self.peek_type(Endian::NATIVE)
+    }
+
+    /// Read `T` from the reader with the given byte order and arguments, then
+    /// restore the position of the reader.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned
+    /// and the position of the reader is also restored.
+    fn peek_type_args<T>(&mut self, endian: Endian, args: T::Args<'_>) -> BinResult<T>
+    where
+        T: BinRead,
+    {
+        let pos = self.stream_position()?;
+        let value = T::read_options(self, endian, args).or_else(restore_position(self, pos))?;
+        self.seek(SeekFrom::Start(pos))?;
+        Ok(value)
+    }
+
+    /// Read the next `count` bytes from the reader, then restore the position
+    /// of the reader.
+    ///
+    /// # Errors
+    ///
+    /// If there are fewer than `count` bytes remaining, or reading fails, an
+    /// [`Error`](crate::Error) variant will be returned and the position of
+    /// the reader is also restored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binrw::{BinReaderExt, io::Cursor};
+    ///
+    /// let mut reader = Cursor::new(b"RIFF\x04\0\0\0");
+    /// assert_eq!(reader.peek_bytes(4).unwrap(), b"RIFF");
+    /// assert_eq!(reader.position(), 0);
+    /// ```
+    fn peek_bytes(&mut self, count: usize) -> BinResult<Vec<u8>> {
+        let pos = self.stream_position()?;
+        let mut bytes = alloc::vec![0; count];
+        self.read_exact(&mut bytes)
+            .or_else(restore_position(self, pos))?;
+        self.seek(SeekFrom::Start(pos))?;
+        Ok(bytes)
+    }
 }
 
 impl<R: Read + Seek + Sized> BinReaderExt for R {}
@@ -0,0 +1,441 @@
+//! Type definitions and helpers for RIFF/IFF-style chunked data.
+//!
+//! Container formats like WAV, AVI, and AIFF are made of chunks which each
+//! start with a four-character code identifying the chunk, followed by the size
+//! of the chunk data, the data itself, and a padding byte if the size is odd.
+//! This module provides a generic [`Chunk`], a [`ChunkIter`] over the chunks in
+//! a region of a stream, and the [`read_chunk`] and [`write_chunk`] helpers for
+//! reading and writing a chunk around any other type.
+
+use crate::{
+    helpers::count_bytes,
+    io::{Read, Seek, SeekFrom, Window, Write},
+    BinRead, BinResult, BinWrite, Endian, Error,
+};
+#[cfg(not(feature = "std"))]
+use alloc::{boxed::Box, vec::Vec};
+use core::{fmt, marker::PhantomData};
+
+/// A four-character code identifying a chunk.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor};
+/// use binrw::chunk::FourCC;
+///
+/// let id = FourCC::read(&mut Cursor::new(b"RIFF")).unwrap();
+/// assert_eq!(id, *b"RIFF");
+/// assert_eq!(id.to_string(), "RIFF");
+/// ```
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct FourCC(pub [u8; 4]);
+
+impl BinRead for FourCC {
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        _: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let mut id = [0; 4];
+        reader.read_exact(&mut id)?;
+        Ok(Self(id))
+    }
+}
+
+impl BinWrite for FourCC {
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        _: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        writer.write_all(&self.0)?;
+        Ok(())
+    }
+}
+
+impl From<[u8; 4]> for FourCC {
+    fn from(id: [u8; 4]) -> Self {
+        Self(id)
+    }
+}
+
+impl PartialEq<[u8; 4]> for FourCC {
+    fn eq(&self, other: &[u8; 4]) -> bool {
+        self.0 == *other
+    }
+}
+
+impl fmt::Display for FourCC {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.escape_ascii())
+    }
+}
+
+impl fmt::Debug for FourCC {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FourCC(\"{}\")", self.0.escape_ascii())
+    }
+}
+
+/// A chunk with a size of type `Size`, and its data.
+///
+/// If `PAD_EVEN` is true, which is the default, chunks with an odd size are
+/// followed by a padding byte which is not included in the size, as in RIFF
+/// and IFF files.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor};
+/// use binrw::chunk::Chunk;
+/// # use binrw::chunk::FourCC;
+///
+/// #[derive(BinRead)]
+/// #[br(little)]
+/// struct Riff {
+///     header: Chunk,
+///     next: Chunk,
+/// }
+///
+/// # let x = Riff::read(&mut Cursor::new(b"abcd\x03\0\0\0xyz\0efgh\0\0\0\0")).unwrap();
+/// # assert_eq!((x.header.id, x.header.size, &x.header.data[..]), (FourCC(*b"abcd"), 3, &b"xyz"[..]));
+/// # assert_eq!(x.next.id, *b"efgh");
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Chunk<Size = u32, const PAD_EVEN: bool = true> {
+    /// The four-character code identifying the chunk.
+    pub id: FourCC,
+
+    /// The size of the chunk data, in bytes.
+    pub size: Size,
+
+    /// The raw chunk data.
+    pub data: Vec<u8>,
+}
+
+impl<Size, const PAD_EVEN: bool> Chunk<Size, PAD_EVEN> {
+    /// Creates a new chunk with the given `id` and `data`, and a size matching
+    /// the data.
+    ///
+    /// # Errors
+    ///
+    /// If the length of the data does not fit in `Size`, the conversion error
+    /// is returned.
+    pub fn new(
+        id: impl Into<FourCC>,
+        data: Vec<u8>,
+    ) -> Result<Self, <Size as TryFrom<usize>>::Error>
+    where
+        Size: TryFrom<usize>,
+    {
+        Ok(Self {
+            id: id.into(),
+            size: Size::try_from(data.len())?,
+            data,
+        })
+    }
+
+    /// Returns the number of padding bytes which follow the chunk data.
+    fn padding(&self) -> u64 {
+        padding(PAD_EVEN, self.data.len() as u64)
+    }
+}
+
+impl<Size, const PAD_EVEN: bool> BinRead for Chunk<Size, PAD_EVEN>
+where
+    Size: for<'a> BinRead<Args<'a> = ()> + Copy + TryInto<usize>,
+{
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let id = FourCC::read_options(reader, endian, ())?;
+        let pos = reader.stream_position()?;
+        let size = Size::read_options(reader, endian, ())?;
+        let len = size.try_into().map_err(|_| Error::AssertFail {
+            pos,
+            message: "chunk size does not fit in a usize".into(),
+        })?;
+        let chunk = Self {
+            id,
+            size,
+            data: count_bytes(len)(reader, endian, ())?,
+        };
+        // The padding byte is skipped rather than read, since it is often
+        // missing after the last chunk of a file.
+        if chunk.padding() != 0 {
+            reader.seek(SeekFrom::Current(1))?;
+        }
+        Ok(chunk)
+    }
+}
+
+impl<Size, const PAD_EVEN: bool> BinWrite for Chunk<Size, PAD_EVEN>
+where
+    Size: for<'a> BinWrite<Args<'a> = ()>,
+{
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.id.write_options(writer, endian, ())?;
+        self.size.write_options(writer, endian, ())?;
+        writer.write_all(&self.data)?;
+        crate::__private::write_padding(writer, self.padding(), 0)
+    }
+}
+
+/// An iterator over the [`Chunk`]s in a region of a stream.
+///
+/// The iterator stops at the end of the region, or after the first error. A
+/// chunk which extends past the end of the region is an error, except that
+/// the padding byte of the last chunk may be missing.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{io::Cursor, Endian};
+/// use binrw::chunk::ChunkIter;
+///
+/// let mut data = Cursor::new(b"fmt \x01\0\0\0a\0data\x02\0\0\0bc");
+/// let ids = ChunkIter::<_>::new(&mut data, Endian::Little, 20)
+///     .unwrap()
+///     .map(|chunk| chunk.map(|chunk| chunk.id.to_string()))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(ids, ["fmt ", "data"]);
+/// ```
+#[derive(Debug)]
+pub struct ChunkIter<'r, R, Size = u32, const PAD_EVEN: bool = true> {
+    reader: &'r mut R,
+    endian: Endian,
+    end: u64,
+    done: bool,
+    _marker: PhantomData<fn() -> Size>,
+}
+
+impl<'r, R: Seek, Size, const PAD_EVEN: bool> ChunkIter<'r, R, Size, PAD_EVEN> {
+    /// Creates a new iterator over the chunks in the next `len` bytes of
+    /// `reader`.
+    ///
+    /// # Errors
+    ///
+    /// If getting the position of the reader fails, or the end of the region
+    /// overflows, an [`Error`] variant will be returned.
+    pub fn new(reader: &'r mut R, endian: Endian, len: u64) -> BinResult<Self> {
+        let pos = reader.stream_position()?;
+        let end = pos.checked_add(len).ok_or_else(|| Error::AssertFail {
+            pos,
+            message: "the end of the chunk region overflows".into(),
+        })?;
+        Ok(Self {
+            reader,
+            endian,
+            end,
+            done: false,
+            _marker: PhantomData,
+        })
+    }
+
+    fn read_next(&mut self) -> Option<BinResult<Chunk<Size, PAD_EVEN>>>
+    where
+        R: Read,
+        Size: for<'a> BinRead<Args<'a> = ()> + Copy + TryInto<usize>,
+    {
+        let pos = match self.reader.stream_position() {
+            Ok(pos) => pos,
+            Err(err) => return Some(Err(err.into())),
+        };
+        if pos >= self.end {
+            return None;
+        }
+
+        Some(
+            Chunk::read_options(self.reader, self.endian, ()).and_then(|chunk| {
+                if self.reader.stream_position()? - chunk.padding() > self.end {
+                    Err(Error::AssertFail {
+                        pos,
+                        message: alloc::format!(
+                            "chunk {} extends past the end of the region",
+                            chunk.id
+                        ),
+                    })
+                } else {
+                    Ok(chunk)
+                }
+            }),
+        )
+    }
+}
+
+impl<R, Size, const PAD_EVEN: bool> Iterator for ChunkIter<'_, R, Size, PAD_EVEN>
+where
+    R: Read + Seek,
+    Size: for<'a> BinRead<Args<'a> = ()> + Copy + TryInto<usize>,
+{
+    type Item = BinResult<Chunk<Size, PAD_EVEN>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let item = self.read_next();
+        self.done = !matches!(item, Some(Ok(_)));
+        item
+    }
+}
+
+/// Creates a parser that reads a chunk with the given `id`, and parses its data
+/// as a `T`.
+///
+/// The data is parsed from a [`Window`] over the chunk data, so the parser
+/// cannot read past the end of the chunk, and positions inside the chunk are
+/// relative to the start of its data. Any part of the data left unread is
+/// skipped, along with the padding byte if `pad_even` is true and the size is
+/// odd.
+///
+/// # Errors
+///
+/// If the chunk has a different id, or reading fails, an [`Error`] variant
+/// will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor};
+/// use binrw::chunk::{read_chunk, FourCC};
+///
+/// #[derive(BinRead)]
+/// struct Format {
+///     channels: u16,
+///     sample_rate: u32,
+/// }
+///
+/// #[derive(BinRead)]
+/// #[br(little)]
+/// struct Wave {
+///     #[br(parse_with = read_chunk::<u32, _, _, _>(FourCC(*b"fmt "), true))]
+///     format: Format,
+///     tail: u8,
+/// }
+///
+/// # let x = Wave::read(&mut Cursor::new(b"fmt \x08\0\0\0\x02\0\x44\xac\0\0\xff\xff\x01")).unwrap();
+/// # assert_eq!((x.format.channels, x.format.sample_rate, x.tail), (2, 44100, 1));
+/// # let mut x = Cursor::new(b"data\0\0\0\0");
+/// # assert!(read_chunk::<u32, u8, _, _>(FourCC(*b"fmt "), true)(&mut x, binrw::Endian::Little, ()).is_err());
+/// ```
+pub fn read_chunk<Size, T, R, Arg>(
+    id: FourCC,
+    pad_even: bool,
+) -> impl Fn(&mut R, Endian, Arg) -> BinResult<T>
+where
+    Size: for<'a> BinRead<Args<'a> = ()> + Into<u64>,
+    T: for<'a> BinRead<Args<'a> = Arg>,
+    R: Read + Seek,
+{
+    move |reader, endian, args| {
+        let pos = reader.stream_position()?;
+        let found = FourCC::read_options(reader, endian, ())?;
+        if found != id {
+            return Err(Error::BadMagic {
+                pos,
+                found: Box::new(found),
+            });
+        }
+
+        let size = Size::read_options(reader, endian, ())?.into();
+        let start = reader.stream_position()?;
+        let value = T::read_options(&mut Window::new(&mut *reader, start, size)?, endian, args)?;
+        reader.seek(SeekFrom::Start(start + size + padding(pad_even, size)))?;
+        Ok(value)
+    }
+}
+
+/// Creates a writer that writes a value as the data of a chunk with the given
+/// `id`.
+///
+/// The size of the chunk is patched in after the value is written, so the
+/// value does not need to know its own size, and a padding byte is written if
+/// `pad_even` is true and the size is odd.
+///
+/// # Errors
+///
+/// If writing or seeking fails, or the size of the value does not fit in
+/// `Size`, an [`Error`] variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor};
+/// use binrw::chunk::{write_chunk, FourCC};
+///
+/// #[derive(BinWrite)]
+/// struct Format {
+///     channels: u16,
+///     sample_rate: u32,
+/// }
+///
+/// #[derive(BinWrite)]
+/// #[bw(little)]
+/// struct Wave {
+///     #[bw(write_with = write_chunk::<u32, _, _, _>(FourCC(*b"fmt "), true))]
+///     format: Format,
+///     #[bw(write_with = write_chunk::<u32, _, _, _>(FourCC(*b"data"), true))]
+///     data: [u8; 3],
+/// }
+///
+/// # let mut x = Cursor::new(Vec::new());
+/// # Wave { format: Format { channels: 2, sample_rate: 44100 }, data: *b"abc" }.write(&mut x).unwrap();
+/// # assert_eq!(x.into_inner(), b"fmt \x06\0\0\0\x02\0\x44\xac\0\0data\x03\0\0\0abc\0");
+/// ```
+pub fn write_chunk<Size, T, W, Arg>(
+    id: FourCC,
+    pad_even: bool,
+) -> impl Fn(&T, &mut W, Endian, Arg) -> BinResult<()>
+where
+    Size: for<'a> BinWrite<Args<'a> = ()> + TryFrom<u64>,
+    T: for<'a> BinWrite<Args<'a> = Arg>,
+    W: Write + Seek,
+{
+    move |value, writer, endian, args| {
+        id.write_options(writer, endian, ())?;
+        let size_pos = writer.stream_position()?;
+        chunk_size::<Size>(0, size_pos)?.write_options(writer, endian, ())?;
+        let start = writer.stream_position()?;
+        value.write_options(writer, endian, args)?;
+        let end = writer.stream_position()?;
+
+        writer.seek(SeekFrom::Start(size_pos))?;
+        chunk_size::<Size>(end - start, size_pos)?.write_options(writer, endian, ())?;
+        writer.seek(SeekFrom::Start(end))?;
+        crate::__private::write_padding(writer, padding(pad_even, end - start), 0)
+    }
+}
+
+/// Converts a chunk size to `Size`.
+fn chunk_size<Size: TryFrom<u64>>(size: u64, pos: u64) -> BinResult<Size> {
+    Size::try_from(size).map_err(|_| Error::AssertFail {
+        pos,
+        message: alloc::format!("chunk size {size} does not fit in the size type"),
+    })
+}
+
+/// Returns the number of padding bytes which follow chunk data of the given
+/// size.
+fn padding(pad_even: bool, size: u64) -> u64 {
+    u64::from(pad_even && size % 2 == 1)
+}
@@ -1,17 +1,196 @@
-use super::{ContextExt, CustomError, Error};
+use super::{
+    message_formatter, theme::write_themed, ContextExt, CustomError, Error, MessageFormatter,
+};
 use alloc::borrow::Cow;
 #[cfg(not(feature = "std"))]
 use alloc::{boxed::Box, format, string::ToString, vec::Vec};
-use core::fmt::{self, Write};
+use core::{
+    fmt::{self, Write},
+    sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering},
+};
+
+static COLOR_CHOICE: AtomicU8 = AtomicU8::new(ColorChoice::Auto as u8);
+static MAX_FRAMES: AtomicUsize = AtomicUsize::new(usize::MAX);
+static COLLAPSE_REPEATED: AtomicBool = AtomicBool::new(true);
+
+/// When to use colors and other terminal styles in backtraces.
+///
+/// Backtraces are only ever styled when the `verbose-backtrace` feature is
+/// enabled.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ColorChoice {
+    /// Styles backtraces only if standard error is a terminal.
+    ///
+    /// Styles are also disabled if the `NO_COLOR` environment variable is set
+    /// or `TERM` is `dumb`, and enabled if the `CLICOLOR_FORCE` environment
+    /// variable is set. Without the `std` feature, there is no terminal to
+    /// detect, so backtraces are never styled.
+    #[default]
+    Auto,
+
+    /// Always styles backtraces.
+    Always,
+
+    /// Never styles backtraces.
+    Never,
+}
+
+impl ColorChoice {
+    /// Returns true if backtraces should be styled.
+    fn enabled(self) -> bool {
+        cfg!(feature = "verbose-backtrace")
+            && match self {
+                ColorChoice::Auto => auto_color(),
+                ColorChoice::Always => true,
+                ColorChoice::Never => false,
+            }
+    }
+}
+
+/// Returns when colors and other terminal styles are used in backtraces.
+#[must_use]
+pub fn color_choice() -> ColorChoice {
+    match COLOR_CHOICE.load(Ordering::Relaxed) {
+        1 => ColorChoice::Always,
+        2 => ColorChoice::Never,
+        _ => ColorChoice::Auto,
+    }
+}
+
+/// Sets when colors and other terminal styles are used in backtraces.
+///
+/// This overrides terminal detection and the `NO_COLOR` and `CLICOLOR_FORCE`
+/// environment variables for the whole program.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::error::{set_color_choice, ColorChoice};
+///
+/// // Errors are written to a log file
+/// set_color_choice(ColorChoice::Never);
+/// # assert_eq!(binrw::error::color_choice(), ColorChoice::Never);
+/// ```
+pub fn set_color_choice(choice: ColorChoice) {
+    COLOR_CHOICE.store(choice as u8, Ordering::Relaxed);
+}
+
+/// Options which control how many frames of a backtrace are displayed.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::error::{set_backtrace_options, BacktraceOptions};
+///
+/// // Only show the innermost 9 frames and the outermost frame
+/// set_backtrace_options(BacktraceOptions {
+///     max_frames: Some(10),
+///     ..BacktraceOptions::DEFAULT
+/// });
+/// # assert_eq!(binrw::error::backtrace_options().max_frames, Some(10));
+/// # set_backtrace_options(BacktraceOptions::DEFAULT);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BacktraceOptions {
+    /// The maximum number of frames to display, or `None` to display all of
+    /// them.
+    ///
+    /// When frames are omitted, the innermost frames and the outermost frame
+    /// are displayed, so at least two frames are always shown. Repeated
+    /// frames which were collapsed count as one frame.
+    pub max_frames: Option<usize>,
+
+    /// If true, consecutive frames from the same place in the code are
+    /// collapsed into the first of them along with a count of the repeats.
+    ///
+    /// Frames are considered the same even if the error passed through them
+    /// at different positions, so the frames of recursive types collapse.
+    pub collapse_repeated: bool,
+}
+
+impl BacktraceOptions {
+    /// The default options, which display all frames and collapse repeated
+    /// frames.
+    pub const DEFAULT: Self = Self {
+        max_frames: None,
+        collapse_repeated: true,
+    };
+}
+
+impl Default for BacktraceOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Returns the options used when displaying backtraces.
+#[must_use]
+pub fn backtrace_options() -> BacktraceOptions {
+    BacktraceOptions {
+        max_frames: match MAX_FRAMES.load(Ordering::Relaxed) {
+            usize::MAX => None,
+            max => Some(max),
+        },
+        collapse_repeated: COLLAPSE_REPEATED.load(Ordering::Relaxed),
+    }
+}
+
+/// Sets the options used when displaying backtraces for the whole program.
+pub fn set_backtrace_options(options: BacktraceOptions) {
+    MAX_FRAMES.store(options.max_frames.unwrap_or(usize::MAX), Ordering::Relaxed);
+    COLLAPSE_REPEATED.store(options.collapse_repeated, Ordering::Relaxed);
+}
+
+#[cfg(feature = "std")]
+fn auto_color() -> bool {
+    use is_terminal::IsTerminal;
+
+    let var = |name| std::env::var_os(name).filter(|value| !value.is_empty());
+    if var("NO_COLOR").is_some() {
+        false
+    } else if var("CLICOLOR_FORCE").map_or(false, |value| value != "0") {
+        true
+    } else if var("TERM").map_or(false, |value| value == "dumb") {
+        false
+    } else {
+        std::io::stderr().is_terminal()
+    }
+}
+
+#[cfg(not(feature = "std"))]
+fn auto_color() -> bool {
+    false
+}
+
+/// The terminal styles used when displaying a backtrace.
+#[derive(Clone, Copy)]
+struct Style {
+    color: bool,
+}
 
-#[cfg(feature = "verbose-backtrace")]
-const BOLD_OPEN: &str = "\x1b[1m";
-#[cfg(feature = "verbose-backtrace")]
-const BOLD_CLOSE: &str = "\x1b[22m";
-#[cfg(not(feature = "verbose-backtrace"))]
-const BOLD_OPEN: &str = "";
-#[cfg(not(feature = "verbose-backtrace"))]
-const BOLD_CLOSE: &str = "";
+impl Style {
+    fn new() -> Self {
+        Self {
+            color: color_choice().enabled(),
+        }
+    }
+
+    fn bold_open(self) -> &'static str {
+        if self.color {
+            "\x1b[1m"
+        } else {
+            ""
+        }
+    }
+
+    fn bold_close(self) -> &'static str {
+        if self.color {
+            "\x1b[22m"
+        } else {
+            ""
+        }
+    }
+}
 
 /// An error backtrace.
 #[non_exhaustive]
@@ -26,6 +205,17 @@ pub struct Backtrace {
     ///
     /// The first frame is the innermost frame.
     pub frames: Vec<BacktraceFrame>,
+
+    /// The native Rust backtrace of the program at the point where this
+    /// backtrace was created, which is when context was first added to the
+    /// source error.
+    ///
+    /// This is only captured if it is enabled by the `RUST_BACKTRACE` or
+    /// `RUST_LIB_BACKTRACE` environment variables. See
+    /// [`std::backtrace::Backtrace::capture`] for details.
+    #[cfg(feature = "native-backtrace")]
+    #[cfg_attr(all(doc, nightly), doc(cfg(feature = "native-backtrace")))]
+    pub native: std::backtrace::Backtrace,
 }
 
 impl Backtrace {
@@ -46,31 +236,78 @@ impl Backtrace {
             error => Self {
                 error: Box::new(error),
                 frames,
+                #[cfg(feature = "native-backtrace")]
+                native: std::backtrace::Backtrace::capture(),
             },
         }
     }
 
-    fn fmt_no_bars(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let mut frames = self.frames.iter();
+    /// Returns the frames to display, as the index of each frame along with
+    /// the number of times it is repeated by the frames after it.
+    fn rows(&self, options: BacktraceOptions) -> Vec<(usize, usize)> {
+        let mut rows = Vec::new();
+        let mut index = 0;
+        while let Some(frame) = self.frames.get(index) {
+            let repeats = if options.collapse_repeated {
+                self.frames[index + 1..]
+                    .iter()
+                    .take_while(|other| frame.same_site(other))
+                    .count()
+            } else {
+                0
+            };
+            rows.push((index, repeats));
+            index += repeats + 1;
+        }
+
+        if let Some(max) = options.max_frames {
+            let max = max.max(2);
+            if rows.len() > max {
+                let last = rows.pop();
+                rows.truncate(max - 1);
+                rows.extend(last);
+            }
+        }
+
+        rows
+    }
 
-        if let Some(first_frame) = frames.next() {
+    fn fmt_no_bars(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        style: Style,
+        messages: &dyn MessageFormatter,
+    ) -> fmt::Result {
+        let mut rows = self.rows(backtrace_options()).into_iter();
+        let (bold_open, bold_close) = (style.bold_open(), style.bold_close());
+
+        if let Some((first_index, first_repeats)) = rows.next() {
+            let first_frame = &self.frames[first_index];
             first_frame.display_with_message(
                 f,
                 &format!(
-                    "{BOLD_OPEN}Error: {}{BOLD_CLOSE}\n    {}{BOLD_OPEN}{}{BOLD_CLOSE}",
-                    FirstErrorFmt(&self.error),
+                    "{bold_open}Error: {}{bold_close}\n    {}{bold_open}{}{bold_close}",
+                    FirstErrorFmt(&self.error, style, messages),
                     if matches!(self.error.as_ref(), Error::EnumErrors { .. }) {
                         "..."
                     } else {
                         "       "
                     },
-                    first_frame.message(),
+                    first_frame.located_message(),
                 ),
                 0,
+                style,
             )?;
+            write_repeats(f, first_repeats)?;
 
-            for (i, frame) in frames.enumerate() {
-                frame.display(f, i + 1)?;
+            let mut next_index = first_repeats + 1;
+            for (index, repeats) in rows {
+                if index > next_index {
+                    writeln!(f, "     …{} frames omitted", index - next_index)?;
+                }
+                self.frames[index].display(f, index, style)?;
+                write_repeats(f, repeats)?;
+                next_index = index + repeats + 1;
             }
         }
 
@@ -92,12 +329,25 @@ impl ContextExt for Backtrace {
             message: message.into(),
             file: caller.file(),
             line: caller.line(),
+            pos: None,
         })
     }
 }
 
 impl fmt::Display for Backtrace {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, message_formatter())
+    }
+}
+
+impl Backtrace {
+    pub(super) fn fmt_with(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        messages: &dyn MessageFormatter,
+    ) -> fmt::Result {
+        let style = Style::new();
+
         if cfg!(feature = "verbose-backtrace") {
             writeln!(
                 f,
@@ -105,7 +355,7 @@ impl fmt::Display for Backtrace {
             )?;
         }
 
-        self.fmt_no_bars(f)?;
+        self.fmt_no_bars(f, style, messages)?;
 
         if cfg!(feature = "verbose-backtrace") {
             writeln!(
@@ -135,6 +385,10 @@ pub enum BacktraceFrame {
 
         /// The origin line number.
         line: u32,
+
+        /// The byte position of the reader when the error passed through the
+        /// frame, if it is known.
+        pos: Option<u64>,
     },
 
     /// A message-only frame.
@@ -150,28 +404,46 @@ impl BacktraceFrame {
         f: &mut fmt::Formatter<'_>,
         message: &impl fmt::Display,
         index: usize,
+        style: Style,
     ) -> fmt::Result {
+        let (bold_open, bold_close) = (style.bold_open(), style.bold_close());
         match self {
             BacktraceFrame::Full {
                 code, file, line, ..
             } => {
                 writeln!(
                     f,
-                    " {index}: {BOLD_OPEN}{message}{BOLD_CLOSE}\n     at {file}:{line}",
+                    " {index}: {bold_open}{message}{bold_close}\n     at {file}:{line}",
                 )?;
-                if let Some(code) = code {
-                    write!(f, "{code}")?;
+                match code {
+                    Some(code) if style.color => write_themed(f, code)?,
+                    Some(code) => write_unstyled(f, code)?,
+                    None => {}
                 }
                 Ok(())
             }
             BacktraceFrame::Message(_) | BacktraceFrame::Custom(_) => {
-                writeln!(f, " {index}: {BOLD_OPEN}{message}{BOLD_CLOSE}")
+                writeln!(f, " {index}: {bold_open}{message}{bold_close}")
             }
         }
     }
 
-    fn display(&self, f: &mut fmt::Formatter<'_>, index: usize) -> fmt::Result {
-        self.display_with_message(f, &self.message(), index)
+    fn display(&self, f: &mut fmt::Formatter<'_>, index: usize, style: Style) -> fmt::Result {
+        self.display_with_message(f, &self.located_message(), index, style)
+    }
+
+    /// Returns the byte position in the reader where the error passed through
+    /// this frame, if it is known.
+    ///
+    /// The position of a [`Custom`](Self::Custom) frame is known only if its
+    /// context is itself an [`Error`] with a position.
+    #[must_use]
+    pub fn pos(&self) -> Option<u64> {
+        match self {
+            BacktraceFrame::Full { pos, .. } => *pos,
+            BacktraceFrame::Message(_) => None,
+            BacktraceFrame::Custom(context) => context.downcast_ref::<Error>()?.pos(),
+        }
     }
 
     fn message(&self) -> Cow<'_, str> {
@@ -180,6 +452,50 @@ impl BacktraceFrame {
             BacktraceFrame::Custom(context) => context.to_string().into(),
         }
     }
+
+    /// Returns true if `other` was generated at the same place in the code as
+    /// this frame, ignoring the position of the reader.
+    fn same_site(&self, other: &Self) -> bool {
+        match (self, other) {
+            (
+                BacktraceFrame::Full {
+                    code,
+                    message,
+                    file,
+                    line,
+                    ..
+                },
+                BacktraceFrame::Full {
+                    code: other_code,
+                    message: other_message,
+                    file: other_file,
+                    line: other_line,
+                    ..
+                },
+            ) => {
+                code == other_code
+                    && message == other_message
+                    && file == other_file
+                    && line == other_line
+            }
+            (BacktraceFrame::Message(message), BacktraceFrame::Message(other)) => message == other,
+            (BacktraceFrame::Custom(context), BacktraceFrame::Custom(other)) => {
+                context.to_string() == other.to_string()
+            }
+            _ => false,
+        }
+    }
+
+    fn located_message(&self) -> Cow<'_, str> {
+        match self {
+            BacktraceFrame::Full {
+                message,
+                pos: Some(pos),
+                ..
+            } => format!("{message} at 0x{pos:x}").into(),
+            frame => frame.message(),
+        }
+    }
 }
 
 impl<T: CustomError + 'static> From<T> for BacktraceFrame {
@@ -188,39 +504,63 @@ impl<T: CustomError + 'static> From<T> for BacktraceFrame {
     }
 }
 
-struct FirstErrorFmt<'a>(&'a Error);
+fn write_repeats(f: &mut fmt::Formatter<'_>, repeats: usize) -> fmt::Result {
+    if repeats == 0 {
+        Ok(())
+    } else {
+        writeln!(f, "     …repeated {repeats}×")
+    }
+}
+
+/// Writes `code` without the terminal escape sequences which were added to it
+/// for syntax highlighting.
+pub(super) fn write_unstyled(f: &mut fmt::Formatter<'_>, code: &str) -> fmt::Result {
+    let mut parts = code.split('\x1b');
+    if let Some(first) = parts.next() {
+        f.write_str(first)?;
+    }
+    for part in parts {
+        f.write_str(part.find('m').map_or("", |end| &part[end + 1..]))?;
+    }
+    Ok(())
+}
+
+struct FirstErrorFmt<'a>(&'a Error, Style, &'a dyn MessageFormatter);
 
 impl fmt::Display for FirstErrorFmt<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.0 {
-            Error::EnumErrors {
-                pos,
-                variant_errors,
-            } => {
-                writeln!(f, "no variants matched at {pos:#x?}...{BOLD_CLOSE}")?;
+            error @ Error::EnumErrors { pos, .. } => {
+                self.2.no_variant_match(f, *pos)?;
+                writeln!(f, "...{}", self.1.bold_close())?;
 
-                for (i, (name, err)) in variant_errors.iter().enumerate() {
+                let variant_errors = error.variant_errors_by_progress();
+                for (i, (name, pos, err)) in variant_errors.into_iter().enumerate() {
                     if i != 0 {
                         writeln!(f)?;
                     }
 
+                    let label = match pos {
+                        Some(pos) => format!("{name} (reached {pos:#x})"),
+                        None => name.to_string(),
+                    };
                     writeln!(
                         f,
-                        "   ╭───────────────────────┄ {name} ┄────────────────────┄"
+                        "   ╭───────────────────────┄ {label} ┄────────────────────┄"
                     )?;
                     writeln!(f, "   ┆")?;
                     write!(f, "   ┆")?;
-                    write!(Indenter(f), "{}", NoBars(err))?;
+                    write!(Indenter(f), "{}", NoBars(err, self.1, self.2))?;
                     write!(
                         f,
                         "\n   ╰─────────────────────────{}──────────────────────┄",
-                        "─".repeat(name.len())
+                        "─".repeat(label.chars().count())
                     )?;
                 }
 
                 Ok(())
             }
-            error => <Error as fmt::Display>::fmt(error, f),
+            error => error.fmt_with(f, self.2),
         }
     }
 }
@@ -247,13 +587,13 @@ impl Write for Indenter<'_, '_> {
     }
 }
 
-struct NoBars<'a>(&'a Error);
+struct NoBars<'a>(&'a Error, Style, &'a dyn MessageFormatter);
 
 impl fmt::Display for NoBars<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.0 {
-            Error::Backtrace(backtrace) => backtrace.fmt_no_bars(f),
-            error => <Error as fmt::Display>::fmt(error, f),
+            Error::Backtrace(backtrace) => backtrace.fmt_no_bars(f, self.1, self.2),
+            error => error.fmt_with(f, self.2),
         }
     }
 }
@@ -261,6 +601,8 @@ impl fmt::Display for NoBars<'_> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::string::String;
 
     #[test]
     #[cfg_attr(coverage_nightly, coverage(off))]
@@ -291,11 +633,13 @@ mod tests {
                 message: Cow::Borrowed(ERR1),
                 file: file!(),
                 line: l1,
+                pos: None,
             }, BacktraceFrame::Full {
                 code: None,
                 message: Cow::Borrowed(ERR2),
                 file: file!(),
                 line: l2,
+                pos: None,
             }, BacktraceFrame::Custom(last)] = &backtrace.frames[..]
             {
                 assert_eq!(line1, *l1);
@@ -308,4 +652,91 @@ mod tests {
             panic!("Not a backtrace")
         }
     }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn backtrace_without_color() {
+        let error = Error::Backtrace(Backtrace::new(
+            Error::AssertFail {
+                pos: 0,
+                message: "assert_failed".to_string(),
+            },
+            alloc::vec![BacktraceFrame::Full {
+                code: Some("  \x1b[1m\x1b[38;5;1mcount\x1b[39m\x1b[22m: u8\n"),
+                message: "While parsing field 'count'".into(),
+                file: "file.rs",
+                line: 1,
+                pos: Some(0x12),
+            }],
+        ));
+
+        set_color_choice(ColorChoice::Never);
+        let output = error.to_string();
+        set_color_choice(ColorChoice::Auto);
+
+        assert!(!output.contains('\x1b'), "{output:?}");
+        assert!(output.contains("  count: u8\n"), "{output:?}");
+        assert!(output.contains("'count' at 0x12\n"), "{output:?}");
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn backtrace_collapse_and_limit() {
+        let frame = |message: &'static str, pos| BacktraceFrame::Full {
+            code: None,
+            message: message.into(),
+            file: "file.rs",
+            line: 1,
+            pos: Some(pos),
+        };
+        let unstyled = |error: &Error| {
+            let output: String = error.to_string();
+            output.replace("\x1b[1m", "").replace("\x1b[22m", "")
+        };
+        let error = Error::Backtrace(Backtrace::new(
+            Error::AssertFail {
+                pos: 0,
+                message: "assert_failed".to_string(),
+            },
+            alloc::vec![
+                frame("inner", 0),
+                frame("nested", 1),
+                frame("nested", 2),
+                frame("nested", 3),
+                frame("a", 4),
+                frame("b", 5),
+                frame("outer", 6),
+            ],
+        ));
+
+        let output = unstyled(&error);
+        assert!(
+            output.contains(" 1: nested at 0x1\n     at file.rs:1\n     …repeated 2×\n 4: a"),
+            "{output}"
+        );
+
+        set_backtrace_options(BacktraceOptions {
+            max_frames: Some(3),
+            ..BacktraceOptions::DEFAULT
+        });
+        let limited = unstyled(&error);
+        set_backtrace_options(BacktraceOptions {
+            max_frames: Some(3),
+            collapse_repeated: false,
+        });
+        let uncollapsed = unstyled(&error);
+        set_backtrace_options(BacktraceOptions::DEFAULT);
+
+        assert!(
+            limited.contains("     …repeated 2×\n     …2 frames omitted\n 6: outer"),
+            "{limited}"
+        );
+        assert!(!limited.contains(" 4: a"), "{limited}");
+        assert!(
+            uncollapsed.contains(
+                " 1: nested at 0x1\n     at file.rs:1\n     …4 frames omitted\n 6: outer"
+            ),
+            "{uncollapsed}"
+        );
+    }
 }
@@ -0,0 +1,159 @@
+use super::{ContextExt, Error};
+use crate::io::{Read, Seek, SeekFrom};
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+/// A hex dump of the bytes of a stream around the position of an error.
+///
+/// This is added to an error as a backtrace frame by [`Error::with_hex_dump`].
+/// It shows the row of bytes containing the error position along with the rows
+/// before and after it, and marks the byte at the error position.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HexDump {
+    /// The position of the error.
+    pub pos: u64,
+
+    /// The position of the first byte in the dump.
+    pub start: u64,
+
+    /// The bytes around the error position.
+    pub bytes: Vec<u8>,
+
+    /// The number of bytes shown on each row.
+    pub width: usize,
+}
+
+impl HexDump {
+    /// Reads the bytes of `reader` around `pos` with `width` bytes on each
+    /// row, then restores the position of the reader.
+    ///
+    /// # Errors
+    ///
+    /// If seeking or reading fails, an error is returned.
+    pub fn new<R: Read + Seek>(reader: &mut R, pos: u64, width: usize) -> crate::io::Result<Self> {
+        let width = width.max(1);
+        let row_start = pos - pos % width as u64;
+        let start = row_start.saturating_sub(width as u64);
+        // Lint: The difference is at most `width`.
+        #[allow(clippy::cast_possible_truncation)]
+        let len = (row_start - start) as usize + 2 * width;
+
+        let saved_pos = reader.stream_position()?;
+        let result = read_up_to(reader, start, len);
+        reader.seek(SeekFrom::Start(saved_pos))?;
+
+        Ok(Self {
+            pos,
+            start,
+            bytes: result?,
+            width,
+        })
+    }
+}
+
+impl fmt::Display for HexDump {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Bytes around 0x{:x}:", self.pos)?;
+
+        if self.pos >= self.start + self.bytes.len() as u64 {
+            write!(f, " (end of stream)")?;
+        }
+
+        for (row, bytes) in self.bytes.chunks(self.width).enumerate() {
+            let row_start = self.start + (row * self.width) as u64;
+            write!(f, "\n     {row_start:08x} ")?;
+            for byte in bytes {
+                write!(f, " {byte:02x}")?;
+            }
+            write!(f, "{:1$}  ", "", (self.width - bytes.len()) * 3)?;
+            for &byte in bytes {
+                let c = if byte.is_ascii_graphic() || byte == b' ' {
+                    char::from(byte)
+                } else {
+                    '.'
+                };
+                write!(f, "{c}")?;
+            }
+
+            let row_range = row_start..row_start + bytes.len() as u64;
+            if row_range.contains(&self.pos) {
+                // Lint: The column is less than `width`.
+                #[allow(clippy::cast_possible_truncation)]
+                let column = (self.pos - row_start) as usize;
+                write!(f, "\n     {:1$}^^", "", 10 + column * 3)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Reads up to `len` bytes starting at `start`, stopping early at the end of
+/// the stream.
+fn read_up_to<R: Read + Seek>(
+    reader: &mut R,
+    start: u64,
+    len: usize,
+) -> crate::io::Result<Vec<u8>> {
+    reader.seek(SeekFrom::Start(start))?;
+    let mut bytes = vec![0; len];
+    let mut filled = 0;
+    while filled < len {
+        match reader.read(&mut bytes[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == crate::io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    bytes.truncate(filled);
+    Ok(bytes)
+}
+
+impl Error {
+    /// Adds a [`HexDump`] of the bytes of `reader` around the position of the
+    /// error to its backtrace, with `width` bytes on each row.
+    ///
+    /// If the error does not have a position, the current position of
+    /// `reader` is used. If reading the bytes fails, the error is returned
+    /// unchanged. The position of `reader` is not changed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use binrw::{BinRead, io::Cursor};
+    /// #[derive(BinRead)]
+    /// #[br(big, magic = b"PK")]
+    /// struct Header {
+    ///     version: u16,
+    /// }
+    ///
+    /// let mut data = Cursor::new(b"\0\0\0\0ZK\x01\x02");
+    /// data.set_position(4);
+    /// let err = Header::read(&mut data)
+    ///     .map(|_| ())
+    ///     .unwrap_err()
+    ///     .with_hex_dump(&mut data, 8);
+    /// // Bytes around 0x4:
+    /// //      00000000  00 00 00 00 5a 4b 01 02  ....ZK..
+    /// //                            ^^
+    /// println!("{err}");
+    /// # assert!(err.to_string().contains("5a 4b 01 02  ....ZK..\n                           ^^"));
+    /// ```
+    #[must_use]
+    pub fn with_hex_dump<R: Read + Seek>(self, reader: &mut R, width: usize) -> Self {
+        let pos = match self.pos() {
+            Some(pos) => pos,
+            None => match reader.stream_position() {
+                Ok(pos) => pos,
+                Err(_) => return self,
+            },
+        };
+
+        match HexDump::new(reader, pos, width) {
+            Ok(dump) => self.with_context(dump),
+            Err(_) => self,
+        }
+    }
+}
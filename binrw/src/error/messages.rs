@@ -0,0 +1,156 @@
+use core::fmt;
+
+/// Formats the messages of the errors raised by binrw itself.
+///
+/// Every method has a default implementation which writes the standard
+/// English message, so an implementation only needs to override the messages
+/// it wants to change. Pass an implementation to
+/// [`Error::display_with`](super::Error::display_with) to display an error
+/// with it, or use `with_message_formatter` to use it for every read and
+/// display inside a closure.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::error::MessageFormatter;
+/// use core::fmt;
+///
+/// struct French;
+///
+/// impl MessageFormatter for French {
+///     fn bad_magic(
+///         &self,
+///         f: &mut fmt::Formatter<'_>,
+///         pos: u64,
+///         found: &dyn fmt::Debug,
+///     ) -> fmt::Result {
+///         write!(f, "signature invalide à 0x{pos:x} : {found:?}")
+///     }
+///
+///     fn no_variant_match(&self, f: &mut fmt::Formatter<'_>, pos: u64) -> fmt::Result {
+///         write!(f, "aucune variante ne correspond à 0x{pos:x}")
+///     }
+/// }
+///
+/// let err = binrw::Error::NoVariantMatch { pos: 4 };
+/// assert_eq!(
+///     err.display_with(&French).to_string(),
+///     "aucune variante ne correspond à 0x4",
+/// );
+/// ```
+pub trait MessageFormatter: Send + Sync {
+    /// Writes the message of an [`Error::BadMagic`](super::Error::BadMagic).
+    ///
+    /// # Errors
+    ///
+    /// If writing to the formatter fails, an error is returned.
+    fn bad_magic(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        pos: u64,
+        found: &dyn fmt::Debug,
+    ) -> fmt::Result {
+        write!(f, "bad magic at 0x{pos:x}: {found:?}")
+    }
+
+    /// Writes the message of an
+    /// [`Error::AssertFail`](super::Error::AssertFail), where `message` is the
+    /// message of the failed assertion.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the formatter fails, an error is returned.
+    fn assert_fail(&self, f: &mut fmt::Formatter<'_>, pos: u64, message: &str) -> fmt::Result {
+        write!(f, "{message} at 0x{pos:x}")
+    }
+
+    /// Writes the message of an assertion which does not have its own
+    /// message, where `condition` is the source code of its condition.
+    ///
+    /// Unlike the other messages, this one is written when the assertion
+    /// fails, and becomes the message of the resulting
+    /// [`Error::AssertFail`](super::Error::AssertFail).
+    ///
+    /// # Errors
+    ///
+    /// If writing to the formatter fails, an error is returned.
+    fn assertion_failed(&self, f: &mut fmt::Formatter<'_>, condition: &str) -> fmt::Result {
+        write!(f, "assertion failed: `{condition}`")
+    }
+
+    /// Writes the message of an
+    /// [`Error::NoVariantMatch`](super::Error::NoVariantMatch), which is also
+    /// used as the heading of an
+    /// [`Error::EnumErrors`](super::Error::EnumErrors).
+    ///
+    /// # Errors
+    ///
+    /// If writing to the formatter fails, an error is returned.
+    fn no_variant_match(&self, f: &mut fmt::Formatter<'_>, pos: u64) -> fmt::Result {
+        write!(f, "no variants matched at 0x{pos:x}")
+    }
+}
+
+/// The standard English messages, which are used unless another
+/// [`MessageFormatter`] is given.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct DefaultMessages;
+
+impl MessageFormatter for DefaultMessages {}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static MESSAGE_FORMATTER: core::cell::Cell<Option<&'static dyn MessageFormatter>> =
+        const { core::cell::Cell::new(None) };
+}
+
+/// Runs `f` with `formatter` used for the messages of built-in errors on the
+/// current thread.
+///
+/// This covers both the messages of assertions which fail while reading
+/// inside `f`, and errors which are displayed inside `f`. The previous
+/// formatter is restored when `f` returns, so other threads and callers are
+/// not affected. To display an error with a formatter after `f` has returned,
+/// use [`Error::display_with`](super::Error::display_with).
+///
+/// Without the `std` feature, this function is not available, and
+/// assertions without their own message always use the wording of
+/// [`DefaultMessages`].
+#[cfg(feature = "std")]
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "std")))]
+pub fn with_message_formatter<T>(
+    formatter: &'static dyn MessageFormatter,
+    f: impl FnOnce() -> T,
+) -> T {
+    struct Restore(Option<&'static dyn MessageFormatter>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            MESSAGE_FORMATTER.with(|current| current.set(self.0));
+        }
+    }
+
+    let _restore = Restore(MESSAGE_FORMATTER.with(|current| current.replace(Some(formatter))));
+    f()
+}
+
+/// Returns the formatter used for the messages of built-in errors on the
+/// current thread.
+///
+/// This is the formatter passed to the innermost call of
+/// `with_message_formatter`, or [`DefaultMessages`] outside of one or without
+/// the `std` feature.
+#[must_use]
+pub fn message_formatter() -> &'static dyn MessageFormatter {
+    #[cfg(feature = "std")]
+    {
+        MESSAGE_FORMATTER
+            .with(core::cell::Cell::get)
+            .unwrap_or(&DefaultMessages)
+    }
+
+    #[cfg(not(feature = "std"))]
+    {
+        &DefaultMessages
+    }
+}
@@ -1,6 +1,11 @@
 //! Functions and type definitions for handling errors.
 
 mod backtrace;
+mod hex_dump;
+mod messages;
+#[cfg(feature = "serde")]
+mod serialize;
+mod theme;
 
 use crate::{io, BinResult};
 use alloc::borrow::Cow;
@@ -8,6 +13,9 @@ use alloc::borrow::Cow;
 use alloc::{boxed::Box, string::String, vec, vec::Vec};
 pub use backtrace::*;
 use core::{any::Any, fmt};
+pub use hex_dump::HexDump;
+pub use messages::*;
+pub use theme::*;
 
 /// The `ContextExt` trait allows extra information to be added to errors.
 ///
@@ -51,6 +59,7 @@ impl ContextExt for Error {
                         message: message.into(),
                         file: caller.file(),
                         line: caller.line(),
+                        pos: None,
                     }],
                 ))
             }
@@ -79,6 +88,7 @@ impl<T> ContextExt for BinResult<T> {
                             message: message.into(),
                             file: caller.file(),
                             line: caller.line(),
+                            pos: None,
                         }],
                     )),
                 })
@@ -161,6 +171,10 @@ impl dyn CustomError {
 }
 
 /// The error type used by [`BinRead`](crate::BinRead).
+///
+/// With the `serde` feature, errors and their backtraces implement
+/// [`Serialize`](https://docs.rs/serde/latest/serde/trait.Serialize.html), so
+/// they can be reported in a machine-readable format like JSON.
 #[non_exhaustive]
 pub enum Error {
     /// An expected [magic number](crate::docs::attribute#magic) was not found.
@@ -241,6 +255,15 @@ pub enum Error {
         variant_errors: Vec<(&'static str, Error)>,
     },
 
+    /// A deadline set by a [`DeadlineReader`] expired before the data could
+    /// be read.
+    ///
+    /// [`DeadlineReader`]: crate::io::DeadlineReader
+    Timeout {
+        /// The byte position reached in the reader when the deadline expired.
+        pos: u64,
+    },
+
     /// An error with additional frames of context used to construct a backtrace
     Backtrace(Backtrace),
 }
@@ -256,6 +279,187 @@ impl Error {
         }
     }
 
+    /// Returns the native Rust backtrace captured when context was first
+    /// added to this error, or `None` if it has no context.
+    ///
+    /// Errors returned by a derived implementation have context added by the
+    /// field which failed to parse, so the backtrace leads to the
+    /// implementation which called the failing parser.
+    #[cfg(feature = "native-backtrace")]
+    #[cfg_attr(all(doc, nightly), doc(cfg(feature = "native-backtrace")))]
+    #[must_use]
+    pub fn native_backtrace(&self) -> Option<&std::backtrace::Backtrace> {
+        match self {
+            Self::Backtrace(backtrace) => Some(&backtrace.native),
+            _ => None,
+        }
+    }
+
+    /// Returns the byte position in the reader where the error occurred.
+    ///
+    /// An [`Error::Io`] has no position of its own, so if the root cause of a
+    /// backtrace is an I/O error, the position of the innermost frame which
+    /// has one is used instead.
+    #[must_use]
+    pub fn pos(&self) -> Option<u64> {
+        match self {
+            Self::BadMagic { pos, .. }
+            | Self::AssertFail { pos, .. }
+            | Self::Custom { pos, .. }
+            | Self::NoVariantMatch { pos }
+            | Self::EnumErrors { pos, .. }
+            | Self::Timeout { pos } => Some(*pos),
+            Self::Io(_) => None,
+            Self::Backtrace(backtrace) => backtrace
+                .error
+                .pos()
+                .or_else(|| backtrace.frames.iter().find_map(BacktraceFrame::pos)),
+        }
+    }
+
+    /// Returns the furthest byte position in the reader which parsing
+    /// reached before the error occurred.
+    ///
+    /// Unlike [`pos`](Self::pos), this also considers the positions recorded
+    /// in the frames of a backtrace, and for an [`Error::EnumErrors`] it is
+    /// the furthest position reached by any of the variants.
+    #[must_use]
+    pub fn deepest_pos(&self) -> Option<u64> {
+        match self {
+            Self::EnumErrors {
+                pos,
+                variant_errors,
+            } => variant_errors
+                .iter()
+                .filter_map(|(_, err)| err.deepest_pos())
+                .max()
+                .or(Some(*pos)),
+            Self::Backtrace(backtrace) => backtrace
+                .frames
+                .iter()
+                .filter_map(BacktraceFrame::pos)
+                .chain(backtrace.error.deepest_pos())
+                .max(),
+            error => error.pos(),
+        }
+    }
+
+    /// Returns the error of each variant of an [`Error::EnumErrors`] along
+    /// with its [deepest position](Self::deepest_pos), sorted so that the
+    /// variants which were parsed furthest come first.
+    ///
+    /// Variants which reached the same position keep their original order.
+    /// For any other error, the list is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use binrw::{BinRead, io::Cursor};
+    /// #[derive(BinRead)]
+    /// #[br(big)]
+    /// enum Message {
+    ///     #[br(magic = 1u8)]
+    ///     Ping,
+    ///     #[br(magic = 2u8)]
+    ///     Data { len: u8, #[br(count = len)] data: Vec<u8> },
+    /// }
+    ///
+    /// let err = Message::read(&mut Cursor::new(b"\x02\x04ab"))
+    ///     .map(|_| ())
+    ///     .unwrap_err();
+    /// let variants = err.variant_errors_by_progress();
+    /// assert_eq!(variants[0].0, "Data");
+    /// assert_eq!(variants[0].1, Some(4));
+    /// assert_eq!(variants[1].0, "Ping");
+    /// ```
+    #[must_use]
+    pub fn variant_errors_by_progress(&self) -> Vec<(&'static str, Option<u64>, &Error)> {
+        let mut errors = match self.root_cause() {
+            Self::EnumErrors { variant_errors, .. } => variant_errors
+                .iter()
+                .map(|(name, err)| (*name, err.deepest_pos(), err))
+                .collect::<Vec<_>>(),
+            _ => Vec::new(),
+        };
+        errors.sort_by_key(|&(_, pos, _)| core::cmp::Reverse(pos));
+        errors
+    }
+
+    /// Returns a value which displays this error using `messages` for the
+    /// messages of built-in errors, instead of the formatter of the current
+    /// thread.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use binrw::{BinRead, io::Cursor};
+    /// use binrw::error::MessageFormatter;
+    /// use core::fmt;
+    ///
+    /// struct Product;
+    ///
+    /// impl MessageFormatter for Product {
+    ///     fn bad_magic(
+    ///         &self,
+    ///         f: &mut fmt::Formatter<'_>,
+    ///         pos: u64,
+    ///         _: &dyn fmt::Debug,
+    ///     ) -> fmt::Result {
+    ///         write!(f, "E001: not a PK file (offset {pos})")
+    ///     }
+    /// }
+    ///
+    /// #[derive(BinRead)]
+    /// #[br(magic = b"PK")]
+    /// struct Header;
+    ///
+    /// let err = Header::read_le(&mut Cursor::new(b"ZIP")).map(|_| ()).unwrap_err();
+    /// assert_eq!(err.display_with(&Product).to_string(), "E001: not a PK file (offset 0)");
+    /// assert_eq!(err.to_string(), "bad magic at 0x0: [90, 73]");
+    /// ```
+    #[must_use]
+    pub fn display_with<'a>(&'a self, messages: &'a dyn MessageFormatter) -> DisplayWith<'a> {
+        DisplayWith {
+            error: self,
+            messages,
+        }
+    }
+
+    /// Returns the category of the [root cause][`Self::root_cause`] of this
+    /// error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use binrw::{BinRead, error::ErrorKind, io::Cursor};
+    /// #[derive(BinRead)]
+    /// #[br(magic = b"PK")]
+    /// struct Header {
+    ///     version: u16,
+    /// }
+    ///
+    /// match Header::read_le(&mut Cursor::new(b"PK\x01")) {
+    ///     Err(err) if err.kind() == ErrorKind::Eof => println!("need more data"),
+    ///     Err(err) => panic!("{err}"),
+    ///     Ok(_) => {}
+    /// }
+    /// # assert_eq!(Header::read_le(&mut Cursor::new(b"PK\x01")).map(|_| ()).unwrap_err().kind(), ErrorKind::Eof);
+    /// # assert_eq!(Header::read_le(&mut Cursor::new(b"ZIP\0")).map(|_| ()).unwrap_err().kind(), ErrorKind::BadMagic);
+    /// ```
+    #[must_use]
+    pub fn kind(&self) -> ErrorKind {
+        match self.root_cause() {
+            error if error.is_eof() => ErrorKind::Eof,
+            Self::BadMagic { .. } => ErrorKind::BadMagic,
+            Self::AssertFail { .. } => ErrorKind::AssertFail,
+            Self::Io(_) => ErrorKind::Io,
+            Self::Custom { .. } => ErrorKind::Custom,
+            Self::NoVariantMatch { .. } | Self::EnumErrors { .. } => ErrorKind::NoVariantMatch,
+            Self::Timeout { .. } => ErrorKind::Timeout,
+            Self::Backtrace(backtrace) => backtrace.error.kind(),
+        }
+    }
+
     /// Check if the [root cause][`Self::root_cause`] of this error is an [`Error::Io`] and an
     /// [`io::ErrorKind::UnexpectedEof`].
     #[must_use]
@@ -282,35 +486,108 @@ impl Error {
     }
 }
 
+/// The category of an [`Error`], as returned by [`Error::kind`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ErrorKind {
+    /// An expected magic number was not found.
+    ///
+    /// See [`Error::BadMagic`].
+    BadMagic,
+
+    /// The reader ran out of data.
+    ///
+    /// This is an [`Error::Io`] with the kind
+    /// [`UnexpectedEof`](io::ErrorKind::UnexpectedEof), or an
+    /// [`Error::EnumErrors`] where every variant ran out of data.
+    Eof,
+
+    /// An assertion failed.
+    ///
+    /// See [`Error::AssertFail`].
+    AssertFail,
+
+    /// None of the variants of an enum could be parsed.
+    ///
+    /// See [`Error::NoVariantMatch`] and [`Error::EnumErrors`].
+    NoVariantMatch,
+
+    /// A user-generated error.
+    ///
+    /// See [`Error::Custom`].
+    Custom,
+
+    /// Any other error from the underlying reader.
+    ///
+    /// See [`Error::Io`].
+    Io,
+
+    /// A deadline expired.
+    ///
+    /// See [`Error::Timeout`].
+    Timeout,
+}
+
 impl From<io::Error> for Error {
     fn from(err: io::Error) -> Self {
+        #[cfg(feature = "std")]
+        if let Some(pos) = io::deadline_expired_pos(&err) {
+            return Self::Timeout { pos };
+        }
+
         Self::Io(err)
     }
 }
 
-impl fmt::Display for Error {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+impl Error {
+    fn fmt_with(&self, f: &mut fmt::Formatter<'_>, messages: &dyn MessageFormatter) -> fmt::Result {
         match self {
-            Self::BadMagic { pos, found } => write!(f, "bad magic at 0x{pos:x}: {found:?}"),
-            Self::AssertFail { pos, message } => write!(f, "{message} at 0x{pos:x}"),
+            Self::BadMagic { pos, found } => messages.bad_magic(f, *pos, found),
+            Self::AssertFail { pos, message } => messages.assert_fail(f, *pos, message),
             Self::Io(err) => fmt::Display::fmt(err, f),
             Self::Custom { pos, err } => write!(f, "{err} at 0x{pos:x}"),
-            Self::NoVariantMatch { pos } => write!(f, "no variants matched at 0x{pos:x}"),
-            Self::EnumErrors {
-                pos,
-                variant_errors,
-            } => {
-                write!(f, "no variants matched at 0x{pos:x}:")?;
-                for (name, err) in variant_errors {
-                    write!(f, "\n  {name}: {err}")?;
+            Self::NoVariantMatch { pos } => messages.no_variant_match(f, *pos),
+            Self::Timeout { pos } => write!(f, "timed out at 0x{pos:x}"),
+            Self::EnumErrors { pos, .. } => {
+                messages.no_variant_match(f, *pos)?;
+                f.write_str(":")?;
+                for (name, pos, err) in self.variant_errors_by_progress() {
+                    write!(f, "\n  {name}")?;
+                    if let Some(pos) = pos {
+                        write!(f, " (reached 0x{pos:x})")?;
+                    }
+                    f.write_str(": ")?;
+                    err.root_cause().fmt_with(f, messages)?;
                 }
                 Ok(())
             }
-            Self::Backtrace(backtrace) => fmt::Display::fmt(backtrace, f),
+            Self::Backtrace(backtrace) => backtrace.fmt_with(f, messages),
         }
     }
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.fmt_with(f, message_formatter())
+    }
+}
+
+/// Displays an [`Error`] using a [`MessageFormatter`] for the messages of
+/// built-in errors.
+///
+/// This is returned by [`Error::display_with`].
+#[derive(Clone, Copy)]
+pub struct DisplayWith<'a> {
+    error: &'a Error,
+    messages: &'a dyn MessageFormatter,
+}
+
+impl fmt::Display for DisplayWith<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt_with(f, self.messages)
+    }
+}
+
 impl fmt::Debug for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         <Error as fmt::Display>::fmt(self, f)
@@ -0,0 +1,155 @@
+use super::{backtrace::write_unstyled, Backtrace, BacktraceFrame, Error};
+use core::fmt;
+use serde::ser::{Serialize, SerializeSeq, SerializeStruct, SerializeStructVariant, Serializer};
+
+/// Serializes a value using its [`Display`](fmt::Display) implementation.
+struct Collect<T>(T);
+
+impl<T: fmt::Display> Serialize for Collect<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&self.0)
+    }
+}
+
+/// Displays code without the syntax highlighting added by `binrw_derive`.
+struct Unstyled(&'static str);
+
+impl fmt::Display for Unstyled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_unstyled(f, self.0)
+    }
+}
+
+/// Serializes the errors of each variant of an enum as a list of objects with
+/// `variant` and `error` fields.
+struct VariantErrors<'a>(&'a [(&'static str, Error)]);
+
+impl Serialize for VariantErrors<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        struct VariantError<'a>(&'static str, &'a Error);
+
+        impl Serialize for VariantError<'_> {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let mut state = serializer.serialize_struct("VariantError", 2)?;
+                state.serialize_field("variant", self.0)?;
+                state.serialize_field("error", self.1)?;
+                state.end()
+            }
+        }
+
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for (name, err) in self.0 {
+            seq.serialize_element(&VariantError(name, err))?;
+        }
+        seq.end()
+    }
+}
+
+/// Errors are serialized as externally tagged enums, the same as the
+/// `Serialize` derive would do.
+///
+/// Values which are only available as trait objects, like the magic of a
+/// [`BadMagic`](Error::BadMagic) error or the error of a
+/// [`Custom`](Error::Custom) error, are serialized as strings.
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "serde")))]
+impl Serialize for Error {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Error::BadMagic { pos, found } => {
+                let mut state = serializer.serialize_struct_variant("Error", 0, "BadMagic", 2)?;
+                state.serialize_field("pos", pos)?;
+                state.serialize_field("found", &Collect(format_args!("{found:?}")))?;
+                state.end()
+            }
+            Error::AssertFail { pos, message } => {
+                let mut state = serializer.serialize_struct_variant("Error", 1, "AssertFail", 2)?;
+                state.serialize_field("pos", pos)?;
+                state.serialize_field("message", message)?;
+                state.end()
+            }
+            Error::Io(err) => {
+                let mut state = serializer.serialize_struct_variant("Error", 2, "Io", 2)?;
+                state.serialize_field("kind", &Collect(format_args!("{:?}", err.kind())))?;
+                state.serialize_field("message", &Collect(err))?;
+                state.end()
+            }
+            Error::Custom { pos, err } => {
+                let mut state = serializer.serialize_struct_variant("Error", 3, "Custom", 2)?;
+                state.serialize_field("pos", pos)?;
+                state.serialize_field("message", &Collect(err))?;
+                state.end()
+            }
+            Error::NoVariantMatch { pos } => {
+                let mut state =
+                    serializer.serialize_struct_variant("Error", 4, "NoVariantMatch", 1)?;
+                state.serialize_field("pos", pos)?;
+                state.end()
+            }
+            Error::EnumErrors {
+                pos,
+                variant_errors,
+            } => {
+                let mut state = serializer.serialize_struct_variant("Error", 5, "EnumErrors", 2)?;
+                state.serialize_field("pos", pos)?;
+                state.serialize_field("variant_errors", &VariantErrors(variant_errors))?;
+                state.end()
+            }
+            Error::Timeout { pos } => {
+                let mut state = serializer.serialize_struct_variant("Error", 6, "Timeout", 1)?;
+                state.serialize_field("pos", pos)?;
+                state.end()
+            }
+            Error::Backtrace(backtrace) => {
+                serializer.serialize_newtype_variant("Error", 7, "Backtrace", backtrace)
+            }
+        }
+    }
+}
+
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "serde")))]
+impl Serialize for Backtrace {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct("Backtrace", 2)?;
+        state.serialize_field("error", &self.error)?;
+        state.serialize_field("frames", &self.frames)?;
+        state.end()
+    }
+}
+
+/// Frames are serialized as externally tagged enums. The code of a
+/// [`Full`](BacktraceFrame::Full) frame is serialized without syntax
+/// highlighting, and the context of a [`Custom`](BacktraceFrame::Custom) frame
+/// is serialized as a message string along with its position, if it has one.
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "serde")))]
+impl Serialize for BacktraceFrame {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            BacktraceFrame::Full {
+                code,
+                message,
+                file,
+                line,
+                pos,
+            } => {
+                let mut state =
+                    serializer.serialize_struct_variant("BacktraceFrame", 0, "Full", 5)?;
+                state.serialize_field("code", &code.map(|code| Collect(Unstyled(code))))?;
+                state.serialize_field("message", message)?;
+                state.serialize_field("file", file)?;
+                state.serialize_field("line", line)?;
+                state.serialize_field("pos", pos)?;
+                state.end()
+            }
+            BacktraceFrame::Message(message) => {
+                serializer.serialize_newtype_variant("BacktraceFrame", 1, "Message", message)
+            }
+            BacktraceFrame::Custom(context) => {
+                let mut state =
+                    serializer.serialize_struct_variant("BacktraceFrame", 2, "Custom", 2)?;
+                state.serialize_field("message", &Collect(context))?;
+                state.serialize_field("pos", &self.pos())?;
+                state.end()
+            }
+        }
+    }
+}
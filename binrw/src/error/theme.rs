@@ -0,0 +1,238 @@
+use core::{
+    fmt,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+/// A color used for syntax highlighting in backtraces.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ThemeColor {
+    /// The default text color of the terminal.
+    Plain,
+
+    /// A color from the 256-color Xterm palette.
+    ///
+    /// Colors 0 to 15 are the standard and bright colors of the terminal’s
+    /// own palette.
+    Xterm(u8),
+
+    /// A 24-bit RGB color.
+    Rgb(u8, u8, u8),
+}
+
+impl ThemeColor {
+    const fn encode(self) -> u32 {
+        match self {
+            ThemeColor::Plain => 0,
+            ThemeColor::Xterm(n) => 1 << 24 | n as u32,
+            ThemeColor::Rgb(r, g, b) => 2 << 24 | (r as u32) << 16 | (g as u32) << 8 | b as u32,
+        }
+    }
+
+    fn decode(value: u32) -> Self {
+        let [tag, r, g, b] = value.to_be_bytes();
+        match tag {
+            1 => ThemeColor::Xterm(b),
+            2 => ThemeColor::Rgb(r, g, b),
+            _ => ThemeColor::Plain,
+        }
+    }
+}
+
+impl fmt::Display for ThemeColor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeColor::Plain => Ok(()),
+            ThemeColor::Xterm(n) => write!(f, "\x1b[38;5;{n}m"),
+            ThemeColor::Rgb(r, g, b) => write!(f, "\x1b[38;2;{r};{g};{b}m"),
+        }
+    }
+}
+
+/// The colors used for syntax highlighting the code shown in backtraces.
+///
+/// Code is only shown in backtraces when the `verbose-backtrace` feature is
+/// enabled and the crate which derived the failing type was built with a
+/// nightly compiler.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::error::{set_theme, Theme, ThemeColor};
+///
+/// // Darker colors for a light terminal background
+/// set_theme(Theme {
+///     string: ThemeColor::Xterm(94),
+///     keyword: ThemeColor::Xterm(124),
+///     ..Theme::DEFAULT
+/// });
+/// # assert_eq!(binrw::error::theme().string, ThemeColor::Xterm(94));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Theme {
+    /// The color of string literals.
+    pub string: ThemeColor,
+
+    /// The color of character literals.
+    pub char: ThemeColor,
+
+    /// The color of numeric literals.
+    pub number: ThemeColor,
+
+    /// The color of keywords, attributes, and types.
+    pub keyword: ThemeColor,
+
+    /// The color of function names.
+    pub function: ThemeColor,
+
+    /// The color of unary operators.
+    pub unary: ThemeColor,
+
+    /// The color of comments.
+    pub comment: ThemeColor,
+}
+
+impl Theme {
+    /// The default theme, designed for a dark terminal background.
+    pub const DEFAULT: Self = Self {
+        string: ThemeColor::Xterm(228),
+        char: ThemeColor::Xterm(141),
+        number: ThemeColor::Xterm(135),
+        keyword: ThemeColor::Xterm(197),
+        function: ThemeColor::Xterm(148),
+        unary: ThemeColor::Xterm(81),
+        comment: ThemeColor::Xterm(243),
+    };
+
+    /// A theme without any colors.
+    pub const PLAIN: Self = Self {
+        string: ThemeColor::Plain,
+        char: ThemeColor::Plain,
+        number: ThemeColor::Plain,
+        keyword: ThemeColor::Plain,
+        function: ThemeColor::Plain,
+        unary: ThemeColor::Plain,
+        comment: ThemeColor::Plain,
+    };
+
+    fn colors(self) -> [ThemeColor; 7] {
+        [
+            self.string,
+            self.char,
+            self.number,
+            self.keyword,
+            self.function,
+            self.unary,
+            self.comment,
+        ]
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+// The colors of the current theme, in the same order as `Theme::colors`.
+static THEME: [AtomicU32; 7] = [
+    AtomicU32::new(Theme::DEFAULT.string.encode()),
+    AtomicU32::new(Theme::DEFAULT.char.encode()),
+    AtomicU32::new(Theme::DEFAULT.number.encode()),
+    AtomicU32::new(Theme::DEFAULT.keyword.encode()),
+    AtomicU32::new(Theme::DEFAULT.function.encode()),
+    AtomicU32::new(Theme::DEFAULT.unary.encode()),
+    AtomicU32::new(Theme::DEFAULT.comment.encode()),
+];
+
+/// Returns the theme used for syntax highlighting in backtraces.
+#[must_use]
+pub fn theme() -> Theme {
+    let [string, char, number, keyword, function, unary, comment] =
+        [0, 1, 2, 3, 4, 5, 6].map(|i| ThemeColor::decode(THEME[i].load(Ordering::Relaxed)));
+    Theme {
+        string,
+        char,
+        number,
+        keyword,
+        function,
+        unary,
+        comment,
+    }
+}
+
+/// Sets the theme used for syntax highlighting in backtraces for the whole
+/// program.
+///
+/// Use [`Theme::PLAIN`] to keep other styling, like bold text, without any
+/// colors, or [`set_color_choice`](super::set_color_choice) to disable styling
+/// entirely.
+pub fn set_theme(theme: Theme) {
+    for (slot, color) in THEME.iter().zip(theme.colors()) {
+        slot.store(color.encode(), Ordering::Relaxed);
+    }
+}
+
+/// Writes code which was syntax highlighted with the default theme by
+/// `binrw_derive` using the current theme.
+pub(super) fn write_themed(f: &mut fmt::Formatter<'_>, code: &str) -> fmt::Result {
+    let theme = theme();
+    if theme == Theme::DEFAULT {
+        return f.write_str(code);
+    }
+
+    let defaults = Theme::DEFAULT.colors();
+    let colors = theme.colors();
+    let mut parts = code.split('\x1b');
+    if let Some(first) = parts.next() {
+        f.write_str(first)?;
+    }
+    for part in parts {
+        let themed = part.find('m').and_then(|end| {
+            let n = part[..end].strip_prefix("[38;5;")?.parse().ok()?;
+            let index = defaults.iter().position(|&c| c == ThemeColor::Xterm(n))?;
+            Some((index, end))
+        });
+        if let Some((index, end)) = themed {
+            write!(f, "{}", colors[index])?;
+            f.write_str(&part[end + 1..])?;
+        } else {
+            f.write_str("\x1b")?;
+            f.write_str(part)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::{format, string::String};
+
+    struct Themed(&'static str);
+
+    impl fmt::Display for Themed {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write_themed(f, self.0)
+        }
+    }
+
+    #[test]
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn theme_round_trip_and_remap() {
+        let code = "\x1b[38;5;197m#\x1b[39m[\x1b[38;5;228m\"a\"\x1b[39m]\x1b[1m";
+        assert_eq!(format!("{}", Themed(code)), code);
+
+        let custom = Theme {
+            string: ThemeColor::Rgb(1, 2, 3),
+            keyword: ThemeColor::Plain,
+            ..Theme::DEFAULT
+        };
+        set_theme(custom);
+        assert_eq!(theme(), custom);
+        let output: String = format!("{}", Themed(code));
+        set_theme(Theme::DEFAULT);
+
+        assert_eq!(output, "#\x1b[39m[\x1b[38;2;1;2;3m\"a\"\x1b[39m]\x1b[1m");
+    }
+}
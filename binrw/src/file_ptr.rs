@@ -125,6 +125,14 @@
 //! # assert!(matches!(x.get(&mut s, 1), Some(Ok(Item(3)))));
 //! # assert!(matches!(x.get(&mut s, 2), None));
 //! ```
+//!
+//! # Finding mistakes in offsets
+//!
+//! A wrong offset base or width usually makes pointers land on data which
+//! belongs to something else. With the `std` feature, a [`RegionTracker`]
+//! records the range of every value read through a `FilePtr`,
+//! [`parse_from_iter`], or [`located_at`], and reports pointed-to values which
+//! overlap each other or extend past the end of their section.
 
 use crate::NamedArgs;
 use crate::{
@@ -136,6 +144,8 @@ use core::num::{
     NonZeroU32, NonZeroU64, NonZeroU8,
 };
 use core::ops::{Deref, DerefMut};
+#[cfg(feature = "std")]
+use core::{cell::RefCell, fmt, ops::Range};
 
 /// A type alias for [`FilePtr`] with 8-bit offsets.
 pub type FilePtr8<T> = FilePtr<u8, T>;
@@ -335,7 +345,7 @@ where
         let before = reader.stream_position()?;
         reader.seek(SeekFrom::Start(relative_to))?;
         reader.seek(ptr.into_seek_from())?;
-        let value = parser(reader, endian, args.inner);
+        let value = track_pointee(reader, |reader| parser(reader, endian, args.inner));
         reader.seek(SeekFrom::Start(before))?;
         value
     }
@@ -541,12 +551,111 @@ where
                     }
                 }
 
-                parser(reader, endian, args.clone())
+                track_pointee(reader, |reader| parser(reader, endian, args.clone()))
             })
             .collect()
     }
 }
 
+/// Creates a parser that seeks to `pos`, reads a value using the [`BinRead`]
+/// implementation of `Value`, and then returns to the original position.
+///
+/// This works like a [`FilePtr`] whose offset is already known, but returns
+/// the value directly. Use [`SeekFrom::Start`] with an absolute offset, or
+/// with a base position plus an offset for offsets which are relative to the
+/// start of a structure.
+///
+/// # Errors
+///
+/// If seeking or reading fails, an [`Error`](crate::Error) variant will be
+/// returned. The original position is restored in either case.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, io::{Cursor, SeekFrom}};
+/// use binrw::file_ptr::located_at;
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Header {
+///     name_offset: u8,
+///     #[br(parse_with = located_at(SeekFrom::Start(name_offset.into())))]
+///     name: [u8; 2],
+///     version: u8,
+/// }
+///
+/// # let mut x = Cursor::new(b"\x02\x01hi");
+/// # let x = Header::read(&mut x).unwrap();
+/// # assert_eq!(&x.name, b"hi");
+/// # assert_eq!(x.version, 1);
+/// # let mut x = Cursor::new(b"\x02\x01hi");
+/// # x.set_position(1);
+/// # let read = located_at::<u32, _, _>(SeekFrom::Start(2));
+/// # assert!(read(&mut x, binrw::Endian::Big, ()).is_err());
+/// # assert_eq!(x.position(), 1);
+/// ```
+pub fn located_at<Value, Args, Reader>(
+    pos: SeekFrom,
+) -> impl Fn(&mut Reader, Endian, Args) -> BinResult<Value>
+where
+    Value: for<'a> BinRead<Args<'a> = Args>,
+    Reader: Read + Seek,
+{
+    located_at_with(pos, Value::read_options)
+}
+
+/// Creates a parser that seeks to `pos`, reads a value using the given
+/// `parser` function, and then returns to the original position.
+///
+/// See [`located_at`] for more information.
+///
+/// # Errors
+///
+/// If seeking or reading fails, an [`Error`](crate::Error) variant will be
+/// returned. The original position is restored in either case.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::read_u24, io::{Cursor, SeekFrom}};
+/// use binrw::file_ptr::located_at_with;
+///
+/// #[derive(BinRead)]
+/// #[br(little, import(base: u64))]
+/// struct Table {
+///     value_offset: u8,
+///     #[br(parse_with = located_at_with(
+///         SeekFrom::Start(base + u64::from(value_offset)),
+///         read_u24,
+///     ))]
+///     value: u32,
+/// }
+///
+/// # let mut x = Cursor::new(b"\xff\x01\x07\x0f\x10");
+/// # binrw::io::Seek::seek(&mut x, SeekFrom::Start(1)).unwrap();
+/// # let x = Table::read_args(&mut x, (1,)).unwrap();
+/// # assert_eq!(x.value, 0x100f07);
+/// ```
+pub fn located_at_with<Value, Args, F, Reader>(
+    pos: SeekFrom,
+    parser: F,
+) -> impl Fn(&mut Reader, Endian, Args) -> BinResult<Value>
+where
+    F: Fn(&mut Reader, Endian, Args) -> BinResult<Value>,
+    Reader: Read + Seek,
+{
+    move |reader, endian, args| {
+        let before = reader.stream_position()?;
+        let value = reader
+            .seek(pos)
+            .map_err(Into::into)
+            .and_then(|_| track_pointee(reader, |reader| parser(reader, endian, args)));
+        reader.seek(SeekFrom::Start(before))?;
+        value
+    }
+}
+
 /// A trait to convert from an integer into [`SeekFrom::Current`].
 pub trait IntoSeekFrom: Copy {
     /// Converts the value.
@@ -607,3 +716,255 @@ pub struct FilePtrArgs<Inner> {
     #[named_args(try_optional)]
     pub inner: Inner,
 }
+
+/// Reads a pointed-to value with `parser`, recording its range with the
+/// active [`RegionTracker`], if any.
+#[cfg(feature = "std")]
+fn track_pointee<R, T, F>(reader: &mut R, parser: F) -> BinResult<T>
+where
+    R: Read + Seek,
+    F: FnOnce(&mut R) -> BinResult<T>,
+{
+    let Some(mark) = REGIONS.with(|regions| regions.borrow().as_ref().map(|r| r.pointees.len()))
+    else {
+        return parser(reader);
+    };
+
+    let start = reader.stream_position()?;
+    let value = parser(reader)?;
+    let end = reader.stream_position()?;
+    REGIONS.with(|regions| match regions.borrow_mut().as_mut() {
+        Some(regions) => regions.record(mark, start..end),
+        None => Ok(()),
+    })?;
+    Ok(value)
+}
+
+#[cfg(not(feature = "std"))]
+fn track_pointee<R, T, F>(reader: &mut R, parser: F) -> BinResult<T>
+where
+    F: FnOnce(&mut R) -> BinResult<T>,
+{
+    parser(reader)
+}
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static REGIONS: RefCell<Option<RegionTracker>> = const { RefCell::new(None) };
+}
+
+/// Records the ranges of pointed-to values while parsing and reports those
+/// which conflict.
+///
+/// Tracking is enabled for the duration of [`track`](Self::track) on the
+/// current thread. Each value read through a [`FilePtr`],
+/// [`parse_from_iter`], or [`located_at`] is checked against the values read
+/// before it, and a [`RegionConflict`] is reported if:
+///
+/// * it overlaps another pointed-to value. Pointers to exactly the same range
+///   are not reported, since sharing a value is common, and neither are values
+///   which are read while parsing another pointed-to value, like a pointer
+///   into its own parent; or
+/// * it starts inside one of the [sections](Self::section) of the tracker but
+///   extends past its end.
+///
+/// Conflicts are collected as [warnings](Self::conflicts) by default. In
+/// [strict](Self::strict) mode, the first conflict is returned as an
+/// [`Error::Custom`](crate::Error::Custom) instead.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, FilePtr8, io::Cursor};
+/// use binrw::file_ptr::{RegionConflict, RegionTracker};
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Names {
+///     first: FilePtr8<u16>,
+///     second: FilePtr8<u16>,
+/// }
+///
+/// let mut tracker = RegionTracker::new();
+/// tracker.section(2..5);
+/// tracker
+///     .track(|| Names::read(&mut Cursor::new(b"\x02\x03abc")))
+///     .map(|_| ())
+///     .unwrap();
+/// assert_eq!(tracker.conflicts(), &[
+///     RegionConflict::Overlap { pointee: 3..5, other: 2..4 },
+/// ]);
+///
+/// tracker.clear();
+/// tracker.strict(true);
+/// let err = tracker
+///     .track(|| Names::read(&mut Cursor::new(b"\x02\x04abcd")))
+///     .map(|_| ())
+///     .unwrap_err();
+/// assert_eq!(
+///     err.custom_err(),
+///     Some(&RegionConflict::PastSection { pointee: 4..6, section: 2..5 }),
+/// );
+/// ```
+#[cfg(feature = "std")]
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "std")))]
+#[derive(Clone, Debug, Default)]
+pub struct RegionTracker {
+    strict: bool,
+    sections: Vec<Range<u64>>,
+    pointees: Vec<Range<u64>>,
+    conflicts: Vec<RegionConflict>,
+}
+
+#[cfg(feature = "std")]
+impl RegionTracker {
+    /// Creates a new tracker which collects conflicts as warnings.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets whether a conflict is returned as an error instead of being
+    /// collected as a warning.
+    pub fn strict(&mut self, strict: bool) -> &mut Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Adds a section of the input. A pointed-to value which starts inside
+    /// the section must also end inside it.
+    pub fn section(&mut self, section: Range<u64>) -> &mut Self {
+        self.sections.push(section);
+        self
+    }
+
+    /// Runs `f` with region tracking enabled on the current thread.
+    ///
+    /// Ranges recorded by earlier calls are kept, so a file can be parsed in
+    /// several steps. A tracker which is already active is suspended until
+    /// `f` returns.
+    pub fn track<T>(&mut self, f: impl FnOnce() -> T) -> T {
+        struct Restore<'a> {
+            tracker: &'a mut RegionTracker,
+            previous: Option<RegionTracker>,
+        }
+
+        impl Drop for Restore<'_> {
+            fn drop(&mut self) {
+                let previous = self.previous.take();
+                if let Some(tracker) = REGIONS.with(|regions| regions.replace(previous)) {
+                    *self.tracker = tracker;
+                }
+            }
+        }
+
+        let tracker = core::mem::take(self);
+        let previous = REGIONS.with(|regions| regions.replace(Some(tracker)));
+        let _restore = Restore {
+            tracker: self,
+            previous,
+        };
+        f()
+    }
+
+    /// Returns the ranges of the pointed-to values which have been read, in
+    /// the order they finished parsing.
+    #[must_use]
+    pub fn pointees(&self) -> &[Range<u64>] {
+        &self.pointees
+    }
+
+    /// Returns the conflicts which have been found.
+    ///
+    /// In strict mode, this also contains the conflict which was returned as
+    /// an error.
+    #[must_use]
+    pub fn conflicts(&self) -> &[RegionConflict] {
+        &self.conflicts
+    }
+
+    /// Forgets every recorded range and conflict. Sections are kept.
+    pub fn clear(&mut self) {
+        self.pointees.clear();
+        self.conflicts.clear();
+    }
+
+    fn record(&mut self, mark: usize, pointee: Range<u64>) -> BinResult<()> {
+        let found = self.conflicts.len();
+
+        if pointee.start < pointee.end {
+            for other in &self.pointees[..mark] {
+                if *other != pointee && other.start < pointee.end && pointee.start < other.end {
+                    self.conflicts.push(RegionConflict::Overlap {
+                        pointee: pointee.clone(),
+                        other: other.clone(),
+                    });
+                }
+            }
+        }
+
+        if let Some(section) = self
+            .sections
+            .iter()
+            .find(|section| section.contains(&pointee.start) && pointee.end > section.end)
+        {
+            self.conflicts.push(RegionConflict::PastSection {
+                pointee: pointee.clone(),
+                section: section.clone(),
+            });
+        }
+
+        let pos = pointee.start;
+        self.pointees.push(pointee);
+        match self.conflicts.get(found) {
+            Some(conflict) if self.strict => Err(crate::Error::Custom {
+                pos,
+                err: Box::new(conflict.clone()),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A problem with the range of a pointed-to value found by a
+/// [`RegionTracker`].
+#[cfg(feature = "std")]
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "std")))]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RegionConflict {
+    /// A pointed-to value overlaps one which was read before it.
+    Overlap {
+        /// The range of the pointed-to value.
+        pointee: Range<u64>,
+
+        /// The range of the earlier value which it overlaps.
+        other: Range<u64>,
+    },
+
+    /// A pointed-to value extends past the end of its section.
+    PastSection {
+        /// The range of the pointed-to value.
+        pointee: Range<u64>,
+
+        /// The section in which the value starts.
+        section: Range<u64>,
+    },
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for RegionConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Overlap { pointee, other } => write!(
+                f,
+                "pointed-to value at {:#x}..{:#x} overlaps value at {:#x}..{:#x}",
+                pointee.start, pointee.end, other.start, other.end
+            ),
+            Self::PastSection { pointee, section } => write!(
+                f,
+                "pointed-to value at {:#x}..{:#x} extends past the end of section {:#x}..{:#x}",
+                pointee.start, pointee.end, section.start, section.end
+            ),
+        }
+    }
+}
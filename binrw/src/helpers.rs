@@ -1,8 +1,8 @@
 //! Helper functions for reading and writing data.
 
 use crate::{
-    io::{self, Read, Seek},
-    BinRead, BinResult, Endian, Error,
+    io::{self, Adler32, BitOrder, BitReader, BitWriter, Checksum, Crc32, Read, Seek, Write},
+    BinRead, BinResult, BinWrite, Endian, Error,
 };
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
@@ -186,6 +186,281 @@ where
     }
 }
 
+/// Creates a parser that reads items into a collection until a condition is
+/// met, then skips to the next multiple of `align` bytes. The terminal item is
+/// added to the collection.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`].
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::until_aligned, io::Cursor, BinReaderExt};
+/// #[derive(BinRead)]
+/// struct Symbol {
+///     #[br(parse_with = until_aligned(|&byte| byte == 0, 4))]
+///     name: Vec<u8>,
+///     value: u8,
+/// }
+///
+/// # let mut x = Cursor::new(b"main\0\0\0\0\x07");
+/// # let x: Symbol = x.read_be().unwrap();
+/// # assert_eq!(x.name, b"main\0");
+/// # assert_eq!(x.value, 7);
+/// ```
+pub fn until_aligned<Reader, T, CondFn, Arg, Ret>(
+    cond: CondFn,
+    align: u64,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<Ret>
+where
+    T: for<'a> BinRead<Args<'a> = Arg>,
+    Reader: Read + Seek,
+    CondFn: Fn(&T) -> bool,
+    Arg: Clone,
+    Ret: FromIterator<T>,
+{
+    let read = until_with(cond, T::read_options);
+    move |reader, endian, args| {
+        let items = read(reader, endian, args)?;
+        skip_to_alignment(reader, align)?;
+        Ok(items)
+    }
+}
+
+/// Creates a parser that reads items into a collection until a condition is
+/// met, then skips to the next multiple of `align` bytes. The terminal item is
+/// discarded.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`].
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::until_exclusive_aligned, io::Cursor, BinReaderExt};
+/// #[derive(BinRead)]
+/// struct Symbol {
+///     #[br(parse_with = until_exclusive_aligned(|&byte| byte == 0, 4))]
+///     name: Vec<u8>,
+///     value: u8,
+/// }
+///
+/// # let mut x = Cursor::new(b"ab\0\xff\x07");
+/// # let x: Symbol = x.read_be().unwrap();
+/// # assert_eq!(x.name, b"ab");
+/// # assert_eq!(x.value, 7);
+/// ```
+pub fn until_exclusive_aligned<Reader, T, CondFn, Arg, Ret>(
+    cond: CondFn,
+    align: u64,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<Ret>
+where
+    T: for<'a> BinRead<Args<'a> = Arg>,
+    Reader: Read + Seek,
+    CondFn: Fn(&T) -> bool,
+    Arg: Clone,
+    Ret: FromIterator<T>,
+{
+    let read = until_exclusive_with(cond, T::read_options);
+    move |reader, endian, args| {
+        let items = read(reader, endian, args)?;
+        skip_to_alignment(reader, align)?;
+        Ok(items)
+    }
+}
+
+/// Seeks forward to the next multiple of `align`.
+fn skip_to_alignment<Reader: Seek>(reader: &mut Reader, align: u64) -> BinResult<()> {
+    if align > 1 {
+        let pos = reader.stream_position()?;
+        let padding = (align - pos % align) % align;
+        if padding != 0 {
+            reader.seek(io::SeekFrom::Start(pos + padding))?;
+        }
+    }
+    Ok(())
+}
+
+/// Creates a parser that reads items into a collection until a sequence of
+/// items is read. The terminal sequence is added to the collection.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`]. An empty sequence ends the collection immediately.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::until_sequence, io::Cursor, BinReaderExt};
+/// #[derive(BinRead)]
+/// struct Headers {
+///     #[br(parse_with = until_sequence(b"\r\n\r\n"))]
+///     data: Vec<u8>,
+/// }
+///
+/// # let mut x = Cursor::new(b"A: 1\r\nB: 2\r\n\r\nbody");
+/// # let x: Headers = x.read_be().unwrap();
+/// # assert_eq!(x.data, b"A: 1\r\nB: 2\r\n\r\n");
+/// ```
+pub fn until_sequence<Reader, T, Seq, Arg, Ret>(
+    sequence: Seq,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<Ret>
+where
+    T: for<'a> BinRead<Args<'a> = Arg> + PartialEq,
+    Reader: Read + Seek,
+    Seq: AsRef<[T]>,
+    Arg: Clone,
+    Ret: FromIterator<T>,
+{
+    until_sequence_with(sequence, T::read_options)
+}
+
+/// Creates a parser that uses a given function to read items into a collection
+/// until a sequence of items is read. The terminal sequence is added to the
+/// collection.
+///
+/// The given `read` function should return one item each time it is called.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`]. An empty sequence ends the collection immediately.
+///
+/// # Examples
+///
+/// Reading records separated by two big-endian `u16` markers:
+///
+/// ```
+/// # use binrw::{BinRead, helpers::until_sequence_with, io::Cursor, BinReaderExt};
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Record {
+///     #[br(parse_with = until_sequence_with([0xffff, 0], u16::read_options))]
+///     data: Vec<u16>,
+/// }
+///
+/// # let mut x = Cursor::new(b"\0\x01\xff\xff\0\x02\xff\xff\0\0");
+/// # let x: Record = x.read_be().unwrap();
+/// # assert_eq!(x.data, &[1, 0xffff, 2, 0xffff, 0]);
+/// ```
+pub fn until_sequence_with<Reader, T, Seq, Arg, ReadFn, Ret>(
+    sequence: Seq,
+    read: ReadFn,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<Ret>
+where
+    T: PartialEq,
+    Reader: Read + Seek,
+    Seq: AsRef<[T]>,
+    Arg: Clone,
+    ReadFn: Fn(&mut Reader, Endian, Arg) -> BinResult<T>,
+    Ret: FromIterator<T>,
+{
+    move |reader, endian, args| {
+        read_until_sequence(reader, endian, args, sequence.as_ref(), &read)
+            .map(|items| items.into_iter().collect())
+    }
+}
+
+/// Creates a parser that reads items into a collection until a sequence of
+/// items is read. The terminal sequence is consumed and discarded.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`]. An empty sequence ends the collection immediately.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::until_sequence_exclusive, io::Cursor, BinReaderExt};
+/// #[derive(BinRead)]
+/// struct Headers {
+///     #[br(parse_with = until_sequence_exclusive(b"\r\n\r\n"))]
+///     data: Vec<u8>,
+///     body: [u8; 4],
+/// }
+///
+/// # let mut x = Cursor::new(b"A: 1\r\nB: 2\r\n\r\nbody");
+/// # let x: Headers = x.read_be().unwrap();
+/// # assert_eq!(x.data, b"A: 1\r\nB: 2");
+/// # assert_eq!(&x.body, b"body");
+/// ```
+pub fn until_sequence_exclusive<Reader, T, Seq, Arg, Ret>(
+    sequence: Seq,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<Ret>
+where
+    T: for<'a> BinRead<Args<'a> = Arg> + PartialEq,
+    Reader: Read + Seek,
+    Seq: AsRef<[T]>,
+    Arg: Clone,
+    Ret: FromIterator<T>,
+{
+    until_sequence_exclusive_with(sequence, T::read_options)
+}
+
+/// Creates a parser that uses a given function to read items into a collection
+/// until a sequence of items is read. The terminal sequence is consumed and
+/// discarded.
+///
+/// The given `read` function should return one item each time it is called.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`]. An empty sequence ends the collection immediately.
+///
+/// # Examples
+///
+/// Reading records separated by two big-endian `u16` markers:
+///
+/// ```
+/// # use binrw::{BinRead, helpers::until_sequence_exclusive_with, io::Cursor, BinReaderExt};
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Record {
+///     #[br(parse_with = until_sequence_exclusive_with([0xffff, 0], u16::read_options))]
+///     data: Vec<u16>,
+/// }
+///
+/// # let mut x = Cursor::new(b"\0\x01\xff\xff\0\x02\xff\xff\0\0");
+/// # let x: Record = x.read_be().unwrap();
+/// # assert_eq!(x.data, &[1, 0xffff, 2]);
+/// ```
+pub fn until_sequence_exclusive_with<Reader, T, Seq, Arg, ReadFn, Ret>(
+    sequence: Seq,
+    read: ReadFn,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<Ret>
+where
+    T: PartialEq,
+    Reader: Read + Seek,
+    Seq: AsRef<[T]>,
+    Arg: Clone,
+    ReadFn: Fn(&mut Reader, Endian, Arg) -> BinResult<T>,
+    Ret: FromIterator<T>,
+{
+    move |reader, endian, args| {
+        let sequence = sequence.as_ref();
+        read_until_sequence(reader, endian, args, sequence, &read).map(|mut items| {
+            items.truncate(items.len() - sequence.len());
+            items.into_iter().collect()
+        })
+    }
+}
+
+/// Reads items until the last items read are equal to `sequence`.
+fn read_until_sequence<Reader, T, Arg, ReadFn>(
+    reader: &mut Reader,
+    endian: Endian,
+    args: Arg,
+    sequence: &[T],
+    read: &ReadFn,
+) -> BinResult<Vec<T>>
+where
+    T: PartialEq,
+    Arg: Clone,
+    ReadFn: Fn(&mut Reader, Endian, Arg) -> BinResult<T>,
+{
+    let mut items = Vec::new();
+    while !items.ends_with(sequence) {
+        items.push(read(reader, endian, args.clone())?);
+    }
+    Ok(items)
+}
+
 /// Creates a parser that reads items into a collection until the end of the
 /// input stream.
 ///
@@ -276,6 +551,203 @@ where
     }
 }
 
+/// How [`until_eof_resync`] finds the start of the next item after an item
+/// fails to parse.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Resync {
+    /// Skips the given number of bytes from the start of the bad item.
+    ///
+    /// At least one byte is always skipped.
+    Skip(u64),
+    /// Scans forward from the byte after the start of the bad item until the
+    /// given magic is found, and continues reading from there.
+    ///
+    /// If the magic is not found, reading stops at the end of the input.
+    Magic(Vec<u8>),
+}
+
+/// The errors recorded by [`until_eof_resync`], each with the position where
+/// the bad item started.
+pub type ResyncErrors = Vec<(u64, Error)>;
+
+/// Creates a parser that reads items into a collection until the end of the
+/// input stream, skipping over items that fail to parse.
+///
+/// When an item fails to parse, the error is recorded along with the position
+/// where the item started, the reader is moved to the start of the next item
+/// as directed by `resync`, and reading continues. The parser returns both the
+/// items which were read successfully and the recorded errors, in order.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`].
+///
+/// # Errors
+///
+/// If seeking or reading fails while resynchronising, an [`Error`] variant
+/// will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, Error, helpers::{until_eof_resync, Resync}, io::Cursor, BinReaderExt};
+/// #[derive(BinRead)]
+/// #[br(magic = b"R")]
+/// struct Record {
+///     value: u8,
+/// }
+///
+/// #[derive(BinRead)]
+/// struct Dump {
+///     #[br(parse_with = until_eof_resync(Resync::Magic(b"R".to_vec())))]
+///     records: (Vec<Record>, Vec<(u64, Error)>),
+/// }
+///
+/// # let mut x = Cursor::new(b"R\x01X\xffR\x02");
+/// # let x: Dump = x.read_be().unwrap();
+/// # let (records, errors) = x.records;
+/// # assert_eq!(records.iter().map(|r| r.value).collect::<Vec<_>>(), [1, 2]);
+/// # assert_eq!(errors.len(), 1);
+/// # assert_eq!(errors[0].0, 2);
+/// ```
+pub fn until_eof_resync<Reader, T, Arg, Ret>(
+    resync: Resync,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<(Ret, ResyncErrors)>
+where
+    T: for<'a> BinRead<Args<'a> = Arg>,
+    Reader: Read + Seek,
+    Arg: Clone,
+    Ret: FromIterator<T>,
+{
+    until_eof_resync_with(resync, T::read_options)
+}
+
+/// Creates a parser that uses a given function to read items into a collection
+/// until the end of the input stream, skipping over items that fail to parse.
+///
+/// The given `read` function should return one item each time it is called.
+/// Errors are handled as in [`until_eof_resync`].
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`].
+///
+/// # Errors
+///
+/// If seeking or reading fails while resynchronising, an [`Error`] variant
+/// will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::{until_eof_resync_with, Resync}, io::Cursor, BinReaderExt};
+/// #[binrw::parser(reader, endian)]
+/// fn even() -> binrw::BinResult<u16> {
+///     let pos = reader.stream_position()?;
+///     let value = u16::read_options(reader, endian, ())?;
+///     if value % 2 == 0 {
+///         Ok(value)
+///     } else {
+///         Err(binrw::Error::AssertFail { pos, message: "odd".into() })
+///     }
+/// }
+///
+/// # let mut x = Cursor::new(b"\0\x02\0\x03\0\x04");
+/// let (values, errors): (Vec<u16>, _) =
+///     until_eof_resync_with(Resync::Skip(2), even)(&mut x, binrw::Endian::Big, ())?;
+/// # assert_eq!(values, [2, 4]);
+/// # assert_eq!(errors.iter().map(|(pos, _)| *pos).collect::<Vec<_>>(), [2]);
+/// # Ok::<(), binrw::Error>(())
+/// ```
+pub fn until_eof_resync_with<Reader, T, Arg, ReadFn, Ret>(
+    resync: Resync,
+    read: ReadFn,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<(Ret, ResyncErrors)>
+where
+    Reader: Read + Seek,
+    Arg: Clone,
+    ReadFn: Fn(&mut Reader, Endian, Arg) -> BinResult<T>,
+    Ret: FromIterator<T>,
+{
+    move |reader, endian, args| {
+        let mut errors = Vec::new();
+        let mut failed = None;
+        let items = from_fn(|| loop {
+            let pos = match reader.stream_position() {
+                Ok(pos) => pos,
+                Err(err) => {
+                    failed = Some(err.into());
+                    return None;
+                }
+            };
+            match read(reader, endian, args.clone()) {
+                Ok(item) => return Some(item),
+                Err(err) if err.is_eof() => return None,
+                Err(err) => {
+                    errors.push((pos, err));
+                    match resync_after(reader, pos, &resync) {
+                        Ok(true) => {}
+                        Ok(false) => return None,
+                        Err(err) => {
+                            failed = Some(err);
+                            return None;
+                        }
+                    }
+                }
+            }
+        })
+        .fuse()
+        .collect();
+
+        match failed {
+            Some(err) => Err(err),
+            None => Ok((items, errors)),
+        }
+    }
+}
+
+/// Moves the reader to the start of the next item after the item at `pos`
+/// failed to parse. Returns `false` if there is no next item.
+fn resync_after<Reader: Read + Seek>(
+    reader: &mut Reader,
+    pos: u64,
+    resync: &Resync,
+) -> BinResult<bool> {
+    const CHUNK_SIZE: usize = 4096;
+
+    match resync {
+        Resync::Skip(count) => {
+            reader.seek(io::SeekFrom::Start(pos + (*count).max(1)))?;
+            Ok(true)
+        }
+        Resync::Magic(magic) => {
+            let mut start = pos + 1;
+            reader.seek(io::SeekFrom::Start(start))?;
+            if magic.is_empty() {
+                return Ok(true);
+            }
+
+            let mut window = Vec::new();
+            loop {
+                let filled = window.len();
+                window.resize(filled + CHUNK_SIZE, 0);
+                let n = reader.read(&mut window[filled..])?;
+                window.truncate(filled + n);
+
+                if let Some(index) = window.windows(magic.len()).position(|w| w == magic) {
+                    reader.seek(io::SeekFrom::Start(start + index as u64))?;
+                    return Ok(true);
+                } else if n == 0 {
+                    return Ok(false);
+                }
+
+                // Keep enough bytes to find a magic split across chunks
+                let consumed = window.len().saturating_sub(magic.len() - 1);
+                window.drain(..consumed);
+                start += consumed as u64;
+            }
+        }
+    }
+}
+
 /// Creates a parser that builds a collection using items from the given
 /// iterable object as arguments for the parser.
 ///
@@ -453,22 +925,12 @@ where
         let mut container = core::iter::empty::<T>().collect::<Ret>();
 
         vec_fast_int!(try (i8 i16 u16 i32 u32 i64 u64 i128 u128) using (container, reader, endian, n) else {
-            // This extra branch for `Vec<u8>` makes it faster than
-            // `vec_fast_int`, but *only* because `vec_fast_int` is not allowed
-            // to use unsafe code to eliminate the unnecessary zero-fill.
-            // Otherwise, performance would be identical and it could be
-            // deleted.
+            // This extra branch for `Vec<u8>` is shared with `count_bytes`,
+            // which copies the bytes straight into the buffer and only grows
+            // it as data is actually read.
             if let Some(bytes) = <dyn core::any::Any>::downcast_mut::<Vec<u8>>(&mut container) {
-                bytes.reserve_exact(n);
-                let byte_count = reader
-                    .take(n.try_into().map_err(not_enough_bytes)?)
-                    .read_to_end(bytes)?;
-
-                if byte_count == n {
-                    Ok(container)
-                } else {
-                    Err(not_enough_bytes(()))
-                }
+                *bytes = count_bytes(n)(reader, endian, ())?;
+                Ok(container)
             } else {
                 core::iter::repeat_with(|| read(reader, endian, args.clone()))
                 .take(n)
@@ -478,27 +940,895 @@ where
     }
 }
 
-/// Reads a 24-bit unsigned integer.
+/// Creates a parser that reads N bytes into a [`Vec<u8>`].
+///
+/// This is the fastest way to read a raw byte payload of a known length, since
+/// the bytes are copied straight from the reader into the buffer instead of
+/// being read one at a time. Only a bounded amount of memory is allocated
+/// before any bytes are read, and the buffer grows with the data, so a count
+/// read from untrusted input cannot cause a huge allocation by itself.
 ///
 /// # Errors
 ///
-/// If reading fails, an [`Error`](crate::Error) variant will be returned.
+/// If the reader runs out of data before N bytes are read, or reading fails,
+/// an [`Error`](crate::Error) variant will be returned.
 ///
 /// # Examples
 ///
 /// ```
-/// # use binrw::{prelude::*, io::Cursor};
+/// # use binrw::{BinRead, helpers::count_bytes, io::Cursor, BinReaderExt};
 /// #[derive(BinRead)]
-/// # #[derive(Debug, PartialEq)]
-/// struct Test {
-///     flags: u8,
-///     #[br(parse_with = binrw::helpers::read_u24)]
-///     value: u32,
+/// struct Payload {
+///     len: u8,
+///
+///     #[br(parse_with = count_bytes(len.into()))]
+///     data: Vec<u8>,
 /// }
-/// #
-/// # assert_eq!(
-/// #     Test::read_be(&mut Cursor::new(b"\x01\x02\x03\x04")).unwrap(),
-/// #     Test { flags: 1, value: 0x20304 }
+///
+/// # let mut x = Cursor::new(b"\x03\x01\x02\x03");
+/// # let x: Payload = x.read_be().unwrap();
+/// # assert_eq!(x.data, &[1, 2, 3]);
+/// # let mut x = Cursor::new(b"\x03\x01\x02");
+/// # assert!(x.read_be::<Payload>().err().unwrap().is_eof());
+/// ```
+pub fn count_bytes<R: Read + Seek>(n: usize) -> impl Fn(&mut R, Endian, ()) -> BinResult<Vec<u8>> {
+    // Bytes are read in chunks of at most this size, so a bad count cannot
+    // cause a huge allocation before the data to fill it has been read
+    const MAX_CHUNK: usize = 64 * 1024;
+
+    move |reader, _, ()| {
+        let mut bytes = Vec::with_capacity(n.min(MAX_CHUNK));
+        while bytes.len() < n {
+            let start = bytes.len();
+            bytes.resize(start + (n - start).min(MAX_CHUNK), 0);
+            reader.read_exact(&mut bytes[start..])?;
+        }
+        Ok(bytes)
+    }
+}
+
+/// Creates a parser that reads N items into a collection, pre-allocating
+/// space for at most `max` items.
+///
+/// This helper is like [`count`], but a count read from untrusted input cannot
+/// cause a huge allocation before any items are read. Space for up to `max`
+/// items is allocated up front, and the collection grows as more items are
+/// read, so reading any number of items up to `max` is as fast as with
+/// [`count`].
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`].
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::count_capped, io::Cursor, BinReaderExt};
+/// #[derive(BinRead)]
+/// struct CountBytes {
+///     len: u32,
+///
+///     #[br(parse_with = count_capped(len as usize, 4096))]
+///     data: Vec<u8>,
+/// }
+///
+/// # let mut x = Cursor::new(b"\0\0\0\x03\x01\x02\x03");
+/// # let x: CountBytes = x.read_be().unwrap();
+/// # assert_eq!(x.data, &[1, 2, 3]);
+/// # let mut x = Cursor::new(b"\xff\xff\xff\xff\x01\x02\x03");
+/// # assert!(x.read_be::<CountBytes>().err().unwrap().is_eof());
+/// ```
+pub fn count_capped<R, T, Arg, Ret>(
+    n: usize,
+    max: usize,
+) -> impl Fn(&mut R, Endian, Arg) -> BinResult<Ret>
+where
+    T: for<'a> BinRead<Args<'a> = Arg>,
+    R: Read + Seek,
+    Arg: Clone,
+    Ret: FromIterator<T> + 'static,
+{
+    count_capped_with(n, max, T::read_options)
+}
+
+/// Creates a parser that uses a given function to read N items into a
+/// collection, pre-allocating space for at most `max` items.
+///
+/// The given `read` function should return one item each time it is called.
+///
+/// This helper is like [`count_with`], but a count read from untrusted input
+/// cannot cause a huge allocation before any items are read. See
+/// [`count_capped`].
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::{count, count_capped_with}, io::Cursor, BinReaderExt};
+/// #[derive(BinRead)]
+/// struct Pairs {
+///     len: u8,
+///
+///     #[br(parse_with = count_capped_with(len as usize, 16, count(2)))]
+///     data: Vec<Vec<u8>>,
+/// }
+///
+/// # let mut x = Cursor::new(b"\x02\x01\x02\x03\x04");
+/// # let x: Pairs = x.read_be().unwrap();
+/// # assert_eq!(x.data, &[[1, 2], [3, 4]]);
+/// # let mut x = Cursor::new(b"\xff\x01\x02\x03\x04");
+/// # assert!(x.read_be::<Pairs>().err().unwrap().is_eof());
+/// ```
+pub fn count_capped_with<R, T, Arg, ReadFn, Ret>(
+    n: usize,
+    max: usize,
+    read: ReadFn,
+) -> impl Fn(&mut R, Endian, Arg) -> BinResult<Ret>
+where
+    R: Read + Seek,
+    Arg: Clone,
+    ReadFn: Fn(&mut R, Endian, Arg) -> BinResult<T>,
+    Ret: FromIterator<T> + 'static,
+{
+    move |reader, endian, args| {
+        let mut container = core::iter::empty::<T>().collect::<Ret>();
+
+        if let Some(bytes) = <dyn core::any::Any>::downcast_mut::<Vec<u8>>(&mut container) {
+            bytes.reserve_exact(n.min(max));
+            let byte_count = reader
+                .take(n.try_into().map_err(not_enough_bytes)?)
+                .read_to_end(bytes)?;
+
+            if byte_count == n {
+                Ok(container)
+            } else {
+                Err(not_enough_bytes(()))
+            }
+        } else {
+            // Collecting a `Vec` into a `Vec` reuses its allocation, so this
+            // only costs an extra copy for other collection types
+            let mut items = Vec::with_capacity(n.min(max));
+            for _ in 0..n {
+                items.push(read(reader, endian, args.clone())?);
+            }
+            Ok(items.into_iter().collect())
+        }
+    }
+}
+
+/// Creates a parser that reads run-length encoded items into a collection
+/// until it contains `len` items.
+///
+/// Each run is a count of type `C` followed by an item, which is repeated
+/// `count` times. Runs are read until the collection has exactly `len` items.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`].
+///
+/// # Errors
+///
+/// If reading fails, or a run would make the collection longer than `len`
+/// items, an [`Error`](crate::Error) variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::read_rle, io::Cursor, BinReaderExt};
+/// #[derive(BinRead)]
+/// struct Row {
+///     width: u8,
+///
+///     #[br(parse_with = read_rle::<u8, _, _, _, _>(width.into()))]
+///     pixels: Vec<u8>,
+/// }
+///
+/// # let mut x = Cursor::new(b"\x06\x03\xff\x01\x00\x02\x07");
+/// # let x: Row = x.read_be().unwrap();
+/// # assert_eq!(x.pixels, &[0xff, 0xff, 0xff, 0, 7, 7]);
+/// # let mut x = Cursor::new(b"\x02\x03\xff");
+/// # assert!(x.read_be::<Row>().is_err());
+/// ```
+pub fn read_rle<C, T, R, Arg, Ret>(len: usize) -> impl Fn(&mut R, Endian, Arg) -> BinResult<Ret>
+where
+    C: for<'a> BinRead<Args<'a> = ()> + Into<u64>,
+    T: for<'a> BinRead<Args<'a> = Arg> + Clone,
+    R: Read + Seek,
+    Arg: Clone,
+    Ret: FromIterator<T>,
+{
+    move |reader, endian, args| {
+        let mut items = Vec::new();
+        while items.len() < len {
+            let pos = reader.stream_position()?;
+            let count = C::read_options(reader, endian, ())?.into();
+            let remaining = len - items.len();
+            if count > remaining as u64 {
+                return Err(Error::AssertFail {
+                    pos,
+                    message: alloc::format!(
+                        "run of {count} items is longer than the {remaining} remaining items"
+                    ),
+                });
+            }
+
+            let value = T::read_options(reader, endian, args.clone())?;
+            // Lint: The count is at most `remaining`, which is a `usize`.
+            #[allow(clippy::cast_possible_truncation)]
+            items.extend(core::iter::repeat(value).take(count as usize));
+        }
+        Ok(items.into_iter().collect())
+    }
+}
+
+/// Creates a writer that writes the items of a collection run-length encoded.
+///
+/// Each run of equal items is written as a count of type `C` followed by the
+/// item. Runs which are too long for `C` are split. This is the writing
+/// counterpart of [`read_rle`].
+///
+/// # Errors
+///
+/// If writing fails, an [`Error`](crate::Error) variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{binwrite, BinWrite, helpers::write_rle, io::Cursor};
+/// #[binwrite]
+/// struct Row {
+///     #[bw(calc = pixels.len() as u8)]
+///     width: u8,
+///
+///     #[bw(write_with = write_rle::<u8, _, _, _, _>())]
+///     pixels: Vec<u8>,
+/// }
+///
+/// # let mut x = Cursor::new(Vec::new());
+/// # Row { pixels: vec![0xff, 0xff, 0xff, 0, 7, 7] }.write_be(&mut x).unwrap();
+/// # assert_eq!(x.into_inner(), b"\x06\x03\xff\x01\x00\x02\x07");
+/// # let mut x = Cursor::new(Vec::new());
+/// # Row { pixels: vec![1; 300] }.write_be(&mut x).unwrap();
+/// # assert_eq!(x.into_inner(), b"\x2c\xff\x01\x2d\x01");
+/// ```
+pub fn write_rle<C, T, Coll, W, Arg>() -> impl Fn(&Coll, &mut W, Endian, Arg) -> BinResult<()>
+where
+    C: for<'a> BinWrite<Args<'a> = ()> + TryFrom<usize>,
+    T: for<'a> BinWrite<Args<'a> = Arg> + PartialEq,
+    for<'a> &'a Coll: IntoIterator<Item = &'a T>,
+    W: Write + Seek,
+    Arg: Clone,
+{
+    move |items, writer, endian, args| {
+        let mut write_run = |value: &T, len: usize| {
+            let count = C::try_from(len).map_err(|_| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "the run count type cannot hold a run of one item",
+                ))
+            })?;
+            count.write_options(writer, endian, ())?;
+            value.write_options(writer, endian, args.clone())
+        };
+
+        let mut run: Option<(&T, usize)> = None;
+        for item in items {
+            if let Some((value, len)) = &mut run {
+                if *value == item && C::try_from(*len + 1).is_ok() {
+                    *len += 1;
+                    continue;
+                }
+                write_run(value, *len)?;
+            }
+            run = Some((item, 1));
+        }
+
+        if let Some((value, len)) = run {
+            write_run(value, len)?;
+        }
+        Ok(())
+    }
+}
+
+/// Creates a parser that reads `N` parallel arrays of `count` items stored one
+/// after another (planar) into a collection of `[T; N]` elements
+/// (interleaved).
+///
+/// If `stride` is given, each array starts `stride` bytes after the start of
+/// the previous one, and the reader seeks to the start of each array.
+/// Otherwise, the arrays are read one after another. The reader is left at the
+/// end of the last array.
+///
+/// This helper can be used to read into any collection type that implements
+/// [`FromIterator`], and elements can be converted into structs using
+/// [`map`](crate::docs::attribute#map).
+///
+/// # Errors
+///
+/// If reading fails, an [`Error`](crate::Error) variant will be returned.
+///
+/// # Examples
+///
+/// Reading planar stereo audio samples into left-right pairs:
+///
+/// ```
+/// # use binrw::{BinRead, helpers::read_planar, io::Cursor, BinReaderExt};
+/// #[derive(BinRead)]
+/// #[br(little)]
+/// struct Samples {
+///     count: u16,
+///
+///     #[br(parse_with = read_planar(count.into(), None))]
+///     frames: Vec<[i16; 2]>,
+/// }
+///
+/// # let mut x = Cursor::new(b"\x02\0\x01\0\x02\0\xff\xff\xfe\xff");
+/// # let x: Samples = x.read_be().unwrap();
+/// # assert_eq!(x.frames, &[[1, -1], [2, -2]]);
+/// # let mut x = Cursor::new(b"\x01\x04\0\0\x02\x05\0\0\x03\x06");
+/// # let rgb: Vec<[u8; 3]> = read_planar(2, Some(4))(&mut x, binrw::Endian::Little, ()).unwrap();
+/// # assert_eq!(rgb, &[[1, 2, 3], [4, 5, 6]]);
+/// ```
+pub fn read_planar<const N: usize, T, R, Arg, Ret>(
+    count: usize,
+    stride: Option<u64>,
+) -> impl Fn(&mut R, Endian, Arg) -> BinResult<Ret>
+where
+    T: for<'a> BinRead<Args<'a> = Arg> + Default,
+    R: Read + Seek,
+    Arg: Clone,
+    Ret: FromIterator<[T; N]>,
+{
+    move |reader, endian, args| {
+        let start = reader.stream_position()?;
+        let mut items = Vec::new();
+        for plane in 0..N {
+            if let Some(stride) = stride {
+                reader.seek(io::SeekFrom::Start(plane_start(start, stride, plane)?))?;
+            }
+
+            for index in 0..count {
+                let value = T::read_options(reader, endian, args.clone())?;
+                if plane == 0 {
+                    items.push(core::array::from_fn(|_| T::default()));
+                }
+                items[index][plane] = value;
+            }
+        }
+        Ok(items.into_iter().collect())
+    }
+}
+
+/// Creates a writer that writes a collection of `[T; N]` elements
+/// (interleaved) as `N` parallel arrays stored one after another (planar).
+///
+/// If `stride` is given, each array starts `stride` bytes after the start of
+/// the previous one, and the writer seeks to the start of each array, skipping
+/// over any gap. Otherwise, the arrays are written one after another. This is
+/// the writing counterpart of [`read_planar`].
+///
+/// # Errors
+///
+/// If writing fails, an [`Error`](crate::Error) variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinWrite, helpers::write_planar, io::Cursor};
+/// #[derive(BinWrite)]
+/// #[bw(little)]
+/// struct Pixels {
+///     #[bw(write_with = write_planar(Some(4)))]
+///     rgb: Vec<[u8; 3]>,
+/// }
+///
+/// # let mut x = Cursor::new(Vec::new());
+/// # Pixels { rgb: vec![[1, 2, 3], [4, 5, 6]] }.write(&mut x).unwrap();
+/// # assert_eq!(x.into_inner(), b"\x01\x04\0\0\x02\x05\0\0\x03\x06");
+/// ```
+pub fn write_planar<const N: usize, T, Coll, W, Arg>(
+    stride: Option<u64>,
+) -> impl Fn(&Coll, &mut W, Endian, Arg) -> BinResult<()>
+where
+    T: for<'a> BinWrite<Args<'a> = Arg>,
+    for<'a> &'a Coll: IntoIterator<Item = &'a [T; N]>,
+    W: Write + Seek,
+    Arg: Clone,
+{
+    move |items, writer, endian, args| {
+        let start = writer.stream_position()?;
+        for plane in 0..N {
+            if let Some(stride) = stride {
+                writer.seek(io::SeekFrom::Start(plane_start(start, stride, plane)?))?;
+            }
+
+            for item in items {
+                item[plane].write_options(writer, endian, args.clone())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn plane_start(start: u64, stride: u64, plane: usize) -> BinResult<u64> {
+    (plane as u64)
+        .checked_mul(stride)
+        .and_then(|offset| start.checked_add(offset))
+        .ok_or_else(|| {
+            Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the planar array stride is too large",
+            ))
+        })
+}
+
+/// Creates a parser that reads N key-value pairs into a collection.
+///
+/// Each key is read with `key_args` and each value with `value_args`. This
+/// helper can be used to read into any collection type of key-value pairs
+/// that implements [`Default`] and [`Extend<(K, V)>`](Extend), like
+/// [`HashMap`](std::collections::HashMap) and
+/// [`BTreeMap`](std::collections::BTreeMap).
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinRead, helpers::read_map, io::Cursor, BinReaderExt};
+/// # use std::collections::BTreeMap;
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Dictionary {
+///     len: u16,
+///
+///     #[br(parse_with = read_map(len.into(), (), ()))]
+///     entries: BTreeMap<u8, u16>,
+/// }
+///
+/// # let mut x = Cursor::new(b"\0\x02\x01\0\x0a\x02\0\x14");
+/// # let x: Dictionary = x.read_be().unwrap();
+/// # assert_eq!(x.entries[&1], 10);
+/// # assert_eq!(x.entries[&2], 20);
+/// ```
+pub fn read_map<R, K, V, KeyArgs, ValueArgs, Ret>(
+    n: usize,
+    key_args: KeyArgs,
+    value_args: ValueArgs,
+) -> impl Fn(&mut R, Endian, ()) -> BinResult<Ret>
+where
+    K: for<'a> BinRead<Args<'a> = KeyArgs>,
+    V: for<'a> BinRead<Args<'a> = ValueArgs>,
+    R: Read + Seek,
+    KeyArgs: Clone,
+    ValueArgs: Clone,
+    Ret: Default + Extend<(K, V)> + IntoIterator<Item = (K, V)>,
+{
+    move |reader, endian, ()| {
+        let mut map = Ret::default();
+        for _ in 0..n {
+            let key = K::read_options(reader, endian, key_args.clone())?;
+            let value = V::read_options(reader, endian, value_args.clone())?;
+            map.extend(core::iter::once((key, value)));
+        }
+        Ok(map)
+    }
+}
+
+/// Creates a writer that writes each key-value pair of a collection.
+///
+/// Each key is written with `key_args` and each value with `value_args`. This
+/// helper can be used to write any collection type whose references iterate
+/// over `(&K, &V)` pairs, like [`HashMap`](std::collections::HashMap) and
+/// [`BTreeMap`](std::collections::BTreeMap). The number of pairs is not
+/// written.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{binwrite, BinWrite, helpers::write_map, io::Cursor};
+/// # use std::collections::BTreeMap;
+/// #[binwrite]
+/// #[bw(big)]
+/// struct Dictionary {
+///     #[bw(calc = entries.len() as u16)]
+///     len: u16,
+///
+///     #[bw(write_with = write_map((), ()))]
+///     entries: BTreeMap<u8, u16>,
+/// }
+///
+/// # let mut entries = BTreeMap::new();
+/// # entries.insert(2, 20);
+/// # entries.insert(1, 10);
+/// # let mut x = Cursor::new(Vec::new());
+/// # Dictionary { entries }.write(&mut x).unwrap();
+/// # assert_eq!(x.into_inner(), b"\0\x02\x01\0\x0a\x02\0\x14");
+/// ```
+pub fn write_map<M, W, K, V, KeyArgs, ValueArgs>(
+    key_args: KeyArgs,
+    value_args: ValueArgs,
+) -> impl Fn(&M, &mut W, Endian, ()) -> BinResult<()>
+where
+    for<'a> &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+    K: for<'a> BinWrite<Args<'a> = KeyArgs>,
+    V: for<'a> BinWrite<Args<'a> = ValueArgs>,
+    W: Write + Seek,
+    KeyArgs: Clone,
+    ValueArgs: Clone,
+{
+    move |map, writer, endian, ()| {
+        for (key, value) in map {
+            key.write_options(writer, endian, key_args.clone())?;
+            value.write_options(writer, endian, value_args.clone())?;
+        }
+        Ok(())
+    }
+}
+
+/// Creates a writer that writes a value prefixed by its length in bytes.
+///
+/// The value is written to a scratch buffer first, then its length is written
+/// as an integer of type `L` followed by the contents of the buffer. The
+/// length is written with `len_endian`, or with the same endianness as the
+/// value if it is `None`.
+///
+/// Since the value is written to a separate buffer, any positions it records
+/// while writing are relative to the start of the value rather than the
+/// output stream.
+///
+/// # Errors
+///
+/// If writing fails, or the length does not fit in `L`, an
+/// [`Error`](crate::Error) variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{BinWrite, Endian, helpers::write_len_prefixed, io::Cursor};
+/// #[derive(BinWrite)]
+/// struct Message {
+///     id: u16,
+///     body: [u8; 3],
+/// }
+///
+/// #[derive(BinWrite)]
+/// #[bw(little)]
+/// struct Frame {
+///     #[bw(write_with = write_len_prefixed::<u32, _, _, _>(Some(Endian::Big)))]
+///     message: Message,
+/// }
+///
+/// # let mut x = Cursor::new(Vec::new());
+/// # Frame { message: Message { id: 1, body: *b"abc" } }.write(&mut x).unwrap();
+/// # assert_eq!(x.into_inner(), b"\0\0\0\x05\x01\0abc");
+/// # let mut x = Cursor::new(Vec::new());
+/// # let write = write_len_prefixed::<u8, Vec<u8>, _, _>(None);
+/// # assert!(write(&vec![0; 256], &mut x, Endian::Little, ()).is_err());
+/// ```
+pub fn write_len_prefixed<L, T, W, Arg>(
+    len_endian: Option<Endian>,
+) -> impl Fn(&T, &mut W, Endian, Arg) -> BinResult<()>
+where
+    L: for<'a> BinWrite<Args<'a> = ()> + TryFrom<usize>,
+    T: for<'a> BinWrite<Args<'a> = Arg>,
+    W: Write + Seek,
+{
+    move |value, writer, endian, args| {
+        let mut payload = io::Cursor::new(Vec::new());
+        value.write_options(&mut payload, endian, args)?;
+        let payload = payload.into_inner();
+
+        let len = L::try_from(payload.len()).map_err(|_| Error::AssertFail {
+            pos: writer.stream_position().unwrap_or_default(),
+            message: alloc::format!(
+                "payload length {} does not fit in the length type",
+                payload.len()
+            ),
+        })?;
+        len.write_options(writer, len_endian.unwrap_or(endian), ())?;
+        writer.write_all(&payload)?;
+        Ok(())
+    }
+}
+
+/// Creates a parser that runs the given `read` function, then returns to the
+/// position where it started.
+///
+/// This can be used to look ahead at upcoming data without consuming it, in
+/// custom parse functions or with [`parse_with`]. The position is restored
+/// whether or not `read` succeeds.
+///
+/// [`parse_with`]: crate::docs::attribute#custom-parserswriters
+///
+/// # Errors
+///
+/// If `read` or seeking fails, an [`Error`](crate::Error) variant will be
+/// returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, helpers::peek, io::Cursor, BinResult};
+/// #[binrw::parser(reader, endian)]
+/// fn version_or_default() -> BinResult<u16> {
+///     // Older files start directly with the payload length
+///     let has_version = peek(u8::read_options)(reader, endian, ())? == 0xff;
+///     if has_version {
+///         u8::read_options(reader, endian, ())?;
+///         u16::read_options(reader, endian, ())
+///     } else {
+///         Ok(1)
+///     }
+/// }
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Header {
+///     #[br(parse_with = version_or_default)]
+///     version: u16,
+///     len: u8,
+/// }
+///
+/// # let x = Header::read(&mut Cursor::new(b"\xff\0\x02\x10")).unwrap();
+/// # assert_eq!((x.version, x.len), (2, 0x10));
+/// # let x = Header::read(&mut Cursor::new(b"\x10")).unwrap();
+/// # assert_eq!((x.version, x.len), (1, 0x10));
+/// # let mut x = Cursor::new(b"\x10");
+/// # assert!(peek(u32::read_options)(&mut x, binrw::Endian::Big, ()).is_err());
+/// # assert_eq!(x.position(), 0);
+/// ```
+pub fn peek<Reader, T, Arg, ReadFn>(
+    read: ReadFn,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<T>
+where
+    Reader: Read + Seek,
+    ReadFn: Fn(&mut Reader, Endian, Arg) -> BinResult<T>,
+{
+    move |reader, endian, args| {
+        let pos = reader.stream_position()?;
+        match read(reader, endian, args) {
+            Ok(value) => {
+                reader.seek(io::SeekFrom::Start(pos))?;
+                Ok(value)
+            }
+            Err(error) => crate::__private::restore_position(reader, pos)(error),
+        }
+    }
+}
+
+/// Creates a parser that runs the given `read` function, then skips to exactly
+/// `size` bytes past the position where it started.
+///
+/// This is the functional counterpart to the
+/// [`pad_size_to`](crate::docs::attribute#padding-and-alignment) directive,
+/// for use in custom parse functions or with [`parse_with`].
+///
+/// [`parse_with`]: crate::docs::attribute#custom-parserswriters
+///
+/// # Errors
+///
+/// If `read` or seeking fails, or `read` consumes more than `size` bytes, an
+/// [`Error`](crate::Error) variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, helpers::padded, io::Cursor, NullString};
+/// #[derive(BinRead)]
+/// struct Entry {
+///     #[br(parse_with = padded(NullString::read_options, 8))]
+///     name: NullString,
+///     id: u8,
+/// }
+///
+/// # let x = Entry::read_le(&mut Cursor::new(b"abc\0\0\0\0\0\x01")).unwrap();
+/// # assert_eq!((x.name.to_string().as_str(), x.id), ("abc", 1));
+/// # let mut x = Cursor::new(b"abcdefgh\0");
+/// # assert!(padded(NullString::read_options, 8)(&mut x, binrw::Endian::Little, ()).is_err());
+/// ```
+pub fn padded<R, T, Arg, ReadFn>(
+    read: ReadFn,
+    size: u64,
+) -> impl Fn(&mut R, Endian, Arg) -> BinResult<T>
+where
+    R: Read + Seek,
+    ReadFn: Fn(&mut R, Endian, Arg) -> BinResult<T>,
+{
+    move |reader, endian, args| {
+        let start = reader.stream_position()?;
+        let value = read(reader, endian, args)?;
+        let len = reader.stream_position()? - start;
+        if len > size {
+            return Err(crate::__private::pad_size_overflow(
+                start,
+                len,
+                size,
+                "padded value",
+            ));
+        }
+        reader.seek(io::SeekFrom::Start(start + size))?;
+        Ok(value)
+    }
+}
+
+/// Creates a writer that runs the given `write` function, then writes zeros
+/// until exactly `size` bytes have been written since the position where it
+/// started.
+///
+/// This is the functional counterpart to the
+/// [`pad_size_to`](crate::docs::attribute#padding-and-alignment) directive,
+/// for use in custom write functions or with [`write_with`].
+///
+/// [`write_with`]: crate::docs::attribute#custom-parserswriters
+///
+/// # Errors
+///
+/// If `write` fails, or writes more than `size` bytes, an
+/// [`Error`](crate::Error) variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, helpers::write_padded, io::Cursor, NullString};
+/// #[derive(BinWrite)]
+/// struct Entry {
+///     #[bw(write_with = write_padded(NullString::write_options, 8))]
+///     name: NullString,
+///     id: u8,
+/// }
+///
+/// # let mut x = Cursor::new(Vec::new());
+/// # Entry { name: "abc".into(), id: 1 }.write_le(&mut x).unwrap();
+/// # assert_eq!(x.into_inner(), b"abc\0\0\0\0\0\x01");
+/// # let mut x = Cursor::new(Vec::new());
+/// # let name = NullString::from("abcdefgh");
+/// # assert!(write_padded(NullString::write_options, 8)(&name, &mut x, binrw::Endian::Little, ()).is_err());
+/// ```
+pub fn write_padded<W, T, Arg, WriteFn>(
+    write: WriteFn,
+    size: u64,
+) -> impl Fn(&T, &mut W, Endian, Arg) -> BinResult<()>
+where
+    W: Write + Seek,
+    WriteFn: Fn(&T, &mut W, Endian, Arg) -> BinResult<()>,
+{
+    move |value, writer, endian, args| {
+        let start = writer.stream_position()?;
+        write(value, writer, endian, args)?;
+        let len = writer.stream_position()? - start;
+        if len > size {
+            return Err(crate::__private::pad_size_overflow(
+                start,
+                len,
+                size,
+                "padded value",
+            ));
+        }
+        crate::__private::write_padding(writer, size - len, 0)
+    }
+}
+
+/// Creates a parser that tries each of the given `parsers` in order and
+/// returns the result of the first one that succeeds.
+///
+/// The position is restored after each failed attempt, like when reading an
+/// enum, so ad-hoc alternatives can be parsed without declaring an enum for
+/// them. `parsers` is a tuple of between two and eight parse functions which
+/// return the same type and take the same arguments.
+///
+/// # Errors
+///
+/// If every parser fails, an [`Error::EnumErrors`] containing the error from
+/// each parser, named by its index in the tuple, will be returned. If seeking
+/// fails, an [`Error`] variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, helpers::alt, io::Cursor, BinResult, Error};
+/// #[binrw::parser(reader, endian)]
+/// fn long_len() -> BinResult<u32> {
+///     let pos = reader.stream_position()?;
+///     if u8::read_options(reader, endian, ())? != 0xff {
+///         return Err(Error::AssertFail { pos, message: "not a long length".into() });
+///     }
+///     u32::read_options(reader, endian, ())
+/// }
+///
+/// #[binrw::parser(reader, endian)]
+/// fn short_len() -> BinResult<u32> {
+///     u8::read_options(reader, endian, ()).map(u32::from)
+/// }
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Chunk {
+///     #[br(parse_with = alt((long_len, short_len)))]
+///     len: u32,
+/// }
+///
+/// # let x = Chunk::read(&mut Cursor::new(b"\xff\0\0\x01\0")).unwrap();
+/// # assert_eq!(x.len, 0x100);
+/// # let x = Chunk::read(&mut Cursor::new(b"\x10")).unwrap();
+/// # assert_eq!(x.len, 0x10);
+/// # let mut x = Cursor::new(b"\xff\0");
+/// # let err = alt((long_len, long_len))(&mut x, binrw::Endian::Big, ()).unwrap_err();
+/// # assert!(matches!(err, Error::EnumErrors { pos: 0, ref variant_errors } if variant_errors.len() == 2 && variant_errors[1].0 == "1"));
+/// # assert_eq!(x.position(), 0);
+/// ```
+pub fn alt<Reader, T, Arg, Parsers>(
+    parsers: Parsers,
+) -> impl Fn(&mut Reader, Endian, Arg) -> BinResult<T>
+where
+    Reader: Read + Seek,
+    Parsers: Alt<Reader, T, Arg>,
+{
+    move |reader, endian, args| parsers.read_alt(reader, endian, args)
+}
+
+/// A tuple of parse functions which can be tried in order by [`alt`].
+pub trait Alt<Reader, T, Arg> {
+    /// Tries each parse function in order, returning the result of the first
+    /// one that succeeds.
+    ///
+    /// # Errors
+    ///
+    /// If every parse function fails, an [`Error::EnumErrors`] will be
+    /// returned.
+    fn read_alt(&self, reader: &mut Reader, endian: Endian, args: Arg) -> BinResult<T>;
+}
+
+macro_rules! alt_impl {
+    ($($parser:ident $index:tt),+) => {
+        impl<Reader, T, Arg, $($parser),+> Alt<Reader, T, Arg> for ($($parser,)+)
+        where
+            Reader: Read + Seek,
+            Arg: Clone,
+            $($parser: Fn(&mut Reader, Endian, Arg) -> BinResult<T>),+
+        {
+            fn read_alt(&self, reader: &mut Reader, endian: Endian, args: Arg) -> BinResult<T> {
+                let pos = reader.stream_position()?;
+                let mut variant_errors = Vec::new();
+                $(
+                    match (self.$index)(reader, endian, args.clone()) {
+                        ok @ Ok(_) => return ok,
+                        Err(error) => variant_errors.push((
+                            stringify!($index),
+                            crate::__private::restore_position_variant(reader, pos, error)?,
+                        )),
+                    }
+                )+
+                Err(Error::EnumErrors { pos, variant_errors })
+            }
+        }
+    };
+}
+
+alt_impl!(P0 0, P1 1);
+alt_impl!(P0 0, P1 1, P2 2);
+alt_impl!(P0 0, P1 1, P2 2, P3 3);
+alt_impl!(P0 0, P1 1, P2 2, P3 3, P4 4);
+alt_impl!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5);
+alt_impl!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5, P6 6);
+alt_impl!(P0 0, P1 1, P2 2, P3 3, P4 4, P5 5, P6 6, P7 7);
+
+/// Reads a 24-bit unsigned integer.
+///
+/// # Errors
+///
+/// If reading fails, an [`Error`](crate::Error) variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor};
+/// #[derive(BinRead)]
+/// # #[derive(Debug, PartialEq)]
+/// struct Test {
+///     flags: u8,
+///     #[br(parse_with = binrw::helpers::read_u24)]
+///     value: u32,
+/// }
+/// #
+/// # assert_eq!(
+/// #     Test::read_be(&mut Cursor::new(b"\x01\x02\x03\x04")).unwrap(),
+/// #     Test { flags: 1, value: 0x20304 }
 /// # );
 /// # assert_eq!(
 /// #     Test::read_le(&mut Cursor::new(b"\x01\x04\x03\x02")).unwrap(),
@@ -557,6 +1887,181 @@ pub fn write_u24(value: &u32) -> binrw::BinResult<()> {
     writer.write_all(&buf[range]).map_err(Into::into)
 }
 
+/// Creates a parser that reads a field of `count` bits from a [`BitReader`],
+/// with its bits in the given order.
+///
+/// The order of the bits in each byte is set for the whole stream by the
+/// [`BitReader`], which can be created with [`map_stream`], and `order`
+/// decides whether the first bit read is the most or least significant bit
+/// of the field. See [`BitReader::read_bits_with`].
+///
+/// [`map_stream`]: crate::docs::attribute#stream-access-and-manipulation
+///
+/// # Errors
+///
+/// If reading fails, or the value does not fit in the field type, an
+/// [`Error`](crate::Error) variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, helpers::read_bits, io::{BitOrder, BitReader, Cursor}};
+/// // A DEFLATE block header, followed by a Huffman code
+/// #[derive(BinRead)]
+/// # #[derive(Debug, PartialEq)]
+/// #[br(map_stream = |reader| BitReader::new(reader, BitOrder::Lsb0))]
+/// struct Block {
+///     #[br(parse_with = read_bits(1, BitOrder::Lsb0))]
+///     last: u8,
+///     #[br(parse_with = read_bits(2, BitOrder::Lsb0))]
+///     kind: u8,
+///     #[br(parse_with = read_bits(5, BitOrder::Msb0))]
+///     code: u8,
+/// }
+///
+/// let block = Block::read_le(&mut Cursor::new(b"\x3b")).unwrap();
+/// assert_eq!(block, Block { last: 1, kind: 1, code: 0b11100 });
+/// ```
+pub fn read_bits<R, T>(
+    count: u32,
+    order: BitOrder,
+) -> impl Fn(&mut BitReader<R>, Endian, ()) -> BinResult<T> + Copy
+where
+    R: Read + Seek,
+    T: TryFrom<u64>,
+{
+    move |reader, _, ()| {
+        let pos = reader.stream_position()?;
+        let value = reader.read_bits_with(count, order)?;
+        T::try_from(value).map_err(|_| Error::AssertFail {
+            pos,
+            message: alloc::format!("{count}-bit value {value:#x} does not fit in the field type"),
+        })
+    }
+}
+
+/// Creates a writer that writes a field as `count` bits to a [`BitWriter`],
+/// with its bits in the given order.
+///
+/// This is the writing counterpart of [`read_bits`]. See
+/// [`BitWriter::write_bits_with`].
+///
+/// # Errors
+///
+/// If writing fails, `count` is greater than 64, or the value does not fit in
+/// `count` bits, an [`Error`](crate::Error) variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, helpers::write_bits, io::{BitOrder, BitWriter, Cursor}};
+/// #[derive(BinWrite)]
+/// #[bw(map_stream = |writer| BitWriter::new(writer, BitOrder::Lsb0))]
+/// struct Block {
+///     #[bw(write_with = write_bits(1, BitOrder::Lsb0))]
+///     last: u8,
+///     #[bw(write_with = write_bits(2, BitOrder::Lsb0))]
+///     kind: u8,
+///     #[bw(write_with = write_bits(5, BitOrder::Msb0))]
+///     code: u8,
+/// }
+///
+/// let mut output = Cursor::new(vec![]);
+/// Block { last: 1, kind: 1, code: 0b11100 }.write_le(&mut output).unwrap();
+/// assert_eq!(output.into_inner(), b"\x3b");
+/// ```
+pub fn write_bits<W, T>(
+    count: u32,
+    order: BitOrder,
+) -> impl Fn(&T, &mut BitWriter<W>, Endian, ()) -> BinResult<()> + Copy
+where
+    W: Write + Seek,
+    T: Copy + Into<u64>,
+{
+    move |value, writer, _, ()| {
+        let value = (*value).into();
+        if count > 64 {
+            return Err(Error::AssertFail {
+                pos: writer.stream_position()?,
+                message: alloc::format!("cannot write {count} bits at once, the limit is 64"),
+            });
+        }
+        if value.checked_shr(count).unwrap_or(0) != 0 {
+            return Err(Error::AssertFail {
+                pos: writer.stream_position()?,
+                message: alloc::format!("value {value:#x} does not fit in {count} bits"),
+            });
+        }
+        writer
+            .write_bits_with(value, count, order)
+            .map_err(Into::into)
+    }
+}
+
+/// Computes the CRC-32 (ISO-HDLC) checksum of a byte slice.
+///
+/// This is the checksum used by zlib, PNG, and Ethernet, and can be used as
+/// the algorithm of the [`checksum`] directive.
+///
+/// [`checksum`]: crate::docs::attribute#checksum
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{binrw, helpers::crc32, io::Cursor, BinRead, BinWrite};
+/// #[binrw]
+/// #[brw(big)]
+/// struct Chunk {
+///     data: [u8; 4],
+///     #[brw(checksum(crc32, over = "data"))]
+///     crc: u32,
+/// }
+///
+/// let mut output = Cursor::new(vec![]);
+/// Chunk { data: *b"IEND" }.write(&mut output).unwrap();
+/// assert_eq!(output.get_ref(), b"IEND\xae\x42\x60\x82");
+///
+/// output.set_position(0);
+/// assert_eq!(Chunk::read(&mut output).unwrap().data, *b"IEND");
+/// ```
+#[must_use]
+pub fn crc32(bytes: &[u8]) -> u32 {
+    checksum(Crc32::new(), bytes)
+}
+
+/// Computes the Adler-32 checksum of a byte slice.
+///
+/// This is the checksum used by zlib streams, and can be used as the
+/// algorithm of the [`checksum`] directive.
+///
+/// [`checksum`]: crate::docs::attribute#checksum
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{binrw, helpers::adler32, io::Cursor, BinWrite};
+/// #[binrw]
+/// #[brw(big)]
+/// struct Stream {
+///     data: [u8; 9],
+///     #[brw(checksum(adler32, over = "data"))]
+///     adler: u32,
+/// }
+///
+/// let mut output = Cursor::new(vec![]);
+/// Stream { data: *b"Wikipedia" }.write(&mut output).unwrap();
+/// assert_eq!(&output.get_ref()[9..], b"\x11\xe6\x03\x98");
+/// ```
+#[must_use]
+pub fn adler32(bytes: &[u8]) -> u32 {
+    checksum(Adler32::new(), bytes)
+}
+
+fn checksum<C: Checksum>(mut checksum: C, bytes: &[u8]) -> C::Output {
+    checksum.update(bytes);
+    checksum.finish()
+}
+
 fn not_enough_bytes<T>(_: T) -> Error {
     Error::Io(io::Error::new(
         io::ErrorKind::UnexpectedEof,
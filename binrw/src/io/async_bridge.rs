@@ -0,0 +1,454 @@
+//! Adapters for using binrw parsers and writers with async streams.
+
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom, Write};
+use crate::{
+    __private::Required,
+    meta::{ReadEndian, WriteEndian},
+    BinRead, BinResult, BinWrite, Endian,
+};
+use core::{future::poll_fn, pin::Pin};
+use futures_io::{AsyncRead, AsyncWrite};
+
+/// The default maximum number of bytes buffered by an [`AsyncReader`].
+pub const DEFAULT_ASYNC_BUFFER_LIMIT: usize = 1 << 20;
+
+/// The number of bytes requested from the async reader at once.
+const CHUNK_SIZE: usize = 8 * 1024;
+
+/// An adapter which reads [`BinRead`] values from an [`AsyncRead`] stream.
+///
+/// Data from the async stream is buffered in memory and parsed with the
+/// normal synchronous [`BinRead`] implementation. If the parser runs out of
+/// buffered data, more data is read from the stream and parsing starts again
+/// from the beginning of the value. Data which is not used by a value stays
+/// in the buffer for the next value.
+///
+/// At most [`limit`](Self::with_limit) bytes are buffered at once, so a
+/// single value must fit inside the limit.
+///
+/// Positions are counted from the start of the async stream. Parsers can seek
+/// freely within the value being read, but cannot seek back into a previous
+/// value, and cannot seek relative to the end of the stream since its length
+/// is not known. For the same reason, parsers which read until the end of the
+/// stream, like [`until_eof`](crate::helpers::until_eof), stop at the end of
+/// the data which has been buffered so far.
+///
+/// Streams from other async runtimes, like Tokio, can be used through their
+/// compatibility layers for the `futures-io` traits.
+///
+/// # Examples
+///
+/// ```
+/// # fn block_on<F: core::future::Future>(f: F) -> F::Output {
+/// #     struct Noop;
+/// #     impl std::task::Wake for Noop { fn wake(self: std::sync::Arc<Self>) {} }
+/// #     let waker = std::sync::Arc::new(Noop).into();
+/// #     let mut cx = core::task::Context::from_waker(&waker);
+/// #     let mut f = Box::pin(f);
+/// #     loop { if let core::task::Poll::Ready(v) = f.as_mut().poll(&mut cx) { return v } }
+/// # }
+/// use binrw::{BinRead, io::AsyncReader};
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Message {
+///     len: u8,
+///     #[br(count = len)]
+///     data: Vec<u8>,
+/// }
+///
+/// # block_on(async {
+/// let mut reader = AsyncReader::new(&b"\x02hi\x01!"[..]);
+/// let first = reader.read::<Message>().await.unwrap();
+/// let second = reader.read::<Message>().await.unwrap();
+/// assert_eq!((first.data, second.data), (b"hi".to_vec(), b"!".to_vec()));
+/// # });
+/// ```
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "futures-io")))]
+#[derive(Debug)]
+pub struct AsyncReader<R> {
+    inner: R,
+    buf: Vec<u8>,
+    pos: u64,
+    limit: usize,
+}
+
+impl<R> AsyncReader<R> {
+    /// Creates a new reader which reads from `inner`, buffering at most
+    /// [`DEFAULT_ASYNC_BUFFER_LIMIT`] bytes.
+    pub fn new(inner: R) -> Self {
+        Self::with_limit(inner, DEFAULT_ASYNC_BUFFER_LIMIT)
+    }
+
+    /// Creates a new reader which reads from `inner`, buffering at most
+    /// `limit` bytes.
+    pub fn with_limit(inner: R, limit: usize) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            pos: 0,
+            limit,
+        }
+    }
+
+    /// Returns the data which has been read from the stream but not used by
+    /// a value yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Returns the position in the stream of the end of the last value which
+    /// was read.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading from the underlying reader directly skips over any buffered
+    /// data.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying reader. Any buffered
+    /// data is lost.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncReader<R> {
+    /// Read `T` from the stream using default arguments.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    pub async fn read<'a, T>(&mut self) -> BinResult<T>
+    where
+        T: BinRead + ReadEndian,
+        T::Args<'a>: Required + Clone,
+    {
+        self.read_type(Endian::Little).await
+    }
+
+    /// Read `T` from the stream with the given byte order.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, an [`Error`](crate::Error) variant will be returned.
+    pub async fn read_type<'a, T>(&mut self, endian: Endian) -> BinResult<T>
+    where
+        T: BinRead,
+        T::Args<'a>: Required + Clone,
+    {
+        self.read_type_args(endian, T::Args::args()).await
+    }
+
+    /// Read `T` from the stream with the given byte order and arguments.
+    ///
+    /// # Errors
+    ///
+    /// If reading fails, or the value does not fit inside the buffer limit, an
+    /// [`Error`](crate::Error) variant will be returned. The data of a value
+    /// which fails to parse stays in the buffer.
+    pub async fn read_type_args<'a, T>(&mut self, endian: Endian, args: T::Args<'a>) -> BinResult<T>
+    where
+        T: BinRead,
+        T::Args<'a>: Clone,
+    {
+        loop {
+            let mut stream = BufferedStream::new(&mut self.buf, self.pos);
+            let result = T::read_options(&mut stream, endian, args.clone());
+            let end = stream.pos;
+            let needs_data = end > self.pos + self.buf.len() as u64;
+
+            match result {
+                Ok(value) if !needs_data => {
+                    // Lint: `end` is within the buffer, so cannot be larger
+                    // than `usize`.
+                    #[allow(clippy::cast_possible_truncation)]
+                    self.buf.drain(..(end - self.pos) as usize);
+                    self.pos = end;
+                    return Ok(value);
+                }
+                Err(err) if !err.is_eof() => return Err(err),
+                result => {
+                    if !self.fill().await? {
+                        return Err(result.err().unwrap_or_else(|| {
+                            Error::new(ErrorKind::UnexpectedEof, "unexpected end of async stream")
+                                .into()
+                        }));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reads more data into the buffer, returning false at the end of the
+    /// stream.
+    async fn fill(&mut self) -> BinResult<bool> {
+        let start = self.buf.len();
+        if start >= self.limit {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "value does not fit inside the async buffer limit",
+            )
+            .into());
+        }
+
+        self.buf
+            .resize(start + CHUNK_SIZE.min(self.limit - start), 0);
+        let inner = &mut self.inner;
+        let buf = &mut self.buf[start..];
+        let result = poll_fn(|cx| Pin::new(&mut *inner).poll_read(cx, buf)).await;
+        let n = result.as_ref().map_or(0, |n| *n);
+        self.buf.truncate(start + n);
+        Ok(result? != 0)
+    }
+}
+
+/// An adapter which writes [`BinWrite`] values to an [`AsyncWrite`] stream.
+///
+/// Each value is written with the normal synchronous [`BinWrite`]
+/// implementation to a buffer in memory, which is then written to the async
+/// stream.
+///
+/// Positions are counted from the start of the async stream. Writers can seek
+/// freely within the value being written, but cannot seek back into a
+/// previous value.
+///
+/// # Examples
+///
+/// ```
+/// # fn block_on<F: core::future::Future>(f: F) -> F::Output {
+/// #     struct Noop;
+/// #     impl std::task::Wake for Noop { fn wake(self: std::sync::Arc<Self>) {} }
+/// #     let waker = std::sync::Arc::new(Noop).into();
+/// #     let mut cx = core::task::Context::from_waker(&waker);
+/// #     let mut f = Box::pin(f);
+/// #     loop { if let core::task::Poll::Ready(v) = f.as_mut().poll(&mut cx) { return v } }
+/// # }
+/// use binrw::{BinWrite, io::AsyncWriter};
+///
+/// #[derive(BinWrite)]
+/// #[bw(big)]
+/// struct Message {
+///     #[bw(align_after = 4)]
+///     kind: u8,
+/// }
+///
+/// # block_on(async {
+/// let mut writer = AsyncWriter::new(Vec::new());
+/// writer.write(&Message { kind: 1 }).await.unwrap();
+/// writer.write(&Message { kind: 2 }).await.unwrap();
+/// assert_eq!(writer.into_inner(), b"\x01\0\0\0\x02\0\0\0");
+/// # });
+/// ```
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "futures-io")))]
+#[derive(Debug)]
+pub struct AsyncWriter<W> {
+    inner: W,
+    buf: Vec<u8>,
+    pos: u64,
+}
+
+impl<W> AsyncWriter<W> {
+    /// Creates a new writer which writes to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Returns the position in the stream of the end of the last value which
+    /// was written.
+    pub fn position(&self) -> u64 {
+        self.pos
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing to the underlying writer directly will corrupt the internal
+    /// position of this `AsyncWriter`.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWriter<W> {
+    /// Write `value` to the stream using default arguments.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    pub async fn write<'a, T>(&mut self, value: &T) -> BinResult<()>
+    where
+        T: BinWrite + WriteEndian + ?Sized,
+        T::Args<'a>: Required,
+    {
+        self.write_type(value, Endian::Little).await
+    }
+
+    /// Write `value` to the stream with the given byte order.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    pub async fn write_type<'a, T>(&mut self, value: &T, endian: Endian) -> BinResult<()>
+    where
+        T: BinWrite + ?Sized,
+        T::Args<'a>: Required,
+    {
+        self.write_type_args(value, endian, T::Args::args()).await
+    }
+
+    /// Write `value` to the stream with the given byte order and arguments.
+    ///
+    /// # Errors
+    ///
+    /// If writing fails, an [`Error`](crate::Error) variant will be returned.
+    /// If the value fails to serialise, nothing is written to the stream.
+    pub async fn write_type_args<T>(
+        &mut self,
+        value: &T,
+        endian: Endian,
+        args: T::Args<'_>,
+    ) -> BinResult<()>
+    where
+        T: BinWrite + ?Sized,
+    {
+        self.buf.clear();
+        value.write_options(
+            &mut BufferedStream::new(&mut self.buf, self.pos),
+            endian,
+            args,
+        )?;
+
+        let mut written = 0;
+        while written < self.buf.len() {
+            let inner = &mut self.inner;
+            let buf = &self.buf[written..];
+            match poll_fn(|cx| Pin::new(&mut *inner).poll_write(cx, buf)).await? {
+                0 => return Err(Error::from(ErrorKind::WriteZero).into()),
+                n => {
+                    written += n;
+                    self.pos += n as u64;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Flushes the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// If flushing fails, an error is returned.
+    pub async fn flush(&mut self) -> Result<()> {
+        let inner = &mut self.inner;
+        poll_fn(|cx| Pin::new(&mut *inner).poll_flush(cx)).await
+    }
+}
+
+/// A stream over the buffered data of an async stream, where the first byte of
+/// the buffer is at position `base` in the async stream.
+struct BufferedStream<'a> {
+    buf: &'a mut Vec<u8>,
+    base: u64,
+    pos: u64,
+}
+
+impl<'a> BufferedStream<'a> {
+    fn new(buf: &'a mut Vec<u8>, base: u64) -> Self {
+        Self {
+            buf,
+            base,
+            pos: base,
+        }
+    }
+
+    fn offset(&self) -> Result<usize> {
+        usize::try_from(self.pos - self.base)
+            .map_err(|_| Error::new(ErrorKind::InvalidInput, "position too large"))
+    }
+}
+
+impl Read for BufferedStream<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let data = self.buf.get(self.offset()?..).unwrap_or_default();
+        let n = data.len().min(buf.len());
+        buf[..n].copy_from_slice(&data[..n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl Write for BufferedStream<'_> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let offset = self.offset()?;
+        let end = offset + buf.len();
+        if self.buf.len() < end {
+            self.buf.resize(end, 0);
+        }
+        self.buf[offset..end].copy_from_slice(buf);
+        self.pos += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for BufferedStream<'_> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => pos,
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset).ok_or_else(|| {
+                Error::new(
+                    ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                )
+            })?,
+            SeekFrom::End(_) => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "cannot seek relative to the end of an async stream",
+                ))
+            }
+        };
+
+        if new_pos < self.base {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "cannot seek before the start of the current value",
+            ));
+        }
+
+        self.pos = new_pos;
+        Ok(new_pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
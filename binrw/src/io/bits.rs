@@ -0,0 +1,463 @@
+//! Adapters for reading and writing streams one bit at a time.
+
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom, Write};
+
+/// The order in which the bits of each byte are read or written.
+///
+/// Formats like DEFLATE read the bits of each byte starting from the least
+/// significant bit, but store some values, like Huffman codes, starting from
+/// their most significant bit. To read these formats, the order of the bits
+/// in each byte is set for the whole stream, and the order of the bits of
+/// individual values can be overridden with
+/// [`BitReader::read_bits_with`] and [`BitWriter::write_bits_with`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum BitOrder {
+    /// The most significant bit of each byte comes first, and the first bit of
+    /// a multi-bit value is its most significant bit.
+    Msb0,
+    /// The least significant bit of each byte comes first, and the first bit of
+    /// a multi-bit value is its least significant bit.
+    Lsb0,
+}
+
+/// A reader adapter which reads individual bits from an underlying reader.
+///
+/// Bits are read from the underlying reader one byte at a time. When the
+/// reader is not [aligned](Self::is_aligned) to a byte boundary, reading bytes
+/// through [`Read`] reads eight bits at a time, so a custom parser can read a
+/// bit field and then continue reading whole values with binrw.
+///
+/// The position of a `BitReader` is the position of the underlying reader, so
+/// a partially read byte counts as read. Seeking discards any bits remaining
+/// in the current byte.
+///
+/// The bit order is used both for the order of the bits in each byte and for
+/// the order of the bits of values read by [`read_bits`](Self::read_bits). It
+/// can be changed with [`set_order`](Self::set_order), which takes effect
+/// from the next byte, and the order of the bits of a single value can be
+/// overridden with [`read_bits_with`](Self::read_bits_with).
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, BinReaderExt, io::{BitOrder, BitReader, Cursor}};
+///
+/// let mut reader = BitReader::new(Cursor::new(b"\xa5\x01\x02"), BitOrder::Msb0);
+/// assert_eq!(reader.read_bits(3).unwrap(), 0b101);
+/// assert!(!reader.read_bit().unwrap());
+/// reader.align();
+/// let value: u16 = reader.read_be().unwrap();
+/// assert_eq!(value, 0x102);
+/// ```
+#[derive(Debug)]
+pub struct BitReader<R> {
+    inner: R,
+    order: BitOrder,
+    byte: u8,
+    remaining: u8,
+}
+
+impl<R> BitReader<R> {
+    /// Creates a new bit reader which reads bits from `inner` in the given
+    /// order.
+    pub fn new(inner: R, order: BitOrder) -> Self {
+        Self {
+            inner,
+            order,
+            byte: 0,
+            remaining: 0,
+        }
+    }
+
+    /// Returns the bit order of the stream.
+    pub fn order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// Sets the bit order of the stream.
+    ///
+    /// If the reader is not [aligned](Self::is_aligned), the bits remaining in
+    /// the current byte are still read in the previous order, and the new
+    /// order takes effect from the next byte.
+    pub fn set_order(&mut self, order: BitOrder) {
+        self.order = order;
+    }
+
+    /// Returns true if there are no bits remaining in the current byte.
+    pub fn is_aligned(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// Discards the bits remaining in the current byte, so the next read
+    /// starts at a byte boundary.
+    pub fn align(&mut self) {
+        self.remaining = 0;
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading from the underlying reader directly skips any bits remaining
+    /// in the current byte.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying reader. Any bits
+    /// remaining in the current byte are discarded.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> BitReader<R> {
+    /// Reads a single bit.
+    ///
+    /// # Errors
+    ///
+    /// If reading from the underlying reader fails, an error is returned.
+    pub fn read_bit(&mut self) -> Result<bool> {
+        if self.remaining == 0 {
+            let mut byte = [0];
+            self.inner.read_exact(&mut byte)?;
+            // The current byte is always stored with its first bit as the
+            // most significant bit, so the order only matters here
+            self.byte = match self.order {
+                BitOrder::Msb0 => byte[0],
+                BitOrder::Lsb0 => byte[0].reverse_bits(),
+            };
+            self.remaining = 8;
+        }
+
+        self.remaining -= 1;
+        Ok((self.byte >> self.remaining) & 1 != 0)
+    }
+
+    /// Reads `count` bits into the low bits of an integer, using the bit order
+    /// of the stream.
+    ///
+    /// # Errors
+    ///
+    /// If reading from the underlying reader fails, an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is greater than 64.
+    pub fn read_bits(&mut self, count: u32) -> Result<u64> {
+        self.read_bits_with(count, self.order)
+    }
+
+    /// Reads `count` bits into the low bits of an integer, with the first bit
+    /// read as the most significant bit of the value if `order` is
+    /// [`Msb0`](BitOrder::Msb0), or as the least significant bit if it is
+    /// [`Lsb0`](BitOrder::Lsb0).
+    ///
+    /// The bits of each byte are still read in the bit order of the stream.
+    ///
+    /// # Errors
+    ///
+    /// If reading from the underlying reader fails, an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is greater than 64.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use binrw::io::{BitOrder, BitReader, Cursor};
+    ///
+    /// // The first bit of each byte is its least significant bit
+    /// let mut reader = BitReader::new(Cursor::new(b"\x0d"), BitOrder::Lsb0);
+    /// assert_eq!(reader.read_bits(2).unwrap(), 0b01);
+    /// // A code stored with its most significant bit first
+    /// assert_eq!(reader.read_bits_with(3, BitOrder::Msb0).unwrap(), 0b110);
+    /// ```
+    pub fn read_bits_with(&mut self, count: u32, order: BitOrder) -> Result<u64> {
+        assert!(count <= 64, "cannot read more than 64 bits at once");
+        let mut value = 0;
+        for index in 0..count {
+            let bit = u64::from(self.read_bit()?);
+            match order {
+                BitOrder::Msb0 => value = (value << 1) | bit,
+                BitOrder::Lsb0 => value |= bit << index,
+            }
+        }
+        Ok(value)
+    }
+}
+
+impl<R: Read> Read for BitReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.is_aligned() {
+            self.inner.read(buf)
+        } else if let Some(byte) = buf.first_mut() {
+            // Lint: Reading 8 bits cannot produce a value larger than a byte
+            #[allow(clippy::cast_possible_truncation)]
+            {
+                *byte = self.read_bits(8)? as u8;
+            }
+            Ok(1)
+        } else {
+            Ok(0)
+        }
+    }
+}
+
+impl<R: Seek> Seek for BitReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.align();
+        self.inner.seek(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        self.inner.stream_position()
+    }
+}
+
+/// A writer adapter which writes individual bits to an underlying writer.
+///
+/// Bits are written to the underlying writer one byte at a time, once all
+/// eight bits of the byte are known. When the writer is not
+/// [aligned](Self::is_aligned) to a byte boundary, writing bytes through
+/// [`Write`] writes eight bits at a time, so a custom writer can write a bit
+/// field and then continue writing whole values with binrw.
+///
+/// A partially written byte is written to the underlying writer by
+/// [`align`](Self::align), [`finish`](Self::finish), or when the writer is
+/// flushed or dropped, which fill the rest of the byte with zeroes. Errors
+/// when writing the byte on drop are ignored, so use `finish` to handle them.
+/// The position of a `BitWriter` is the position of the underlying writer,
+/// and it cannot seek while a byte is partially written.
+///
+/// The bit order is used both for the order of the bits in each byte and for
+/// the order of the bits of values written by
+/// [`write_bits`](Self::write_bits). It can be changed with
+/// [`set_order`](Self::set_order), which takes effect from the next byte, and
+/// the order of the bits of a single value can be overridden with
+/// [`write_bits_with`](Self::write_bits_with).
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinWrite, io::{BitOrder, BitWriter, Cursor}};
+///
+/// let mut writer = BitWriter::new(Cursor::new(Vec::new()), BitOrder::Msb0);
+/// writer.write_bits(0b101, 3).unwrap();
+/// writer.write_bit(false).unwrap();
+/// writer.align().unwrap();
+/// 0x102_u16.write_be(&mut writer).unwrap();
+/// assert_eq!(writer.finish().unwrap().into_inner(), b"\xa0\x01\x02");
+/// ```
+#[derive(Debug)]
+pub struct BitWriter<W: Write> {
+    // This is only `None` after `finish` moves the writer out.
+    inner: Option<W>,
+    order: BitOrder,
+    // The bit order of the partially written byte.
+    byte_order: BitOrder,
+    byte: u8,
+    filled: u8,
+}
+
+impl<W: Write> BitWriter<W> {
+    /// Creates a new bit writer which writes bits to `inner` in the given
+    /// order.
+    pub fn new(inner: W, order: BitOrder) -> Self {
+        Self {
+            inner: Some(inner),
+            order,
+            byte_order: order,
+            byte: 0,
+            filled: 0,
+        }
+    }
+
+    /// Returns the bit order of the stream.
+    pub fn order(&self) -> BitOrder {
+        self.order
+    }
+
+    /// Sets the bit order of the stream.
+    ///
+    /// If the writer is not [aligned](Self::is_aligned), the rest of the
+    /// partially written byte is still written in the previous order, and the
+    /// new order takes effect from the next byte.
+    pub fn set_order(&mut self, order: BitOrder) {
+        self.order = order;
+    }
+
+    /// Returns true if no byte is partially written.
+    pub fn is_aligned(&self) -> bool {
+        self.filled == 0
+    }
+
+    /// Gets a reference to the underlying writer.
+    // Lint: The writer is only taken by `finish`, which consumes `self`, so
+    // this cannot panic.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect(Self::MOVED)
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Writing to the underlying writer directly while a byte is partially
+    /// written will write the data before that byte.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner_mut()
+    }
+
+    const MOVED: &'static str = "the writer is only moved out by `finish`";
+
+    fn inner_mut(&mut self) -> &mut W {
+        self.inner.as_mut().expect(Self::MOVED)
+    }
+
+    /// Writes a single bit.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the underlying writer fails, an error is returned.
+    pub fn write_bit(&mut self, bit: bool) -> Result<()> {
+        if self.filled == 0 {
+            self.byte_order = self.order;
+        }
+
+        let shift = match self.byte_order {
+            BitOrder::Msb0 => 7 - self.filled,
+            BitOrder::Lsb0 => self.filled,
+        };
+        self.byte |= u8::from(bit) << shift;
+        self.filled += 1;
+
+        if self.filled == 8 {
+            self.write_byte()?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes the low `count` bits of `value`, using the bit order of the
+    /// stream.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the underlying writer fails, an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is greater than 64.
+    pub fn write_bits(&mut self, value: u64, count: u32) -> Result<()> {
+        self.write_bits_with(value, count, self.order)
+    }
+
+    /// Writes the low `count` bits of `value`, starting with its most
+    /// significant bit if `order` is [`Msb0`](BitOrder::Msb0), or with its
+    /// least significant bit if it is [`Lsb0`](BitOrder::Lsb0).
+    ///
+    /// The bits of each byte are still written in the bit order of the
+    /// stream.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the underlying writer fails, an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is greater than 64.
+    pub fn write_bits_with(&mut self, value: u64, count: u32, order: BitOrder) -> Result<()> {
+        assert!(count <= 64, "cannot write more than 64 bits at once");
+        for index in 0..count {
+            let shift = match order {
+                BitOrder::Msb0 => count - 1 - index,
+                BitOrder::Lsb0 => index,
+            };
+            self.write_bit((value >> shift) & 1 != 0)?;
+        }
+        Ok(())
+    }
+
+    /// Fills the rest of a partially written byte with zeroes and writes it,
+    /// so the next write starts at a byte boundary.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the underlying writer fails, an error is returned.
+    pub fn align(&mut self) -> Result<()> {
+        if !self.is_aligned() {
+            self.write_byte()?;
+        }
+        Ok(())
+    }
+
+    /// Writes any partially written byte, then returns the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// If writing to the underlying writer fails, an error is returned.
+    // Lint: The writer is only taken by `finish`, which consumes `self`, so
+    // this cannot panic.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn finish(mut self) -> Result<W> {
+        self.align()?;
+        Ok(self.inner.take().expect(Self::MOVED))
+    }
+
+    /// Writes the partially written byte and starts a new one.
+    fn write_byte(&mut self) -> Result<()> {
+        let byte = self.byte;
+        self.inner_mut().write_all(&[byte])?;
+        self.byte = 0;
+        self.filled = 0;
+        Ok(())
+    }
+}
+
+impl<W: Write> Write for BitWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.is_aligned() {
+            self.inner_mut().write(buf)
+        } else if let Some(&byte) = buf.first() {
+            self.write_bits(byte.into(), 8)?;
+            Ok(1)
+        } else {
+            Ok(0)
+        }
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.align()?;
+        self.inner_mut().flush()
+    }
+}
+
+impl<W: Write + Seek> Seek for BitWriter<W> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        if self.is_aligned() || matches!(pos, SeekFrom::Current(0)) {
+            self.inner_mut().seek(pos)
+        } else {
+            Err(Error::new(
+                ErrorKind::InvalidInput,
+                "cannot seek while a byte is partially written",
+            ))
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        self.inner_mut().stream_position()
+    }
+}
+
+impl<W: Write> Drop for BitWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            // Dtors should not panic, so the error is ignored
+            let _ = self.align();
+        }
+    }
+}
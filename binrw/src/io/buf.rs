@@ -0,0 +1,187 @@
+//! Adapters for reading from [`bytes::Buf`] and writing to [`bytes::BufMut`].
+
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom, Write};
+use bytes::{Buf, BufMut};
+
+/// A reader adapter which reads from a [`Buf`].
+///
+/// A `Buf` can only be advanced, so seeking backwards fails. Seeking forwards
+/// skips bytes. To parse a [`Bytes`](bytes::Bytes) with full seek support,
+/// wrap it in a [`Cursor`](super::Cursor) instead, which does not copy the
+/// data.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinReaderExt, io::BufReadAdapter};
+/// use bytes::Bytes;
+///
+/// let mut reader = BufReadAdapter::new(Bytes::from_static(b"\0\x01\0\x02"));
+/// let value: u16 = reader.read_be().unwrap();
+/// assert_eq!(value, 1);
+/// assert_eq!(reader.into_inner(), &b"\0\x02"[..]);
+/// ```
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "bytes")))]
+#[derive(Debug)]
+pub struct BufReadAdapter<B> {
+    inner: B,
+    pos: u64,
+}
+
+impl<B> BufReadAdapter<B> {
+    /// Creates a new reader which reads from `inner`.
+    pub fn new(inner: B) -> Self {
+        Self { inner, pos: 0 }
+    }
+
+    /// Gets a reference to the underlying buffer.
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying buffer.
+    ///
+    /// Advancing the underlying buffer directly will corrupt the internal
+    /// position of this `BufReadAdapter`.
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying buffer.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: Buf> Read for BufReadAdapter<B> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = buf.len().min(self.inner.remaining());
+        self.inner.copy_to_slice(&mut buf[..n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<B: Buf> Seek for BufReadAdapter<B> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::Current(n) => self.pos.checked_add_signed(n),
+            SeekFrom::End(n) => (self.pos + self.inner.remaining() as u64).checked_add_signed(n),
+        };
+
+        match target {
+            Some(n) if n >= self.pos => {
+                let skip = usize::try_from(n - self.pos)
+                    .unwrap_or(usize::MAX)
+                    .min(self.inner.remaining());
+                self.inner.advance(skip);
+                self.pos = n;
+                Ok(n)
+            }
+            _ => Err(Error::new(
+                ErrorKind::Other,
+                "cannot seek backwards in a `Buf`",
+            )),
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+/// A writer adapter which writes to a [`BufMut`].
+///
+/// A `BufMut` can only be appended to, so seeking backwards fails. Seeking
+/// forwards writes zeroes.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinWrite, io::BufWriteAdapter};
+/// use bytes::BytesMut;
+///
+/// let mut writer = BufWriteAdapter::new(BytesMut::new());
+/// 0x102_u16.write_be(&mut writer).unwrap();
+/// assert_eq!(writer.into_inner().freeze(), &b"\x01\x02"[..]);
+/// ```
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "bytes")))]
+#[derive(Debug)]
+pub struct BufWriteAdapter<B> {
+    inner: B,
+    pos: u64,
+}
+
+impl<B> BufWriteAdapter<B> {
+    /// Creates a new writer which writes to `inner`.
+    pub fn new(inner: B) -> Self {
+        Self { inner, pos: 0 }
+    }
+
+    /// Gets a reference to the underlying buffer.
+    pub fn get_ref(&self) -> &B {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying buffer.
+    ///
+    /// Writing to the underlying buffer directly will corrupt the internal
+    /// position of this `BufWriteAdapter`.
+    pub fn get_mut(&mut self) -> &mut B {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying buffer.
+    pub fn into_inner(self) -> B {
+        self.inner
+    }
+}
+
+impl<B: BufMut> Write for BufWriteAdapter<B> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let n = buf.len().min(self.inner.remaining_mut());
+        self.inner.put_slice(&buf[..n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl<B: BufMut> Seek for BufWriteAdapter<B> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::Current(n) | SeekFrom::End(n) => self.pos.checked_add_signed(n),
+        };
+
+        match target {
+            Some(n) if n >= self.pos => {
+                match usize::try_from(n - self.pos) {
+                    Ok(count) if count <= self.inner.remaining_mut() => {
+                        self.inner.put_bytes(0, count);
+                    }
+                    _ => {
+                        return Err(Error::new(
+                            ErrorKind::WriteZero,
+                            "not enough space in `BufMut`",
+                        ))
+                    }
+                }
+                self.pos = n;
+                Ok(n)
+            }
+            _ => Err(Error::new(
+                ErrorKind::Other,
+                "cannot seek backwards in a `BufMut`",
+            )),
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
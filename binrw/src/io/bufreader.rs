@@ -8,6 +8,32 @@ use super::SeekFrom;
 /// buffer every time a [`Seek`](super::Seek) method is called. It also caches
 /// the underlying stream position to avoid unnecessary system calls.
 ///
+/// Relative and absolute seeks which land inside the read buffer are serviced
+/// from the buffer, and querying the stream position never touches the
+/// wrapped stream once the position is known. This makes it a good choice for
+/// parsing from a [`File`](std::fs::File), where binrw frequently queries the
+/// position and seeks short distances.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, io::{BufReader, Cursor}};
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Header {
+///     offset: u8,
+///     #[br(seek_before = binrw::io::SeekFrom::Start(offset.into()), restore_position)]
+///     value: u16,
+///     len: u8,
+/// }
+///
+/// let mut reader = BufReader::new(Cursor::new(b"\x02\x03\x00\x04"));
+/// let header = Header::read(&mut reader).unwrap();
+/// assert_eq!(header.value, 4);
+/// assert_eq!(header.len, 3);
+/// ```
+///
 /// # Limitations
 ///
 /// Reading or seeking the wrapped stream object directly will cause an
@@ -204,5 +230,8 @@ impl<T: super::Read> std::io::BufRead for BufReader<T> {
 
     fn consume(&mut self, amt: usize) {
         self.inner.consume(amt);
+        if let Some(pos) = &mut self.pos {
+            *pos += amt as u64;
+        }
     }
 }
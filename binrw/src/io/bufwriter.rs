@@ -0,0 +1,202 @@
+//! Wrapper type to add buffering to write streams.
+
+use super::{Error, ErrorKind, Result, Seek, SeekFrom, Write};
+use alloc::vec::Vec;
+
+/// The default buffer capacity of a [`BufWriter`].
+const DEFAULT_CAPACITY: usize = 8 * 1024;
+
+/// A wrapper to add buffering to a write stream.
+///
+/// Small writes, like the ones made when writing each field of a struct, are
+/// collected in a buffer and written to the wrapped stream together. Unlike
+/// [`std::io::BufWriter`], this wrapper also implements [`Seek`]. The buffer
+/// is written before every seek which moves the stream, but querying the
+/// stream position is answered from a cached position without writing the
+/// buffer or calling into the wrapped stream.
+///
+/// Any buffered data is written when the `BufWriter` is dropped, but errors
+/// which happen then are ignored. Call [`flush`](Write::flush) or
+/// [`into_inner`](Self::into_inner) before dropping the writer to handle
+/// them.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinWrite, io::{BufWriter, Cursor}};
+///
+/// #[derive(BinWrite)]
+/// #[bw(little)]
+/// struct Header {
+///     magic: u32,
+///     #[bw(align_after = 8)]
+///     version: u16,
+/// }
+///
+/// let mut writer = BufWriter::new(Cursor::new(Vec::new()));
+/// Header { magic: 1, version: 2 }.write(&mut writer).unwrap();
+/// assert_eq!(writer.buffer().len(), 8);
+/// let data = writer.into_inner().unwrap().into_inner();
+/// assert_eq!(data, b"\x01\0\0\0\x02\0\0\0");
+/// ```
+#[derive(Debug)]
+pub struct BufWriter<W: Write> {
+    // This is only `None` after `into_inner` moves the writer out.
+    inner: Option<W>,
+    buf: Vec<u8>,
+    capacity: usize,
+    pos: Option<u64>,
+}
+
+impl<W: Write> BufWriter<W> {
+    /// Creates a new `BufWriter<W>` with a default buffer capacity.
+    pub fn new(inner: W) -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY, inner)
+    }
+
+    /// Creates a new `BufWriter<W>` with the specified buffer capacity.
+    pub fn with_capacity(capacity: usize, inner: W) -> Self {
+        Self {
+            inner: Some(inner),
+            buf: Vec::with_capacity(capacity),
+            capacity,
+            pos: None,
+        }
+    }
+
+    /// Returns a reference to the buffered data which has not been written to
+    /// the underlying writer yet.
+    pub fn buffer(&self) -> &[u8] {
+        &self.buf
+    }
+
+    /// Returns the number of bytes the internal buffer can hold before it is
+    /// written to the underlying writer.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Gets a reference to the underlying writer.
+    // Lint: The writer is only taken by `into_inner`, which consumes `self`,
+    // so this cannot panic.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect(Self::MOVED)
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// It is inadvisable to directly write to the underlying writer as it
+    /// will, at the least, break the cached position information.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner_mut()
+    }
+
+    /// Writes any buffered data, then unwraps this `BufWriter<W>`, returning
+    /// the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// If writing the buffered data fails, an error is returned and the
+    /// buffered data is lost.
+    // Lint: The writer is only taken by `into_inner`, which consumes `self`,
+    // so this cannot panic.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn into_inner(mut self) -> Result<W> {
+        self.flush_buf()?;
+        Ok(self.inner.take().expect(Self::MOVED))
+    }
+
+    const MOVED: &'static str = "the writer is only moved out by `into_inner`";
+
+    fn inner_mut(&mut self) -> &mut W {
+        self.inner.as_mut().expect(Self::MOVED)
+    }
+
+    /// Writes all of the buffered data to the underlying writer.
+    fn flush_buf(&mut self) -> Result<()> {
+        let mut written = 0;
+        let mut result = Ok(());
+        let inner = self.inner.as_mut().expect(Self::MOVED);
+        while written < self.buf.len() {
+            match inner.write(&self.buf[written..]) {
+                Ok(0) => {
+                    result = Err(Error::new(
+                        ErrorKind::WriteZero,
+                        "failed to write the buffered data",
+                    ));
+                    break;
+                }
+                Ok(n) => written += n,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => {
+                    result = Err(err);
+                    break;
+                }
+            }
+        }
+        self.buf.drain(..written);
+        result
+    }
+}
+
+impl<W: Write> Write for BufWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        if self.buf.len() + buf.len() > self.capacity {
+            self.flush_buf()?;
+        }
+
+        let n = if buf.len() >= self.capacity {
+            self.inner_mut().write(buf).map_err(|err| {
+                self.pos = None;
+                err
+            })?
+        } else {
+            self.buf.extend_from_slice(buf);
+            buf.len()
+        };
+
+        if let Some(pos) = &mut self.pos {
+            *pos += n as u64;
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.flush_buf()?;
+        self.inner_mut().flush()
+    }
+}
+
+impl<W: Write + Seek> Seek for BufWriter<W> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        if matches!(pos, SeekFrom::Current(0)) {
+            return self.stream_position();
+        }
+
+        self.flush_buf()?;
+        self.pos = None;
+        let pos = self.inner_mut().seek(pos)?;
+        self.pos = Some(pos);
+        Ok(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        if let Some(pos) = self.pos {
+            Ok(pos)
+        } else {
+            let pos = self.inner_mut().stream_position()? + self.buf.len() as u64;
+            self.pos = Some(pos);
+            Ok(pos)
+        }
+    }
+}
+
+impl<W: Write> Drop for BufWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            // Dtors should not panic, so the error is ignored
+            let _ = self.flush_buf();
+        }
+    }
+}
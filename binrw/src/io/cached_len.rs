@@ -0,0 +1,158 @@
+//! Types for seekable reader adapters which cache the length of the
+//! underlying reader.
+
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom};
+
+/// Read adapter which caches the length and position of an underlying reader.
+///
+/// Seeking relative to the end of the stream, querying the stream position,
+/// and reading at the end of the stream are answered without calling into the
+/// underlying reader. This makes parsers which use [`SeekFrom::End`] or read
+/// until the end of the stream, like
+/// [`until_eof`](crate::helpers::until_eof), cheaper when measuring the
+/// underlying reader is expensive, such as for network-backed or
+/// [chained](super::ChainSeek) readers.
+///
+/// Reads stop at the cached length, so the underlying reader must not change
+/// length while it is wrapped.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, helpers::until_eof, io::{CachedLen, Cursor, SeekFrom}};
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Archive {
+///     #[br(seek_before = SeekFrom::End(-1), restore_position)]
+///     count: u8,
+///     #[br(parse_with = until_eof)]
+///     data: Vec<u8>,
+/// }
+///
+/// let mut reader = CachedLen::new(Cursor::new(b"\x01\x02\x03")).unwrap();
+/// assert_eq!(reader.len(), 3);
+/// let archive = Archive::read(&mut reader).unwrap();
+/// assert_eq!(archive.count, 3);
+/// assert_eq!(archive.data, [1, 2, 3]);
+/// ```
+#[derive(Debug)]
+pub struct CachedLen<T> {
+    inner: T,
+    pos: u64,
+    len: u64,
+}
+
+impl<T> CachedLen<T> {
+    /// Gets a reference to the underlying reader.
+    #[must_use]
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying reader as doing so may corrupt the cached position of this
+    /// `CachedLen`.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the wrapped value.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns the cached length of the underlying reader.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true if the cached length of the underlying reader is zero.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the number of bytes between the current position and the end
+    /// of the stream.
+    #[must_use]
+    pub fn remaining(&self) -> u64 {
+        self.len.saturating_sub(self.pos)
+    }
+}
+
+impl<T: Seek> CachedLen<T> {
+    /// Creates a new reader which measures and caches the length of `inner`.
+    ///
+    /// # Errors
+    ///
+    /// If seeking the underlying reader fails, an error is returned.
+    pub fn new(mut inner: T) -> Result<Self> {
+        let pos = inner.stream_position()?;
+        let len = inner.seek(SeekFrom::End(0))?;
+        inner.seek(SeekFrom::Start(pos))?;
+        Ok(Self { inner, pos, len })
+    }
+
+    /// Creates a new reader which uses `len` as the length of `inner`, for
+    /// when the length is already known.
+    ///
+    /// # Errors
+    ///
+    /// If getting the position of the underlying reader fails, an error is
+    /// returned.
+    pub fn with_len(mut inner: T, len: u64) -> Result<Self> {
+        let pos = inner.stream_position()?;
+        Ok(Self { inner, pos, len })
+    }
+}
+
+impl<T: Read> Read for CachedLen<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let limit = self.remaining();
+
+        // Don't call into inner reader at all at EOF because it may still block
+        if limit == 0 {
+            return Ok(0);
+        }
+
+        // Lint: It is impossible for this cast to truncate because the value
+        // being cast is the minimum of two values, and one of the value types
+        // is already `usize`.
+        #[allow(clippy::cast_possible_truncation)]
+        let max = (buf.len() as u64).min(limit) as usize;
+        let n = self.inner.read(&mut buf[0..max])?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<T: Seek> Seek for CachedLen<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+        };
+
+        match new_pos {
+            Some(new_pos) if new_pos == self.pos => Ok(new_pos),
+            Some(new_pos) => {
+                self.pos = self.inner.seek(SeekFrom::Start(new_pos))?;
+                Ok(self.pos)
+            }
+            None => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )),
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
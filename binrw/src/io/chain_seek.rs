@@ -0,0 +1,164 @@
+//! Types for seekable reader adapters which join several underlying readers.
+
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom};
+use alloc::vec::Vec;
+
+/// Read adapter which joins several underlying readers into one continuous
+/// stream, with seek support.
+///
+/// This is useful for data which is split across several files, such as
+/// split archives or multi-volume dumps. Unlike `Read::chain`, any number of
+/// readers can be joined, and seeking to any position in the combined stream
+/// seeks to the corresponding position in the correct underlying reader.
+///
+/// The length of each reader is measured once when the `ChainSeek` is
+/// created, so the underlying readers must not change length afterwards.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinReaderExt, io::{ChainSeek, Cursor, Seek, SeekFrom}};
+///
+/// let parts = vec![Cursor::new(&b"\0\x01\0"[..]), Cursor::new(&b"\x02\0\x03"[..])];
+/// let mut reader = ChainSeek::new(parts).unwrap();
+/// reader.seek(SeekFrom::Start(2)).unwrap();
+/// let value: u16 = reader.read_be().unwrap();
+/// assert_eq!(value, 2);
+/// assert_eq!(reader.len(), 6);
+/// ```
+#[derive(Debug)]
+pub struct ChainSeek<T> {
+    inner: Vec<T>,
+    ends: Vec<u64>,
+    pos: u64,
+    index: usize,
+}
+
+impl<T> ChainSeek<T> {
+    /// Gets a reference to the underlying readers.
+    #[must_use]
+    pub fn get_ref(&self) -> &[T] {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying readers.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying readers as doing so may corrupt the internal position of
+    /// this `ChainSeek`.
+    pub fn get_mut(&mut self) -> &mut [T] {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the wrapped readers.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<T> {
+        self.inner
+    }
+
+    /// Returns the combined length of the underlying readers.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.ends.last().copied().unwrap_or(0)
+    }
+
+    /// Returns true if the combined length of the underlying readers is zero.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn start_of(&self, index: usize) -> u64 {
+        index.checked_sub(1).map_or(0, |index| self.ends[index])
+    }
+}
+
+impl<T: Seek> ChainSeek<T> {
+    /// Creates a new reader which reads from each of the given readers in
+    /// turn, starting at the beginning of the first reader.
+    ///
+    /// # Errors
+    ///
+    /// If measuring the length of any of the readers fails, or their combined
+    /// length overflows, an error is returned.
+    pub fn new(mut inner: Vec<T>) -> Result<Self> {
+        let mut ends = Vec::with_capacity(inner.len());
+        let mut end = 0_u64;
+        for reader in &mut inner {
+            end = end
+                .checked_add(reader.seek(SeekFrom::End(0))?)
+                .ok_or_else(|| {
+                    Error::new(
+                        ErrorKind::InvalidInput,
+                        "combined length of readers overflows",
+                    )
+                })?;
+            ends.push(end);
+        }
+
+        let mut chain = Self {
+            inner,
+            ends,
+            pos: 0,
+            index: 0,
+        };
+        chain.seek(SeekFrom::Start(0))?;
+        Ok(chain)
+    }
+}
+
+impl<T: Read + Seek> Read for ChainSeek<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        while let Some(&end) = self.ends.get(self.index) {
+            let remaining = end - self.pos;
+            if remaining == 0 {
+                self.index += 1;
+                if let Some(reader) = self.inner.get_mut(self.index) {
+                    reader.seek(SeekFrom::Start(0))?;
+                }
+                continue;
+            }
+
+            let limit = usize::try_from(remaining).map_or(buf.len(), |n| n.min(buf.len()));
+            let n = self.inner[self.index].read(&mut buf[..limit])?;
+            self.pos += n as u64;
+            return Ok(n);
+        }
+
+        Ok(0)
+    }
+}
+
+impl<T: Seek> Seek for ChainSeek<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => self.len().checked_add_signed(offset),
+        }
+        .ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+
+        let index = self.ends.partition_point(|&end| end <= new_pos);
+        if index < self.inner.len() {
+            let start = self.start_of(index);
+            self.inner[index].seek(SeekFrom::Start(new_pos - start))?;
+        }
+
+        self.pos = new_pos;
+        self.index = index;
+        Ok(new_pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
@@ -0,0 +1,443 @@
+//! Reader and writer adapters which compute a checksum over the bytes read or
+//! written through them.
+
+use super::{Read, Result, Seek, SeekFrom, Write};
+
+/// A checksum algorithm which can be computed incrementally.
+///
+/// Implement this trait to use other algorithms, such as ones provided by
+/// other crates, with a [`ChecksumReader`] or [`ChecksumWriter`]. With the
+/// `digest` feature enabled, any cryptographic hash function can be used with
+/// [`DigestChecksum`].
+pub trait Checksum {
+    /// The type of the computed checksum.
+    type Output;
+
+    /// Adds `bytes` to the checksum.
+    fn update(&mut self, bytes: &[u8]);
+
+    /// Returns the checksum of all of the bytes added since the checksum was
+    /// created or last reset.
+    fn finish(&self) -> Self::Output;
+
+    /// Resets the checksum to its initial state.
+    fn reset(&mut self);
+}
+
+/// The CRC-32 (ISO-HDLC) checksum, as used by zlib, PNG, and Ethernet.
+///
+/// See also [`helpers::crc32`](crate::helpers::crc32).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crc32 {
+    state: u32,
+}
+
+impl Crc32 {
+    /// Creates a new CRC-32 checksum.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { state: !0 }
+    }
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Crc32 {
+    type Output = u32;
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.state = bytes.iter().fold(self.state, |crc, &byte| {
+            (0..8).fold(crc ^ u32::from(byte), |crc, _| {
+                (crc >> 1) ^ (0xedb8_8320 & 0u32.wrapping_sub(crc & 1))
+            })
+        });
+    }
+
+    fn finish(&self) -> u32 {
+        !self.state
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// The Adler-32 checksum, as used by zlib streams.
+///
+/// See also [`helpers::adler32`](crate::helpers::adler32).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    const MOD: u32 = 65521;
+
+    // The largest number of bytes which can be added before `b` may overflow
+    const CHUNK_SIZE: usize = 5552;
+
+    /// Creates a new Adler-32 checksum.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { a: 1, b: 0 }
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Checksum for Adler32 {
+    type Output = u32;
+
+    fn update(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(Self::CHUNK_SIZE) {
+            for &byte in chunk {
+                self.a += u32::from(byte);
+                self.b += self.a;
+            }
+            self.a %= Self::MOD;
+            self.b %= Self::MOD;
+        }
+    }
+
+    fn finish(&self) -> u32 {
+        (self.b << 16) | self.a
+    }
+
+    fn reset(&mut self) {
+        *self = Self::new();
+    }
+}
+
+/// A [`Checksum`] which computes a [`digest::Digest`], such as SHA-256 or MD5.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::io::{Checksum, DigestChecksum};
+/// use sha2::Sha256;
+///
+/// let mut checksum = DigestChecksum::<Sha256>::new();
+/// checksum.update(b"abc");
+/// assert_eq!(checksum.finish()[..4], [0xba, 0x78, 0x16, 0xbf]);
+/// ```
+#[cfg(feature = "digest")]
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "digest")))]
+#[derive(Debug, Clone, Default)]
+pub struct DigestChecksum<D>(D);
+
+#[cfg(feature = "digest")]
+impl<D: digest::Digest> DigestChecksum<D> {
+    /// Creates a new checksum using the hash function `D`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self(D::new())
+    }
+
+    /// Gets a reference to the underlying hasher.
+    pub fn get_ref(&self) -> &D {
+        &self.0
+    }
+
+    /// Consumes this wrapper, returning the underlying hasher.
+    pub fn into_inner(self) -> D {
+        self.0
+    }
+}
+
+#[cfg(feature = "digest")]
+impl<D: digest::Digest + Clone> Checksum for DigestChecksum<D> {
+    type Output = digest::Output<D>;
+
+    fn update(&mut self, bytes: &[u8]) {
+        self.0.update(bytes);
+    }
+
+    fn finish(&self) -> Self::Output {
+        self.0.clone().finalize()
+    }
+
+    fn reset(&mut self) {
+        self.0 = D::new();
+    }
+}
+
+/// A reader adapter which computes a checksum over the bytes read through it.
+///
+/// This is the reading counterpart of [`ChecksumWriter`], and can be used to
+/// verify a checksum or signature over data while it is parsed, without
+/// reading the data a second time. Bytes are added to the checksum in the
+/// order they are read, and only while the reader is active. A reader starts
+/// out active, and the region of the stream which is checked can be
+/// controlled with [`start`](Self::start), which resets the checksum, and
+/// [`stop`](Self::stop). Seeking does not change the checksum, so bytes which
+/// are skipped over are not checked and bytes which are read again are
+/// checked again.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, BinResult, io::{ChecksumReader, Crc32, Cursor, Seek}};
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Header {
+///     kind: u8,
+///     len: u16,
+/// }
+///
+/// #[binrw::parser(reader, endian)]
+/// fn parse_checked() -> BinResult<Header> {
+///     let mut reader = ChecksumReader::new(reader, Crc32::new());
+///     let header = Header::read_options(&mut reader, endian, ())?;
+///     let pos = reader.stream_position()?;
+///     let expected = reader.stop();
+///     let crc = u32::read_options(&mut reader, endian, ())?;
+///     if crc == expected {
+///         Ok(header)
+///     } else {
+///         Err(binrw::Error::AssertFail {
+///             pos,
+///             message: "bad header checksum".into(),
+///         })
+///     }
+/// }
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct File {
+///     #[br(parse_with = parse_checked)]
+///     header: Header,
+/// }
+///
+/// let mut data = Cursor::new(b"\x01\x00\x02\x10\x8d\xd2\x09");
+/// assert_eq!(File::read(&mut data).unwrap().header.len, 2);
+/// ```
+#[derive(Debug)]
+pub struct ChecksumReader<R, C> {
+    inner: R,
+    checksum: C,
+    active: bool,
+}
+
+impl<R, C: Checksum> ChecksumReader<R, C> {
+    /// Creates a new reader which reads from `inner` and adds every byte it
+    /// reads to `checksum`.
+    pub fn new(inner: R, checksum: C) -> Self {
+        Self {
+            inner,
+            checksum,
+            active: true,
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read from the underlying reader directly are not added to the
+    /// checksum.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Gets a reference to the checksum.
+    pub fn checksum(&self) -> &C {
+        &self.checksum
+    }
+
+    /// Returns the checksum of the bytes read so far.
+    pub fn value(&self) -> C::Output {
+        self.checksum.finish()
+    }
+
+    /// Returns true if read bytes are currently added to the checksum.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Resets the checksum and starts adding read bytes to it.
+    pub fn start(&mut self) {
+        self.checksum.reset();
+        self.active = true;
+    }
+
+    /// Stops adding read bytes to the checksum, and returns the checksum of
+    /// the bytes read since the last [`start`](Self::start).
+    pub fn stop(&mut self) -> C::Output {
+        self.active = false;
+        self.checksum.finish()
+    }
+
+    /// Starts adding read bytes to the checksum again without resetting it.
+    pub fn resume(&mut self) {
+        self.active = true;
+    }
+
+    /// Consumes this wrapper, returning the underlying reader and the
+    /// checksum.
+    pub fn into_inner(self) -> (R, C) {
+        (self.inner, self.checksum)
+    }
+}
+
+impl<R: Read, C: Checksum> Read for ChecksumReader<R, C> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        if self.active {
+            self.checksum.update(&buf[..n]);
+        }
+        Ok(n)
+    }
+}
+
+impl<R: Seek, C> Seek for ChecksumReader<R, C> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        self.inner.stream_position()
+    }
+}
+
+/// A writer adapter which computes a checksum over the bytes written through
+/// it.
+///
+/// Bytes are added to the checksum in the order they are written, and only
+/// while the writer is active. A writer starts out active, and the region of
+/// the stream which is checked can be controlled with [`start`](Self::start),
+/// which resets the checksum, and [`stop`](Self::stop). Seeking does not
+/// change the checksum, so bytes which are skipped over are not checked and
+/// bytes which are overwritten are checked again.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinResult, BinWrite, io::{ChecksumWriter, Crc32, Cursor}};
+///
+/// #[binrw::writer(writer, endian)]
+/// fn write_checked(data: &[u8; 4]) -> BinResult<()> {
+///     let mut writer = ChecksumWriter::new(writer, Crc32::new());
+///     data.write_options(&mut writer, endian, ())?;
+///     let crc = writer.stop();
+///     crc.write_options(&mut writer, endian, ())
+/// }
+///
+/// #[derive(BinWrite)]
+/// #[bw(big)]
+/// struct Chunk {
+///     #[bw(write_with = write_checked)]
+///     data: [u8; 4],
+/// }
+///
+/// let mut output = Cursor::new(vec![]);
+/// Chunk { data: *b"IEND" }.write(&mut output).unwrap();
+/// assert_eq!(output.get_ref(), b"IEND\xae\x42\x60\x82");
+/// ```
+#[derive(Debug)]
+pub struct ChecksumWriter<W, C> {
+    inner: W,
+    checksum: C,
+    active: bool,
+}
+
+impl<W, C: Checksum> ChecksumWriter<W, C> {
+    /// Creates a new writer which writes to `inner` and adds every byte it
+    /// writes to `checksum`.
+    pub fn new(inner: W, checksum: C) -> Self {
+        Self {
+            inner,
+            checksum,
+            active: true,
+        }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written to the underlying writer directly are not added to the
+    /// checksum.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Gets a reference to the checksum.
+    pub fn checksum(&self) -> &C {
+        &self.checksum
+    }
+
+    /// Returns the checksum of the bytes written so far.
+    pub fn value(&self) -> C::Output {
+        self.checksum.finish()
+    }
+
+    /// Returns true if written bytes are currently added to the checksum.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Resets the checksum and starts adding written bytes to it.
+    pub fn start(&mut self) {
+        self.checksum.reset();
+        self.active = true;
+    }
+
+    /// Stops adding written bytes to the checksum, and returns the checksum
+    /// of the bytes written since the last [`start`](Self::start).
+    pub fn stop(&mut self) -> C::Output {
+        self.active = false;
+        self.checksum.finish()
+    }
+
+    /// Starts adding written bytes to the checksum again without resetting
+    /// it.
+    pub fn resume(&mut self) {
+        self.active = true;
+    }
+
+    /// Consumes this wrapper, returning the underlying writer and the
+    /// checksum.
+    pub fn into_inner(self) -> (W, C) {
+        (self.inner, self.checksum)
+    }
+}
+
+impl<W: Write, C: Checksum> Write for ChecksumWriter<W, C> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let n = self.inner.write(buf)?;
+        if self.active {
+            self.checksum.update(&buf[..n]);
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Seek, C> Seek for ChecksumWriter<W, C> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        self.inner.stream_position()
+    }
+}
@@ -0,0 +1,330 @@
+//! Reader adapters which decrypt data encrypted with a block cipher.
+
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom};
+use cipher::{Block, BlockDecrypt, StreamCipher, StreamCipherSeek, Unsigned};
+
+/// A reader adapter which decrypts data encrypted with a stream cipher, or a
+/// block cipher in a streaming mode like CTR.
+///
+/// The keystream starts at the current position of the underlying reader, so
+/// this adapter can be created by
+/// [`map_stream`](crate::docs::attribute#stream-access-and-manipulation) at
+/// the start of an encrypted region, with the key taken from the arguments of
+/// the type. Seeking moves the keystream along with the
+/// underlying reader, so encrypted data can be parsed with random access.
+/// Positions are the same as the positions of the underlying reader.
+///
+/// # Examples
+///
+/// ```
+/// use aes::{cipher::KeyIvInit, Aes128};
+/// use binrw::{BinRead, io::{Cursor, StreamCipherReader}};
+///
+/// type Aes128Ctr = ctr::Ctr128BE<Aes128>;
+///
+/// #[derive(BinRead)]
+/// #[br(big, import(key: [u8; 16], nonce: [u8; 16]))]
+/// #[br(map_stream = |reader| {
+///     StreamCipherReader::new(reader, Aes128Ctr::new(&key.into(), &nonce.into()))
+/// })]
+/// struct Secret {
+///     value: u32,
+/// }
+///
+/// let mut data = Cursor::new(b"\x05\xe2\x42\x8b");
+/// let secret = Secret::read_args(&mut data, ([1; 16], [2; 16])).unwrap();
+/// assert_eq!(secret.value, 0x1234_5678);
+/// ```
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "cipher")))]
+#[derive(Debug)]
+pub struct StreamCipherReader<R, C> {
+    inner: R,
+    cipher: C,
+}
+
+impl<R, C> StreamCipherReader<R, C> {
+    /// Creates a new reader which decrypts data read from `inner` using
+    /// `cipher`.
+    pub fn new(inner: R, cipher: C) -> Self {
+        Self { inner, cipher }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Reading from or seeking the underlying reader directly will put the
+    /// keystream out of sync with the data.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying reader and the cipher.
+    pub fn into_inner(self) -> (R, C) {
+        (self.inner, self.cipher)
+    }
+}
+
+impl<R: Read, C: StreamCipher> Read for StreamCipherReader<R, C> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.cipher
+            .try_apply_keystream(&mut buf[..n])
+            .map_err(|_| Error::new(ErrorKind::InvalidData, "keystream exhausted"))?;
+        Ok(n)
+    }
+}
+
+impl<R: Seek, C: StreamCipherSeek> Seek for StreamCipherReader<R, C> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let old_pos = self.inner.stream_position()?;
+        let new_pos = self.inner.seek(pos)?;
+        let keystream_pos = self
+            .cipher
+            .try_current_pos::<u64>()
+            .ok()
+            .and_then(|keystream_pos| {
+                if new_pos >= old_pos {
+                    keystream_pos.checked_add(new_pos - old_pos)
+                } else {
+                    keystream_pos.checked_sub(old_pos - new_pos)
+                }
+            });
+
+        if let Some(Ok(())) = keystream_pos.map(|pos| self.cipher.try_seek(pos)) {
+            Ok(new_pos)
+        } else {
+            self.inner.seek(SeekFrom::Start(old_pos))?;
+            Err(Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek outside of the keystream",
+            ))
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        self.inner.stream_position()
+    }
+}
+
+/// A reader adapter which decrypts data encrypted with a block cipher in CBC
+/// mode.
+///
+/// The encrypted data starts at the position of the underlying reader when
+/// the first byte is read or the first seek happens, so this adapter can be
+/// created by [`map_stream`](crate::docs::attribute#stream-access-and-manipulation)
+/// at the start of an encrypted region, with the key taken from the
+/// arguments of the type. Each block is decrypted using only the ciphertext
+/// of the previous block, so seeking within the encrypted data is supported.
+/// Positions are the same as the positions of the underlying reader, and
+/// seeking before the start of the encrypted data is an error.
+///
+/// The encrypted data must be a whole number of blocks long. Any padding is
+/// not removed and is read as part of the decrypted data.
+///
+/// # Examples
+///
+/// ```
+/// use aes::{cipher::KeyInit, Aes128};
+/// use binrw::{BinRead, io::{CbcReader, Cursor}};
+///
+/// #[derive(BinRead)]
+/// #[br(big, import(key: [u8; 16], iv: [u8; 16]))]
+/// #[br(map_stream = |reader| CbcReader::new(reader, Aes128::new(&key.into()), iv.into()))]
+/// struct Secret {
+///     value: u32,
+/// }
+///
+/// let mut data = Cursor::new(
+///     b"\x7f\x07\xda\x55\xd0\x13\x75\xb3\x91\x50\x20\x5f\xcb\x45\x98\x62",
+/// );
+/// let secret = Secret::read_args(&mut data, ([1; 16], [2; 16])).unwrap();
+/// assert_eq!(secret.value, 0x1234_5678);
+/// ```
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "cipher")))]
+#[derive(Debug)]
+pub struct CbcReader<R, C: BlockDecrypt> {
+    inner: R,
+    cipher: C,
+    iv: Block<C>,
+    // The position where the encrypted data starts in the underlying reader,
+    // which is only known after the first read or seek.
+    start: Option<u64>,
+    // The position relative to the start of the encrypted data.
+    pos: u64,
+    // The position of the underlying reader relative to the start of the
+    // encrypted data, if it is known.
+    inner_pos: Option<u64>,
+    // The index, plaintext, and ciphertext of the last decrypted block.
+    block: Option<(u64, Block<C>, Block<C>)>,
+}
+
+impl<R, C: BlockDecrypt> CbcReader<R, C> {
+    /// Creates a new reader which decrypts data read from `inner` using
+    /// `cipher` and the initialisation vector `iv`.
+    pub fn new(inner: R, cipher: C, iv: Block<C>) -> Self {
+        Self {
+            inner,
+            cipher,
+            iv,
+            start: None,
+            pos: 0,
+            inner_pos: None,
+            block: None,
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying reader as doing so may corrupt the internal position of
+    /// this `CbcReader`.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner_pos = None;
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying reader and the cipher.
+    pub fn into_inner(self) -> (R, C) {
+        (self.inner, self.cipher)
+    }
+
+    fn block_size() -> u64 {
+        C::BlockSize::U64
+    }
+}
+
+impl<R: Read + Seek, C: BlockDecrypt> CbcReader<R, C> {
+    fn start(&mut self) -> Result<u64> {
+        if let Some(start) = self.start {
+            Ok(start)
+        } else {
+            let start = self.inner.stream_position()?;
+            self.start = Some(start);
+            self.inner_pos = Some(0);
+            Ok(start)
+        }
+    }
+
+    /// Reads the ciphertext block at `index`, returning `false` if the
+    /// encrypted data ends before the block.
+    fn read_block(&mut self, index: u64, block: &mut Block<C>) -> Result<bool> {
+        let start = self.start()?;
+        let pos = index * Self::block_size();
+        if self.inner_pos != Some(pos) {
+            self.inner_pos = None;
+            self.inner.seek(SeekFrom::Start(start + pos))?;
+        }
+
+        let mut filled = 0;
+        while filled < block.len() {
+            match self.inner.read(&mut block[filled..]) {
+                Ok(0) => break,
+                Ok(n) => filled += n,
+                Err(err) if err.kind() == ErrorKind::Interrupted => {}
+                Err(err) => return Err(err),
+            }
+        }
+        self.inner_pos = Some(pos + filled as u64);
+
+        if filled == 0 {
+            Ok(false)
+        } else if filled == block.len() {
+            Ok(true)
+        } else {
+            Err(Error::new(
+                ErrorKind::InvalidData,
+                "encrypted data is not a whole number of blocks",
+            ))
+        }
+    }
+
+    /// Decrypts the block at `index`, returning `false` if the encrypted data
+    /// ends before the block.
+    fn decrypt_block(&mut self, index: u64) -> Result<bool> {
+        let prev = match (index, self.block.take()) {
+            (0, _) => self.iv.clone(),
+            (_, Some((prev_index, _, ciphertext))) if prev_index + 1 == index => ciphertext,
+            _ => {
+                let mut prev = Block::<C>::default();
+                if !self.read_block(index - 1, &mut prev)? {
+                    return Ok(false);
+                }
+                prev
+            }
+        };
+
+        let mut ciphertext = Block::<C>::default();
+        if !self.read_block(index, &mut ciphertext)? {
+            return Ok(false);
+        }
+
+        let mut plaintext = ciphertext.clone();
+        self.cipher.decrypt_block(&mut plaintext);
+        for (byte, prev) in plaintext.iter_mut().zip(prev.iter()) {
+            *byte ^= *prev;
+        }
+        self.block = Some((index, plaintext, ciphertext));
+        Ok(true)
+    }
+}
+
+impl<R: Read + Seek, C: BlockDecrypt> Read for CbcReader<R, C> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let index = self.pos / Self::block_size();
+        if !matches!(self.block, Some((block_index, ..)) if block_index == index)
+            && !self.decrypt_block(index)?
+        {
+            return Ok(0);
+        }
+
+        let Some((_, plaintext, _)) = &self.block else {
+            unreachable!("a block was just decrypted")
+        };
+
+        // Lint: The offset is always less than the block size.
+        #[allow(clippy::cast_possible_truncation)]
+        let offset = (self.pos % Self::block_size()) as usize;
+        let n = buf.len().min(plaintext.len() - offset);
+        buf[..n].copy_from_slice(&plaintext[offset..offset + n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: Read + Seek, C: BlockDecrypt> Seek for CbcReader<R, C> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let start = self.start()?;
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(offset) => (start + self.pos).checked_add_signed(offset),
+            SeekFrom::End(offset) => {
+                self.inner_pos = None;
+                Some(self.inner.seek(SeekFrom::End(offset))?)
+            }
+        };
+
+        match new_pos {
+            Some(new_pos) if new_pos >= start => {
+                self.pos = new_pos - start;
+                Ok(new_pos)
+            }
+            _ => Err(Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek before the start of the encrypted data",
+            )),
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.start()? + self.pos)
+    }
+}
@@ -0,0 +1,279 @@
+//! Adapters which count the bytes read from or written to a stream.
+
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom, Write};
+
+/// A reader adapter which counts the bytes read from an underlying reader.
+///
+/// The position of the underlying reader is cached, so calling
+/// [`stream_position`](Seek::stream_position) only queries the underlying
+/// reader the first time, and again after an error or a call to
+/// [`get_mut`](Self::get_mut). This avoids a system call per query when
+/// reading from a `File`.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinReaderExt, io::{CountingReader, Cursor}};
+///
+/// let mut reader = CountingReader::new(Cursor::new(b"\0\x01\0\x02"));
+/// let value: u16 = reader.read_be().unwrap();
+/// assert_eq!(value, 1);
+/// assert_eq!(reader.bytes_read(), 2);
+/// ```
+#[derive(Debug)]
+pub struct CountingReader<R> {
+    inner: R,
+    count: u64,
+    pos: Option<u64>,
+}
+
+impl<R> CountingReader<R> {
+    /// Creates a new counting reader which reads from `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            count: 0,
+            pos: None,
+        }
+    }
+
+    /// Returns the number of bytes which have been read through this reader.
+    ///
+    /// Bytes skipped by seeking are not counted.
+    pub fn bytes_read(&self) -> u64 {
+        self.count
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read from the underlying reader directly are not counted. The
+    /// cached position is discarded, since the underlying reader may be
+    /// moved.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.pos = None;
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf).map_err(|err| {
+            self.pos = None;
+            err
+        })?;
+        self.count += n as u64;
+        if let Some(pos) = &mut self.pos {
+            *pos += n as u64;
+        }
+        Ok(n)
+    }
+}
+
+impl<R: Seek> Seek for CountingReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.pos = None;
+        let pos = self.inner.seek(pos)?;
+        self.pos = Some(pos);
+        Ok(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        cached_position(&mut self.inner, &mut self.pos)
+    }
+}
+
+/// A writer adapter which counts the bytes written to an underlying writer.
+///
+/// The position of the underlying writer is cached in the same way as
+/// [`CountingReader`].
+///
+/// A counting writer over a [`Discard`] writer, created by
+/// [`sink`](CountingWriter::sink), measures the size of a value without
+/// storing it anywhere.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinWrite, io::CountingWriter};
+///
+/// #[derive(BinWrite)]
+/// #[bw(little)]
+/// struct Header {
+///     version: u16,
+///     #[bw(pad_after = 2)]
+///     flags: u32,
+/// }
+///
+/// let mut counter = CountingWriter::sink();
+/// Header { version: 1, flags: 0 }.write(&mut counter).unwrap();
+/// assert_eq!(counter.bytes_written(), 8);
+/// ```
+#[derive(Debug)]
+pub struct CountingWriter<W> {
+    inner: W,
+    count: u64,
+    pos: Option<u64>,
+}
+
+impl<W> CountingWriter<W> {
+    /// Creates a new counting writer which writes to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            count: 0,
+            pos: None,
+        }
+    }
+
+    /// Returns the number of bytes which have been written through this
+    /// writer.
+    ///
+    /// Bytes skipped by seeking are not counted, and bytes which are
+    /// overwritten after seeking backwards are counted again.
+    pub fn bytes_written(&self) -> u64 {
+        self.count
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Bytes written to the underlying writer directly are not counted. The
+    /// cached position is discarded, since the underlying writer may be
+    /// moved.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.pos = None;
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl CountingWriter<Discard> {
+    /// Creates a new counting writer which discards everything written to it.
+    #[must_use]
+    pub fn sink() -> Self {
+        Self::new(Discard::new())
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let n = self.inner.write(buf).map_err(|err| {
+            self.pos = None;
+            err
+        })?;
+        self.count += n as u64;
+        if let Some(pos) = &mut self.pos {
+            *pos += n as u64;
+        }
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Seek> Seek for CountingWriter<W> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.pos = None;
+        let pos = self.inner.seek(pos)?;
+        self.pos = Some(pos);
+        Ok(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        cached_position(&mut self.inner, &mut self.pos)
+    }
+}
+
+fn cached_position<T: Seek>(inner: &mut T, pos: &mut Option<u64>) -> Result<u64> {
+    if let Some(pos) = *pos {
+        Ok(pos)
+    } else {
+        let new_pos = inner.stream_position()?;
+        *pos = Some(new_pos);
+        Ok(new_pos)
+    }
+}
+
+/// A seekable writer which discards everything written to it.
+///
+/// This behaves like a [`Cursor`](crate::io::Cursor) over a buffer which is
+/// never stored: it keeps track of its position and of the length of the data
+/// which would have been written, including any gaps left by seeking past the
+/// end.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Discard {
+    pos: u64,
+    len: u64,
+}
+
+impl Discard {
+    /// Creates a new empty writer.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the length of the data which would have been written.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true if no data would have been written.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl Write for Discard {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.pos += buf.len() as u64;
+        self.len = self.len.max(self.pos);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for Discard {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+        };
+
+        if let Some(new_pos) = new_pos {
+            self.pos = new_pos;
+            Ok(new_pos)
+        } else {
+            Err(Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            ))
+        }
+    }
+}
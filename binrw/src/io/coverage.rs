@@ -0,0 +1,169 @@
+//! Reader adapter which records which parts of a stream have been read.
+
+use super::{Read, Result, Seek, SeekFrom};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+/// A reader adapter which records the byte ranges read from an underlying
+/// reader.
+///
+/// After parsing, [`gaps`](Self::gaps) reports the parts of the input which
+/// were never read, which shows which parts of a file a schema does not
+/// account for yet.
+///
+/// Bytes skipped by seeking are not recorded, and bytes which are read more
+/// than once are recorded once. Like [`CountingReader`](super::CountingReader),
+/// the position of the underlying reader is cached so it is only queried the
+/// first time, and again after an error or a call to
+/// [`get_mut`](Self::get_mut).
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, io::{CoverageReader, Cursor}};
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Header {
+///     magic: u16,
+///     #[br(pad_before = 2)]
+///     len: u16,
+/// }
+///
+/// let mut reader = CoverageReader::new(Cursor::new(b"\x7fB\0\0\0\x02??"));
+/// Header::read(&mut reader).unwrap();
+/// assert_eq!(reader.covered(), &[0..2, 4..6]);
+/// assert_eq!(reader.gaps_to_end().unwrap(), [2..4, 6..8]);
+/// ```
+#[derive(Debug)]
+pub struct CoverageReader<R> {
+    inner: R,
+    pos: Option<u64>,
+    covered: Vec<Range<u64>>,
+}
+
+impl<R> CoverageReader<R> {
+    /// Creates a new coverage reader which reads from `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            pos: None,
+            covered: Vec::new(),
+        }
+    }
+
+    /// Returns the byte ranges which have been read through this reader.
+    ///
+    /// The ranges are sorted, and adjacent or overlapping reads are merged.
+    pub fn covered(&self) -> &[Range<u64>] {
+        &self.covered
+    }
+
+    /// Returns the byte ranges before `end` which have not been read through
+    /// this reader.
+    pub fn gaps(&self, end: u64) -> Vec<Range<u64>> {
+        let mut gaps = Vec::new();
+        let mut start = 0;
+        for range in &self.covered {
+            if range.start >= end {
+                break;
+            }
+            if range.start > start {
+                gaps.push(start..range.start);
+            }
+            start = range.end;
+        }
+        if start < end {
+            gaps.push(start..end);
+        }
+        gaps
+    }
+
+    /// Forgets every byte range which has been read so far.
+    pub fn clear(&mut self) {
+        self.covered.clear();
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read from the underlying reader directly are not recorded. The
+    /// cached position is discarded, since the underlying reader may be
+    /// moved.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.pos = None;
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn record(&mut self, range: Range<u64>) {
+        let first = self.covered.partition_point(|r| r.end < range.start);
+        let last = self.covered.partition_point(|r| r.start <= range.end);
+        if first == last {
+            self.covered.insert(first, range);
+        } else {
+            let start = range.start.min(self.covered[first].start);
+            let end = range.end.max(self.covered[last - 1].end);
+            self.covered.drain(first + 1..last);
+            self.covered[first] = start..end;
+        }
+    }
+}
+
+impl<R: Seek> CoverageReader<R> {
+    /// Returns the byte ranges which have not been read through this reader,
+    /// up to the end of the underlying reader.
+    ///
+    /// # Errors
+    ///
+    /// If finding the length of the underlying reader fails, an error is
+    /// returned.
+    pub fn gaps_to_end(&mut self) -> Result<Vec<Range<u64>>> {
+        let pos = self.stream_position()?;
+        let end = self.inner.seek(SeekFrom::End(0))?;
+        self.seek(SeekFrom::Start(pos))?;
+        Ok(self.gaps(end))
+    }
+}
+
+impl<R: Read + Seek> Read for CoverageReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let start = self.stream_position()?;
+        let n = self.inner.read(buf).map_err(|err| {
+            self.pos = None;
+            err
+        })?;
+        if n != 0 {
+            self.record(start..start + n as u64);
+        }
+        self.pos = Some(start + n as u64);
+        Ok(n)
+    }
+}
+
+impl<R: Seek> Seek for CoverageReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.pos = None;
+        let pos = self.inner.seek(pos)?;
+        self.pos = Some(pos);
+        Ok(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        if let Some(pos) = self.pos {
+            Ok(pos)
+        } else {
+            let pos = self.inner.stream_position()?;
+            self.pos = Some(pos);
+            Ok(pos)
+        }
+    }
+}
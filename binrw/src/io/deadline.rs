@@ -0,0 +1,202 @@
+//! Reader adapter which limits how long reads can take.
+
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom};
+use std::time::{Duration, Instant};
+
+/// Streams which support a timeout on blocking reads.
+///
+/// This is used by [`DeadlineReader`] to stop a read from blocking past its
+/// deadline.
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "std")))]
+pub trait ReadTimeout {
+    /// Sets the timeout of subsequent reads, or removes it if `timeout` is
+    /// `None`.
+    ///
+    /// A read which times out must fail with [`ErrorKind::WouldBlock`] or
+    /// [`ErrorKind::TimedOut`].
+    ///
+    /// # Errors
+    ///
+    /// If the timeout cannot be set, an error is returned.
+    fn set_read_timeout(&mut self, timeout: Option<Duration>) -> Result<()>;
+}
+
+impl ReadTimeout for std::net::TcpStream {
+    fn set_read_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        std::net::TcpStream::set_read_timeout(self, timeout)
+    }
+}
+
+#[cfg(unix)]
+impl ReadTimeout for std::os::unix::net::UnixStream {
+    fn set_read_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        std::os::unix::net::UnixStream::set_read_timeout(self, timeout)
+    }
+}
+
+impl<T: ReadTimeout + ?Sized> ReadTimeout for &mut T {
+    fn set_read_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        (**self).set_read_timeout(timeout)
+    }
+}
+
+/// A reader adapter which fails reads from a stalled stream instead of
+/// blocking forever.
+///
+/// Each read can be limited to a [read timeout](Self::read_timeout), and all
+/// reads together can be limited to a [total deadline](Self::deadline). When
+/// either expires, reading fails with an error which binrw converts into
+/// [`Error::Timeout`](crate::Error::Timeout), containing the position reached
+/// in the stream.
+///
+/// Positions are counted from zero when the reader is created, or taken from
+/// the underlying reader after seeking.
+///
+/// # Examples
+///
+/// ```no_run
+/// use binrw::{BinReaderExt, io::{DeadlineReader, NoSeek}};
+/// use std::{net::TcpStream, time::Duration};
+///
+/// let stream = TcpStream::connect("127.0.0.1:8080").unwrap();
+/// let mut reader = NoSeek::new(
+///     DeadlineReader::new(stream)
+///         .read_timeout(Duration::from_secs(5))
+///         .deadline_in(Duration::from_secs(30)),
+/// );
+/// match reader.read_be::<u32>() {
+///     Ok(value) => println!("read {value}"),
+///     Err(binrw::Error::Timeout { pos }) => println!("peer stalled at {pos}"),
+///     Err(err) => println!("{err}"),
+/// }
+/// ```
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "std")))]
+#[derive(Debug)]
+pub struct DeadlineReader<R> {
+    inner: R,
+    read_timeout: Option<Duration>,
+    deadline: Option<Instant>,
+    pos: u64,
+}
+
+impl<R> DeadlineReader<R> {
+    /// Creates a new reader which reads from `inner` without any timeouts.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            read_timeout: None,
+            deadline: None,
+            pos: 0,
+        }
+    }
+
+    /// Limits each read to at most `timeout`.
+    #[must_use]
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Limits all reads to finish before `deadline`.
+    #[must_use]
+    pub fn deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Limits all reads to finish within `timeout` from now.
+    #[must_use]
+    pub fn deadline_in(self, timeout: Duration) -> Self {
+        self.deadline(Instant::now() + timeout)
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// The read timeout of the underlying reader is changed by each read
+    /// through this wrapper.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying reader.
+    ///
+    /// The underlying reader keeps the read timeout set by the last read
+    /// through this wrapper.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+
+    fn expired(&self) -> Error {
+        Error::new(ErrorKind::TimedOut, DeadlineExpired { pos: self.pos })
+    }
+}
+
+impl<R: Read + ReadTimeout> Read for DeadlineReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let remaining = match self.deadline {
+            Some(deadline) => match deadline.checked_duration_since(Instant::now()) {
+                Some(remaining) if !remaining.is_zero() => Some(remaining),
+                _ => return Err(self.expired()),
+            },
+            None => None,
+        };
+
+        let timeout = match (self.read_timeout, remaining) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        };
+
+        // A zero timeout is rejected by the standard library streams
+        self.inner
+            .set_read_timeout(timeout.filter(|timeout| !timeout.is_zero()))?;
+
+        match self.inner.read(buf) {
+            Ok(n) => {
+                self.pos += n as u64;
+                Ok(n)
+            }
+            Err(err) if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {
+                Err(self.expired())
+            }
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl<R: Seek> Seek for DeadlineReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.pos = self.inner.seek(pos)?;
+        Ok(self.pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        self.pos = self.inner.stream_position()?;
+        Ok(self.pos)
+    }
+}
+
+#[derive(Debug)]
+struct DeadlineExpired {
+    pos: u64,
+}
+
+impl core::fmt::Display for DeadlineExpired {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "deadline expired at 0x{:x}", self.pos)
+    }
+}
+
+impl std::error::Error for DeadlineExpired {}
+
+/// Returns the position reached if `err` was returned by a [`DeadlineReader`]
+/// whose deadline expired.
+pub(crate) fn deadline_expired_pos(err: &Error) -> Option<u64> {
+    err.get_ref()?
+        .downcast_ref::<DeadlineExpired>()
+        .map(|expired| expired.pos)
+}
@@ -0,0 +1,709 @@
+//! Reader and writer adapters for binary data which is encoded as text.
+
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom, Write};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// The number of encoded bytes which are read or written at once.
+const CHUNK_SIZE: usize = 64;
+
+/// A reader adapter which decodes hex encoded data.
+///
+/// Every two hex digits in the underlying reader are read as one byte.
+/// Uppercase and lowercase digits are both accepted, but the data must not
+/// contain whitespace or other separators.
+///
+/// Positions and seeks are in decoded bytes, relative to the position of the
+/// underlying reader when the first byte is read or the first seek happens,
+/// so this adapter can be created by
+/// [`map_stream`](crate::docs::attribute#stream-access-and-manipulation) at
+/// the start of the encoded data.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, io::{Cursor, HexReader}};
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Message {
+///     kind: u8,
+///     value: u16,
+/// }
+///
+/// let message = Message::read(&mut HexReader::new(Cursor::new(b"01beEF"))).unwrap();
+/// assert_eq!(message.kind, 1);
+/// assert_eq!(message.value, 0xbeef);
+/// ```
+#[derive(Debug)]
+pub struct HexReader<R> {
+    inner: R,
+    start: Option<u64>,
+    pos: u64,
+    // The position of the underlying reader relative to the start of the
+    // encoded data, if it is known.
+    inner_pos: Option<u64>,
+}
+
+impl<R> HexReader<R> {
+    /// Creates a new reader which decodes hex encoded data read from `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            start: None,
+            pos: 0,
+            inner_pos: None,
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying reader as doing so may corrupt the internal position of
+    /// this `HexReader`.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner_pos = None;
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Seek> HexReader<R> {
+    fn start(&mut self) -> Result<u64> {
+        lazy_start(&mut self.inner, &mut self.start, &mut self.inner_pos)
+    }
+
+    fn locate(&mut self, pos: u64) -> Result<()> {
+        let start = self.start()?;
+        locate(&mut self.inner, &mut self.inner_pos, start, pos)
+    }
+}
+
+impl<R: Read + Seek> Read for HexReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.locate(self.pos * 2)?;
+
+        let mut encoded = [0; CHUNK_SIZE];
+        let max = buf.len().min(CHUNK_SIZE / 2) * 2;
+        let n = read_full(&mut self.inner, &mut encoded[..max])?;
+        self.inner_pos = self.inner_pos.map(|pos| pos + n as u64);
+
+        if n % 2 != 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "odd number of hex digits",
+            ));
+        }
+
+        for (byte, digits) in buf.iter_mut().zip(encoded[..n].chunks_exact(2)) {
+            *byte = (hex_value(digits[0])? << 4) | hex_value(digits[1])?;
+        }
+
+        self.pos += n as u64 / 2;
+        Ok(n / 2)
+    }
+}
+
+impl<R: Seek> Seek for HexReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let start = self.start()?;
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => {
+                let len = encoded_len(&mut self.inner, &mut self.inner_pos, start)?;
+                (len / 2).checked_add_signed(offset)
+            }
+        };
+
+        match new_pos {
+            Some(new_pos) if fits(start, new_pos.checked_mul(2)) => {
+                self.pos = new_pos;
+                Ok(new_pos)
+            }
+            _ => Err(invalid_seek()),
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+/// A writer adapter which hex encodes data.
+///
+/// Every byte is written to the underlying writer as two lowercase hex
+/// digits.
+///
+/// Positions and seeks are in decoded bytes, relative to the position of the
+/// underlying writer when it was wrapped.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinWrite, io::{Cursor, HexWriter}};
+///
+/// let mut writer = HexWriter::new(Cursor::new(Vec::new()));
+/// 0xbeef_u16.write_be(&mut writer).unwrap();
+/// assert_eq!(writer.into_inner().into_inner(), b"beef");
+/// ```
+#[derive(Debug)]
+pub struct HexWriter<W> {
+    inner: W,
+    pos: u64,
+}
+
+impl<W> HexWriter<W> {
+    /// Creates a new writer which hex encodes data written to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self { inner, pos: 0 }
+    }
+
+    /// Gets a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying writer as doing so may corrupt the internal position of
+    /// this `HexWriter`.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for HexWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let mut encoded = [0; CHUNK_SIZE];
+        let n = buf.len().min(CHUNK_SIZE / 2);
+        for (&byte, digits) in buf[..n].iter().zip(encoded.chunks_exact_mut(2)) {
+            digits[0] = HEX_DIGITS[usize::from(byte >> 4)];
+            digits[1] = HEX_DIGITS[usize::from(byte & 0xf)];
+        }
+
+        self.inner.write_all(&encoded[..n * 2])?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Seek> Seek for HexWriter<W> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let start = self.inner.stream_position()?.saturating_sub(self.pos * 2);
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => {
+                let end = self.inner.seek(SeekFrom::End(0))?;
+                (end.saturating_sub(start) / 2).checked_add_signed(offset)
+            }
+        };
+
+        match new_pos {
+            Some(new_pos) if fits(start, new_pos.checked_mul(2)) => {
+                self.inner.seek(SeekFrom::Start(start + new_pos * 2))?;
+                self.pos = new_pos;
+                Ok(new_pos)
+            }
+            _ => {
+                self.inner.seek(SeekFrom::Start(start + self.pos * 2))?;
+                Err(invalid_seek())
+            }
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+/// A reader adapter which decodes base64 encoded data.
+///
+/// The data must use the standard base64 alphabet with padding, as defined in
+/// [RFC 4648], and must not contain whitespace or other separators.
+///
+/// Positions and seeks are in decoded bytes, relative to the position of the
+/// underlying reader when the first byte is read or the first seek happens,
+/// so this adapter can be created by
+/// [`map_stream`](crate::docs::attribute#stream-access-and-manipulation) at
+/// the start of the encoded data.
+///
+/// [RFC 4648]: https://datatracker.ietf.org/doc/html/rfc4648#section-4
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, io::{Base64Reader, Cursor}};
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Message {
+///     kind: u8,
+///     value: u16,
+/// }
+///
+/// let message = Message::read(&mut Base64Reader::new(Cursor::new(b"Ab7v"))).unwrap();
+/// assert_eq!(message.kind, 1);
+/// assert_eq!(message.value, 0xbeef);
+/// ```
+#[derive(Debug)]
+pub struct Base64Reader<R> {
+    inner: R,
+    start: Option<u64>,
+    pos: u64,
+    // The position of the underlying reader relative to the start of the
+    // encoded data, if it is known.
+    inner_pos: Option<u64>,
+    // The most recently decoded bytes, and their position.
+    decoded: [u8; CHUNK_SIZE / 4 * 3],
+    decoded_pos: u64,
+    decoded_len: usize,
+}
+
+impl<R> Base64Reader<R> {
+    /// Creates a new reader which decodes base64 encoded data read from
+    /// `inner`.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            start: None,
+            pos: 0,
+            inner_pos: None,
+            decoded: [0; CHUNK_SIZE / 4 * 3],
+            decoded_pos: 0,
+            decoded_len: 0,
+        }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying reader as doing so may corrupt the internal position of
+    /// this `Base64Reader`.
+    pub fn get_mut(&mut self) -> &mut R {
+        self.inner_pos = None;
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the underlying reader.
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Seek> Base64Reader<R> {
+    fn start(&mut self) -> Result<u64> {
+        lazy_start(&mut self.inner, &mut self.start, &mut self.inner_pos)
+    }
+
+    fn locate(&mut self, pos: u64) -> Result<()> {
+        let start = self.start()?;
+        locate(&mut self.inner, &mut self.inner_pos, start, pos)
+    }
+}
+
+impl<R: Read + Seek> Base64Reader<R> {
+    /// Decodes the data starting at the group which contains the current
+    /// position.
+    fn fill(&mut self) -> Result<()> {
+        let group = self.pos / 3;
+        self.locate(group * 4)?;
+
+        let mut encoded = [0; CHUNK_SIZE];
+        let n = read_full(&mut self.inner, &mut encoded)?;
+        self.inner_pos = self.inner_pos.map(|pos| pos + n as u64);
+
+        if n % 4 != 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "base64 data is not a whole number of groups",
+            ));
+        }
+
+        self.decoded_pos = group * 3;
+        self.decoded_len = 0;
+        let groups = encoded[..n].chunks_exact(4);
+        let count = groups.len();
+        for (index, chars) in groups.enumerate() {
+            let padding = chars.iter().rev().take_while(|&&c| c == b'=').count();
+            if padding > 2 || (padding != 0 && index + 1 != count) {
+                return Err(Error::new(ErrorKind::InvalidData, "invalid base64 padding"));
+            }
+
+            let mut bits = 0;
+            for (i, &c) in chars[..4 - padding].iter().enumerate() {
+                bits |= base64_value(c)? << (18 - 6 * i);
+            }
+
+            let bytes = bits.to_be_bytes();
+            let len = 3 - padding;
+            self.decoded[self.decoded_len..self.decoded_len + len].copy_from_slice(&bytes[1..=len]);
+            self.decoded_len += len;
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: Read + Seek> Read for Base64Reader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let decoded_end = self.decoded_pos + self.decoded_len as u64;
+        if self.pos < self.decoded_pos || self.pos >= decoded_end {
+            self.fill()?;
+        }
+
+        // Lint: The decoded bytes were just filled starting at the group which
+        // contains the current position, so the offset is at most 2 beyond the
+        // end of the decoded bytes.
+        #[allow(clippy::cast_possible_truncation)]
+        let offset = (self.pos - self.decoded_pos) as usize;
+        let available = self.decoded[..self.decoded_len]
+            .get(offset..)
+            .unwrap_or(&[]);
+        let n = buf.len().min(available.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<R: Read + Seek> Seek for Base64Reader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let start = self.start()?;
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => self.decoded_len()?.checked_add_signed(offset),
+        };
+
+        match new_pos {
+            Some(new_pos) if fits(start, (new_pos / 3).checked_mul(4)) => {
+                self.pos = new_pos;
+                Ok(new_pos)
+            }
+            _ => Err(invalid_seek()),
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+impl<R: Read + Seek> Base64Reader<R> {
+    fn decoded_len(&mut self) -> Result<u64> {
+        let start = self.start()?;
+        let len = encoded_len(&mut self.inner, &mut self.inner_pos, start)?;
+        if len % 4 != 0 {
+            return Err(Error::new(
+                ErrorKind::InvalidData,
+                "base64 data is not a whole number of groups",
+            ));
+        }
+
+        let mut padding = 0;
+        if len != 0 {
+            let mut last = [0; 2];
+            self.locate(len - 2)?;
+            self.inner.read_exact(&mut last)?;
+            self.inner_pos = Some(len);
+            padding = u64::from(last[0] == b'=') + u64::from(last[1] == b'=');
+        }
+
+        Ok(len / 4 * 3 - padding)
+    }
+}
+
+/// A writer adapter which base64 encodes data.
+///
+/// Data is written using the standard base64 alphabet with padding, as
+/// defined in [RFC 4648]. Because every 3 bytes are encoded together, written
+/// bytes are kept in memory so that seeking to and overwriting any position
+/// is possible, and are only encoded and written to the underlying writer
+/// when the writer is [flushed](Write::flush), when
+/// [`into_inner`](Self::into_inner) is called, or when the writer is dropped.
+/// Errors which happen when the writer is dropped are ignored.
+///
+/// Positions and seeks are in decoded bytes, relative to the position of the
+/// underlying writer when it is first flushed.
+///
+/// [RFC 4648]: https://datatracker.ietf.org/doc/html/rfc4648#section-4
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinWrite, io::{Base64Writer, Cursor}};
+///
+/// let mut writer = Base64Writer::new(Cursor::new(Vec::new()));
+/// 0xbeef_u16.write_be(&mut writer).unwrap();
+/// assert_eq!(writer.into_inner().unwrap().into_inner(), b"vu8=");
+/// ```
+#[derive(Debug)]
+pub struct Base64Writer<W: Write + Seek> {
+    // This is only `None` after `into_inner` moves the writer out.
+    inner: Option<W>,
+    start: Option<u64>,
+    data: Vec<u8>,
+    pos: u64,
+    // The first byte which has changed since the last flush.
+    dirty: Option<usize>,
+}
+
+impl<W: Write + Seek> Base64Writer<W> {
+    /// Creates a new writer which base64 encodes data written to `inner`.
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner: Some(inner),
+            start: None,
+            data: Vec::new(),
+            pos: 0,
+            dirty: None,
+        }
+    }
+
+    /// Returns all of the bytes which have been written, before encoding.
+    pub fn buffer(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Gets a reference to the underlying writer.
+    // Lint: The writer is only taken by `into_inner`, which consumes `self`,
+    // so this cannot panic.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn get_ref(&self) -> &W {
+        self.inner.as_ref().expect(Self::MOVED)
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying writer as doing so may corrupt the encoded data.
+    pub fn get_mut(&mut self) -> &mut W {
+        self.inner_mut()
+    }
+
+    /// Encodes and writes any changed data, then unwraps this
+    /// `Base64Writer<W>`, returning the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// If writing the encoded data fails, an error is returned.
+    // Lint: The writer is only taken by `into_inner`, which consumes `self`,
+    // so this cannot panic.
+    #[allow(clippy::missing_panics_doc)]
+    pub fn into_inner(mut self) -> Result<W> {
+        self.write_data()?;
+        Ok(self.inner.take().expect(Self::MOVED))
+    }
+
+    const MOVED: &'static str = "the writer is only moved out by `into_inner`";
+
+    fn inner_mut(&mut self) -> &mut W {
+        self.inner.as_mut().expect(Self::MOVED)
+    }
+
+    /// Encodes and writes every group which has changed since the last time
+    /// the data was written.
+    fn write_data(&mut self) -> Result<()> {
+        let Some(dirty) = self.dirty else {
+            return Ok(());
+        };
+
+        let inner = self.inner.as_mut().expect(Self::MOVED);
+        let start = lazy_start(inner, &mut self.start, &mut None)?;
+        let group = dirty / 3;
+        inner.seek(SeekFrom::Start(start + group as u64 * 4))?;
+
+        let mut encoded = [0; CHUNK_SIZE];
+        for chunk in self.data[group * 3..].chunks(CHUNK_SIZE / 4 * 3) {
+            let mut encoded_len = 0;
+            for bytes in chunk.chunks(3) {
+                encoded[encoded_len..encoded_len + 4].fill(b'=');
+                encode_group(bytes, &mut encoded[encoded_len..encoded_len + 4]);
+                encoded_len += 4;
+            }
+            inner.write_all(&encoded[..encoded_len])?;
+        }
+
+        self.dirty = None;
+        Ok(())
+    }
+}
+
+impl<W: Write + Seek> Write for Base64Writer<W> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let Some(end) = usize::try_from(self.pos)
+            .ok()
+            .and_then(|pos| pos.checked_add(buf.len()))
+        else {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "position is too large for a base64 writer",
+            ));
+        };
+
+        // Lint: `end` was computed from `self.pos` without truncation.
+        #[allow(clippy::cast_possible_truncation)]
+        let pos = self.pos as usize;
+        if self.data.len() < end {
+            self.data.resize(end, 0);
+        }
+        self.data[pos..end].copy_from_slice(buf);
+        self.dirty = Some(self.dirty.map_or(pos, |dirty| dirty.min(pos)));
+        self.pos = end as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.write_data()?;
+        self.inner_mut().flush()
+    }
+}
+
+impl<W: Write + Seek> Seek for Base64Writer<W> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => (self.data.len() as u64).checked_add_signed(offset),
+        };
+
+        if let Some(new_pos) = new_pos {
+            self.pos = new_pos;
+            Ok(new_pos)
+        } else {
+            Err(invalid_seek())
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+impl<W: Write + Seek> Drop for Base64Writer<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            // Dtors should not panic, so the error is ignored
+            let _ = self.write_data();
+        }
+    }
+}
+
+fn hex_value(digit: u8) -> Result<u8> {
+    match digit {
+        b'0'..=b'9' => Ok(digit - b'0'),
+        b'a'..=b'f' => Ok(digit - b'a' + 10),
+        b'A'..=b'F' => Ok(digit - b'A' + 10),
+        _ => Err(Error::new(ErrorKind::InvalidData, "invalid hex digit")),
+    }
+}
+
+fn base64_value(c: u8) -> Result<u32> {
+    match c {
+        b'A'..=b'Z' => Ok(u32::from(c - b'A')),
+        b'a'..=b'z' => Ok(u32::from(c - b'a') + 26),
+        b'0'..=b'9' => Ok(u32::from(c - b'0') + 52),
+        b'+' => Ok(62),
+        b'/' => Ok(63),
+        _ => Err(Error::new(
+            ErrorKind::InvalidData,
+            "invalid base64 character",
+        )),
+    }
+}
+
+/// Encodes up to 3 bytes into the first characters of `encoded`.
+fn encode_group(bytes: &[u8], encoded: &mut [u8]) {
+    let mut group = [0; 4];
+    group[1..=bytes.len()].copy_from_slice(bytes);
+    let bits = u32::from_be_bytes(group);
+    for (i, c) in encoded[..=bytes.len()].iter_mut().enumerate() {
+        *c = BASE64_ALPHABET[((bits >> (18 - 6 * i)) & 0x3f) as usize];
+    }
+}
+
+fn lazy_start<T: Seek>(
+    inner: &mut T,
+    start: &mut Option<u64>,
+    inner_pos: &mut Option<u64>,
+) -> Result<u64> {
+    if let Some(start) = *start {
+        Ok(start)
+    } else {
+        let pos = inner.stream_position()?;
+        *start = Some(pos);
+        *inner_pos = Some(0);
+        Ok(pos)
+    }
+}
+
+fn locate<T: Seek>(inner: &mut T, inner_pos: &mut Option<u64>, start: u64, pos: u64) -> Result<()> {
+    if *inner_pos != Some(pos) {
+        *inner_pos = None;
+        inner.seek(SeekFrom::Start(start + pos))?;
+        *inner_pos = Some(pos);
+    }
+    Ok(())
+}
+
+fn encoded_len<T: Seek>(inner: &mut T, inner_pos: &mut Option<u64>, start: u64) -> Result<u64> {
+    let len = inner.seek(SeekFrom::End(0))?.saturating_sub(start);
+    *inner_pos = Some(len);
+    Ok(len)
+}
+
+fn fits(start: u64, offset: Option<u64>) -> bool {
+    offset
+        .and_then(|offset| start.checked_add(offset))
+        .is_some()
+}
+
+fn read_full<R: Read>(inner: &mut R, buf: &mut [u8]) -> Result<usize> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match inner.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(err) if err.kind() == ErrorKind::Interrupted => {}
+            Err(err) => return Err(err),
+        }
+    }
+    Ok(filled)
+}
+
+fn invalid_seek() -> Error {
+    Error::new(
+        ErrorKind::InvalidInput,
+        "invalid seek to a negative or overflowing position",
+    )
+}
@@ -0,0 +1,114 @@
+//! Reader for memory-mapped files.
+
+use super::{Cursor, Read, Result, Seek, SeekFrom};
+use memmap2::Mmap;
+use std::fs::File;
+
+/// A reader which reads from a memory-mapped file.
+///
+/// Reading from a memory-mapped file does not make a system call for every
+/// read, and only the parts of the file which are actually read are loaded
+/// into memory, so large files can be parsed lazily.
+///
+/// The mapped data can also be accessed directly with
+/// [`as_slice`](Self::as_slice), so types which borrow data using
+/// [`BinReadBorrowed`](crate::BinReadBorrowed) can be read from the file
+/// without copying it.
+///
+/// # Examples
+///
+/// ```
+/// # use std::io::Write;
+/// use binrw::{BinRead, BinReadBorrowed, io::MmapReader};
+///
+/// #[derive(BinRead)]
+/// #[br(big, borrow = 'a)]
+/// struct Entry<'a> {
+///     len: u8,
+///     #[br(count = len)]
+///     name: &'a str,
+/// }
+///
+/// # let path = std::env::temp_dir().join("binrw_mmap_doctest");
+/// # std::fs::File::create(&path).unwrap().write_all(b"\x03abc").unwrap();
+/// let file = std::fs::File::open(&path).unwrap();
+/// // SAFETY: The file is not modified while it is mapped.
+/// let reader = unsafe { MmapReader::map(&file) }.unwrap();
+/// let entry = Entry::read_borrowed(reader.as_slice()).unwrap();
+/// assert_eq!(entry.name, "abc");
+/// # drop(reader);
+/// # std::fs::remove_file(path).unwrap();
+/// ```
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "mmap")))]
+#[derive(Debug)]
+pub struct MmapReader {
+    inner: Cursor<Mmap>,
+}
+
+impl MmapReader {
+    /// Creates a new reader which reads from an existing memory map.
+    #[must_use]
+    pub fn new(map: Mmap) -> Self {
+        Self {
+            inner: Cursor::new(map),
+        }
+    }
+
+    /// Maps `file` into memory and creates a new reader which reads from it.
+    ///
+    /// # Errors
+    ///
+    /// If mapping the file fails, an error is returned.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified, by this or any other process, while
+    /// it is mapped. See [`Mmap::map`] for details.
+    pub unsafe fn map(file: &File) -> Result<Self> {
+        Mmap::map(file).map(Self::new)
+    }
+
+    /// Returns all of the mapped data.
+    #[must_use]
+    pub fn as_slice(&self) -> &[u8] {
+        self.inner.get_ref()
+    }
+
+    /// Gets a reference to the underlying memory map.
+    #[must_use]
+    pub fn get_ref(&self) -> &Mmap {
+        self.inner.get_ref()
+    }
+
+    /// Consumes this reader, returning the underlying memory map.
+    #[must_use]
+    pub fn into_inner(self) -> Mmap {
+        self.inner.into_inner()
+    }
+}
+
+impl AsRef<[u8]> for MmapReader {
+    fn as_ref(&self) -> &[u8] {
+        self.as_slice()
+    }
+}
+
+impl Read for MmapReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        self.inner.read(buf)
+    }
+
+    fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        self.inner.read_exact(buf)
+    }
+}
+
+impl Seek for MmapReader {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.inner.position())
+    }
+}
@@ -4,22 +4,75 @@
 //! used by binrw. In `no_std` environments, a compatible subset API is exposed
 //! instead.
 
+#[cfg(feature = "futures-io")]
+mod async_bridge;
+mod bits;
+#[cfg(feature = "bytes")]
+mod buf;
 #[cfg(feature = "std")]
 mod bufreader;
+mod bufwriter;
+mod cached_len;
+mod chain_seek;
+mod checksum;
+#[cfg(feature = "cipher")]
+mod cipher;
+mod counting;
+mod coverage;
+#[cfg(feature = "std")]
+mod deadline;
+mod encoding;
+#[cfg(feature = "mmap")]
+mod mmap;
 #[cfg(not(feature = "std"))]
 mod no_std;
 pub mod prelude;
+mod relative;
 mod seek;
 mod take_seek;
+mod tee;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod window;
 
+#[cfg(feature = "futures-io")]
+pub use async_bridge::{AsyncReader, AsyncWriter, DEFAULT_ASYNC_BUFFER_LIMIT};
+pub use bits::{BitOrder, BitReader, BitWriter};
+#[cfg(feature = "bytes")]
+pub use buf::{BufReadAdapter, BufWriteAdapter};
 #[cfg(feature = "std")]
 pub use bufreader::BufReader;
 #[cfg(all(doc, not(feature = "std")))]
 #[doc(hidden)]
 pub struct BufReader;
+pub use bufwriter::BufWriter;
+pub use cached_len::CachedLen;
+pub use chain_seek::ChainSeek;
+#[cfg(feature = "digest")]
+pub use checksum::DigestChecksum;
+pub use checksum::{Adler32, Checksum, ChecksumReader, ChecksumWriter, Crc32};
+#[cfg(feature = "cipher")]
+pub use cipher::{CbcReader, StreamCipherReader};
+pub use counting::{CountingReader, CountingWriter, Discard};
+pub use coverage::CoverageReader;
+#[cfg(feature = "std")]
+pub(crate) use deadline::deadline_expired_pos;
+#[cfg(feature = "std")]
+pub use deadline::{DeadlineReader, ReadTimeout};
+#[cfg(all(doc, not(feature = "std")))]
+#[doc(hidden)]
+pub struct DeadlineReader;
+pub use encoding::{Base64Reader, Base64Writer, HexReader, HexWriter};
+#[cfg(feature = "mmap")]
+pub use mmap::MmapReader;
 #[cfg(not(feature = "std"))]
 pub use no_std::*;
+pub use relative::RelativeWriter;
 pub use seek::NoSeek;
 #[cfg(feature = "std")]
 pub use std::io::{Bytes, Cursor, Error, ErrorKind, Read, Result, Seek, SeekFrom, Write};
 pub use take_seek::*;
+pub use tee::TeeReader;
+#[cfg(feature = "wasm")]
+pub use wasm::{ArrayBufferReader, BlobReader};
+pub use window::Window;
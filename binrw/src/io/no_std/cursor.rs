@@ -4,7 +4,14 @@ use core::cmp;
 
 /// A `Cursor` wraps an in-memory buffer and provides it with a
 /// [`Seek`] implementation.
-#[derive(Clone, Debug, Default)]
+///
+/// Cursors over any in-memory buffer can be read from. Cursors over
+/// `&mut [u8]`, `[u8; N]`, and `Box<[u8]>` can also be written to, and
+/// overwrite the existing data without growing the buffer. Cursors over
+/// `Vec<u8>` and `&mut Vec<u8>` overwrite the existing data and extend the
+/// vector when writing past its end, filling any gap left by seeking past the
+/// end with zeroes.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
 pub struct Cursor<T: AsRef<[u8]>> {
     inner: T,
     pos: u64,
@@ -75,7 +82,7 @@ impl<T: AsRef<[u8]>> Seek for Cursor<T> {
                 self.pos = n;
                 Ok(self.pos)
             }
-            None => Err(Error::new(
+            None => Err(Error::new_static(
                 ErrorKind::InvalidInput,
                 "invalid seek to a negative or overflowing position",
             )),
@@ -99,9 +106,9 @@ fn slice_write(pos_mut: &mut u64, slice: &mut [u8], buf: &[u8]) -> Result<usize>
 // Resizing write implementation
 fn vec_write(pos_mut: &mut u64, vec: &mut Vec<u8>, buf: &[u8]) -> Result<usize> {
     let pos: usize = (*pos_mut).try_into().map_err(|_| {
-        Error::new(
+        Error::new_static(
             ErrorKind::InvalidInput,
-            &"cursor position exceeds maximum possible vector length",
+            "cursor position exceeds maximum possible vector length",
         )
     })?;
     // Make sure the internal buffer is as least as big as where we
@@ -170,3 +177,15 @@ impl Write for Cursor<Box<[u8]>> {
         Ok(())
     }
 }
+
+impl<const N: usize> Write for Cursor<[u8; N]> {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        slice_write(&mut self.pos, &mut self.inner, buf)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
@@ -18,13 +18,17 @@ impl fmt::Debug for Error {
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        fmt::Debug::fmt(&self, f)
+        match self.repr {
+            Repr::Simple(kind) => fmt::Display::fmt(&kind, f),
+            Repr::SimpleMessage(_, message) => f.write_str(message),
+        }
     }
 }
 
 #[derive(Debug)]
 enum Repr {
     Simple(ErrorKind),
+    SimpleMessage(ErrorKind, &'static str),
 }
 
 /// A list specifying general categories of I/O error.
@@ -74,9 +78,43 @@ pub enum ErrorKind {
     UnexpectedEof,
 }
 
+impl ErrorKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            ErrorKind::NotFound => "entity not found",
+            ErrorKind::PermissionDenied => "permission denied",
+            ErrorKind::ConnectionRefused => "connection refused",
+            ErrorKind::ConnectionReset => "connection reset",
+            ErrorKind::ConnectionAborted => "connection aborted",
+            ErrorKind::NotConnected => "not connected",
+            ErrorKind::AddrInUse => "address in use",
+            ErrorKind::AddrNotAvailable => "address not available",
+            ErrorKind::BrokenPipe => "broken pipe",
+            ErrorKind::AlreadyExists => "entity already exists",
+            ErrorKind::WouldBlock => "operation would block",
+            ErrorKind::InvalidInput => "invalid input parameter",
+            ErrorKind::InvalidData => "invalid data",
+            ErrorKind::TimedOut => "timed out",
+            ErrorKind::WriteZero => "write zero",
+            ErrorKind::Interrupted => "operation interrupted",
+            ErrorKind::Other => "other error",
+            ErrorKind::UnexpectedEof => "unexpected end of file",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl Error {
     /// Creates a new I/O error from a known kind of error as well as an
     /// arbitrary error payload.
+    ///
+    /// The payload is discarded, and the error is displayed using the
+    /// description of its kind.
     #[must_use]
     pub fn new<A>(kind: ErrorKind, _: A) -> Self {
         Self {
@@ -84,11 +122,19 @@ impl Error {
         }
     }
 
+    /// Creates a new I/O error from a known kind of error and a static
+    /// message, which is kept for display.
+    pub(crate) const fn new_static(kind: ErrorKind, message: &'static str) -> Self {
+        Self {
+            repr: Repr::SimpleMessage(kind, message),
+        }
+    }
+
     /// Returns the corresponding [`ErrorKind`] for this error.
     #[must_use]
     pub fn kind(&self) -> ErrorKind {
         match self.repr {
-            Repr::Simple(kind) => kind,
+            Repr::Simple(kind) | Repr::SimpleMessage(kind, _) => kind,
         }
     }
 }
@@ -35,7 +35,7 @@ pub trait Read {
             }
         }
         if !buf.is_empty() {
-            Err(Error::new(
+            Err(Error::new_static(
                 ErrorKind::UnexpectedEof,
                 "failed to fill whole buffer",
             ))
@@ -67,7 +67,7 @@ pub trait Read {
         let mut tmp = Vec::new();
         let amt = self.read_to_end(&mut tmp)?;
         let str = core::str::from_utf8(&tmp).map_err(|_| {
-            Error::new(ErrorKind::InvalidData, "stream did not contain valid UTF-8")
+            Error::new_static(ErrorKind::InvalidData, "stream did not contain valid UTF-8")
         })?;
         buf.push_str(str);
         Ok(amt)
@@ -221,7 +221,7 @@ impl Read for &[u8] {
     #[inline]
     fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
         if buf.len() > self.len() {
-            return Err(Error::new(
+            return Err(Error::new_static(
                 ErrorKind::UnexpectedEof,
                 "failed to fill whole buffer",
             ));
@@ -251,7 +251,7 @@ impl Read for &[u8] {
 }
 
 /// Enumeration of possible methods to seek within an I/O object.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub enum SeekFrom {
     /// Sets the offset to the provided number of bytes.
     Start(u64),
@@ -297,7 +297,7 @@ pub trait Write {
         while !buf.is_empty() {
             match self.write(buf) {
                 Ok(0) => {
-                    return Err(Error::new(
+                    return Err(Error::new_static(
                         ErrorKind::WriteZero,
                         "failed to write whole buffer",
                     ));
@@ -343,7 +343,7 @@ pub trait Write {
                 if output.error.is_err() {
                     output.error
                 } else {
-                    Err(Error::new(ErrorKind::Other, "formatter error"))
+                    Err(Error::new_static(ErrorKind::Other, "formatter error"))
                 }
             }
         }
@@ -373,9 +373,9 @@ impl Write for &mut [u8] {
         if self.write(data)? == data.len() {
             Ok(())
         } else {
-            Err(Error::new(
+            Err(Error::new_static(
                 ErrorKind::WriteZero,
-                &"failed to write whole buffer",
+                "failed to write whole buffer",
             ))
         }
     }
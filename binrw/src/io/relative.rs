@@ -0,0 +1,165 @@
+//! Types for seekable writer adapters which translate positions to be relative
+//! to a starting position.
+
+use super::{Error, ErrorKind, Result, Seek, SeekFrom, Write};
+
+/// Write adapter which presents the part of an underlying writer starting at a
+/// given position as an independent stream starting at position zero.
+///
+/// Positions and seeks are relative to the start of the region, so structures
+/// which compute offsets relative to their own start can be written inside a
+/// larger file without adding a base offset to every offset expression. This
+/// is the writing counterpart of [`Window`](super::Window).
+///
+/// The end of the region, used by [`SeekFrom::End`], is the furthest position
+/// written so far, so seeking to the end of the stream always seeks to the end
+/// of the nested structure, even when the underlying writer already contains
+/// data after it.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinWrite, io::{Cursor, RelativeWriter, Seek, SeekFrom}};
+///
+/// #[derive(BinWrite)]
+/// #[bw(big)]
+/// struct Entry {
+///     offset: u8,
+///     #[bw(seek_before = SeekFrom::Start((*offset).into()))]
+///     value: u8,
+/// }
+///
+/// let mut data = Cursor::new(vec![0xff; 2]);
+/// data.seek(SeekFrom::End(0)).unwrap();
+///
+/// // The entry starts at offset 2, and its offset is relative to that
+/// let mut writer = RelativeWriter::new(&mut data).unwrap();
+/// Entry { offset: 2, value: 7 }.write(&mut writer).unwrap();
+/// assert_eq!(writer.offset(), 2);
+/// assert_eq!(data.into_inner(), b"\xff\xff\x02\x00\x07");
+/// ```
+#[derive(Debug)]
+pub struct RelativeWriter<T> {
+    inner: T,
+    start: u64,
+    pos: u64,
+    len: u64,
+}
+
+impl<T> RelativeWriter<T> {
+    /// Gets a reference to the underlying writer.
+    #[must_use]
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying writer.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying writer as doing so may corrupt the internal position of
+    /// this `RelativeWriter`.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the wrapped value.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns the position of the start of the region in the underlying
+    /// writer.
+    #[must_use]
+    pub fn offset(&self) -> u64 {
+        self.start
+    }
+
+    /// Returns the length of the region, which is the furthest position
+    /// written so far.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true if nothing has been written to the region.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T: Seek> RelativeWriter<T> {
+    /// Creates a new writer whose region starts at the current position of
+    /// `inner`.
+    ///
+    /// # Errors
+    ///
+    /// If getting the position of the underlying writer fails, an error is
+    /// returned.
+    pub fn new(mut inner: T) -> Result<Self> {
+        let start = inner.stream_position()?;
+        Ok(Self {
+            inner,
+            start,
+            pos: 0,
+            len: 0,
+        })
+    }
+
+    /// Creates a new writer whose region starts at position `start` of
+    /// `inner`, and seeks to the start of the region.
+    ///
+    /// # Errors
+    ///
+    /// If seeking the underlying writer fails, an error is returned.
+    pub fn with_offset(mut inner: T, start: u64) -> Result<Self> {
+        inner.seek(SeekFrom::Start(start))?;
+        Ok(Self {
+            inner,
+            start,
+            pos: 0,
+            len: 0,
+        })
+    }
+}
+
+impl<T: Write> Write for RelativeWriter<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        let n = self.inner.write(buf)?;
+        self.pos += n as u64;
+        self.len = self.len.max(self.pos);
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<T: Seek> Seek for RelativeWriter<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+        };
+
+        if let Some((pos, inner_pos)) =
+            new_pos.and_then(|pos| Some((pos, self.start.checked_add(pos)?)))
+        {
+            self.inner.seek(SeekFrom::Start(inner_pos))?;
+            self.pos = pos;
+            Ok(pos)
+        } else {
+            Err(Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            ))
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
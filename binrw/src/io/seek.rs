@@ -10,17 +10,48 @@ use alloc::{string::String, vec::Vec};
 ///
 /// This is useful when reading or writing from unseekable streams where binrw
 /// does not *actually* need to seek to successfully parse or write the data.
+///
+/// A wrapper created with [`new`](Self::new) can only “seek” to its current
+/// position. A wrapper created with [`forward_reader`](Self::forward_reader) or
+/// [`forward_writer`](Self::forward_writer) can also seek forwards, by
+/// skipping or zero-filling the bytes in between, so that sequential formats
+/// with gaps can be read from or written to sockets and pipes. Seeking
+/// backwards or relative to the end of the stream is always an error.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, io::NoSeek};
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Header {
+///     #[br(pad_before = 2)]
+///     value: u16,
+/// }
+///
+/// let stdin_like: &[u8] = b"\xff\xff\x01\x02";
+/// let header = Header::read(&mut NoSeek::forward_reader(stdin_like)).unwrap();
+/// assert_eq!(header.value, 0x102);
+/// ```
 pub struct NoSeek<T> {
     /// The original stream.
     inner: T,
     /// The virtual position of the seekable stream.
     pos: u64,
+    /// Advances the original stream by the given number of bytes, if seeking
+    /// forwards is supported.
+    forward: Option<fn(&mut T, u64) -> super::Result<()>>,
 }
 
 impl<T> NoSeek<T> {
     /// Creates a new seekable wrapper for the given value.
     pub fn new(inner: T) -> Self {
-        NoSeek { inner, pos: 0 }
+        NoSeek {
+            inner,
+            pos: 0,
+            forward: None,
+        }
     }
 
     /// Gets a mutable reference to the underlying value.
@@ -39,11 +70,65 @@ impl<T> NoSeek<T> {
     }
 }
 
+impl<T: super::Read> NoSeek<T> {
+    /// Creates a new seekable wrapper for the given reader which seeks
+    /// forwards by reading and discarding bytes.
+    pub fn forward_reader(inner: T) -> Self {
+        NoSeek {
+            inner,
+            pos: 0,
+            forward: Some(skip_bytes),
+        }
+    }
+}
+
+impl<T: super::Write> NoSeek<T> {
+    /// Creates a new seekable wrapper for the given writer which seeks
+    /// forwards by writing zeroes.
+    pub fn forward_writer(inner: T) -> Self {
+        NoSeek {
+            inner,
+            pos: 0,
+            forward: Some(write_zeroes),
+        }
+    }
+}
+
+fn skip_bytes<T: super::Read>(inner: &mut T, mut count: u64) -> super::Result<()> {
+    let mut buf = [0; 256];
+    while count != 0 {
+        let len = buf.len().min(usize::try_from(count).unwrap_or(usize::MAX));
+        inner.read_exact(&mut buf[..len])?;
+        count -= len as u64;
+    }
+    Ok(())
+}
+
+fn write_zeroes<T: super::Write>(inner: &mut T, mut count: u64) -> super::Result<()> {
+    let buf = [0; 256];
+    while count != 0 {
+        let len = buf.len().min(usize::try_from(count).unwrap_or(usize::MAX));
+        inner.write_all(&buf[..len])?;
+        count -= len as u64;
+    }
+    Ok(())
+}
+
 impl<T> super::Seek for NoSeek<T> {
     fn seek(&mut self, pos: SeekFrom) -> super::Result<u64> {
-        match pos {
-            SeekFrom::Start(n) if self.pos == n => Ok(n),
-            SeekFrom::Current(0) => Ok(self.pos),
+        let target = match pos {
+            SeekFrom::Start(n) => Some(n),
+            SeekFrom::Current(n) => self.pos.checked_add_signed(n),
+            SeekFrom::End(_) => None,
+        };
+
+        match (target, self.forward) {
+            (Some(n), _) if self.pos == n => Ok(n),
+            (Some(n), Some(forward)) if n > self.pos => {
+                forward(&mut self.inner, n - self.pos)?;
+                self.pos = n;
+                Ok(n)
+            }
             // https://github.com/rust-lang/rust/issues/86442
             _ => Err(Error::new(ErrorKind::Other, "seek on unseekable file")),
         }
@@ -0,0 +1,91 @@
+//! Reader adapter which copies the bytes it reads into another writer.
+
+use super::{Read, Result, Seek, SeekFrom, Write};
+
+/// A reader adapter which copies every byte read from an underlying reader
+/// into a sink.
+///
+/// This can be used to keep the exact bytes which a value was parsed from, for
+/// example to write them back unchanged or to dump them when debugging.
+///
+/// Bytes are copied to the sink in the order they are read. Bytes skipped by
+/// seeking are not copied, and bytes which are read again after seeking
+/// backwards are copied again.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, io::{Cursor, TeeReader}};
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Record {
+///     kind: u8,
+///     value: u16,
+/// }
+///
+/// let mut reader = TeeReader::new(Cursor::new(b"\x01\x02\x03\x04"), Vec::new());
+/// let record = Record::read(&mut reader).unwrap();
+/// assert_eq!(record.value, 0x203);
+///
+/// let (_, raw) = reader.into_inner();
+/// assert_eq!(raw, b"\x01\x02\x03");
+/// ```
+#[derive(Debug)]
+pub struct TeeReader<R, W> {
+    inner: R,
+    sink: W,
+}
+
+impl<R, W> TeeReader<R, W> {
+    /// Creates a new reader which reads from `inner` and copies every byte it
+    /// reads into `sink`.
+    pub fn new(inner: R, sink: W) -> Self {
+        Self { inner, sink }
+    }
+
+    /// Gets a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Bytes read from the underlying reader directly are not copied.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.inner
+    }
+
+    /// Gets a reference to the sink.
+    pub fn sink(&self) -> &W {
+        &self.sink
+    }
+
+    /// Gets a mutable reference to the sink.
+    pub fn sink_mut(&mut self) -> &mut W {
+        &mut self.sink
+    }
+
+    /// Consumes this wrapper, returning the underlying reader and the sink.
+    pub fn into_inner(self) -> (R, W) {
+        (self.inner, self.sink)
+    }
+}
+
+impl<R: Read, W: Write> Read for TeeReader<R, W> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.sink.write_all(&buf[..n])?;
+        Ok(n)
+    }
+}
+
+impl<R: Seek, W> Seek for TeeReader<R, W> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.inner.seek(pos)
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        self.inner.stream_position()
+    }
+}
@@ -0,0 +1,349 @@
+//! Readers for JavaScript binary data in WebAssembly.
+
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom};
+use js_sys::{ArrayBuffer, Object, Reflect, Uint8Array};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, FileReaderSync, ReadableStream, ReadableStreamDefaultReader};
+
+/// The number of bytes read from a [`Blob`] at once by a [`BlobReader`].
+const BLOB_CHUNK_SIZE: u32 = 64 * 1024;
+
+/// A reader which reads from JavaScript binary data.
+///
+/// The data stays in JavaScript memory, and only the bytes which are actually
+/// read are copied into WebAssembly memory, so binrw parsers can be run
+/// against an [`ArrayBuffer`], a [`Blob`] like a user-selected `File`, or the
+/// contents of a [`ReadableStream`] without copying the data through an
+/// intermediate `Vec` first.
+///
+/// # Examples
+///
+/// ```no_run
+/// use binrw::{BinRead, io::ArrayBufferReader};
+/// use web_sys::Blob;
+///
+/// #[derive(BinRead)]
+/// #[br(big, magic = b"PK")]
+/// struct Header {
+///     version: u16,
+/// }
+///
+/// async fn read_header(file: &Blob) -> binrw::BinResult<Header> {
+///     let mut reader = ArrayBufferReader::from_blob(file).await?;
+///     Header::read(&mut reader)
+/// }
+/// ```
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "wasm")))]
+#[derive(Debug)]
+pub struct ArrayBufferReader {
+    // Each chunk with the position where it starts.
+    chunks: Vec<(u64, Uint8Array)>,
+    len: u64,
+    pos: u64,
+}
+
+impl ArrayBufferReader {
+    /// Creates a new reader which reads from `buffer`.
+    #[must_use]
+    pub fn new(buffer: &ArrayBuffer) -> Self {
+        Self::from_array(Uint8Array::new(buffer))
+    }
+
+    /// Creates a new reader which reads from the bytes viewed by `array`.
+    #[must_use]
+    pub fn from_array(array: Uint8Array) -> Self {
+        Self::from_chunks([array])
+    }
+
+    /// Creates a new reader which reads from the concatenation of `chunks`.
+    pub fn from_chunks<I: IntoIterator<Item = Uint8Array>>(chunks: I) -> Self {
+        let mut len = 0;
+        let chunks = chunks
+            .into_iter()
+            .filter(|chunk| chunk.length() != 0)
+            .map(|chunk| {
+                let start = len;
+                len += u64::from(chunk.length());
+                (start, chunk)
+            })
+            .collect();
+        Self {
+            chunks,
+            len,
+            pos: 0,
+        }
+    }
+
+    /// Creates a new reader which reads from the contents of `blob`.
+    ///
+    /// # Errors
+    ///
+    /// If reading the blob fails, an error is returned.
+    pub async fn from_blob(blob: &Blob) -> Result<Self> {
+        let buffer = JsFuture::from(blob.array_buffer())
+            .await
+            .map_err(js_error)?;
+        Ok(Self::new(buffer.unchecked_ref()))
+    }
+
+    /// Creates a new reader which reads from the contents of `stream`.
+    ///
+    /// The stream is read to the end, keeping each chunk in JavaScript
+    /// memory. Every chunk of the stream must be a [`Uint8Array`].
+    ///
+    /// # Errors
+    ///
+    /// If the stream is locked, reading it fails, or it contains a chunk
+    /// which is not a `Uint8Array`, an error is returned.
+    pub async fn from_stream(stream: &ReadableStream) -> Result<Self> {
+        if stream.locked() {
+            return Err(Error::new(ErrorKind::Other, "the stream is locked"));
+        }
+
+        let reader = stream
+            .get_reader()
+            .unchecked_into::<ReadableStreamDefaultReader>();
+        let mut chunks = Vec::new();
+        loop {
+            let result = JsFuture::from(reader.read()).await.map_err(js_error)?;
+            let done = Reflect::get(&result, &JsValue::from_str("done")).map_err(js_error)?;
+            if done.is_truthy() {
+                break;
+            }
+
+            let value = Reflect::get(&result, &JsValue::from_str("value")).map_err(js_error)?;
+            let chunk = value.dyn_into::<Uint8Array>().map_err(|_| {
+                Error::new(
+                    ErrorKind::InvalidData,
+                    "the stream contains a chunk which is not a Uint8Array",
+                )
+            })?;
+            chunks.push(chunk);
+        }
+        reader.release_lock();
+
+        Ok(Self::from_chunks(chunks))
+    }
+
+    /// Returns the total length of the data.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true if there is no data.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Consumes this reader, returning the chunks of data.
+    #[must_use]
+    pub fn into_inner(self) -> Vec<Uint8Array> {
+        self.chunks.into_iter().map(|(_, chunk)| chunk).collect()
+    }
+}
+
+impl Read for ArrayBufferReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.pos >= self.len || buf.is_empty() {
+            return Ok(0);
+        }
+
+        let index = self.chunks.partition_point(|(start, _)| *start <= self.pos) - 1;
+        let (start, chunk) = &self.chunks[index];
+
+        // Lint: Chunk offsets always fit in a `u32` since chunk lengths do.
+        #[allow(clippy::cast_possible_truncation)]
+        let offset = (self.pos - start) as u32;
+        let n = (chunk.length() - offset).min(u32::try_from(buf.len()).unwrap_or(u32::MAX));
+        chunk
+            .subarray(offset, offset + n)
+            .copy_to(&mut buf[..n as usize]);
+        self.pos += u64::from(n);
+        Ok(n as usize)
+    }
+}
+
+impl Seek for ArrayBufferReader {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+        };
+
+        if let Some(pos) = new_pos {
+            self.pos = pos;
+            Ok(pos)
+        } else {
+            Err(Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            ))
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+/// A reader which synchronously reads from a JavaScript [`Blob`], like a
+/// user-selected `File`.
+///
+/// Data is read from the blob in chunks as it is needed, so only the parts of
+/// a large file which are actually parsed are loaded. Reading a blob
+/// synchronously uses [`FileReaderSync`], which is only available in web
+/// workers. On the main thread, use
+/// [`ArrayBufferReader::from_blob`] instead.
+///
+/// # Examples
+///
+/// ```no_run
+/// use binrw::{BinRead, io::BlobReader};
+/// use web_sys::Blob;
+///
+/// #[derive(BinRead)]
+/// #[br(big, magic = b"PK")]
+/// struct Header {
+///     version: u16,
+/// }
+///
+/// fn read_header(file: Blob) -> binrw::BinResult<Header> {
+///     let mut reader = BlobReader::new(file)?;
+///     Header::read(&mut reader)
+/// }
+/// ```
+#[cfg_attr(all(doc, nightly), doc(cfg(feature = "wasm")))]
+#[derive(Debug)]
+pub struct BlobReader {
+    blob: Blob,
+    reader: FileReaderSync,
+    len: u64,
+    pos: u64,
+    // The most recently read chunk with the position where it starts.
+    chunk: Option<(u64, Uint8Array)>,
+}
+
+impl BlobReader {
+    /// Creates a new reader which reads from `blob`.
+    ///
+    /// # Errors
+    ///
+    /// If [`FileReaderSync`] is not available, an error is returned.
+    pub fn new(blob: Blob) -> Result<Self> {
+        let reader = FileReaderSync::new().map_err(js_error)?;
+        // Lint: Blob sizes are whole numbers that fit in a `u64`.
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let len = blob.size() as u64;
+        Ok(Self {
+            blob,
+            reader,
+            len,
+            pos: 0,
+            chunk: None,
+        })
+    }
+
+    /// Returns the size of the blob.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true if the blob is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Gets a reference to the underlying blob.
+    #[must_use]
+    pub fn get_ref(&self) -> &Blob {
+        &self.blob
+    }
+
+    /// Consumes this reader, returning the underlying blob.
+    #[must_use]
+    pub fn into_inner(self) -> Blob {
+        self.blob
+    }
+
+    fn load_chunk(&mut self) -> Result<(u64, Uint8Array)> {
+        let start = self.pos - self.pos % u64::from(BLOB_CHUNK_SIZE);
+        let end = self.len.min(start + u64::from(BLOB_CHUNK_SIZE));
+        // Lint: Blob positions are always exactly representable.
+        #[allow(clippy::cast_precision_loss)]
+        let slice = self
+            .blob
+            .slice_with_f64_and_f64(start as f64, end as f64)
+            .map_err(js_error)?;
+        let buffer = self.reader.read_as_array_buffer(&slice).map_err(js_error)?;
+        Ok((start, Uint8Array::new(&buffer)))
+    }
+}
+
+impl Read for BlobReader {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        if self.pos >= self.len || buf.is_empty() {
+            return Ok(0);
+        }
+
+        let (start, chunk) = match self.chunk.take() {
+            Some((start, chunk))
+                if (start..start + u64::from(chunk.length())).contains(&self.pos) =>
+            {
+                (start, chunk)
+            }
+            _ => self.load_chunk()?,
+        };
+
+        // Lint: The offset is always less than the chunk size.
+        #[allow(clippy::cast_possible_truncation)]
+        let offset = (self.pos - start) as u32;
+        let n = (chunk.length() - offset).min(u32::try_from(buf.len()).unwrap_or(u32::MAX));
+        chunk
+            .subarray(offset, offset + n)
+            .copy_to(&mut buf[..n as usize]);
+        self.pos += u64::from(n);
+        self.chunk = Some((start, chunk));
+        Ok(n as usize)
+    }
+}
+
+impl Seek for BlobReader {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+        };
+
+        if let Some(pos) = new_pos {
+            self.pos = pos;
+            Ok(pos)
+        } else {
+            Err(Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            ))
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
+
+/// Converts a JavaScript exception into an I/O error.
+// Lint: This is used with `map_err`, which passes the error by value.
+#[allow(clippy::needless_pass_by_value)]
+fn js_error(error: JsValue) -> Error {
+    let message = error.dyn_ref::<Object>().map_or_else(
+        || format!("{error:?}"),
+        |error| String::from(error.to_string()),
+    );
+    Error::new(ErrorKind::Other, message)
+}
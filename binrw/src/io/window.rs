@@ -0,0 +1,151 @@
+//! Types for seekable reader adapters which expose part of the underlying
+//! reader as a separate stream.
+
+use super::{Error, ErrorKind, Read, Result, Seek, SeekFrom};
+
+/// Read adapter which exposes a range of bytes of an underlying reader as an
+/// independent stream starting at position zero.
+///
+/// Positions and seeks are relative to the start of the window, and reads stop
+/// at the end of the window. This allows nested containers to be parsed with
+/// offsets relative to the start of the container, without adding a base
+/// offset to every offset expression.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinRead, io::{Cursor, SeekFrom, Window}};
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Entry {
+///     offset: u8,
+///     #[br(seek_before = SeekFrom::Start(offset.into()))]
+///     value: u8,
+/// }
+///
+/// // The entry starts at offset 2, and its offset is relative to that
+/// let mut data = Cursor::new(b"\xff\xff\x02\xff\x07");
+/// let mut window = Window::new(&mut data, 2, 3).unwrap();
+/// let entry = Entry::read(&mut window).unwrap();
+/// assert_eq!(entry.value, 7);
+/// ```
+#[derive(Debug)]
+pub struct Window<T> {
+    inner: T,
+    start: u64,
+    len: u64,
+    pos: u64,
+}
+
+impl<T> Window<T> {
+    /// Gets a reference to the underlying reader.
+    #[must_use]
+    pub fn get_ref(&self) -> &T {
+        &self.inner
+    }
+
+    /// Gets a mutable reference to the underlying reader.
+    ///
+    /// Care should be taken to avoid modifying the internal I/O state of the
+    /// underlying reader as doing so may corrupt the internal position of
+    /// this `Window`.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Consumes this wrapper, returning the wrapped value.
+    #[must_use]
+    pub fn into_inner(self) -> T {
+        self.inner
+    }
+
+    /// Returns the position of the start of the window in the underlying
+    /// reader.
+    #[must_use]
+    pub fn offset(&self) -> u64 {
+        self.start
+    }
+
+    /// Returns the length of the window.
+    #[must_use]
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns true if the window is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+impl<T: Seek> Window<T> {
+    /// Creates a new window over the `len` bytes of `inner` starting at
+    /// position `start`, and seeks to the start of the window.
+    ///
+    /// # Errors
+    ///
+    /// If the end of the window overflows, or seeking the underlying reader
+    /// fails, an error is returned.
+    pub fn new(mut inner: T, start: u64, len: u64) -> Result<Self> {
+        if start.checked_add(len).is_none() {
+            return Err(Error::new(ErrorKind::InvalidInput, "window end overflows"));
+        }
+
+        inner.seek(SeekFrom::Start(start))?;
+        Ok(Self {
+            inner,
+            start,
+            len,
+            pos: 0,
+        })
+    }
+}
+
+impl<T: Read> Read for Window<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let limit = self.len.saturating_sub(self.pos);
+
+        // Don't call into inner reader at all at EOF because it may still block
+        if limit == 0 {
+            return Ok(0);
+        }
+
+        // Lint: It is impossible for this cast to truncate because the value
+        // being cast is the minimum of two values, and one of the value types
+        // is already `usize`.
+        #[allow(clippy::cast_possible_truncation)]
+        let max = (buf.len() as u64).min(limit) as usize;
+        let n = self.inner.read(&mut buf[0..max])?;
+        self.pos += n as u64;
+        Ok(n)
+    }
+}
+
+impl<T: Seek> Seek for Window<T> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+        };
+
+        if let Some((pos, inner_pos)) =
+            new_pos.and_then(|pos| Some((pos, self.start.checked_add(pos)?)))
+        {
+            self.inner.seek(SeekFrom::Start(inner_pos))?;
+            self.pos = pos;
+            Ok(pos)
+        } else {
+            Err(Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            ))
+        }
+    }
+
+    fn stream_position(&mut self) -> Result<u64> {
+        Ok(self.pos)
+    }
+}
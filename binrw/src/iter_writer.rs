@@ -0,0 +1,82 @@
+use crate::{
+    io::{Seek, Write},
+    BinResult, BinWrite, Endian,
+};
+use core::fmt;
+
+/// A wrapper that writes each item produced by an iterator, without collecting
+/// the items into a collection first.
+///
+/// The iterator is cloned each time the wrapper is written, so it should be
+/// cheap to clone, like a [`Range`](core::ops::Range), a slice iterator, or
+/// adapters over them. Any items which borrow from other data are written
+/// through the [`BinWrite`] implementation for references.
+///
+/// # Examples
+///
+/// ```
+/// use binrw::{BinWrite, IterWriter, io::Cursor};
+///
+/// #[derive(BinWrite)]
+/// #[bw(big)]
+/// struct Export<I: IntoIterator<Item = u16> + Clone> {
+///     count: u32,
+///     values: IterWriter<I>,
+/// }
+///
+/// let count = 3;
+/// let export = Export {
+///     count,
+///     values: IterWriter::new((0..count as u16).map(|i| i * 2)),
+/// };
+///
+/// let mut output = Cursor::new(vec![]);
+/// export.write(&mut output).unwrap();
+/// assert_eq!(output.into_inner(), b"\0\0\0\x03\0\0\0\x02\0\x04");
+/// ```
+#[derive(Clone)]
+pub struct IterWriter<I> {
+    /// The iterator which produces the items to write.
+    pub iter: I,
+}
+
+impl<I> IterWriter<I> {
+    /// Creates a new `IterWriter` which writes the items produced by `iter`.
+    pub fn new(iter: I) -> Self {
+        Self { iter }
+    }
+}
+
+impl<I> BinWrite for IterWriter<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: BinWrite,
+    for<'a> <I::Item as BinWrite>::Args<'a>: Clone,
+{
+    type Args<'a> = <I::Item as BinWrite>::Args<'a>;
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        args: Self::Args<'_>,
+    ) -> BinResult<()> {
+        for item in self.iter.clone() {
+            item.write_options(writer, endian, args.clone())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<I> From<I> for IterWriter<I> {
+    fn from(iter: I) -> Self {
+        Self::new(iter)
+    }
+}
+
+impl<I> fmt::Debug for IterWriter<I> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IterWriter").finish_non_exhaustive()
+    }
+}
@@ -22,12 +22,15 @@ extern crate std;
 pub mod __private;
 mod binread;
 mod binwrite;
+pub mod chunk;
 pub mod docs;
 pub mod endian;
 pub mod error;
 pub mod file_ptr;
 pub mod helpers;
 pub mod io;
+#[doc(hidden)]
+pub mod iter_writer;
 pub mod meta;
 mod named_args;
 #[doc(hidden)]
@@ -35,6 +38,7 @@ pub mod pos_value;
 pub mod punctuated;
 #[doc(hidden)]
 pub mod strings;
+pub mod tlv;
 
 #[cfg(all(doc, not(feature = "std")))]
 use alloc::vec::Vec;
@@ -45,6 +49,7 @@ pub use {
     endian::Endian,
     error::Error,
     file_ptr::{FilePtr, FilePtr128, FilePtr16, FilePtr32, FilePtr64, FilePtr8},
+    iter_writer::IterWriter,
     named_args::NamedArgs,
     pos_value::PosValue,
     strings::{NullString, NullWideString},
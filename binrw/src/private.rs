@@ -4,7 +4,13 @@ use crate::{
     BinRead, BinResult, BinWrite, Endian, Error,
 };
 #[cfg(not(feature = "std"))]
-use alloc::{boxed::Box, string::String};
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 
 pub use crate::named_args::{
     builder_helper, passthrough_helper, Needed, Optional, Satisfied, SatisfiedOrOptional,
@@ -64,6 +70,27 @@ where
     }
 }
 
+#[must_use]
+pub fn assertion_failed_message(condition: &str) -> String {
+    struct Message<'a>(&'a str);
+
+    impl core::fmt::Display for Message<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            crate::error::message_formatter().assertion_failed(f, self.0)
+        }
+    }
+
+    Message(condition).to_string()
+}
+
+pub fn assert_error_with<T, F, Err>(value: &T, pos: u64, error_fn: F) -> Err
+where
+    T: ?Sized,
+    F: FnOnce(&T, u64) -> Err,
+{
+    error_fn(value, pos)
+}
+
 // This validates the map function return value by trying to coerce it into
 // a function with the expected return type. If this is not done, the
 // compiler will emit the diagnostic on the `#[derive]`d attribute instead of
@@ -101,6 +128,17 @@ where
     }
 }
 
+// Like `magic`, but returns the value instead of comparing it, for runtime
+// magic values that need to be matched against more than one variant. The
+// expected value is only used to infer the type to read.
+pub fn read_magic_value<R, B>(reader: &mut R, _: &B, endian: Endian) -> BinResult<B>
+where
+    B: for<'a> BinRead<Args<'a> = ()>,
+    R: Read + Seek,
+{
+    B::read_options(reader, endian, ())
+}
+
 pub fn parse_fn_type_hint<Ret, ParseFn, R, Args>(f: ParseFn) -> ParseFn
 where
     R: Read + Seek,
@@ -117,7 +155,7 @@ where
     a
 }
 
-pub fn write_function_args_type_hint<T, W, Args, F>(_: F, a: Args) -> Args
+pub fn write_function_args_type_hint<T, W, Args, F>(_: &F, a: Args) -> Args
 where
     W: Write + Seek,
     F: FnOnce(&T, &mut W, Endian, Args) -> BinResult<()>,
@@ -151,6 +189,24 @@ where
     x
 }
 
+pub fn map_field_writer_type_hint<'a, Writer, MapFn, Output>(_: &Writer, x: MapFn) -> MapFn
+where
+    Writer: Write + Seek + 'a,
+    MapFn: Fn(&'a mut Writer) -> Output,
+    Output: Write + Seek + 'a,
+{
+    x
+}
+
+pub fn write_hook_type_hint<T, Writer, HookFn>(_: &Writer, hook: HookFn) -> HookFn
+where
+    T: ?Sized,
+    Writer: Write + Seek,
+    HookFn: FnOnce(&T, &mut Writer, Endian) -> BinResult<()>,
+{
+    hook
+}
+
 pub fn write_fn_type_hint<T, WriterFn, Writer, Args>(x: WriterFn) -> WriterFn
 where
     Writer: Write + Seek,
@@ -159,6 +215,14 @@ where
     x
 }
 
+pub fn write_fn_writer_type_hint<T, WriterFn, Writer, Args>(_: &Writer, x: WriterFn) -> WriterFn
+where
+    Writer: Write + Seek,
+    WriterFn: FnOnce(&T, &mut Writer, Endian, Args) -> BinResult<()>,
+{
+    x
+}
+
 pub fn write_map_args_type_hint<Input, Output, MapFn, Args>(_: &MapFn, args: Args) -> Args
 where
     MapFn: FnOnce(Input) -> Output,
@@ -250,30 +314,298 @@ where
     func
 }
 
-pub fn write_zeroes<W: Write>(writer: &mut W, count: u64) -> BinResult<()> {
+pub fn checked_repr<T: TryFrom<i128>>(
+    value: i128,
+    pos: u64,
+    message: &'static str,
+) -> BinResult<T> {
+    T::try_from(value).map_err(|_| Error::AssertFail {
+        pos,
+        message: message.into(),
+    })
+}
+
+#[must_use]
+pub fn offset_of_value(target: Option<u64>, base: Option<u64>) -> Option<u64> {
+    // A field which was not written has no position
+    match target {
+        Some(target) => target.checked_sub(base?),
+        None => Some(0),
+    }
+}
+
+pub fn write_patch<T, W>(
+    writer: &mut W,
+    slot: u64,
+    value: Option<u64>,
+    endian: Endian,
+    message: &'static str,
+) -> BinResult<()>
+where
+    T: TryFrom<u64> + for<'a> BinWrite<Args<'a> = ()>,
+    W: Write + Seek,
+{
+    let value = value
+        .and_then(|value| T::try_from(value).ok())
+        .ok_or_else(|| Error::AssertFail {
+            pos: slot,
+            message: message.into(),
+        })?;
+    let pos = writer.stream_position()?;
+    writer.seek(SeekFrom::Start(slot))?;
+    value.write_options(writer, endian, ())?;
+    writer.seek(SeekFrom::Start(pos))?;
+    Ok(())
+}
+
+/// A writer which keeps a copy of the bytes written through it while a
+/// checksum is pending, so that the checksum can be computed over them.
+///
+/// Bytes are only kept from the start of the first pending checksum range,
+/// and are discarded once every pending checksum has been written.
+pub struct ChecksumBuffer<W> {
+    inner: W,
+    start: u64,
+    pos: u64,
+    pending: usize,
+    data: Vec<u8>,
+}
+
+impl<W: Write + Seek> ChecksumBuffer<W> {
+    pub fn new(mut inner: W) -> BinResult<Self> {
+        let start = inner.stream_position()?;
+        Ok(Self {
+            inner,
+            start,
+            pos: start,
+            pending: 0,
+            data: Vec::new(),
+        })
+    }
+
+    /// Starts keeping the bytes of a checksum range, returning the position
+    /// where the range starts.
+    pub fn begin(&mut self) -> u64 {
+        if self.pending == 0 {
+            self.start = self.pos;
+        }
+        self.pending += 1;
+        self.pos
+    }
+
+    fn end(&mut self) {
+        self.pending = self.pending.saturating_sub(1);
+        if self.pending == 0 {
+            self.data.clear();
+        }
+    }
+
+    fn bytes(&self, start: u64, end: u64) -> &[u8] {
+        let offset = |pos: u64| {
+            usize::try_from(pos.saturating_sub(self.start))
+                .unwrap_or(usize::MAX)
+                .min(self.data.len())
+        };
+        &self.data[offset(start)..offset(end).max(offset(start))]
+    }
+}
+
+impl<W: Write> Write for ChecksumBuffer<W> {
+    fn write(&mut self, buf: &[u8]) -> crate::io::Result<usize> {
+        let count = self.inner.write(buf)?;
+        let written = &buf[..count];
+        if self.pending != 0 {
+            if let Ok(offset) = usize::try_from(self.pos.saturating_sub(self.start)) {
+                // Bytes written before the start of the first pending range
+                // are not part of any pending checksum
+                let skip = usize::try_from(self.start.saturating_sub(self.pos))
+                    .unwrap_or(usize::MAX)
+                    .min(count);
+                let written = &written[skip..];
+                let end = offset + written.len();
+                if self.data.len() < end {
+                    self.data.resize(end, 0);
+                }
+                self.data[offset..end].copy_from_slice(written);
+            }
+        }
+        self.pos += count as u64;
+        Ok(count)
+    }
+
+    fn flush(&mut self) -> crate::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Seek> Seek for ChecksumBuffer<W> {
+    fn seek(&mut self, pos: SeekFrom) -> crate::io::Result<u64> {
+        self.pos = self.inner.seek(pos)?;
+        Ok(self.pos)
+    }
+}
+
+/// A writer which discards everything written past a fixed number of bytes
+/// from where it was created.
+pub struct TruncatingWriter<W> {
+    inner: W,
+    pos: u64,
+    end: u64,
+}
+
+impl<W: Write + Seek> TruncatingWriter<W> {
+    pub fn new(mut inner: W, limit: u64) -> BinResult<Self> {
+        let pos = inner.stream_position()?;
+        Ok(Self {
+            inner,
+            pos,
+            end: pos.saturating_add(limit),
+        })
+    }
+}
+
+impl<W: Write> Write for TruncatingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> crate::io::Result<usize> {
+        let keep = usize::try_from(self.end.saturating_sub(self.pos))
+            .unwrap_or(usize::MAX)
+            .min(buf.len());
+        self.inner.write_all(&buf[..keep])?;
+        self.pos += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> crate::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<W: Seek> Seek for TruncatingWriter<W> {
+    fn seek(&mut self, pos: SeekFrom) -> crate::io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(pos) => pos,
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset).ok_or_else(|| {
+                crate::io::Error::new(
+                    crate::io::ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                )
+            })?,
+            SeekFrom::End(_) => self.inner.seek(pos)?,
+        };
+        // The inner writer never moves past the end, so that discarded
+        // bytes are never written
+        self.inner.seek(SeekFrom::Start(pos.min(self.end)))?;
+        self.pos = pos;
+        Ok(pos)
+    }
+}
+
+#[must_use]
+pub fn pad_size_overflow(pos: u64, size: u64, pad_to_size: u64, message: &'static str) -> Error {
+    Error::AssertFail {
+        pos,
+        message: format!("{message} is {size} bytes, which is larger than {pad_to_size} bytes"),
+    }
+}
+
+pub fn write_checksum<T, W, F>(
+    writer: &mut ChecksumBuffer<W>,
+    slot: u64,
+    start: u64,
+    end: u64,
+    algorithm: F,
+    endian: Endian,
+) -> BinResult<()>
+where
+    T: for<'a> BinWrite<Args<'a> = ()>,
+    W: Write + Seek,
+    F: FnOnce(&[u8]) -> T,
+{
+    let value = algorithm(writer.bytes(start, end));
+    writer.end();
+    let pos = writer.stream_position()?;
+    writer.seek(SeekFrom::Start(slot))?;
+    value.write_options(writer, endian, ())?;
+    writer.seek(SeekFrom::Start(pos))?;
+    Ok(())
+}
+
+pub fn verify_checksum<T, R, F>(
+    reader: &mut R,
+    start: u64,
+    end: u64,
+    algorithm: F,
+    value: &T,
+) -> BinResult<()>
+where
+    T: PartialEq + core::fmt::Debug,
+    R: Read + Seek,
+    F: FnOnce(&[u8]) -> T,
+{
+    let pos = reader.stream_position()?;
+    let mut bytes = vec![0; usize::try_from(end.saturating_sub(start)).unwrap_or(usize::MAX)];
+    reader.seek(SeekFrom::Start(start))?;
+    reader.read_exact(&mut bytes)?;
+    reader.seek(SeekFrom::Start(pos))?;
+
+    let expected = algorithm(&bytes);
+    if expected == *value {
+        Ok(())
+    } else {
+        Err(Error::AssertFail {
+            pos: start,
+            message: format!("checksum mismatch: expected {expected:#x?}, found {value:#x?}"),
+        })
+    }
+}
+
+pub fn write_padding<W: Write>(writer: &mut W, count: u64, byte: u8) -> BinResult<()> {
     const BUF_SIZE: u16 = 0x20;
-    const ZEROES: [u8; BUF_SIZE as usize] = [0u8; BUF_SIZE as usize];
+    let fill = [byte; BUF_SIZE as usize];
 
     if count <= BUF_SIZE.into() {
         // Lint: `count` is guaranteed to be <= BUF_SIZE
         #[allow(clippy::cast_possible_truncation)]
-        writer.write_all(&ZEROES[..count as usize])?;
+        writer.write_all(&fill[..count as usize])?;
     } else {
         let full_chunks = count / u64::from(BUF_SIZE);
         let remaining = count % u64::from(BUF_SIZE);
 
         for _ in 0..full_chunks {
-            writer.write_all(&ZEROES)?;
+            writer.write_all(&fill)?;
         }
 
         // Lint: `remaining` is guaranteed to be < BUF_SIZE
         #[allow(clippy::cast_possible_truncation)]
-        writer.write_all(&ZEROES[..remaining as usize])?;
+        writer.write_all(&fill[..remaining as usize])?;
     }
 
     Ok(())
 }
 
+pub fn write_alignment<W: Write + Seek>(
+    writer: &mut W,
+    align: u64,
+    base: Option<u64>,
+    byte: u8,
+) -> BinResult<()> {
+    let pos = writer.stream_position()?;
+    if align == 0 {
+        return Err(Error::AssertFail {
+            pos,
+            message: "alignment must not be zero".into(),
+        });
+    }
+    let base = base.ok_or_else(|| Error::AssertFail {
+        pos,
+        message: "alignment anchor was not written".into(),
+    })?;
+    let rem = (pos % align + align - base % align) % align;
+    if rem != 0 {
+        write_padding(writer, align - rem, byte)?;
+    }
+    Ok(())
+}
+
 #[cfg(feature = "std")]
 pub use std::eprintln;
 
@@ -1,6 +1,10 @@
 //! Type definitions for wrappers which parse interleaved data.
 
-use crate::{BinRead, BinResult, VecArgs};
+use crate::{
+    error::ErrorKind,
+    io::{Read, Seek},
+    BinRead, BinResult, Endian, VecArgs,
+};
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 use core::fmt;
@@ -9,10 +13,14 @@ use core::fmt;
 /// other values of type `P`.
 ///
 /// To use this parser, you must specify the parsing strategy by selecting
-/// either [`separated()`] or [`separated_trailing()`] using [`parse_with`].
+/// either [`separated()`] or [`separated_trailing()`] for a known number of
+/// values, or [`delimited()`] or [`delimited_trailing()`] for a list which
+/// ends when no separator follows a value, using [`parse_with`].
 ///
 /// [`separated()`]: Self::separated
 /// [`separated_trailing()`]: Self::separated_trailing
+/// [`delimited()`]: Self::delimited
+/// [`delimited_trailing()`]: Self::delimited_trailing
 /// [`parse_with`]: crate::docs::attribute#custom-parserswriters
 ///
 /// Consider using a `Vec<(T, P)>` or `(Vec<(T, P)>, Option<T>>)` instead if you
@@ -76,8 +84,12 @@ where
     /// # assert_eq!(*y.x, vec![3, 2, 1]);
     /// # assert_eq!(y.x.separators, vec![0, 1]);
     /// ```
-    #[crate::parser(reader, endian)]
-    pub fn separated<'a>(args: VecArgs<T::Args<'a>>, ...) -> BinResult<Self>
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn separated<'a, R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: VecArgs<T::Args<'a>>,
+    ) -> BinResult<Self>
     where
         T::Args<'a>: Clone,
     {
@@ -102,8 +114,12 @@ where
     /// # Errors
     ///
     /// If reading fails, an [`Error`](crate::Error) variant will be returned.
-    #[crate::parser(reader, endian)]
-    pub fn separated_trailing<'a>(args: VecArgs<T::Args<'a>>, ...) -> BinResult<Self>
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn separated_trailing<'a, R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: VecArgs<T::Args<'a>>,
+    ) -> BinResult<Self>
     where
         T::Args<'a>: Clone,
     {
@@ -118,6 +134,141 @@ where
         Ok(Self { data, separators })
     }
 
+    /// Parses values of type `T` separated by values of type `P` without a
+    /// trailing separator value, until a separator cannot be read.
+    ///
+    /// At least one value is read. After each value, a separator is read, and
+    /// if it is not there, the reader is rewound to the end of the value and
+    /// the list ends. A separator is not there if reading it reaches the end
+    /// of the stream or fails with a bad magic, a failed assertion, or no
+    /// matching enum variant, so separators are usually types with a
+    /// [`magic`](crate::docs::attribute#magic) or an
+    /// [assertion](crate::docs::attribute#assert).
+    ///
+    /// # Errors
+    ///
+    /// If reading a value fails, or reading a separator fails for any other
+    /// reason, an [`Error`](crate::Error) variant will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use binrw::{prelude::*, io::Cursor};
+    /// use binrw::punctuated::Punctuated;
+    ///
+    /// #[derive(BinRead)]
+    /// #[br(magic = b',')]
+    /// struct Comma;
+    ///
+    /// #[derive(BinRead)]
+    /// struct MyList {
+    ///     #[br(parse_with = Punctuated::delimited)]
+    ///     x: Punctuated<u8, Comma>,
+    ///     end: u8,
+    /// }
+    ///
+    /// # let mut x = Cursor::new(b"1,2,3;");
+    /// # let y: MyList = x.read_be().unwrap();
+    /// # assert_eq!(*y.x, b"123");
+    /// # assert_eq!(y.end, b';');
+    /// ```
+    pub fn delimited<'a, R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: T::Args<'a>,
+    ) -> BinResult<Self>
+    where
+        T::Args<'a>: Clone,
+    {
+        Self::read_delimited(reader, endian, args, false)
+    }
+
+    /// Parses values of type `T` separated by values of type `P`, with an
+    /// optional trailing separator value, until a separator or a value after a
+    /// separator cannot be read.
+    ///
+    /// This is like [`delimited()`](Self::delimited), but if a value is not
+    /// there after a separator, the reader is rewound to the end of the
+    /// separator and the list ends with the trailing separator.
+    ///
+    /// # Errors
+    ///
+    /// If reading the first value fails, or reading a separator or a later
+    /// value fails for a reason other than it not being there, an
+    /// [`Error`](crate::Error) variant will be returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use binrw::{prelude::*, io::Cursor};
+    /// use binrw::punctuated::Punctuated;
+    ///
+    /// #[derive(BinRead)]
+    /// #[br(magic = b',')]
+    /// struct Comma;
+    ///
+    /// #[derive(BinRead)]
+    /// #[br(assert(value.is_ascii_digit()))]
+    /// struct Digit {
+    ///     value: u8,
+    /// }
+    ///
+    /// #[derive(BinRead)]
+    /// struct MyList {
+    ///     #[br(parse_with = Punctuated::delimited_trailing)]
+    ///     x: Punctuated<Digit, Comma>,
+    ///     end: u8,
+    /// }
+    ///
+    /// # let mut x = Cursor::new(b"1,2,3,;");
+    /// # let y: MyList = x.read_be().unwrap();
+    /// # assert_eq!(y.x.len(), 3);
+    /// # assert_eq!(y.x.separators.len(), 3);
+    /// # assert_eq!(y.end, b';');
+    /// ```
+    pub fn delimited_trailing<'a, R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: T::Args<'a>,
+    ) -> BinResult<Self>
+    where
+        T::Args<'a>: Clone,
+    {
+        Self::read_delimited(reader, endian, args, true)
+    }
+
+    fn read_delimited<'a, R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        args: T::Args<'a>,
+        trailing: bool,
+    ) -> BinResult<Self>
+    where
+        T::Args<'a>: Clone,
+    {
+        let mut data = Vec::new();
+        let mut separators = Vec::new();
+
+        data.push(T::read_options(reader, endian, args.clone())?);
+        while let Some(separator) = try_read(reader, |reader| P::read_options(reader, endian, ()))?
+        {
+            separators.push(separator);
+            let value = if trailing {
+                match try_read(reader, |reader| {
+                    T::read_options(reader, endian, args.clone())
+                })? {
+                    Some(value) => value,
+                    None => break,
+                }
+            } else {
+                T::read_options(reader, endian, args.clone())?
+            };
+            data.push(value);
+        }
+
+        Ok(Self { data, separators })
+    }
+
     /// Consumes this object, returning the data values while dropping the
     /// separator values.
     ///
@@ -132,6 +283,31 @@ where
     }
 }
 
+/// Reads a value, rewinding the reader and returning `None` if the value is
+/// not there.
+///
+/// A value is not there if the root cause of the error is the end of the
+/// stream, a bad magic, a failed assertion, or no matching enum variant. Any
+/// other error is returned after rewinding the reader.
+fn try_read<R: Read + Seek, V>(
+    reader: &mut R,
+    read: impl FnOnce(&mut R) -> BinResult<V>,
+) -> BinResult<Option<V>> {
+    let pos = reader.stream_position()?;
+    match read(reader) {
+        Ok(value) => Ok(Some(value)),
+        Err(error) => match error.kind() {
+            ErrorKind::Eof
+            | ErrorKind::BadMagic
+            | ErrorKind::AssertFail
+            | ErrorKind::NoVariantMatch => {
+                crate::__private::restore_position_variant(reader, pos, error).map(|_| None)
+            }
+            _ => crate::__private::restore_position(reader, pos)(error),
+        },
+    }
+}
+
 impl<T: BinRead + fmt::Debug, P: BinRead> fmt::Debug for Punctuated<T, P> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.data.fmt(f)
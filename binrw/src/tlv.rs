@@ -0,0 +1,288 @@
+//! Type definitions and helpers for type-length-value (TLV) data.
+//!
+//! Many formats, like EMV and BER, are made of records which each start with a
+//! tag identifying the type of the record, followed by the length of the value
+//! and then the value itself. This module provides a generic [`Tlv`] record,
+//! a [`TlvIter`] over the records in a region of a stream, and a [`dispatch`]
+//! helper which chooses how to parse each value by its tag.
+
+use crate::{
+    helpers::count_bytes,
+    io::{Read, Seek, SeekFrom, Window, Write},
+    BinRead, BinResult, BinWrite, Endian, Error,
+};
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// A type-length-value record with a tag of type `Tag` and a length of type
+/// `Len`.
+///
+/// The length is the number of bytes in the value.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, io::Cursor};
+/// use binrw::tlv::Tlv;
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Message {
+///     header: Tlv<u8, u16>,
+/// }
+///
+/// # let x = Message::read(&mut Cursor::new(b"\x01\0\x02ab")).unwrap();
+/// # assert_eq!((x.header.tag, x.header.len, &x.header.value[..]), (1, 2, &b"ab"[..]));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tlv<Tag, Len> {
+    /// The tag identifying the type of the record.
+    pub tag: Tag,
+
+    /// The length of the value, in bytes.
+    pub len: Len,
+
+    /// The raw bytes of the value.
+    pub value: Vec<u8>,
+}
+
+impl<Tag, Len> Tlv<Tag, Len> {
+    /// Creates a new record with the given `tag` and `value`, and a length
+    /// matching the value.
+    ///
+    /// # Errors
+    ///
+    /// If the length of the value does not fit in `Len`, the conversion error
+    /// is returned.
+    pub fn new(tag: Tag, value: Vec<u8>) -> Result<Self, <Len as TryFrom<usize>>::Error>
+    where
+        Len: TryFrom<usize>,
+    {
+        Ok(Self {
+            tag,
+            len: Len::try_from(value.len())?,
+            value,
+        })
+    }
+}
+
+impl<Tag, Len> BinRead for Tlv<Tag, Len>
+where
+    Tag: for<'a> BinRead<Args<'a> = ()>,
+    Len: for<'a> BinRead<Args<'a> = ()> + Copy + TryInto<usize>,
+{
+    type Args<'a> = ();
+
+    fn read_options<R: Read + Seek>(
+        reader: &mut R,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<Self> {
+        let (tag, len, value_len) = read_header::<Tag, Len, R>(reader, endian)?;
+        Ok(Self {
+            tag,
+            len,
+            value: count_bytes(value_len)(reader, endian, ())?,
+        })
+    }
+}
+
+impl<Tag, Len> BinWrite for Tlv<Tag, Len>
+where
+    Tag: for<'a> BinWrite<Args<'a> = ()>,
+    Len: for<'a> BinWrite<Args<'a> = ()>,
+{
+    type Args<'a> = ();
+
+    fn write_options<W: Write + Seek>(
+        &self,
+        writer: &mut W,
+        endian: Endian,
+        (): Self::Args<'_>,
+    ) -> BinResult<()> {
+        self.tag.write_options(writer, endian, ())?;
+        self.len.write_options(writer, endian, ())?;
+        writer.write_all(&self.value)?;
+        Ok(())
+    }
+}
+
+/// An iterator over the [`Tlv`] records in a region of a stream.
+///
+/// The iterator stops at the end of the region, or after the first error. A
+/// record which extends past the end of the region is an error.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{io::Cursor, Endian};
+/// use binrw::tlv::{Tlv, TlvIter};
+///
+/// let mut data = Cursor::new(b"\x01\x01a\x02\x02bc\xff");
+/// let records = TlvIter::<_, u8, u8>::new(&mut data, Endian::Big, 7)
+///     .unwrap()
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+/// assert_eq!(records, [
+///     Tlv::new(1, b"a".to_vec()).unwrap(),
+///     Tlv::new(2, b"bc".to_vec()).unwrap(),
+/// ]);
+/// assert_eq!(data.position(), 7);
+/// ```
+#[derive(Debug)]
+pub struct TlvIter<'r, R, Tag, Len> {
+    reader: &'r mut R,
+    endian: Endian,
+    end: u64,
+    done: bool,
+    _marker: PhantomData<fn() -> (Tag, Len)>,
+}
+
+impl<'r, R: Seek, Tag, Len> TlvIter<'r, R, Tag, Len> {
+    /// Creates a new iterator over the records in the next `len` bytes of
+    /// `reader`.
+    ///
+    /// # Errors
+    ///
+    /// If getting the position of the reader fails, or the end of the region
+    /// overflows, an [`Error`] variant will be returned.
+    pub fn new(reader: &'r mut R, endian: Endian, len: u64) -> BinResult<Self> {
+        let pos = reader.stream_position()?;
+        let end = pos.checked_add(len).ok_or_else(|| Error::AssertFail {
+            pos,
+            message: "the end of the TLV region overflows".into(),
+        })?;
+        Ok(Self {
+            reader,
+            endian,
+            end,
+            done: false,
+            _marker: PhantomData,
+        })
+    }
+
+    fn read_next(&mut self) -> Option<BinResult<Tlv<Tag, Len>>>
+    where
+        R: Read,
+        Tag: for<'a> BinRead<Args<'a> = ()>,
+        Len: for<'a> BinRead<Args<'a> = ()> + Copy + TryInto<usize>,
+    {
+        let pos = match self.reader.stream_position() {
+            Ok(pos) => pos,
+            Err(err) => return Some(Err(err.into())),
+        };
+        if pos >= self.end {
+            return None;
+        }
+
+        Some(
+            Tlv::read_options(self.reader, self.endian, ()).and_then(|record| {
+                if self.reader.stream_position()? > self.end {
+                    Err(Error::AssertFail {
+                        pos,
+                        message: "TLV record extends past the end of the region".into(),
+                    })
+                } else {
+                    Ok(record)
+                }
+            }),
+        )
+    }
+}
+
+impl<R, Tag, Len> Iterator for TlvIter<'_, R, Tag, Len>
+where
+    R: Read + Seek,
+    Tag: for<'a> BinRead<Args<'a> = ()>,
+    Len: for<'a> BinRead<Args<'a> = ()> + Copy + TryInto<usize>,
+{
+    type Item = BinResult<Tlv<Tag, Len>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let item = self.read_next();
+        self.done = !matches!(item, Some(Ok(_)));
+        item
+    }
+}
+
+/// Creates a parser that reads a TLV record and parses its value with the
+/// parser chosen by `select` for its tag.
+///
+/// `select` is called with the tag and a [`Window`] over the value, so the
+/// value parser cannot read past the end of the value, and positions inside
+/// the value are relative to its start. Any part of the value left unread by
+/// `select` is skipped.
+///
+/// # Errors
+///
+/// If reading the tag or length fails, or `select` returns an error, an
+/// [`Error`] variant will be returned.
+///
+/// # Examples
+///
+/// ```
+/// # use binrw::{prelude::*, helpers::until_eof_with, io::Cursor, NullString};
+/// use binrw::tlv;
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Field {
+///     Name(NullString),
+///     Id(u16),
+///     Unknown(u8),
+/// }
+///
+/// #[derive(BinRead)]
+/// #[br(big)]
+/// struct Card {
+///     #[br(parse_with = until_eof_with(tlv::dispatch::<u8, u8, _, _, _>(
+///         |tag, value, endian| match tag {
+///             0x50 => NullString::read_options(value, endian, ()).map(Field::Name),
+///             0x5a => u16::read_options(value, endian, ()).map(Field::Id),
+///             tag => Ok(Field::Unknown(tag)),
+///         },
+///     )))]
+///     fields: Vec<Field>,
+/// }
+///
+/// # let x = Card::read(&mut Cursor::new(b"\x50\x03ab\0\x99\x01\xff\x5a\x02\x01\x02")).unwrap();
+/// # assert_eq!(x.fields, [Field::Name("ab".into()), Field::Unknown(0x99), Field::Id(0x102)]);
+/// ```
+pub fn dispatch<Tag, Len, T, R, F>(select: F) -> impl Fn(&mut R, Endian, ()) -> BinResult<T>
+where
+    Tag: for<'a> BinRead<Args<'a> = ()>,
+    Len: for<'a> BinRead<Args<'a> = ()> + Copy + TryInto<usize>,
+    R: Read + Seek,
+    F: Fn(Tag, &mut Window<&mut R>, Endian) -> BinResult<T>,
+{
+    move |reader, endian, ()| {
+        let (tag, _, len) = read_header::<Tag, Len, R>(reader, endian)?;
+        let len = len as u64;
+        let start = reader.stream_position()?;
+        let value = select(tag, &mut Window::new(&mut *reader, start, len)?, endian)?;
+        reader.seek(SeekFrom::Start(start + len))?;
+        Ok(value)
+    }
+}
+
+/// Reads the tag and length of a TLV record, returning them with the length
+/// converted to a `usize`.
+fn read_header<Tag, Len, R>(reader: &mut R, endian: Endian) -> BinResult<(Tag, Len, usize)>
+where
+    Tag: for<'a> BinRead<Args<'a> = ()>,
+    Len: for<'a> BinRead<Args<'a> = ()> + Copy + TryInto<usize>,
+    R: Read + Seek,
+{
+    let tag = Tag::read_options(reader, endian, ())?;
+    let pos = reader.stream_position()?;
+    let len = Len::read_options(reader, endian, ())?;
+    let value_len = len.try_into().map_err(|_| Error::AssertFail {
+        pos,
+        message: "TLV length does not fit in a usize".into(),
+    })?;
+    Ok((tag, len, value_len))
+}
@@ -111,3 +111,13 @@ fn vec_u8() {
         binrw::Error::Io(..)
     ));
 }
+
+#[test]
+fn vec_u8_huge_count() {
+    let error = Vec::<u8>::read_args(
+        &mut Cursor::new(b"ab"),
+        binrw::VecArgs::builder().count(usize::MAX).finalize(),
+    )
+    .expect_err("accepted bad data");
+    assert!(error.is_eof());
+}
@@ -0,0 +1,72 @@
+use binrw::{
+    chunk::{read_chunk, write_chunk, Chunk, ChunkIter, FourCC},
+    io::{Cursor, Seek, SeekFrom},
+    BinRead, BinWrite, Endian, Error,
+};
+
+#[test]
+fn chunk_round_trip_pads_odd_size() {
+    let chunk = Chunk::<u32>::new(*b"LIST", b"abc".to_vec()).unwrap();
+    let mut x = Cursor::new(Vec::new());
+    chunk.write_le(&mut x).unwrap();
+    assert_eq!(x.get_ref(), b"LIST\x03\0\0\0abc\0");
+
+    x.seek(SeekFrom::Start(0)).unwrap();
+    assert_eq!(Chunk::<u32>::read_le(&mut x).unwrap(), chunk);
+    assert_eq!(x.position(), 12);
+}
+
+#[test]
+fn chunk_without_padding() {
+    let chunk = Chunk::<u16, false>::new(*b"IHDR", b"abc".to_vec()).unwrap();
+    let mut x = Cursor::new(Vec::new());
+    chunk.write_be(&mut x).unwrap();
+    assert_eq!(x.get_ref(), b"IHDR\0\x03abc");
+}
+
+#[test]
+fn chunk_iter_missing_final_padding() {
+    let mut x = Cursor::new(b"abcd\x01\0\0\0x\0efgh\x01\0\0\0y");
+    let chunks = ChunkIter::<_>::new(&mut x, Endian::Little, 19)
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[1].id, *b"efgh");
+}
+
+#[test]
+fn chunk_iter_overrun() {
+    let mut x = Cursor::new(b"abcd\x04\0\0\0wxyz");
+    let mut iter = ChunkIter::<_>::new(&mut x, Endian::Little, 10).unwrap();
+    assert!(matches!(
+        iter.next(),
+        Some(Err(Error::AssertFail { pos: 0, .. }))
+    ));
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn write_chunk_size_overflow() {
+    let mut x = Cursor::new(Vec::new());
+    let write = write_chunk::<u8, _, _, _>(FourCC(*b"data"), false);
+    assert!(write(&vec![0u8; 256], &mut x, Endian::Little, ()).is_err());
+}
+
+#[test]
+fn read_chunk_skips_unread_data() {
+    let mut x = Cursor::new(b"data\x03\0\0\0abc\0\xff");
+    let read = read_chunk::<u32, u8, _, _>(FourCC(*b"data"), true);
+    assert_eq!(read(&mut x, Endian::Little, ()).unwrap(), b'a');
+    assert_eq!(x.position(), 12);
+}
+
+#[test]
+fn read_chunk_bad_id() {
+    let mut x = Cursor::new(b"junk\0\0\0\0");
+    let read = read_chunk::<u32, u8, _, _>(FourCC(*b"data"), true);
+    assert!(matches!(
+        read(&mut x, Endian::Little, ()),
+        Err(Error::BadMagic { pos: 0, .. })
+    ));
+}
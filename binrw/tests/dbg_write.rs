@@ -0,0 +1,77 @@
+#[cfg(feature = "std")]
+#[test]
+fn dbg_write() {
+    use binrw::{io::Cursor, BinWrite};
+
+    #[derive(BinWrite, Debug)]
+    struct Inner(u32);
+
+    #[derive(BinWrite)]
+    #[bw(big)]
+    struct Test {
+        before: u16,
+        #[bw(dbg, pad_before = 2, pad_after = 1)]
+        value: u32,
+        #[bw(dbg, align_before = 10, align_after = 16)]
+        inner: Inner,
+        #[bw(dbg, pad_size_to = 4)]
+        last: u8,
+        #[bw(dbg, restore_position)]
+        terminator: u8,
+    }
+
+    let test = Test {
+        before: 0,
+        value: 4,
+        inner: Inner(0xeffed),
+        last: 0x42,
+        terminator: 0x69,
+    };
+
+    if let Some("1") = option_env!("BINRW_IN_CHILD_PROC") {
+        test.write(&mut Cursor::new(Vec::new())).unwrap();
+    } else {
+        use std::process::{Command, Stdio};
+
+        let result = Command::new(env!("CARGO"))
+            .env("BINRW_IN_CHILD_PROC", "1")
+            .args([
+                "test",
+                "-q",
+                "--test",
+                "dbg_write",
+                "--",
+                "--nocapture",
+                "dbg_write",
+            ])
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .output()
+            .unwrap()
+            .stderr;
+
+        assert_eq!(
+            std::str::from_utf8(&result).unwrap(),
+            format!(
+                concat!(
+                    "[{file}:{offset_0} | pad_before 0x2]\n",
+                    "[{file}:{offset_0} | offset 0x4..0x8, 4 bytes] value = 0x4\n",
+                    "[{file}:{offset_0} | pad_after 0x1]\n",
+                    "[{file}:{offset_1} | align_before 0xa]\n",
+                    "[{file}:{offset_1} | offset 0xa..0xe, 4 bytes] inner = Inner(\n",
+                    "    0xeffed,\n",
+                    ")\n",
+                    "[{file}:{offset_1} | align_after 0x10]\n",
+                    "[{file}:{offset_2} | offset 0x10..0x11, 1 bytes] last = 0x42\n",
+                    "[{file}:{offset_2} | pad_size_to 0x4]\n",
+                    "[{file}:{offset_3} | offset 0x14..0x15, 1 bytes] terminator = 0x69\n",
+                ),
+                file = core::file!(),
+                offset_0 = if cfg!(nightly) { 13 } else { 9 },
+                offset_1 = if cfg!(nightly) { 15 } else { 9 },
+                offset_2 = if cfg!(nightly) { 17 } else { 9 },
+                offset_3 = if cfg!(nightly) { 19 } else { 9 },
+            )
+        );
+    }
+}
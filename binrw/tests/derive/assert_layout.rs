@@ -0,0 +1,45 @@
+use binrw::{binrw, io::Cursor, BinRead, BinWrite};
+
+#[test]
+fn assert_layout() {
+    #[derive(BinRead, Clone, Copy, Debug, PartialEq)]
+    #[br(little, assert_layout)]
+    #[repr(C, packed)]
+    struct Header {
+        kind: u8,
+        #[br(big)]
+        len: u32,
+        flags: [u16; 2],
+        _marker: core::marker::PhantomData<()>,
+    }
+
+    let data = b"\x01\0\0\0\x04\x02\0\x03\0";
+    let header = Header::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(
+        header,
+        Header {
+            kind: 1,
+            len: 4,
+            flags: [2, 3],
+            _marker: core::marker::PhantomData,
+        }
+    );
+    assert_eq!(core::mem::size_of::<Header>(), data.len());
+}
+
+#[test]
+fn assert_layout_tuple() {
+    #[binrw]
+    #[brw(big, assert_layout)]
+    #[repr(C)]
+    #[derive(Debug, PartialEq)]
+    struct Pair(u32, u32);
+
+    let data = b"\0\0\0\x01\0\0\0\x02";
+    let pair = Pair::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(pair, Pair(1, 2));
+
+    let mut out = Cursor::new(Vec::new());
+    pair.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), data);
+}
@@ -0,0 +1,81 @@
+use binrw::{io::Cursor, BinRead, BinReadBorrowed, BinResult, Endian};
+
+#[test]
+fn borrow_slices() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big, borrow = 'a)]
+    struct Packet<'a> {
+        len: u8,
+        #[br(count = len)]
+        payload: &'a [u8],
+        name_len: u16,
+        #[br(count = name_len)]
+        name: &'a str,
+        trailer: u16,
+    }
+
+    let data = b"\x02\x01\x02\0\x03abc\0\x04";
+    let packet = Packet::read_borrowed(data).unwrap();
+    assert_eq!(
+        packet,
+        Packet {
+            len: 2,
+            payload: &data[1..3],
+            name_len: 3,
+            name: "abc",
+            trailer: 4,
+        }
+    );
+    assert!(core::ptr::eq(packet.payload, &data[1..3]));
+}
+
+#[test]
+fn borrow_nested() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(borrow = 'a, import(len: usize))]
+    struct Inner<'a>(#[br(count = len)] &'a [u8]);
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little, borrow = 'data)]
+    struct Outer<'data> {
+        len: u8,
+        #[br(args(len.into()))]
+        inner: Inner<'data>,
+        #[br(parse_with = rest)]
+        rest: &'data [u8],
+    }
+
+    fn rest<'data>(reader: &mut Cursor<&'data [u8]>, _: Endian, (): ()) -> BinResult<&'data [u8]> {
+        let data = *reader.get_ref();
+        let start = usize::try_from(reader.position()).unwrap();
+        reader.set_position(data.len() as u64);
+        Ok(&data[start..])
+    }
+
+    let data = b"\x01\x02\x03\x04";
+    assert_eq!(
+        Outer::read_borrowed(data).unwrap(),
+        Outer {
+            len: 1,
+            inner: Inner(b"\x02"),
+            rest: b"\x03\x04",
+        }
+    );
+}
+
+#[test]
+fn borrow_errors() {
+    #[derive(BinRead, Debug)]
+    #[br(big, borrow = 'a)]
+    struct Test<'a> {
+        _len: u8,
+        #[br(count = _len)]
+        _name: &'a str,
+    }
+
+    assert!(Test::read_borrowed(b"\x04ab").unwrap_err().is_eof());
+    assert!(matches!(
+        Test::read_borrowed(b"\x01\xff").unwrap_err().root_cause(),
+        binrw::Error::Custom { pos: 1, .. }
+    ));
+}
@@ -0,0 +1,117 @@
+use binrw::{binrw, binwrite, helpers::crc32, io::Cursor, BinRead, BinWrite};
+
+#[test]
+fn checksum() {
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    struct Chunk {
+        kind: [u8; 4],
+        #[br(count = 2)]
+        data: Vec<u8>,
+        #[brw(checksum(crc32, over = "kind..=data"))]
+        crc: u32,
+    }
+
+    let chunk = Chunk {
+        kind: *b"tEST",
+        data: vec![1, 2],
+    };
+
+    let mut x = Cursor::new(Vec::new());
+    chunk.write(&mut x).unwrap();
+    let expected = [
+        b"tEST".as_slice(),
+        &[1, 2],
+        &crc32(b"tEST\x01\x02").to_be_bytes(),
+    ]
+    .concat();
+    assert_eq!(x.get_ref(), &expected);
+
+    x.set_position(0);
+    assert_eq!(Chunk::read(&mut x).unwrap(), chunk);
+}
+
+#[test]
+fn checksum_before_data() {
+    fn sum(bytes: &[u8]) -> u16 {
+        bytes.iter().map(|&byte| u16::from(byte)).sum()
+    }
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(little)]
+    struct Test {
+        #[brw(checksum(sum, over = "a..=b"))]
+        check: u16,
+        a: u8,
+        #[brw(pad_before = 1)]
+        #[br(map_stream = |r| r)]
+        #[bw(map_stream = |w| w)]
+        b: u16,
+    }
+
+    let mut x = Cursor::new(vec![0xff]);
+    x.set_position(1);
+    Test { a: 0x80, b: 0x8180 }.write(&mut x).unwrap();
+    assert_eq!(x.get_ref(), &[0xff, 0x81, 1, 0x80, 0, 0x80, 0x81]);
+
+    x.set_position(1);
+    assert_eq!(Test::read(&mut x).unwrap(), Test { a: 0x80, b: 0x8180 });
+}
+
+#[test]
+fn checksum_mismatch() {
+    #[derive(BinRead, Debug)]
+    #[br(little)]
+    struct Test {
+        data: [u8; 2],
+        #[br(checksum(|bytes: &[u8]| bytes[0] ^ bytes[1], over = "data"))]
+        check: u8,
+    }
+
+    let test = Test::read(&mut Cursor::new(b"\x01\x02\x03")).unwrap();
+    assert_eq!((test.data, test.check), ([1, 2], 3));
+
+    let error = Test::read(&mut Cursor::new(b"\x01\x02\x04")).unwrap_err();
+    match error {
+        binrw::Error::AssertFail { pos, message } => {
+            assert_eq!(pos, 0);
+            assert_eq!(message, "checksum mismatch: expected 0x3, found 0x4");
+        }
+        error => panic!("unexpected error: {error:?}"),
+    }
+}
+
+#[test]
+fn checksum_ranges() {
+    fn sum(bytes: &[u8]) -> u8 {
+        bytes.iter().fold(0, |sum, &byte| sum.wrapping_add(byte))
+    }
+
+    #[binwrite]
+    #[bw(little)]
+    struct Test {
+        head: u8,
+        a: [u8; 2],
+        #[bw(checksum(sum, over = "a"))]
+        a_sum: u8,
+        b: [u8; 2],
+        #[bw(checksum(sum, over = "a..=b"))]
+        ab_sum: u8,
+        c: [u8; 2],
+        #[bw(checksum(sum, over = "c"))]
+        c_sum: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test {
+        head: 0x10,
+        a: [1, 2],
+        b: [4, 5],
+        c: [6, 7],
+    }
+    .write(&mut x)
+    .unwrap();
+    assert_eq!(x.into_inner(), [0x10, 1, 2, 3, 4, 5, 15, 6, 7, 13]);
+}
@@ -180,6 +180,207 @@ fn enum_magic() {
     assert_eq!(result, Test::One { a: 515 });
 }
 
+#[test]
+fn enum_fallback() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    enum Test {
+        #[br(default)]
+        Unknown { tag: u8, len: u8 },
+        #[br(magic(0u8))]
+        Zero { a: u16 },
+        #[br(magic(1u8))]
+        One { a: u8 },
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\0\x01\x02")).unwrap(),
+        Test::Zero { a: 0x102 }
+    );
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x01\x02")).unwrap(),
+        Test::One { a: 2 }
+    );
+    let mut data = Cursor::new(b"\x07\x02");
+    assert_eq!(
+        Test::read(&mut data).unwrap(),
+        Test::Unknown { tag: 7, len: 2 }
+    );
+    assert_eq!(data.stream_position().unwrap(), 2);
+    Test::read(&mut Cursor::new(b"\x07")).expect_err("accepted bad data");
+}
+
+#[test]
+fn enum_fallback_unit() {
+    #[derive(BinRead, Debug, PartialEq)]
+    enum Test {
+        #[br(magic(0u8))]
+        Zero { a: u8 },
+        #[br(default)]
+        Unknown,
+    }
+
+    let mut data = Cursor::new(b"\x01\x02");
+    assert_eq!(Test::read_le(&mut data).unwrap(), Test::Unknown);
+    assert_eq!(data.stream_position().unwrap(), 0);
+}
+
+#[test]
+fn enum_tag() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little, import(kind: u8), tag = kind)]
+    enum Test {
+        #[br(tag = 1)]
+        One { a: u8 },
+        #[br(tag = 2)]
+        Two(u16),
+        #[br(tag = 3)]
+        Empty,
+    }
+
+    assert_eq!(
+        Test::read_args(&mut Cursor::new(b"\x01\x02"), (1,)).unwrap(),
+        Test::One { a: 1 }
+    );
+    assert_eq!(
+        Test::read_args(&mut Cursor::new(b"\x01\x02"), (2,)).unwrap(),
+        Test::Two(0x201)
+    );
+    let mut data = Cursor::new(b"\x01\x02");
+    assert_eq!(Test::read_args(&mut data, (3,)).unwrap(), Test::Empty);
+    assert_eq!(data.stream_position().unwrap(), 0);
+    Test::read_args(&mut Cursor::new(b"\x01\x02"), (4,)).expect_err("accepted bad tag");
+
+    let mut data = Cursor::new(b"\x01");
+    Test::read_args(&mut data, (2,)).expect_err("accepted bad data");
+    assert_eq!(data.stream_position().unwrap(), 0);
+}
+
+#[test]
+fn enum_tag_fallback() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import { kind: u8 }, tag = kind)]
+    enum Test {
+        #[br(tag = 0)]
+        Zero(u8),
+        #[br(default)]
+        Unknown {
+            #[br(calc = kind)]
+            kind: u8,
+            data: u8,
+        },
+    }
+
+    assert_eq!(
+        Test::read_le_args(&mut Cursor::new(b"\x05"), binrw::args! { kind: 0 }).unwrap(),
+        Test::Zero(5)
+    );
+    assert_eq!(
+        Test::read_le_args(&mut Cursor::new(b"\x05"), binrw::args! { kind: 9 }).unwrap(),
+        Test::Unknown { kind: 9, data: 5 }
+    );
+}
+
+#[test]
+fn enum_selector() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(
+        little,
+        import(kind: u8),
+        selector = kind,
+        match(0..=9 => Small, 10 | 20 => Big, k if k % 2 == 1 => Odd)
+    )]
+    enum Test {
+        Small(u8),
+        Big(u16),
+        Odd,
+        #[br(default)]
+        Other {
+            a: u8,
+        },
+    }
+
+    let data = b"\x01\x02";
+    assert_eq!(
+        Test::read_args(&mut Cursor::new(data), (5,)).unwrap(),
+        Test::Small(1)
+    );
+    assert_eq!(
+        Test::read_args(&mut Cursor::new(data), (20,)).unwrap(),
+        Test::Big(0x201)
+    );
+    assert_eq!(
+        Test::read_args(&mut Cursor::new(data), (11,)).unwrap(),
+        Test::Odd
+    );
+    assert_eq!(
+        Test::read_args(&mut Cursor::new(data), (12,)).unwrap(),
+        Test::Other { a: 1 }
+    );
+}
+
+#[test]
+fn enum_selector_read() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(stream = s, selector = <u8 as BinRead>::read_le(&mut *s)?, match(0 => A, 1 => B))]
+    enum Test {
+        A(u8),
+        B(u16),
+    }
+
+    assert_eq!(
+        Test::read_be(&mut Cursor::new(b"\x01\x02\x03")).unwrap(),
+        Test::B(0x203)
+    );
+    let mut data = Cursor::new(b"\x02\x02\x03");
+    Test::read_be(&mut data).expect_err("accepted bad selector");
+    assert_eq!(data.stream_position().unwrap(), 0);
+}
+
+#[test]
+fn enum_shared_magic() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little)]
+    enum Test {
+        #[br(magic = b"AB")]
+        Ab(u8),
+        #[br(magic = b"CD")]
+        Cd { a: u16 },
+        #[br(magic = b"EF")]
+        Ef,
+        #[br(default)]
+        Unknown([u8; 3]),
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"CD\x01\x02")).unwrap(),
+        Test::Cd { a: 0x201 }
+    );
+    let mut data = Cursor::new(b"EF\x01");
+    assert_eq!(Test::read(&mut data).unwrap(), Test::Ef);
+    assert_eq!(data.stream_position().unwrap(), 2);
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"XYZ")).unwrap(),
+        Test::Unknown(*b"XYZ")
+    );
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"ABC")).unwrap(),
+        Test::Ab(b'C')
+    );
+    // a matching variant which fails to parse falls through to the fallback
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"CD\x01")).unwrap(),
+        Test::Unknown(*b"CD\x01")
+    );
+
+    let mut data = Cursor::new(b"C");
+    match Test::read(&mut data).expect_err("accepted bad data") {
+        binrw::Error::Io(..) => {}
+        error => panic!("expected Io; got {error:?}"),
+    }
+    assert_eq!(data.stream_position().unwrap(), 0);
+}
+
 #[test]
 fn enum_magic_holey() {
     #[derive(BinRead, Debug, PartialEq)]
@@ -317,6 +518,40 @@ fn enum_return_unexpected_error() {
     assert!(matches!(error, binrw::Error::NoVariantMatch { .. }));
 }
 
+#[test]
+fn enum_keyed_repr() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little, repr = u16)]
+    #[repr(u16)]
+    enum Test {
+        #[br(assert(a != 0))]
+        One {
+            a: u8,
+        } = 0x100,
+        Two(u8),
+        Three,
+    }
+
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\0\x01\x02")).unwrap(),
+        Test::One { a: 2 }
+    );
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x01\x01\x03")).unwrap(),
+        Test::Two(3)
+    );
+    assert_eq!(
+        Test::read(&mut Cursor::new(b"\x02\x01")).unwrap(),
+        Test::Three
+    );
+    let mut data = Cursor::new(b"\0\x01\0");
+    Test::read(&mut data).expect_err("accepted failed assertion");
+    assert_eq!(data.stream_position().unwrap(), 0);
+    let mut data = Cursor::new(b"\x03\x01");
+    Test::read(&mut data).expect_err("accepted bad discriminant");
+    assert_eq!(data.stream_position().unwrap(), 0);
+}
+
 #[test]
 fn mixed_enum() {
     #[derive(BinRead, Debug, Eq, PartialEq)]
@@ -1,10 +1,14 @@
+mod assert_layout;
 mod binwrite_temp;
+mod borrow;
+mod checksum;
 mod r#enum;
 mod fn_helper;
 mod map_args;
 mod r#struct;
 mod struct_generic;
 mod struct_map;
+mod union;
 mod unit_enum;
 mod unit_struct;
 mod write;
@@ -122,6 +122,38 @@ fn assert_formatted() {
     }
 }
 
+#[test]
+fn assert_closure() {
+    #[derive(Debug)]
+    struct Oops(u8, u64);
+    impl core::fmt::Display for Oops {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "oops {} at {}", self.0, self.1)
+        }
+    }
+
+    #[allow(dead_code)]
+    #[derive(BinRead, Debug)]
+    #[br(assert(self.a < self.b, |test, _| Oops(test.b, 0)))]
+    struct Test {
+        #[br(assert(a != 0, |a, pos| Oops(*a, pos)))]
+        a: u8,
+        b: u8,
+    }
+
+    Test::read_le(&mut Cursor::new("\x01\x02")).unwrap();
+
+    let mut data = Cursor::new("\x7f\0\x05");
+    data.set_position(1);
+    let error = Test::read_le(&mut data).expect_err("accepted bad data");
+    let error = error.custom_err::<Oops>().expect("bad error type");
+    assert_eq!((error.0, error.1), (0, 1));
+
+    let error = Test::read_le(&mut Cursor::new("\x02\x01")).expect_err("accepted bad data");
+    let error = error.custom_err::<Oops>().expect("bad error type");
+    assert_eq!((error.0, error.1), (1, 0));
+}
+
 #[test]
 fn calc_temp_field() {
     #[binread]
@@ -170,6 +202,29 @@ fn count_too_big() {
     }
 }
 
+#[test]
+fn count_max_count() {
+    #[derive(BinRead, Debug)]
+    #[br(little)]
+    struct Test {
+        _len: u32,
+        #[br(count = _len, max_count = 2)]
+        items: Vec<u16>,
+    }
+
+    let test = Test::read(&mut Cursor::new(b"\x02\0\0\0\x01\0\x02\0")).unwrap();
+    assert_eq!(test.items, [1, 2]);
+
+    let error = Test::read(&mut Cursor::new(b"\xff\xff\xff\xff")).expect_err("accepted bad count");
+    match error {
+        binrw::Error::AssertFail { pos, message } => {
+            assert_eq!(pos, 4);
+            assert_eq!(message, "count 4294967295 of `items` exceeds max_count 2");
+        }
+        _ => panic!("bad error type"),
+    }
+}
+
 #[test]
 fn count_no_useless_conversion_lint() {
     const LEN: usize = 1;
@@ -185,6 +240,55 @@ fn count_no_useless_conversion_lint() {
     );
 }
 
+#[test]
+fn count_byte_vec_single_read() {
+    struct ReadCounter {
+        inner: Cursor<Vec<u8>>,
+        reads: usize,
+    }
+
+    impl binrw::io::Read for ReadCounter {
+        fn read(&mut self, buf: &mut [u8]) -> binrw::io::Result<usize> {
+            self.reads += 1;
+            self.inner.read(buf)
+        }
+    }
+
+    impl Seek for ReadCounter {
+        fn seek(&mut self, pos: SeekFrom) -> binrw::io::Result<u64> {
+            self.inner.seek(pos)
+        }
+    }
+
+    #[derive(BinRead, Debug)]
+    #[br(little)]
+    struct Test {
+        _len: u16,
+        #[br(count = _len)]
+        data: Vec<u8>,
+        #[br(count = _len)]
+        data_path: std::vec::Vec<u8>,
+    }
+
+    let mut bytes = 1000u16.to_le_bytes().to_vec();
+    bytes.extend((0..2000).map(|i| i as u8));
+    let mut reader = ReadCounter {
+        inner: Cursor::new(bytes),
+        reads: 0,
+    };
+    let test = Test::read(&mut reader).unwrap();
+    assert_eq!(test.data.len(), 1000);
+    assert_eq!(test.data[999], 231);
+    assert_eq!(test.data_path[0], 232);
+    assert_eq!(reader.reads, 3);
+
+    let mut reader = ReadCounter {
+        inner: Cursor::new(b"\x02\0\x01".to_vec()),
+        reads: 0,
+    };
+    assert!(Test::read(&mut reader).unwrap_err().is_eof());
+}
+
 #[test]
 fn deref_now() {
     #[derive(BinRead, Debug, PartialEq)]
@@ -504,6 +608,64 @@ fn ignore_and_default() {
     );
 }
 
+#[test]
+fn ignore_and_default_with_value() {
+    #[derive(Debug, Eq, PartialEq)]
+    struct NoDefault(u8);
+
+    #[binrw::binread]
+    #[derive(Debug, PartialEq)]
+    #[br(big, import(offset: u8))]
+    struct Test {
+        a: u8,
+        #[br(default = NoDefault(a + offset))]
+        b: NoDefault,
+        #[br(ignore(NoDefault(a * 2)))]
+        c: NoDefault,
+        #[br(temp, default = a + 1)]
+        d: u8,
+        #[br(calc(d))]
+        e: u8,
+    }
+
+    let result = Test::read_args(&mut Cursor::new(b"\x02"), (3,)).unwrap();
+    assert_eq!(
+        result,
+        Test {
+            a: 2,
+            b: NoDefault(5),
+            c: NoDefault(4),
+            e: 3,
+        }
+    );
+}
+
+#[test]
+fn lookahead() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Chunk {
+        size: u8,
+        #[br(count = count, pad_after = 1)]
+        records: Vec<u16>,
+        #[br(lookahead = SeekFrom::Start(u64::from(size)))]
+        count: u8,
+        tail: u8,
+    }
+
+    let mut data = Cursor::new(b"\x05\0\x01\0\x02\x02\xff");
+    assert_eq!(
+        Chunk::read(&mut data).unwrap(),
+        Chunk {
+            size: 5,
+            records: vec![1, 2],
+            count: 2,
+            tail: 0xff,
+        }
+    );
+    assert_eq!(data.stream_position().unwrap(), 7);
+}
+
 #[test]
 fn magic_byte() {
     #[derive(BinRead, Debug)]
@@ -540,6 +702,41 @@ fn magic_const() {
     assert_eq!(Test::MAGIC, b'a');
 }
 
+#[test]
+fn magic_expr() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import(kind: u16), magic = kind)]
+    struct Child {
+        a: u8,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        index: u16,
+        #[br(args(index + 1))]
+        child: Child,
+        #[br(magic = [index as u8; 2])]
+        b: u8,
+    }
+
+    let result = Test::read(&mut Cursor::new(b"\0\x01\0\x02\x03\x01\x01\x04")).unwrap();
+    assert_eq!(
+        result,
+        Test {
+            index: 1,
+            child: Child { a: 3 },
+            b: 4
+        }
+    );
+
+    let error = Test::read(&mut Cursor::new(b"\0\x01\0\x01\x03\x01\x01\x04"))
+        .expect_err("accepted bad data");
+    assert!(
+        matches!(error, binrw::Error::Backtrace(bt) if matches!(*bt.error, binrw::Error::BadMagic { pos: 2, .. }))
+    );
+}
+
 #[test]
 fn map_stream() {
     use binrw::io::TakeSeekExt;
@@ -705,6 +902,58 @@ fn parse_with_default_args() {
     );
 }
 
+#[test]
+fn args_iter() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(import(size: u8))]
+    struct Entry(#[br(count = size)] Vec<u8>);
+
+    #[allow(dead_code)]
+    #[derive(BinRead, Debug)]
+    #[br(big)]
+    struct Table {
+        len: u8,
+        #[br(count = len)]
+        sizes: Vec<u8>,
+        #[br(args_iter = sizes.iter().map(|&size| (size,)))]
+        entries: Vec<Entry>,
+        #[br(args_iter = (0..len).map(|index| -> <Vec<u8> as BinRead>::Args<'_> {
+            args! { count: usize::from(index) }
+        }))]
+        triangle: Vec<Vec<u8>>,
+    }
+
+    let table = Table::read(&mut Cursor::new(b"\x02\x01\x02\x03\x04\x05\x06")).unwrap();
+    assert_eq!(table.entries, [Entry(vec![3]), Entry(vec![4, 5])]);
+    assert_eq!(table.triangle, [vec![], vec![6]]);
+}
+
+#[test]
+fn switch() {
+    #[binrw::parser(reader, endian)]
+    fn parse_short() -> BinResult<u32> {
+        u16::read_options(reader, endian, ()).map(u32::from)
+    }
+
+    #[binrw::parser(reader)]
+    fn parse_byte() -> BinResult<u32> {
+        u8::read(reader).map(u32::from)
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        mode: u8,
+        #[br(switch(mode, 0 => parse_short, 1 | 2 => u32::read_options, _ => parse_byte))]
+        value: u32,
+    }
+
+    let read = |data: &[u8]| Test::read(&mut Cursor::new(data)).unwrap().value;
+    assert_eq!(read(b"\0\x01\x02"), 0x102);
+    assert_eq!(read(b"\x01\0\0\x01\x02"), 0x102);
+    assert_eq!(read(b"\x03\x04"), 4);
+}
+
 #[test]
 fn args_type_hint_borrowck() {
     #[derive(BinRead, Debug, PartialEq)]
@@ -10,3 +10,50 @@ fn derive_generic() {
     let result = Test::<u8>::read_le(&mut Cursor::new(b"\0\x01\x02")).unwrap();
     assert_eq!(result.a, [0, 1, 2]);
 }
+
+#[test]
+fn derive_generic_bound() {
+    #[derive(BinRead)]
+    #[br(bound = "T: for<'a> BinRead<Args<'a> = ()>")]
+    struct Test<T> {
+        a: [T; 2],
+    }
+
+    let result = Test::<u16>::read_be(&mut Cursor::new(b"\0\x01\0\x02")).unwrap();
+    assert_eq!(result.a, [1, 2]);
+}
+
+#[test]
+fn derive_phantom_data() {
+    struct Marker;
+
+    #[derive(BinRead)]
+    struct Test<T> {
+        a: u8,
+        marker: core::marker::PhantomData<T>,
+        b: u8,
+    }
+
+    let result = Test::<Marker>::read_le(&mut Cursor::new(b"\x01\x02")).unwrap();
+    assert_eq!((result.a, result.b), (1, 2));
+}
+
+#[test]
+fn derive_const_generic() {
+    #[derive(BinRead)]
+    #[br(import { key: [u8; N] })]
+    struct Test<const N: usize> {
+        #[br(count = N, map = |data: Vec<u8>| data.iter().zip(key).map(|(a, b)| a ^ b).collect())]
+        a: Vec<u8>,
+        #[br(pad_size_to = N * 2)]
+        b: u8,
+    }
+
+    let result = Test::<2>::read_le_args(
+        &mut Cursor::new(b"\x03\x03\x01\0\0\0\x02"),
+        binrw::args! { key: [1, 2] },
+    )
+    .unwrap();
+    assert_eq!(result.a, [2, 1]);
+    assert_eq!(result.b, 1);
+}
@@ -104,6 +104,40 @@ fn map_repr_struct() {
     assert_eq!(result.a, 1);
 }
 
+#[test]
+fn map_repr_struct_mirror() {
+    #[derive(BinRead)]
+    #[br(big, import(version: u8))]
+    struct RawHeader {
+        #[br(assert(len <= 0x100 || version > 1))]
+        len: u32,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(repr = RawHeader)]
+    struct Header {
+        len: u16,
+    }
+
+    impl TryFrom<RawHeader> for Header {
+        type Error = &'static str;
+
+        fn try_from(raw: RawHeader) -> Result<Self, Self::Error> {
+            u16::try_from(raw.len)
+                .map(|len| Self { len })
+                .map_err(|_| "length too large")
+        }
+    }
+
+    let result = Header::read_be_args(&mut Cursor::new(b"\0\0\x01\0"), (2,)).unwrap();
+    assert_eq!(result, Header { len: 0x100 });
+    Header::read_be_args(&mut Cursor::new(b"\0\0\x01\x01"), (1,))
+        .expect_err("accepted bad raw value");
+    let mut data = Cursor::new(b"\0\x01\0\0");
+    Header::read_be_args(&mut data, (2,)).expect_err("accepted bad conversion");
+    assert_eq!(data.position(), 0);
+}
+
 #[test]
 fn map_repr_struct_field() {
     #[derive(BinRead, Debug)]
@@ -0,0 +1,42 @@
+use binrw::{io::Cursor, BinRead};
+
+#[test]
+fn union_tag() {
+    #[derive(BinRead)]
+    #[br(little, import(kind: u8), tag = kind)]
+    union Test {
+        #[br(tag = 1)]
+        a: u32,
+        #[br(tag = 2)]
+        b: [u8; 4],
+        #[br(tag = 3, big)]
+        c: u16,
+    }
+
+    let data = b"\x01\x02\x03\x04";
+    let test = Test::read_args(&mut Cursor::new(data), (1,)).unwrap();
+    assert_eq!(unsafe { test.a }, 0x4030201);
+    let test = Test::read_args(&mut Cursor::new(data), (2,)).unwrap();
+    assert_eq!(unsafe { test.b }, *data);
+    let test = Test::read_args(&mut Cursor::new(data), (3,)).unwrap();
+    assert_eq!(unsafe { test.c }, 0x102);
+    assert!(Test::read_args(&mut Cursor::new(data), (4,)).is_err());
+}
+
+#[test]
+fn union_selector() {
+    #[derive(BinRead)]
+    #[br(big, magic = b"U", stream = s, selector = <u8>::read(s)?, match(0 => a, 1 | 2 => b))]
+    union Test {
+        a: u8,
+        b: u16,
+    }
+
+    let test = Test::read(&mut Cursor::new(b"U\0\x12")).unwrap();
+    assert_eq!(unsafe { test.a }, 0x12);
+    let test = Test::read(&mut Cursor::new(b"U\x02\x12\x34")).unwrap();
+    assert_eq!(unsafe { test.b }, 0x1234);
+    let mut data = Cursor::new(b"U\x03\x12\x34");
+    assert!(Test::read(&mut data).is_err());
+    assert_eq!(data.position(), 0);
+}
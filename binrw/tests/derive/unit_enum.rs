@@ -82,6 +82,35 @@ fn unit_enum_magic_bytes() {
     assert_eq!(Test::read(&mut Cursor::new(b"two0")).unwrap(), Test::Two);
 }
 
+#[test]
+fn unit_enum_magic_expr() {
+    #[derive(BinRead, Debug, Eq, PartialEq)]
+    #[br(big, import(base: u16))]
+    enum Test {
+        #[br(magic = base)]
+        Base,
+        #[br(magic = base + 1)]
+        Next,
+        #[br(magic(b"\xff\xff"))]
+        Max,
+    }
+
+    assert_eq!(
+        Test::read_args(&mut Cursor::new(b"\0\x05"), (5,)).unwrap(),
+        Test::Base
+    );
+    assert_eq!(
+        Test::read_args(&mut Cursor::new(b"\0\x06"), (5,)).unwrap(),
+        Test::Next
+    );
+    assert_eq!(
+        Test::read_args(&mut Cursor::new(b"\xff\xff"), (5,)).unwrap(),
+        Test::Max
+    );
+    let error = Test::read_args(&mut Cursor::new(b"\0\x05"), (6,)).expect_err("accepted bad data");
+    assert!(matches!(error, binrw::Error::NoVariantMatch { .. }));
+}
+
 #[test]
 fn unit_enum_magic_pre_assert() {
     #[derive(BinRead, Debug, Eq, PartialEq)]
@@ -188,6 +217,69 @@ fn unit_enum_repr() {
     assert_eq!(Test::read(&mut Cursor::new(b"\0\x02")).unwrap(), Test::Two);
 }
 
+#[test]
+fn unit_enum_repr_fallback() {
+    #[derive(BinRead, Debug, Eq, PartialEq)]
+    #[br(repr(u8))]
+    enum Test {
+        A = 1,
+        #[br(default)]
+        Unknown = 0xff,
+        B = 2,
+    }
+
+    assert_eq!(Test::read_le(&mut Cursor::new(b"\x01")).unwrap(), Test::A);
+    assert_eq!(Test::read_le(&mut Cursor::new(b"\x02")).unwrap(), Test::B);
+    assert_eq!(
+        Test::read_le(&mut Cursor::new(b"\xff")).unwrap(),
+        Test::Unknown
+    );
+    let mut data = Cursor::new(b"\x03");
+    assert_eq!(Test::read_le(&mut data).unwrap(), Test::Unknown);
+    assert_eq!(data.stream_position().unwrap(), 1);
+}
+
+#[test]
+fn unit_enum_repr_unknown() {
+    #[derive(BinRead, Debug, Eq, PartialEq)]
+    #[br(big, repr(u16))]
+    #[repr(u16)]
+    enum Test {
+        A,
+        B = 5,
+        C,
+        #[br(default)]
+        Unknown(u16),
+    }
+
+    assert_eq!(Test::read(&mut Cursor::new(b"\0\0")).unwrap(), Test::A);
+    assert_eq!(Test::read(&mut Cursor::new(b"\0\x05")).unwrap(), Test::B);
+    assert_eq!(Test::read(&mut Cursor::new(b"\0\x06")).unwrap(), Test::C);
+    let mut data = Cursor::new(b"\x01\x02");
+    assert_eq!(Test::read(&mut data).unwrap(), Test::Unknown(0x102));
+    assert_eq!(data.stream_position().unwrap(), 2);
+    Test::read(&mut Cursor::new(b"\x01")).expect_err("accepted bad data");
+}
+
+#[test]
+fn unit_enum_magic_fallback() {
+    #[derive(BinRead, Debug, Eq, PartialEq)]
+    enum Test {
+        #[br(default)]
+        Unknown,
+        #[br(magic(b"AB"))]
+        Ab,
+        #[br(magic(1u8))]
+        One,
+    }
+
+    assert_eq!(Test::read_le(&mut Cursor::new(b"AB")).unwrap(), Test::Ab);
+    assert_eq!(Test::read_le(&mut Cursor::new(b"\x01")).unwrap(), Test::One);
+    let mut data = Cursor::new(b"\x02");
+    assert_eq!(Test::read_le(&mut data).unwrap(), Test::Unknown);
+    assert_eq!(data.stream_position().unwrap(), 0);
+}
+
 #[test]
 fn unit_enum_rewind_on_eof() {
     #[derive(BinRead, Debug)]
@@ -28,3 +28,44 @@ fn pass_args() {
 
     assert_eq!(x.into_inner(), b"\0\0\0\x01\x02");
 }
+
+#[test]
+fn pass_args_default_and_raw() {
+    #[binwrite]
+    #[bw(import { x: u8, y: u8 = 7 })]
+    struct TestInner {
+        #[bw(calc = x)]
+        x_copy: u8,
+
+        #[bw(calc = y)]
+        y_copy: u8,
+    }
+
+    #[derive(BinWrite)]
+    #[bw(big)]
+    struct Test {
+        #[bw(args { x: 1 })]
+        defaulted: TestInner,
+        #[bw(args { x: 1, y: 2 })]
+        explicit: TestInner,
+        #[bw(args_raw = TestInnerBinWriteArgs::builder().x(3).finalize())]
+        raw: TestInner,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test {
+        defaulted: TestInner {},
+        explicit: TestInner {},
+        raw: TestInner {},
+    }
+    .write(&mut x)
+    .unwrap();
+    TestInner {}
+        .write_be_args(
+            &mut x,
+            TestInnerBinWriteArgs::builder().x(4).y(5).finalize(),
+        )
+        .unwrap();
+
+    assert_eq!(x.into_inner(), [1, 7, 1, 2, 3, 7, 4, 5]);
+}
@@ -16,6 +16,32 @@ fn assert_fail() {
     }
 }
 
+#[test]
+fn assert_closure() {
+    #[derive(Debug)]
+    struct TooBig(u32);
+    impl core::fmt::Display for TooBig {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "{} is too big", self.0)
+        }
+    }
+
+    #[binwrite]
+    #[bw(assert(self.x != self.y, |test, _| TooBig(test.y)))]
+    struct Test {
+        #[bw(assert(*x < 10, |x, _| TooBig(*x)))]
+        x: u32,
+        y: u32,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    let err = x.write_be(&Test { x: 10, y: 0 }).unwrap_err();
+    assert_eq!(err.custom_err::<TooBig>().map(|err| err.0), Some(10));
+
+    let err = x.write_be(&Test { x: 1, y: 1 }).unwrap_err();
+    assert_eq!(err.custom_err::<TooBig>().map(|err| err.0), Some(1));
+}
+
 #[test]
 fn top_level_assert_fail() {
     #[binwrite]
@@ -80,3 +106,60 @@ fn top_level_assert_self_struct() {
         panic!("Assert error expected");
     }
 }
+
+#[test]
+fn assert_before_write() {
+    #[derive(Debug, PartialEq)]
+    struct TooManyEntries(usize);
+
+    impl core::fmt::Display for TooManyEntries {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            write!(f, "too many entries: {}", self.0)
+        }
+    }
+
+    #[binwrite]
+    #[bw(big, magic = b"TEST")]
+    struct Test {
+        header: u32,
+        #[bw(assert(entries.len() <= usize::from(*max), TooManyEntries(entries.len())))]
+        entries: Vec<u8>,
+        max: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    let err = x
+        .write_be(&Test {
+            header: 1,
+            entries: vec![1, 2, 3],
+            max: 2,
+        })
+        .unwrap_err();
+    assert!(matches!(err, binrw::Error::Custom { pos: 0, .. }));
+    assert_eq!(err.custom_err::<TooManyEntries>(), Some(&TooManyEntries(3)));
+    assert!(x.into_inner().is_empty());
+}
+
+#[test]
+fn assert_calc_field() {
+    #[binwrite]
+    #[bw(big)]
+    struct Test {
+        header: u8,
+        #[bw(calc = data.len() as u8)]
+        len: u8,
+        // Checked once `len` has been calculated
+        #[bw(assert(len < 2, "too long"))]
+        data: Vec<u8>,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    let err = x
+        .write_be(&Test {
+            header: 1,
+            data: vec![1, 2],
+        })
+        .unwrap_err();
+    assert!(matches!(err, binrw::Error::AssertFail { .. }));
+    assert_eq!(x.into_inner(), [1, 2]);
+}
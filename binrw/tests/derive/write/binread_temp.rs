@@ -1,4 +1,4 @@
-use binrw::{binrw, io::Cursor, BinWrite, Endian};
+use binrw::{binrw, io::Cursor, BinRead, BinWrite, Endian};
 
 #[test]
 fn binread_temp_applies() {
@@ -27,3 +27,77 @@ fn binread_temp_applies() {
 
     assert_eq!(x.into_inner(), [0, 0, 0, 1]);
 }
+
+#[test]
+fn binread_temp_count() {
+    #[binrw]
+    #[brw(big)]
+    #[derive(Debug, PartialEq)]
+    struct Test {
+        #[br(temp)]
+        len: u8,
+        #[br(count = len)]
+        items: Vec<u16>,
+        #[br(temp)]
+        len2: u32,
+        #[br(count = len2 as usize)]
+        items2: Vec<u8>,
+    }
+
+    let value = Test {
+        items: vec![1, 2],
+        items2: vec![3],
+    };
+
+    let mut x = Cursor::new(Vec::new());
+    value.write(&mut x).unwrap();
+    assert_eq!(x.get_ref(), &[2, 0, 1, 0, 2, 0, 0, 0, 1, 3]);
+
+    x.set_position(0);
+    assert_eq!(Test::read(&mut x).unwrap(), value);
+}
+
+#[test]
+fn binread_temp_count_overflow() {
+    #[binrw]
+    #[brw(big)]
+    struct Test {
+        #[br(temp)]
+        len: u8,
+        #[br(count = len)]
+        items: Vec<u8>,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    let error = Test {
+        items: vec![0; 256],
+    }
+    .write(&mut x)
+    .unwrap_err();
+    assert!(matches!(error, binrw::Error::Custom { pos: 0, .. }));
+}
+
+#[test]
+fn brw_temp_value() {
+    #[binrw]
+    #[brw(big)]
+    #[derive(Debug, PartialEq)]
+    struct Test {
+        #[brw(temp = u8::try_from(items.len()).unwrap())]
+        len: u8,
+        #[br(count = len)]
+        items: Vec<u8>,
+        #[brw(temp = 0xff)]
+        #[br(assert(end == 0xff))]
+        end: u8,
+    }
+
+    let value = Test { items: vec![1, 2] };
+
+    let mut x = Cursor::new(Vec::new());
+    value.write(&mut x).unwrap();
+    assert_eq!(x.get_ref(), &[2, 1, 2, 0xff]);
+
+    x.set_position(0);
+    assert_eq!(Test::read(&mut x).unwrap(), value);
+}
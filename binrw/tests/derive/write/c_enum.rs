@@ -46,6 +46,28 @@ fn round_trip_unit_enum() {
     assert_eq!(x.into_inner(), data);
 }
 
+#[test]
+fn round_trip_unknown_repr_enum() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(repr(u16))]
+    enum Test {
+        A,
+        B,
+        #[brw(default)]
+        Unknown(u16),
+    }
+
+    let data = &[0, 0, 0, 1, 0x12, 0x34];
+    let test: [Test; 3] = Cursor::new(data).read_be().unwrap();
+    assert_eq!(test, [Test::A, Test::B, Test::Unknown(0x1234)]);
+
+    let mut x = Cursor::new(Vec::new());
+
+    test.write_options(&mut x, Endian::Big, ()).unwrap();
+
+    assert_eq!(x.into_inner(), data);
+}
+
 #[test]
 fn magic_enum_round_trip() {
     #[derive(BinRead, BinWrite)]
@@ -72,3 +94,28 @@ fn magic_enum_round_trip() {
 
     assert_eq!(x.into_inner(), data);
 }
+
+#[test]
+fn checked_repr() {
+    #[derive(BinWrite)]
+    #[bw(repr(u8), checked_repr)]
+    #[repr(u16)]
+    enum Test {
+        A = 1,
+        B = 0x100,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test::A.write_options(&mut x, Endian::Big, ()).unwrap();
+    assert_eq!(x.get_ref(), &[1]);
+
+    let error = Test::B.write_options(&mut x, Endian::Big, ()).unwrap_err();
+    match error {
+        binrw::Error::AssertFail { pos, message } => {
+            assert_eq!(pos, 1);
+            assert_eq!(message, "discriminant of `Test::B` does not fit in `u8`");
+        }
+        error => panic!("unexpected error: {error:?}"),
+    }
+    assert_eq!(x.into_inner(), [1]);
+}
@@ -45,6 +45,37 @@ fn calc_visibility() {
     assert_eq!(x.into_inner(), [1, 0, 2, 0, 3]);
 }
 
+#[test]
+fn calc_forward_reference() {
+    #[binwrite]
+    struct Test {
+        // `body_len` and `checksum` are declared later but can still be used
+        #[bw(calc = body_len + 1)]
+        len: u16,
+
+        #[bw(calc = checksum.wrapping_add(1))]
+        check: u8,
+
+        #[bw(calc = body.len() as u16)]
+        body_len: u16,
+
+        body: Vec<u8>,
+
+        #[bw(calc = body.iter().fold(0, |sum, b| sum ^ b))]
+        checksum: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+
+    Test {
+        body: vec![1, 2, 4],
+    }
+    .write_options(&mut x, Endian::Big, ())
+    .unwrap();
+
+    assert_eq!(x.into_inner(), [0, 4, 8, 0, 3, 1, 2, 4, 7]);
+}
+
 #[test]
 fn try_calc() {
     #[binwrite]
@@ -24,3 +24,50 @@ fn custom_writer() {
 
     assert_eq!(x.into_inner(), b"\x01abcd");
 }
+
+#[test]
+fn custom_writer_closure() {
+    #[derive(BinWrite)]
+    struct Test {
+        x: u8,
+
+        #[bw(write_with = |y, writer, _, ()| {
+            writer.write_all(&[*x + 1])?;
+            writer.write_all(&y.to_le_bytes())?;
+            Ok(())
+        })]
+        y: u16,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+
+    Test { x: 1, y: 2 }
+        .write_options(&mut x, Endian::Big, ())
+        .unwrap();
+
+    assert_eq!(x.into_inner(), b"\x01\x02\x02\0");
+}
+
+#[test]
+fn custom_writer_non_copy_closure() {
+    #[derive(BinWrite)]
+    struct Test {
+        #[bw(write_with = {
+            let prefix = b"abc".to_vec();
+            move |y: &u8, writer, _, ()| {
+                writer.write_all(&prefix)?;
+                writer.write_all(&[*y])?;
+                Ok(())
+            }
+        })]
+        y: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+
+    Test { y: 1 }
+        .write_options(&mut x, Endian::Big, ())
+        .unwrap();
+
+    assert_eq!(x.into_inner(), b"abc\x01");
+}
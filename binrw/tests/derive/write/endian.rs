@@ -65,3 +65,52 @@ fn top_level_endian() {
 
     assert_eq!(x.into_inner(), [1, 0, 2, 0, 0, 0, 0, 3, 0, 0, 0, 4]);
 }
+
+#[test]
+fn own_field_endian() {
+    #[derive(BinWrite)]
+    struct Flags {
+        le_flag: u8,
+    }
+
+    #[derive(BinWrite)]
+    #[bw(is_little = self.flags.le_flag == 1)]
+    struct Container {
+        flags: Flags,
+        len: u16,
+        #[bw(is_big = flags.le_flag == 1)]
+        magic: u16,
+    }
+
+    #[derive(BinWrite)]
+    enum Record {
+        #[bw(is_little = *le == 1)]
+        Data { le: u8, value: u32 },
+    }
+
+    let mut x = Cursor::new(Vec::new());
+
+    for le_flag in [0, 1] {
+        Container {
+            flags: Flags { le_flag },
+            len: 2,
+            magic: 3,
+        }
+        .write_be(&mut x)
+        .unwrap();
+        Record::Data {
+            le: le_flag,
+            value: 4,
+        }
+        .write_be(&mut x)
+        .unwrap();
+    }
+
+    assert_eq!(
+        x.into_inner(),
+        [
+            0, 0, 2, 3, 0, 0, 0, 0, 0, 4, //
+            1, 2, 0, 0, 3, 1, 4, 0, 0, 0,
+        ]
+    );
+}
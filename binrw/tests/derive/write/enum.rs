@@ -71,3 +71,30 @@ fn enum_one_way() {
         b"BBB\xBB\xAA\0\0\0\x02CCCAAA\x03\x02\x01\0\xFF"
     );
 }
+
+#[test]
+fn keyed_repr_enum_round_trip() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(big, repr = u8)]
+    #[repr(u8)]
+    enum Test {
+        A(u16) = 1,
+        B {
+            x: u8,
+        } = 5,
+        C,
+        #[brw(default)]
+        Unknown(u8),
+    }
+
+    let data = b"\x01\x02\x03\x05\x04\x06\x09";
+    let test: [Test; 4] = Cursor::new(data).read_be().unwrap();
+    assert_eq!(
+        test,
+        [Test::A(0x203), Test::B { x: 4 }, Test::C, Test::Unknown(9)]
+    );
+
+    let mut x = Cursor::new(Vec::new());
+    test.write_options(&mut x, Endian::Big, ()).unwrap();
+    assert_eq!(x.into_inner(), data);
+}
@@ -0,0 +1,60 @@
+use binrw::{
+    binwrite,
+    io::{Cursor, Seek, Write},
+    BinResult, BinWrite, Endian,
+};
+
+#[test]
+fn pre_post_write() {
+    #[binwrite]
+    #[bw(big, pre_write = Self::header, post_write = |_, writer, endian| {
+        let len = writer.stream_position()? as u16;
+        len.write_options(writer, endian, ())
+    })]
+    struct Test {
+        a: u16,
+    }
+
+    impl Test {
+        fn header<W: Write + Seek>(&self, writer: &mut W, _: Endian) -> BinResult<()> {
+            writer.write_all(&[self.a as u8])?;
+            Ok(())
+        }
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test { a: 0x102 }.write(&mut x).unwrap();
+    assert_eq!(x.into_inner(), [2, 1, 2, 0, 3]);
+}
+
+#[test]
+fn pre_write_error() {
+    #[binwrite]
+    #[bw(little, pre_write = |this: &Self, _, _| if this.0 == 0 {
+        Err(binrw::Error::AssertFail { pos: 0, message: "zero".into() })
+    } else {
+        Ok(())
+    })]
+    struct Test(u8);
+
+    let mut x = Cursor::new(Vec::new());
+    assert!(Test(0).write(&mut x).is_err());
+    assert!(x.into_inner().is_empty());
+}
+
+#[test]
+fn variant_hooks() {
+    #[binwrite]
+    #[bw(little, magic = 1u8, post_write = |_, writer, _| Ok(writer.write_all(b"!")?))]
+    enum Test {
+        #[bw(magic = 2u8, pre_write = |_, writer, _| Ok(writer.write_all(b"A")?))]
+        A(u8),
+        #[bw(magic = 3u8)]
+        B(u8),
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test::A(4).write(&mut x).unwrap();
+    Test::B(5).write(&mut x).unwrap();
+    assert_eq!(x.into_inner(), b"\x01A\x02\x04!\x01\x03\x05!");
+}
@@ -35,3 +35,24 @@ fn if_cond() {
 
     assert_eq!(&x.into_inner(), &[3, 0, 4, 0, 0, 0, 5]);
 }
+
+#[test]
+fn if_cond_else_pad() {
+    #[derive(BinWrite)]
+    #[bw(big)]
+    struct Test {
+        x: u8,
+        #[bw(if(*x > 1), else_pad = 4)]
+        y: u32,
+        #[bw(if(*x > 1), else_pad = 2, pad_byte = 0xff)]
+        z: u16,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test { x: 1, y: 2, z: 3 }.write(&mut x).unwrap();
+    assert_eq!(&x.into_inner(), &[1, 0, 0, 0, 0, 0xff, 0xff]);
+
+    let mut x = Cursor::new(Vec::new());
+    Test { x: 2, y: 3, z: 4 }.write(&mut x).unwrap();
+    assert_eq!(&x.into_inner(), &[2, 0, 0, 0, 3, 0, 4]);
+}
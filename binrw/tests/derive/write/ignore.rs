@@ -1,4 +1,4 @@
-use binrw::{binwrite, io::Cursor, BinWrite, Endian};
+use binrw::{binrw, binwrite, io::Cursor, BinRead, BinWrite, Endian};
 
 #[test]
 fn ignore_is_not_written() {
@@ -17,3 +17,36 @@ fn ignore_is_not_written() {
     // Since it's bw(ignore), nothing is written here.
     assert_eq!(x.into_inner(), b"");
 }
+
+#[test]
+fn ignore_with_value_round_trip() {
+    #[derive(Debug, PartialEq)]
+    struct Cache(u32);
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(big)]
+    struct Test {
+        x: u32,
+        #[brw(ignore = Cache(x * 2))]
+        cache: Cache,
+    }
+
+    let mut data = Cursor::new(Vec::new());
+    Test {
+        x: 1,
+        cache: Cache(7),
+    }
+    .write(&mut data)
+    .unwrap();
+    assert_eq!(data.get_ref(), b"\0\0\0\x01");
+
+    data.set_position(0);
+    assert_eq!(
+        Test::read(&mut data).unwrap(),
+        Test {
+            x: 1,
+            cache: Cache(2)
+        }
+    );
+}
@@ -39,3 +39,63 @@ fn magic_one_way() {
 
     assert_eq!(out.into_inner(), data);
 }
+
+#[test]
+fn magic_expr() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(big, import(kind: u16), magic = kind)]
+    struct Child {
+        #[brw(magic = [kind as u8; 2])]
+        a: u8,
+    }
+
+    let data = b"\0\x07\x07\x07\x01";
+
+    let child = Child::read_args(&mut Cursor::new(data), (7,)).unwrap();
+    assert_eq!(child, Child { a: 1 });
+
+    let mut out = Cursor::new(Vec::new());
+    child.write_args(&mut out, (7,)).unwrap();
+
+    assert_eq!(out.into_inner(), data);
+}
+
+#[test]
+fn magic_expr_from_field() {
+    #[derive(BinWrite)]
+    #[bw(big)]
+    enum Chunk {
+        #[bw(magic = *fourcc)]
+        Data {
+            #[bw(ignore)]
+            fourcc: [u8; 4],
+            len: u16,
+        },
+        #[bw(magic = b"END\0")]
+        End,
+    }
+
+    #[derive(BinWrite)]
+    #[bw(big)]
+    struct Record {
+        kind: u8,
+        #[bw(magic = [b'A' + *kind; 2])]
+        value: u16,
+        chunk: Chunk,
+    }
+
+    let mut out = Cursor::new(Vec::new());
+    Record {
+        kind: 1,
+        value: 2,
+        chunk: Chunk::Data {
+            fourcc: *b"fmt ",
+            len: 3,
+        },
+    }
+    .write(&mut out)
+    .unwrap();
+    Chunk::End.write(&mut out).unwrap();
+
+    assert_eq!(out.into_inner(), b"\x01BB\0\x02fmt \0\x03END\0");
+}
@@ -1,4 +1,5 @@
 use binrw::{
+    binwrite,
     io::{Cursor, Seek, SeekFrom, Write},
     BinWrite,
 };
@@ -101,3 +102,85 @@ fn map_stream_write_with_args() {
 
     assert_eq!(out.into_inner(), &[138, 129, 141, 128],);
 }
+
+/// Buffers everything written to it and writes each byte twice on flush.
+struct Doubler<T> {
+    inner: T,
+    buf: Vec<u8>,
+}
+
+impl<T> Doubler<T> {
+    fn new(inner: T) -> Self {
+        Self {
+            inner,
+            buf: Vec::new(),
+        }
+    }
+}
+
+impl<T: Write> Write for Doubler<T> {
+    fn write(&mut self, buf: &[u8]) -> binrw::io::Result<usize> {
+        self.buf.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> binrw::io::Result<()> {
+        for b in self.buf.drain(..) {
+            self.inner.write_all(&[b, b])?;
+        }
+        self.inner.flush()
+    }
+}
+
+impl<T: Seek> Seek for Doubler<T> {
+    fn seek(&mut self, pos: SeekFrom) -> binrw::io::Result<u64> {
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn map_stream_buffered() {
+    #[binwrite]
+    #[bw(big)]
+    struct Test {
+        #[bw(size_of = "a")]
+        len: u8,
+        #[bw(map_stream = Doubler::new)]
+        a: Vec<u8>,
+        b: u8,
+    }
+
+    let mut out = Cursor::new(vec![]);
+    Test {
+        a: vec![1, 2, 3],
+        b: 4,
+    }
+    .write(&mut out)
+    .unwrap();
+
+    assert_eq!(out.into_inner(), &[6, 1, 1, 2, 2, 3, 3, 4]);
+}
+
+#[test]
+fn map_stream_buffered_struct() {
+    #[derive(BinWrite, Debug, PartialEq)]
+    #[bw(big, map_stream = Doubler::new)]
+    struct Inner(u16);
+
+    #[derive(BinWrite, Debug, PartialEq)]
+    #[bw(big)]
+    struct Test {
+        a: Inner,
+        b: u8,
+    }
+
+    let mut out = Cursor::new(vec![]);
+    Test {
+        a: Inner(0x102),
+        b: 3,
+    }
+    .write(&mut out)
+    .unwrap();
+
+    assert_eq!(out.into_inner(), &[1, 1, 2, 2, 3]);
+}
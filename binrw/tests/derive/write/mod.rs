@@ -6,15 +6,20 @@ mod calc;
 mod custom_writer;
 mod endian;
 mod r#enum;
+mod hooks;
 mod if_cond;
 mod ignore;
 mod import;
 mod magic;
 mod map;
 mod map_stream;
+mod offset_of;
 mod padding;
+mod position;
 mod restore_position;
 mod simple;
+mod size_of;
 mod stream;
 mod struct_generic;
 mod top_level_map;
+mod union;
@@ -0,0 +1,80 @@
+use binrw::{binwrite, io::Cursor, BinWrite};
+
+#[test]
+fn offset_of() {
+    #[binwrite]
+    #[bw(big)]
+    struct Test {
+        magic: u8,
+
+        #[bw(offset_of = "data")]
+        data_offset: u16,
+
+        #[bw(align_before = 8)]
+        data: [u8; 2],
+    }
+
+    let mut x = Cursor::new(vec![0xff]);
+    x.set_position(1);
+    Test {
+        magic: 0xaa,
+        data: [1, 2],
+    }
+    .write(&mut x)
+    .unwrap();
+    assert_eq!(x.into_inner(), [0xff, 0xaa, 0, 8, 0, 0, 0, 0, 1, 2]);
+}
+
+#[test]
+fn offset_of_base() {
+    #[binwrite]
+    #[bw(little)]
+    struct Test {
+        header: u8,
+
+        #[bw(offset_of = "header..data")]
+        data_offset: u8,
+
+        #[bw(offset_of = "header..header")]
+        header_offset: u8,
+
+        data: u8,
+    }
+
+    let mut x = Cursor::new(vec![0; 2]);
+    x.set_position(2);
+    Test { header: 1, data: 2 }.write(&mut x).unwrap();
+    assert_eq!(x.into_inner(), [0, 0, 1, 3, 0, 2]);
+}
+
+#[test]
+fn offset_of_conditional() {
+    #[binwrite]
+    #[bw(big)]
+    struct Test {
+        #[bw(offset_of = "a")]
+        a_offset: u8,
+
+        #[bw(offset_of = "b")]
+        b_offset: u8,
+
+        #[bw(if(*has_a))]
+        a: u8,
+
+        #[bw(if(!*has_a))]
+        b: u8,
+
+        #[bw(ignore)]
+        has_a: bool,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test {
+        a: 1,
+        b: 2,
+        has_a: false,
+    }
+    .write(&mut x)
+    .unwrap();
+    assert_eq!(x.into_inner(), [0, 2, 2]);
+}
@@ -73,3 +73,160 @@ fn padding_one_way() {
 
     assert_eq!(x.into_inner(), data);
 }
+
+#[test]
+fn padding_byte() {
+    #[derive(BinWrite)]
+    #[bw(pad_byte = 0xcc)]
+    struct Test {
+        #[bw(pad_before = 2, align_after = 4)]
+        x: u8,
+
+        #[bw(pad_byte = 0xff, align_before = 8, pad_after = 1)]
+        y: u8,
+
+        #[bw(pad_size_to = 3)]
+        z: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+
+    Test { x: 1, y: 2, z: 3 }
+        .write_options(&mut x, Endian::Big, ())
+        .unwrap();
+
+    assert_eq!(
+        x.into_inner(),
+        [0xcc, 0xcc, 1, 0xcc, 0xff, 0xff, 0xff, 0xff, 2, 0xff, 3, 0xcc, 0xcc]
+    );
+}
+
+#[test]
+fn align_relative() {
+    #[derive(BinWrite)]
+    #[bw(align_relative)]
+    struct Record {
+        #[bw(align_after = 4)]
+        x: u8,
+        #[bw(align_relative = "x", align_before = 2)]
+        y: u8,
+        #[bw(align_relative = "y", align_after = 4)]
+        z: u16,
+    }
+
+    #[derive(BinWrite)]
+    struct Test {
+        a: u8,
+        record: Record,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test {
+        a: 0xff,
+        record: Record { x: 1, y: 2, z: 3 },
+    }
+    .write_options(&mut x, Endian::Big, ())
+    .unwrap();
+
+    assert_eq!(x.into_inner(), [0xff, 1, 0, 0, 0, 2, 0, 3, 0]);
+}
+
+#[test]
+fn align_relative_skipped_anchor() {
+    #[derive(BinWrite)]
+    struct Test {
+        #[bw(if(false))]
+        x: u8,
+        #[bw(align_relative = "x", align_before = 4)]
+        y: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    let error = Test { x: 1, y: 2 }
+        .write_options(&mut x, Endian::Big, ())
+        .unwrap_err();
+    assert!(matches!(error, binrw::Error::AssertFail { pos: 0, .. }));
+}
+
+#[test]
+fn align_zero() {
+    #[derive(BinWrite)]
+    struct Test {
+        x: u8,
+        #[bw(align_before = self.align)]
+        y: u8,
+        #[bw(ignore)]
+        align: u64,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    let error = Test {
+        x: 1,
+        y: 2,
+        align: 0,
+    }
+    .write_options(&mut x, Endian::Big, ())
+    .unwrap_err();
+    assert!(matches!(error, binrw::Error::AssertFail { pos: 1, .. }));
+}
+
+#[test]
+fn pad_size_overflow() {
+    #[derive(BinWrite)]
+    #[bw(big)]
+    struct Test {
+        #[bw(pad_size_to = 4)]
+        grow: Vec<u8>,
+        #[bw(pad_size_to = 4, pad_size_overflow = truncate, pad_byte = 0xff)]
+        truncate: Vec<u8>,
+        #[bw(pad_size_to = 4, pad_size_overflow = truncate)]
+        truncate_short: u16,
+        tail: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test {
+        grow: vec![1, 2, 3, 4, 5],
+        truncate: vec![6, 7, 8, 9, 10, 11],
+        truncate_short: 0x0c0d,
+        tail: 0xee,
+    }
+    .write(&mut x)
+    .unwrap();
+
+    assert_eq!(
+        x.into_inner(),
+        [1, 2, 3, 4, 5, 6, 7, 8, 9, 0xc, 0xd, 0, 0, 0xee]
+    );
+}
+
+#[test]
+fn pad_size_overflow_error() {
+    #[derive(BinWrite)]
+    struct Test {
+        a: u8,
+        #[bw(pad_size_to = 2, pad_size_overflow = error)]
+        b: Vec<u8>,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test {
+        a: 0,
+        b: vec![1, 2],
+    }
+    .write_options(&mut x, Endian::Big, ())
+    .unwrap();
+    assert_eq!(x.into_inner(), [0, 1, 2]);
+
+    let mut x = Cursor::new(Vec::new());
+    let error = Test {
+        a: 0,
+        b: vec![1, 2, 3],
+    }
+    .write_options(&mut x, Endian::Big, ())
+    .unwrap_err();
+    assert!(
+        matches!(error, binrw::Error::AssertFail { pos: 1, ref message } if message.contains("`b`")),
+        "{error:?}"
+    );
+}
@@ -0,0 +1,80 @@
+use binrw::{binwrite, io::Cursor, BinWrite};
+
+#[test]
+fn write_position() {
+    #[binwrite]
+    #[bw(big)]
+    struct Record {
+        kind: u8,
+        #[bw(calc = (write_position - struct_position) as u8)]
+        relative: u8,
+        #[bw(calc = write_position as u8)]
+        absolute: u8,
+        #[bw(pad_size_to = 8 - write_position)]
+        name: Vec<u8>,
+        #[bw(assert(write_position == struct_position + 7))]
+        end: u8,
+    }
+
+    let mut x = Cursor::new(vec![0xff]);
+    x.set_position(1);
+    Record {
+        kind: 1,
+        name: vec![2, 3],
+        end: 4,
+    }
+    .write_le(&mut x)
+    .unwrap();
+
+    assert_eq!(x.into_inner(), [0xff, 1, 1, 3, 2, 3, 0, 0, 4]);
+}
+
+#[test]
+fn write_position_assert() {
+    #[derive(BinWrite)]
+    struct Test {
+        a: u8,
+        #[bw(assert(write_position == 1))]
+        b: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test { a: 1, b: 2 }.write_le(&mut x).unwrap();
+    assert_eq!(x.into_inner(), [1, 2]);
+
+    let mut x = Cursor::new(vec![0]);
+    x.set_position(1);
+    let error = Test { a: 1, b: 2 }.write_le(&mut x).unwrap_err();
+    assert!(matches!(error, binrw::Error::AssertFail { .. }));
+}
+
+#[test]
+fn write_position_variant() {
+    #[binwrite]
+    #[bw(big)]
+    enum Test {
+        #[bw(magic = 1u8)]
+        A {
+            #[bw(calc = write_position as u16)]
+            offset: u16,
+        },
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test::A {}.write_le(&mut x).unwrap();
+    assert_eq!(x.into_inner(), [1, 0, 1]);
+}
+
+#[test]
+fn write_position_shadowed() {
+    #[binwrite]
+    struct Test {
+        write_position: u8,
+        #[bw(calc = *write_position)]
+        copy: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test { write_position: 5 }.write_le(&mut x).unwrap();
+    assert_eq!(x.into_inner(), [5, 5]);
+}
@@ -21,3 +21,27 @@ fn restore_position_writing() {
     }
     assert_eq!(x, b"\0\xff\xff\xff");
 }
+
+#[test]
+fn restore_position_placeholder() {
+    #[derive(BinWrite)]
+    #[bw(big)]
+    struct Test {
+        #[bw(restore_position)]
+        reserved: [u8; 6],
+        #[bw(if(*flag != 0))]
+        flag: u8,
+        #[bw(pad_before = 1)]
+        value: u16,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test {
+        reserved: [0xff; 6],
+        flag: 1,
+        value: 0x102,
+    }
+    .write(&mut x)
+    .unwrap();
+    assert_eq!(x.into_inner(), b"\x01\0\x01\x02\xff\xff");
+}
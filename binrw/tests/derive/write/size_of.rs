@@ -0,0 +1,97 @@
+use binrw::{binrw, io::Cursor, BinRead, BinWrite};
+
+#[test]
+fn size_of() {
+    #[binrw]
+    #[brw(big)]
+    #[derive(Debug, PartialEq)]
+    struct Test {
+        #[br(temp)]
+        #[bw(size_of = "body")]
+        len: u16,
+
+        #[br(count = len)]
+        body: Vec<u8>,
+
+        trailer: u8,
+    }
+
+    let value = Test {
+        body: vec![1, 2, 3],
+        trailer: 4,
+    };
+
+    let mut x = Cursor::new(Vec::new());
+    value.write(&mut x).unwrap();
+    assert_eq!(x.get_ref(), b"\0\x03\x01\x02\x03\x04");
+
+    x.set_position(0);
+    assert_eq!(Test::read(&mut x).unwrap(), value);
+}
+
+#[test]
+fn size_of_range() {
+    #[binrw::binwrite]
+    #[bw(little)]
+    struct Test {
+        magic: u8,
+
+        // Includes itself
+        #[bw(size_of = "total..=body")]
+        total: u32,
+
+        // Includes the padding of the measured field
+        #[bw(big, size_of = "body")]
+        body_len: u8,
+
+        #[bw(pad_after = 2)]
+        body: [u8; 3],
+
+        trailer: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test {
+        magic: 0xaa,
+        body: [1, 2, 3],
+        trailer: 0xbb,
+    }
+    .write(&mut x)
+    .unwrap();
+    assert_eq!(x.into_inner(), [0xaa, 10, 0, 0, 0, 5, 1, 2, 3, 0, 0, 0xbb]);
+}
+
+#[test]
+fn size_of_after() {
+    #[binrw::binwrite]
+    #[bw(big)]
+    struct Test {
+        body: Vec<u16>,
+
+        #[bw(size_of = "body")]
+        len: u8,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test { body: vec![1, 2] }.write(&mut x).unwrap();
+    assert_eq!(x.into_inner(), b"\0\x01\0\x02\x04");
+}
+
+#[test]
+fn size_of_overflow() {
+    #[binrw::binwrite]
+    #[bw(big)]
+    struct Test {
+        #[bw(size_of = "body")]
+        len: u8,
+
+        body: Vec<u8>,
+    }
+
+    let error = Test {
+        body: vec![0; 0x100],
+    }
+    .write(&mut Cursor::new(Vec::new()))
+    .unwrap_err();
+    assert!(matches!(error, binrw::Error::AssertFail { pos: 0, .. }));
+}
@@ -1,4 +1,4 @@
-use binrw::{io::Cursor, BinWrite};
+use binrw::{binrw, io::Cursor, BinRead, BinWrite};
 
 #[test]
 fn derive_allows_default() {
@@ -18,3 +18,61 @@ fn derive_allows_default() {
         .unwrap();
     assert_eq!(b"\0\0\x01", &result[..]);
 }
+
+#[test]
+fn derive_generic_bound() {
+    #[binrw]
+    #[br(bound = "T: for<'a> BinRead<Args<'a> = ()>")]
+    #[bw(bound = "T: for<'a> BinWrite<Args<'a> = ()>")]
+    struct Test<T> {
+        a: u16,
+        b: T,
+    }
+
+    let mut result = Cursor::new(Vec::new());
+    Test::<u8> { a: 0, b: 1 }.write_be(&mut result).unwrap();
+    assert_eq!(result.get_ref(), b"\0\0\x01");
+
+    result.set_position(0);
+    assert_eq!(Test::<u8>::read_be(&mut result).unwrap().b, 1);
+}
+
+#[test]
+fn derive_phantom_data() {
+    struct Marker;
+
+    #[derive(BinWrite)]
+    struct Test<T> {
+        a: u8,
+        marker: core::marker::PhantomData<T>,
+        b: u8,
+    }
+
+    let mut result = Vec::new();
+    Test::<Marker> {
+        a: 1,
+        marker: core::marker::PhantomData,
+        b: 2,
+    }
+    .write_be(&mut Cursor::new(&mut result))
+    .unwrap();
+    assert_eq!(b"\x01\x02", &result[..]);
+}
+
+#[test]
+fn derive_const_generic() {
+    #[derive(BinWrite)]
+    #[bw(import { key: [u8; N] })]
+    struct Test<const N: usize> {
+        #[bw(pad_size_to = N * 2)]
+        a: u8,
+        #[bw(map = |b: &[u8; N]| core::array::from_fn::<u8, N, _>(|i| b[i] ^ key[i]))]
+        b: [u8; N],
+    }
+
+    let mut result = Vec::new();
+    Test::<2> { a: 1, b: [1, 1] }
+        .write_be_args(&mut Cursor::new(&mut result), binrw::args! { key: [2, 3] })
+        .unwrap();
+    assert_eq!(b"\x01\0\0\0\x03\x02", &result[..]);
+}
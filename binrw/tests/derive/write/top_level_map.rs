@@ -31,3 +31,53 @@ fn round_trip_top_level_map() {
 
     assert_eq!(x.into_inner(), data);
 }
+
+#[test]
+fn round_trip_top_level_repr() {
+    #[binrw]
+    #[brw(little, import(checked: bool))]
+    struct RawFlags {
+        #[bw(assert(!checked || bits & 0x80 == 0))]
+        bits: u8,
+    }
+
+    #[binrw]
+    #[derive(Debug, PartialEq)]
+    #[brw(repr = RawFlags)]
+    struct Flags {
+        compressed: bool,
+        encrypted: bool,
+    }
+
+    impl From<RawFlags> for Flags {
+        fn from(raw: RawFlags) -> Self {
+            Self {
+                compressed: raw.bits & 1 != 0,
+                encrypted: raw.bits & 2 != 0,
+            }
+        }
+    }
+
+    impl From<&Flags> for RawFlags {
+        fn from(flags: &Flags) -> Self {
+            Self {
+                bits: u8::from(flags.compressed) | (u8::from(flags.encrypted) << 1),
+            }
+        }
+    }
+
+    let data = b"\x02";
+
+    let test: Flags = Cursor::new(data).read_le_args((true,)).unwrap();
+    assert_eq!(
+        test,
+        Flags {
+            compressed: false,
+            encrypted: true
+        }
+    );
+    let mut x = Cursor::new(Vec::new());
+    test.write_le_args(&mut x, (true,)).unwrap();
+
+    assert_eq!(x.into_inner(), data);
+}
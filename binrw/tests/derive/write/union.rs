@@ -0,0 +1,48 @@
+use binrw::{io::Cursor, BinReaderExt, BinWrite};
+
+#[test]
+fn union_round_trip() {
+    #[derive(BinWrite, binrw::BinRead)]
+    #[brw(little, import(kind: u8), tag = kind)]
+    #[bw(unsafe_assume_active)]
+    union Test {
+        #[brw(tag = 1)]
+        a: u32,
+        #[brw(tag = 2, big)]
+        b: u16,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test { a: 0x4030201 }
+        .write_options(&mut x, binrw::Endian::Little, (1,))
+        .unwrap();
+    Test { b: 0x102 }
+        .write_options(&mut x, binrw::Endian::Little, (2,))
+        .unwrap();
+    Test { a: 0 }
+        .write_options(&mut x, binrw::Endian::Little, (3,))
+        .expect_err("accepted bad tag");
+    assert_eq!(x.get_ref(), b"\x01\x02\x03\x04\x01\x02");
+
+    x.set_position(0);
+    let test: Test = x.read_le_args((1,)).unwrap();
+    assert_eq!(unsafe { test.a }, 0x4030201);
+    let test: Test = x.read_le_args((2,)).unwrap();
+    assert_eq!(unsafe { test.b }, 0x102);
+}
+
+#[test]
+fn union_selector() {
+    #[derive(BinWrite)]
+    #[bw(big, magic = b"U", import(wide: bool), selector = wide, match(false => a, true => b))]
+    #[bw(unsafe_assume_active)]
+    union Test {
+        a: u8,
+        b: u16,
+    }
+
+    let mut x = Cursor::new(Vec::new());
+    Test { a: 1 }.write_args(&mut x, (false,)).unwrap();
+    Test { b: 2 }.write_args(&mut x, (true,)).unwrap();
+    assert_eq!(x.into_inner(), b"U\x01U\0\x02");
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "std")]
+
+use binrw::{
+    file_ptr::{parse_from_iter, RegionConflict, RegionTracker},
+    io::Cursor,
+    BinRead, FilePtr8,
+};
+
+#[test]
+fn region_tracker_overlap() {
+    #[derive(BinRead)]
+    #[br(big)]
+    struct Table {
+        offsets: [u8; 3],
+        #[br(parse_with = parse_from_iter(offsets.iter().map(|&o| u64::from(o))), seek_before = binrw::io::SeekFrom::Start(0))]
+        values: Vec<u16>,
+    }
+
+    let mut tracker = RegionTracker::new();
+    let table = tracker
+        .track(|| Table::read(&mut Cursor::new(b"\x03\x03\x04abcd")))
+        .unwrap();
+    assert_eq!(table.offsets, [3, 3, 4]);
+    assert_eq!(table.values, [0x6162, 0x6162, 0x6263]);
+    assert_eq!(tracker.pointees(), &[3..5, 3..5, 4..6]);
+    assert_eq!(
+        tracker.conflicts(),
+        &[
+            RegionConflict::Overlap {
+                pointee: 4..6,
+                other: 3..5
+            },
+            RegionConflict::Overlap {
+                pointee: 4..6,
+                other: 3..5
+            },
+        ]
+    );
+    assert_eq!(
+        tracker.conflicts()[0].to_string(),
+        "pointed-to value at 0x4..0x6 overlaps value at 0x3..0x5"
+    );
+
+    tracker.clear();
+    assert!(tracker.pointees().is_empty());
+    assert!(tracker.conflicts().is_empty());
+}
+
+#[test]
+fn region_tracker_nested() {
+    #[derive(BinRead)]
+    #[br(big)]
+    struct Node {
+        data: u8,
+        next: FilePtr8<u8>,
+    }
+
+    #[derive(BinRead)]
+    #[br(big)]
+    struct Root {
+        node: FilePtr8<Node>,
+    }
+
+    let mut tracker = RegionTracker::new();
+    let root = tracker
+        .track(|| Root::read(&mut Cursor::new(b"\x01\x07\x01")))
+        .unwrap();
+    assert_eq!(root.node.data, 7);
+    assert_eq!(*root.node.next, 7);
+    assert_eq!(tracker.pointees(), &[1..2, 1..3]);
+    assert!(tracker.conflicts().is_empty());
+}
+
+#[test]
+fn region_tracker_strict() {
+    #[derive(BinRead)]
+    #[br(big)]
+    struct Pair {
+        _a: FilePtr8<u16>,
+        _b: FilePtr8<u16>,
+        _c: u8,
+    }
+
+    let mut tracker = RegionTracker::new();
+    tracker.strict(true).section(3..5);
+    let mut reader = Cursor::new(b"\x03\x04\x09abc");
+    let err = tracker
+        .track(|| Pair::read(&mut reader))
+        .map(|_| ())
+        .unwrap_err();
+    assert_eq!(
+        err.custom_err(),
+        Some(&RegionConflict::Overlap {
+            pointee: 4..6,
+            other: 3..5
+        })
+    );
+    assert_eq!(
+        tracker.conflicts(),
+        &[
+            RegionConflict::Overlap {
+                pointee: 4..6,
+                other: 3..5
+            },
+            RegionConflict::PastSection {
+                pointee: 4..6,
+                section: 3..5
+            },
+        ]
+    );
+}
+
+#[test]
+fn region_tracker_inactive() {
+    #[derive(BinRead)]
+    #[br(big)]
+    struct Pair {
+        a: FilePtr8<u16>,
+        b: FilePtr8<u16>,
+    }
+
+    let mut outer = RegionTracker::new();
+    let mut inner = RegionTracker::new();
+    outer.track(|| {
+        inner
+            .track(|| Pair::read(&mut Cursor::new(b"\x02\x03abc")))
+            .map(|_| ())
+            .unwrap();
+    });
+    assert_eq!(inner.conflicts().len(), 1);
+    assert!(outer.pointees().is_empty());
+
+    let pair = Pair::read(&mut Cursor::new(b"\x02\x03abc")).unwrap();
+    assert_eq!(*pair.a, 0x6162);
+    assert_eq!(*pair.b, 0x6263);
+    assert_eq!(inner.pointees(), &[2..4, 3..5]);
+}
@@ -0,0 +1,173 @@
+use binrw::{
+    io::{AsyncReader, AsyncWriter, SeekFrom},
+    BinRead, BinWrite, Endian,
+};
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use futures_io::AsyncRead;
+use std::sync::Arc;
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct Noop;
+    impl std::task::Wake for Noop {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    let waker = Arc::new(Noop).into();
+    let mut cx = Context::from_waker(&waker);
+    let mut future = Box::pin(future);
+    loop {
+        if let Poll::Ready(value) = future.as_mut().poll(&mut cx) {
+            return value;
+        }
+    }
+}
+
+/// Returns one byte at a time, and is pending on every other poll.
+struct Trickle<'a> {
+    data: &'a [u8],
+    pending: bool,
+    polls: usize,
+}
+
+impl<'a> Trickle<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pending: true,
+            polls: 0,
+        }
+    }
+}
+
+impl AsyncRead for Trickle<'_> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut [u8],
+    ) -> Poll<std::io::Result<usize>> {
+        self.polls += 1;
+        self.pending = !self.pending;
+        if self.pending {
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+
+        let n = self.data.len().min(buf.len()).min(1);
+        buf[..n].copy_from_slice(&self.data[..n]);
+        self.data = &self.data[n..];
+        Poll::Ready(Ok(n))
+    }
+}
+
+#[derive(BinRead, BinWrite, Debug, PartialEq)]
+#[brw(big)]
+struct Message {
+    offset: u8,
+    #[br(seek_before = SeekFrom::Current(offset.into()), restore_position)]
+    #[bw(ignore)]
+    peeked: u8,
+    len: u8,
+    #[br(count = len)]
+    data: Vec<u8>,
+}
+
+#[test]
+fn async_reader() {
+    block_on(async {
+        let mut reader = AsyncReader::new(Trickle::new(b"\x02\x02ab\x00\x01c\xff"));
+        assert_eq!(
+            reader.read::<Message>().await.unwrap(),
+            Message {
+                offset: 2,
+                peeked: b'b',
+                len: 2,
+                data: b"ab".to_vec(),
+            }
+        );
+        assert_eq!(reader.position(), 4);
+        assert_eq!(
+            reader.read::<Message>().await.unwrap(),
+            Message {
+                offset: 0,
+                peeked: 1,
+                len: 1,
+                data: b"c".to_vec(),
+            }
+        );
+        assert_eq!(reader.position(), 7);
+        assert!(reader.buffer().is_empty());
+
+        // Data of a failed read stays in the buffer
+        let err = reader.read_type::<u16>(Endian::Big).await.unwrap_err();
+        assert!(err.is_eof());
+        assert_eq!(reader.buffer(), b"\xff");
+        assert_eq!(reader.read_type::<u8>(Endian::Big).await.unwrap(), 0xff);
+        assert!(reader.get_ref().polls > 16);
+    });
+}
+
+#[test]
+fn async_reader_errors() {
+    block_on(async {
+        // Errors other than EOF are returned immediately with stream positions
+        let mut reader = AsyncReader::new(&b"\x01\x02\x03"[..]);
+        reader.read_type::<u8>(Endian::Big).await.unwrap();
+        let err = reader
+            .read_type_args::<binrw::NullString>(Endian::Big, ())
+            .await
+            .unwrap_err();
+        assert!(err.is_eof());
+
+        #[derive(BinRead, Debug)]
+        #[br(big, magic = 2u8)]
+        struct Magic;
+
+        let mut reader = AsyncReader::new(&b"\x01\x03"[..]);
+        reader.read_type::<u8>(Endian::Big).await.unwrap();
+        let err = reader.read::<Magic>().await.unwrap_err();
+        assert!(matches!(err, binrw::Error::BadMagic { pos: 1, .. }));
+
+        // Seeking relative to the end is not possible
+        let mut reader = AsyncReader::new(&b"\x01"[..]);
+        reader.read_type_args::<u8>(Endian::Big, ()).await.unwrap();
+        #[derive(BinRead, Debug)]
+        struct End(#[br(seek_before = SeekFrom::End(0))] ());
+        reader.read_type::<End>(Endian::Big).await.unwrap_err();
+
+        // Values must fit inside the buffer limit
+        let mut reader = AsyncReader::with_limit(&[0u8; 8][..], 3);
+        let err = reader.read_type::<u32>(Endian::Big).await.unwrap_err();
+        assert!(!err.is_eof());
+        assert_eq!(reader.buffer(), [0, 0, 0]);
+    });
+}
+
+#[test]
+fn async_writer() {
+    #[derive(BinWrite)]
+    #[bw(big)]
+    struct Aligned {
+        #[bw(align_after = 4)]
+        kind: u8,
+    }
+
+    block_on(async {
+        let mut writer = AsyncWriter::new(Vec::new());
+        writer.write(&Aligned { kind: 1 }).await.unwrap();
+        writer.write_type(&0x203_u16, Endian::Little).await.unwrap();
+        // Alignment is relative to the start of the stream
+        writer.write(&Aligned { kind: 4 }).await.unwrap();
+        writer
+            .write_type_args(&[5_u8], Endian::Big, ())
+            .await
+            .unwrap();
+        writer.flush().await.unwrap();
+        assert_eq!(writer.position(), 9);
+        assert_eq!(writer.get_ref().len(), 9);
+        assert_eq!(writer.into_inner(), b"\x01\0\0\0\x03\x02\x04\0\x05");
+    });
+}
@@ -0,0 +1,296 @@
+use binrw::{
+    io::{BitOrder, BitReader, BitWriter, Cursor, Read, Seek, SeekFrom, Write},
+    BinRead, BinReaderExt, BinWrite,
+};
+
+#[test]
+fn read_msb0() {
+    let mut reader = BitReader::new(Cursor::new(b"\xb4\x81\xff"), BitOrder::Msb0);
+    assert!(reader.is_aligned());
+    assert!(reader.read_bit().unwrap());
+    assert!(!reader.is_aligned());
+    assert_eq!(reader.read_bits(3).unwrap(), 0b011);
+    assert_eq!(reader.read_bits(8).unwrap(), 0b0100_1000);
+    assert_eq!(reader.read_bits(0).unwrap(), 0);
+    assert_eq!(reader.stream_position().unwrap(), 2);
+    reader.align();
+    assert!(reader.is_aligned());
+    assert_eq!(reader.read_bits(8).unwrap(), 0xff);
+    reader.read_bit().unwrap_err();
+}
+
+#[test]
+fn read_lsb0() {
+    let mut reader = BitReader::new(Cursor::new(b"\xb4\x81"), BitOrder::Lsb0);
+    assert!(!reader.read_bit().unwrap());
+    assert_eq!(reader.read_bits(3).unwrap(), 0b010);
+    assert_eq!(reader.read_bits(8).unwrap(), 0b0001_1011);
+    assert_eq!(reader.read_bits(4).unwrap(), 0b1000);
+    assert!(reader.is_aligned());
+}
+
+#[test]
+fn read_bytes_unaligned() {
+    let mut reader = BitReader::new(Cursor::new(b"\x12\x34\x56\x78"), BitOrder::Msb0);
+    assert_eq!(reader.read_bits(4).unwrap(), 1);
+    let value: u16 = reader.read_be().unwrap();
+    assert_eq!(value, 0x2345);
+    assert_eq!(reader.read_bits(4).unwrap(), 6);
+
+    let mut buf = [0; 4];
+    assert_eq!(reader.read(&mut buf).unwrap(), 1);
+    assert_eq!(buf[0], 0x78);
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+}
+
+#[test]
+fn read_seek() {
+    let mut reader = BitReader::new(Cursor::new(b"\xf0\x0f"), BitOrder::Msb0);
+    assert_eq!(reader.read_bits(2).unwrap(), 0b11);
+    assert_eq!(reader.seek(SeekFrom::Start(0)).unwrap(), 0);
+    assert!(reader.is_aligned());
+    assert_eq!(reader.read_bits(12).unwrap(), 0xf00);
+    assert_eq!(reader.into_inner().position(), 2);
+}
+
+#[test]
+fn write_msb0() {
+    let mut writer = BitWriter::new(Cursor::new(Vec::new()), BitOrder::Msb0);
+    writer.write_bit(true).unwrap();
+    writer.write_bits(0b011, 3).unwrap();
+    writer.write_bits(0b0100_1000, 8).unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"\xb4");
+    assert!(!writer.is_aligned());
+    writer.align().unwrap();
+    assert!(writer.is_aligned());
+    writer.write_bits(0xff, 8).unwrap();
+    writer.write_bit(true).unwrap();
+    assert_eq!(writer.finish().unwrap().into_inner(), b"\xb4\x80\xff\x80");
+}
+
+#[test]
+fn write_lsb0() {
+    let mut writer = BitWriter::new(Cursor::new(Vec::new()), BitOrder::Lsb0);
+    writer.write_bit(false).unwrap();
+    writer.write_bits(0b010, 3).unwrap();
+    writer.write_bits(0b0001_1011, 8).unwrap();
+    writer.write_bits(0b1000, 4).unwrap();
+    assert!(writer.is_aligned());
+    assert_eq!(writer.finish().unwrap().into_inner(), b"\xb4\x81");
+}
+
+#[test]
+fn write_bytes_unaligned() {
+    let mut writer = BitWriter::new(Cursor::new(Vec::new()), BitOrder::Msb0);
+    writer.write_bits(1, 4).unwrap();
+    0x2345_u16.write_be(&mut writer).unwrap();
+    writer.write_bits(6, 4).unwrap();
+    assert_eq!(writer.write(&[0x78]).unwrap(), 1);
+    assert_eq!(writer.write(&[]).unwrap(), 0);
+    writer.flush().unwrap();
+    assert_eq!(writer.finish().unwrap().into_inner(), b"\x12\x34\x56\x78");
+}
+
+#[test]
+fn write_flush() {
+    let mut writer = BitWriter::new(Cursor::new(Vec::new()), BitOrder::Msb0);
+    writer.write_bits(0xab, 8).unwrap();
+    writer.flush().unwrap();
+    writer.write_bit(true).unwrap();
+    writer.flush().unwrap();
+    assert!(writer.is_aligned());
+    assert_eq!(writer.get_ref().get_ref(), b"\xab\x80");
+    writer.write_bit(true).unwrap();
+    assert_eq!(writer.finish().unwrap().into_inner(), b"\xab\x80\x80");
+}
+
+#[test]
+fn write_drop() {
+    let mut output = Cursor::new(Vec::new());
+    let mut writer = BitWriter::new(&mut output, BitOrder::Lsb0);
+    writer.write_bits(0b101, 3).unwrap();
+    drop(writer);
+    assert_eq!(output.into_inner(), b"\x05");
+}
+
+#[test]
+fn write_seek() {
+    let mut writer = BitWriter::new(Cursor::new(Vec::new()), BitOrder::Msb0);
+    writer.write_bits(0xab, 8).unwrap();
+    writer.write_bit(true).unwrap();
+    assert_eq!(writer.stream_position().unwrap(), 1);
+    writer.seek(SeekFrom::Start(0)).unwrap_err();
+    writer.align().unwrap();
+    assert_eq!(writer.seek(SeekFrom::Start(0)).unwrap(), 0);
+    writer.write_bits(0xcd, 8).unwrap();
+    assert_eq!(writer.get_mut().get_ref(), b"\xcd\x80");
+}
+
+#[test]
+fn round_trip() {
+    for order in [BitOrder::Msb0, BitOrder::Lsb0] {
+        let mut writer = BitWriter::new(Cursor::new(Vec::new()), order);
+        writer.write_bits(u64::MAX, 64).unwrap();
+        writer.write_bits(0x5, 3).unwrap();
+        writer.write_bits(0x1234, 13).unwrap();
+        let data = writer.finish().unwrap().into_inner();
+        assert_eq!(data.len(), 10);
+
+        let mut reader = BitReader::new(Cursor::new(data), order);
+        assert_eq!(reader.read_bits(64).unwrap(), u64::MAX);
+        assert_eq!(reader.read_bits(3).unwrap(), 0x5);
+        assert_eq!(reader.read_bits(13).unwrap(), 0x1234);
+    }
+}
+
+#[test]
+#[should_panic]
+fn read_too_many_bits() {
+    let mut reader = BitReader::new(Cursor::new([0; 16]), BitOrder::Msb0);
+    let _ = reader.read_bits(65);
+}
+
+#[test]
+fn read_bits_with_order() {
+    let mut reader = BitReader::new(Cursor::new(b"\x0d\x00"), BitOrder::Lsb0);
+    assert_eq!(reader.read_bits(2).unwrap(), 0b01);
+    assert_eq!(reader.read_bits_with(3, BitOrder::Msb0).unwrap(), 0b110);
+    assert_eq!(reader.read_bits_with(3, BitOrder::Lsb0).unwrap(), 0b000);
+
+    let mut reader = BitReader::new(Cursor::new(b"\xc0"), BitOrder::Msb0);
+    assert_eq!(reader.read_bits_with(4, BitOrder::Lsb0).unwrap(), 0b0011);
+}
+
+#[test]
+fn set_order() {
+    let mut reader = BitReader::new(Cursor::new(b"\x80\x80"), BitOrder::Msb0);
+    assert_eq!(reader.order(), BitOrder::Msb0);
+    assert!(reader.read_bit().unwrap());
+    // The rest of the current byte is still read in the old order
+    reader.set_order(BitOrder::Lsb0);
+    assert_eq!(reader.order(), BitOrder::Lsb0);
+    assert_eq!(reader.read_bits(7).unwrap(), 0);
+    assert_eq!(reader.read_bits(8).unwrap(), 0x80);
+
+    let mut writer = BitWriter::new(Cursor::new(Vec::new()), BitOrder::Msb0);
+    writer.write_bit(true).unwrap();
+    writer.set_order(BitOrder::Lsb0);
+    assert_eq!(writer.order(), BitOrder::Lsb0);
+    writer.write_bits(0, 7).unwrap();
+    writer.write_bit(true).unwrap();
+    assert_eq!(writer.finish().unwrap().into_inner(), b"\x80\x01");
+}
+
+#[test]
+fn write_bits_with_order() {
+    let mut writer = BitWriter::new(Cursor::new(Vec::new()), BitOrder::Lsb0);
+    writer.write_bits(0b01, 2).unwrap();
+    writer.write_bits_with(0b110, 3, BitOrder::Msb0).unwrap();
+    writer.write_bits_with(0b001, 3, BitOrder::Lsb0).unwrap();
+    assert_eq!(writer.finish().unwrap().into_inner(), b"\x2d");
+}
+
+#[test]
+fn field_order() {
+    use binrw::helpers::{read_bits, write_bits};
+
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[br(map_stream = |reader| BitReader::new(reader, BitOrder::Lsb0))]
+    #[bw(map_stream = |writer| BitWriter::new(writer, BitOrder::Lsb0))]
+    struct Fields {
+        #[br(parse_with = read_bits(3, BitOrder::Lsb0))]
+        #[bw(write_with = write_bits(3, BitOrder::Lsb0))]
+        kind: u8,
+        #[br(parse_with = read_bits(7, BitOrder::Msb0))]
+        #[bw(write_with = write_bits(7, BitOrder::Msb0))]
+        code: u16,
+        #[br(parse_with = read_bits(6, BitOrder::Lsb0))]
+        #[bw(write_with = write_bits(6, BitOrder::Lsb0))]
+        extra: u8,
+    }
+
+    let value = Fields {
+        kind: 0b101,
+        code: 0b110_0001,
+        extra: 0b10_1100,
+    };
+    let mut output = Cursor::new(Vec::new());
+    value.write_le(&mut output).unwrap();
+    let data = output.into_inner();
+    assert_eq!(data, b"\x1d\xb2");
+    assert_eq!(Cursor::new(data).read_le::<Fields>().unwrap(), value);
+}
+
+#[test]
+fn field_too_large() {
+    use binrw::helpers::read_bits;
+
+    #[derive(BinRead, Debug)]
+    #[br(map_stream = |reader| BitReader::new(reader, BitOrder::Msb0))]
+    struct Field {
+        #[br(parse_with = read_bits(9, BitOrder::Msb0))]
+        _value: u8,
+    }
+
+    let error = Cursor::new(b"\xff\xff").read_le::<Field>().unwrap_err();
+    assert!(matches!(
+        error.root_cause(),
+        binrw::Error::AssertFail { pos: 0, .. }
+    ));
+}
+
+#[test]
+fn field_partial_byte() {
+    use binrw::helpers::write_bits;
+
+    #[derive(BinWrite)]
+    #[bw(map_stream = |writer| BitWriter::new(writer, BitOrder::Msb0))]
+    struct Flags {
+        #[bw(write_with = write_bits(3, BitOrder::Msb0))]
+        kind: u8,
+        #[bw(write_with = write_bits(2, BitOrder::Msb0))]
+        mode: u8,
+    }
+
+    let mut output = Cursor::new(Vec::new());
+    Flags {
+        kind: 0b101,
+        mode: 0b11,
+    }
+    .write_be(&mut output)
+    .unwrap();
+    assert_eq!(output.into_inner(), b"\xb8");
+}
+
+#[test]
+fn field_write_bits_errors() {
+    use binrw::{helpers::write_bits, Error};
+
+    #[derive(BinWrite)]
+    #[bw(map_stream = |writer| BitWriter::new(writer, BitOrder::Msb0))]
+    struct Narrow {
+        #[bw(write_with = write_bits(3, BitOrder::Msb0))]
+        kind: u8,
+    }
+
+    #[derive(BinWrite)]
+    #[bw(map_stream = |writer| BitWriter::new(writer, BitOrder::Msb0))]
+    struct Wide {
+        #[bw(write_with = write_bits(65, BitOrder::Msb0))]
+        kind: u64,
+    }
+
+    let mut output = Cursor::new(Vec::new());
+    Narrow { kind: 0b111 }.write_be(&mut output).unwrap();
+    assert_eq!(output.get_ref(), b"\xe0");
+
+    let error = Narrow { kind: 0b1000 }
+        .write_be(&mut Cursor::new(Vec::new()))
+        .unwrap_err();
+    assert!(matches!(error.root_cause(), Error::AssertFail { .. }));
+
+    let error = Wide { kind: 0 }
+        .write_be(&mut Cursor::new(Vec::new()))
+        .unwrap_err();
+    assert!(matches!(error.root_cause(), Error::AssertFail { .. }));
+}
@@ -0,0 +1,82 @@
+use binrw::{
+    io::{BufReadAdapter, BufWriteAdapter, Read, Seek, SeekFrom, Write},
+    BinRead, BinWrite,
+};
+use bytes::{Buf, Bytes, BytesMut};
+
+#[test]
+fn buf_read_adapter() {
+    let mut stream = BufReadAdapter::new(Bytes::from_static(b"helloworld"));
+    let mut buf = [0; 3];
+
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"hel");
+    assert_eq!(stream.stream_position().unwrap(), 3);
+
+    // Forward seeks skip bytes
+    assert_eq!(stream.seek(SeekFrom::Current(2)).unwrap(), 5);
+    assert_eq!(stream.seek(SeekFrom::Start(6)).unwrap(), 6);
+    assert_eq!(stream.read(&mut buf).unwrap(), 3);
+    assert_eq!(&buf, b"orl");
+    assert_eq!(stream.seek(SeekFrom::End(0)).unwrap(), 10);
+    assert_eq!(stream.read(&mut buf).unwrap(), 0);
+
+    // Backward seeks fail and maintain correct stream position
+    stream.seek(SeekFrom::Start(0)).unwrap_err();
+    stream.seek(SeekFrom::Current(-1)).unwrap_err();
+    assert_eq!(stream.stream_position().unwrap(), 10);
+    assert!(stream.get_ref().is_empty());
+}
+
+#[test]
+fn buf_read_adapter_chained() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        a: u16,
+        #[br(pad_before = 1)]
+        b: u32,
+    }
+
+    // A non-contiguous `Buf`
+    let buf = Bytes::from_static(b"\0\x01\xff\0").chain(Bytes::from_static(b"\x02\x03\x04"));
+    let mut stream = BufReadAdapter::new(buf);
+    assert_eq!(
+        Test::read(&mut stream).unwrap(),
+        Test {
+            a: 1,
+            b: 0x0002_0304,
+        }
+    );
+    assert_eq!(stream.stream_position().unwrap(), 7);
+}
+
+#[test]
+fn buf_write_adapter() {
+    #[derive(BinWrite)]
+    #[bw(big)]
+    struct Test {
+        a: u16,
+        #[bw(pad_before = 2)]
+        b: u8,
+    }
+
+    let mut stream = BufWriteAdapter::new(BytesMut::new());
+    Test { a: 0x102, b: 3 }.write(&mut stream).unwrap();
+    assert_eq!(stream.stream_position().unwrap(), 5);
+    assert_eq!(stream.seek(SeekFrom::End(1)).unwrap(), 6);
+    stream.seek(SeekFrom::Current(-1)).unwrap_err();
+    assert_eq!(stream.get_ref().len(), 6);
+    assert_eq!(stream.into_inner().freeze(), &b"\x01\x02\0\0\x03\0"[..]);
+}
+
+#[test]
+fn buf_write_adapter_full() {
+    let mut storage = [0; 4];
+    let mut stream = BufWriteAdapter::new(&mut storage[..]);
+    stream.write_all(b"ab").unwrap();
+    stream.seek(SeekFrom::Current(3)).unwrap_err();
+    assert_eq!(stream.stream_position().unwrap(), 2);
+    stream.write_all(b"cde").unwrap_err();
+    assert_eq!(storage, *b"abcd");
+}
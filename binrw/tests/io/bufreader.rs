@@ -135,7 +135,9 @@ fn bufreader() {
     assert_eq!(stream.fill_buf().unwrap(), b"helloworld");
     stream.consume(5);
     assert_eq!(stream.buffer(), b"world");
-    assert_eq!(stream.stream_position().unwrap(), 0);
+    assert_eq!(stream.stream_position().unwrap(), 5);
+    assert_eq!(stream.seek(SeekFrom::Current(-2)).unwrap(), 3);
+    assert_eq!(stream.buffer(), b"loworld");
 
     // into_inner
     let mut buf = Vec::new();
@@ -160,3 +162,52 @@ fn bufreader() {
     assert_eq!(stream.stream_position().unwrap(), 18);
     assert_eq!(&buf, b"if i don't survive");
 }
+
+#[test]
+fn bufreader_parse_seeks_within_buffer() {
+    use binrw::BinRead;
+
+    struct Counter<T> {
+        inner: T,
+        reads: usize,
+        seeks: usize,
+    }
+
+    impl<T: Read> Read for Counter<T> {
+        fn read(&mut self, buf: &mut [u8]) -> binrw::io::Result<usize> {
+            self.reads += 1;
+            self.inner.read(buf)
+        }
+    }
+
+    impl<T: Seek> Seek for Counter<T> {
+        fn seek(&mut self, pos: SeekFrom) -> binrw::io::Result<u64> {
+            self.seeks += 1;
+            self.inner.seek(pos)
+        }
+    }
+
+    #[derive(BinRead)]
+    #[br(little)]
+    struct Test {
+        offset: u8,
+        #[br(seek_before = SeekFrom::Start(offset.into()), restore_position)]
+        pointee: u16,
+        #[br(count = 2)]
+        rest: Vec<u8>,
+    }
+
+    let mut stream = BufReader::new(Counter {
+        inner: Cursor::new(b"\x03\x01\x02\x04\x00"),
+        reads: 0,
+        seeks: 0,
+    });
+    let result = Test::read(&mut stream).unwrap();
+    assert_eq!(result.offset, 3);
+    assert_eq!(result.pointee, 4);
+    assert_eq!(result.rest, [1, 2]);
+    assert_eq!(stream.stream_position().unwrap(), 3);
+    assert_eq!(stream.get_ref().reads, 1);
+    // Only the initial position query reaches the wrapped stream
+    assert_eq!(stream.get_ref().seeks, 1);
+}
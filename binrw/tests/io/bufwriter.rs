@@ -0,0 +1,128 @@
+#![allow(clippy::seek_from_current)]
+use binrw::{
+    io::{BufWriter, Cursor, Seek, SeekFrom, Write},
+    BinWrite,
+};
+
+#[derive(Debug, Default)]
+struct Counter {
+    inner: Cursor<Vec<u8>>,
+    writes: usize,
+    seeks: usize,
+}
+
+impl Write for Counter {
+    fn write(&mut self, buf: &[u8]) -> binrw::io::Result<usize> {
+        self.writes += 1;
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> binrw::io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for Counter {
+    fn seek(&mut self, pos: SeekFrom) -> binrw::io::Result<u64> {
+        self.seeks += 1;
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn bufwriter_coalesces_writes() {
+    let mut writer = BufWriter::with_capacity(16, Counter::default());
+    for i in 0..8_u8 {
+        writer.write_all(&[i]).unwrap();
+    }
+    assert_eq!(writer.get_ref().writes, 0);
+    assert_eq!(writer.buffer(), [0, 1, 2, 3, 4, 5, 6, 7]);
+
+    writer.write_all(&[8; 10]).unwrap();
+    assert_eq!(writer.get_ref().writes, 1);
+    assert_eq!(writer.buffer(), [8; 10]);
+
+    writer.write_all(&[9; 20]).unwrap();
+    assert_eq!(writer.get_ref().writes, 3);
+    assert!(writer.buffer().is_empty());
+
+    writer.write_all(&[10]).unwrap();
+    let counter = writer.into_inner().unwrap();
+    assert_eq!(counter.writes, 4);
+    assert_eq!(counter.inner.get_ref().len(), 39);
+}
+
+#[test]
+fn bufwriter_position_from_cache() {
+    let mut writer = BufWriter::with_capacity(16, Counter::default());
+    writer.write_all(b"abc").unwrap();
+    assert_eq!(writer.stream_position().unwrap(), 3);
+    assert_eq!(writer.get_ref().seeks, 1);
+    writer.write_all(b"def").unwrap();
+    assert_eq!(writer.stream_position().unwrap(), 6);
+    assert_eq!(writer.seek(SeekFrom::Current(0)).unwrap(), 6);
+    assert_eq!(writer.get_ref().seeks, 1);
+    assert_eq!(writer.get_ref().writes, 0);
+}
+
+#[test]
+fn bufwriter_flushes_before_seek() {
+    let mut writer = BufWriter::with_capacity(16, Counter::default());
+    writer.write_all(b"abcd").unwrap();
+    assert_eq!(writer.seek(SeekFrom::Start(1)).unwrap(), 1);
+    assert_eq!(writer.get_ref().writes, 1);
+    assert_eq!(writer.get_ref().inner.get_ref(), b"abcd");
+    writer.write_all(b"X").unwrap();
+    assert_eq!(writer.stream_position().unwrap(), 2);
+    assert_eq!(writer.seek(SeekFrom::End(0)).unwrap(), 4);
+    assert_eq!(writer.get_ref().inner.get_ref(), b"aXcd");
+}
+
+#[test]
+fn bufwriter_flush() {
+    let mut writer = BufWriter::new(Counter::default());
+    writer.write_all(b"abc").unwrap();
+    writer.flush().unwrap();
+    assert!(writer.buffer().is_empty());
+    assert_eq!(writer.get_ref().inner.get_ref(), b"abc");
+}
+
+#[test]
+fn bufwriter_flushes_on_drop() {
+    let mut data = Vec::new();
+    {
+        let mut writer = BufWriter::new(&mut data);
+        writer.write_all(b"abc").unwrap();
+    }
+    assert_eq!(data, b"abc");
+}
+
+#[test]
+fn bufwriter_write_derive() {
+    #[derive(BinWrite)]
+    #[bw(big)]
+    struct Test {
+        a: u8,
+        #[bw(pad_before = 1)]
+        b: u16,
+        #[bw(seek_before = SeekFrom::Start(8), restore_position)]
+        c: u32,
+        d: u32,
+    }
+
+    let mut writer = BufWriter::new(Counter::default());
+    Test {
+        a: 1,
+        b: 2,
+        c: 3,
+        d: 4,
+    }
+    .write(&mut writer)
+    .unwrap();
+    let counter = writer.into_inner().unwrap();
+    assert!(counter.writes <= 4);
+    assert_eq!(
+        counter.inner.into_inner(),
+        b"\x01\0\0\x02\0\0\0\x04\0\0\0\x03"
+    );
+}
@@ -0,0 +1,101 @@
+#![allow(clippy::seek_from_current, clippy::seek_to_start_instead_of_rewind)]
+use binrw::{
+    helpers::until_eof,
+    io::{CachedLen, Cursor, Read, Seek, SeekFrom},
+    BinRead,
+};
+
+struct Counter<T> {
+    inner: T,
+    reads: usize,
+    seeks: usize,
+}
+
+impl<T> Counter<T> {
+    fn new(inner: T) -> Self {
+        Counter {
+            inner,
+            reads: 0,
+            seeks: 0,
+        }
+    }
+}
+
+impl<T: Read> Read for Counter<T> {
+    fn read(&mut self, buf: &mut [u8]) -> binrw::io::Result<usize> {
+        self.reads += 1;
+        self.inner.read(buf)
+    }
+}
+
+impl<T: Seek> Seek for Counter<T> {
+    fn seek(&mut self, pos: SeekFrom) -> binrw::io::Result<u64> {
+        self.seeks += 1;
+        self.inner.seek(pos)
+    }
+}
+
+#[test]
+fn cached_len() {
+    let mut data = Cursor::new(b"helloworld");
+    data.set_position(2);
+    let mut stream = CachedLen::new(Counter::new(data)).unwrap();
+    assert_eq!(stream.len(), 10);
+    assert!(!stream.is_empty());
+    assert_eq!(stream.remaining(), 8);
+    assert_eq!(stream.stream_position().unwrap(), 2);
+    let seeks = stream.get_ref().seeks;
+
+    // Null seeks and position queries do not reach the underlying reader
+    assert_eq!(stream.seek(SeekFrom::Current(0)).unwrap(), 2);
+    assert_eq!(stream.seek(SeekFrom::End(-8)).unwrap(), 2);
+    assert_eq!(stream.get_ref().seeks, seeks);
+
+    let mut buf = [0; 3];
+    assert_eq!(stream.seek(SeekFrom::End(-3)).unwrap(), 7);
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"rld");
+    assert_eq!(stream.get_ref().seeks, seeks + 1);
+
+    // Reads at the end do not reach the underlying reader
+    let reads = stream.get_ref().reads;
+    assert_eq!(stream.read(&mut buf).unwrap(), 0);
+    assert_eq!(stream.read(&mut buf).unwrap(), 0);
+    assert_eq!(stream.get_ref().reads, reads);
+
+    // Invalid seek maintains correct stream position
+    stream.seek(SeekFrom::End(-11)).unwrap_err();
+    assert_eq!(stream.stream_position().unwrap(), 10);
+
+    // Reads stop at the cached length
+    let mut stream = CachedLen::with_len(Cursor::new(b"helloworld"), 5).unwrap();
+    let mut buf = Vec::new();
+    assert_eq!(stream.read_to_end(&mut buf).unwrap(), 5);
+    assert_eq!(buf, b"hello");
+    assert_eq!(stream.into_inner().position(), 5);
+}
+
+#[test]
+fn cached_len_until_eof() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little)]
+    struct Test {
+        #[br(seek_before = SeekFrom::End(-2), restore_position)]
+        trailer: u16,
+        #[br(parse_with = until_eof)]
+        values: Vec<u16>,
+    }
+
+    let mut stream = CachedLen::new(Counter::new(Cursor::new(b"\x01\0\x02\0\x03\0"))).unwrap();
+    let seeks = stream.get_ref().seeks;
+    assert_eq!(
+        Test::read(&mut stream).unwrap(),
+        Test {
+            trailer: 3,
+            values: vec![1, 2, 3],
+        }
+    );
+    // One seek to the trailer and one to restore the position; no seek is
+    // needed to measure the stream again
+    assert_eq!(stream.get_ref().seeks, seeks + 2);
+}
@@ -0,0 +1,89 @@
+#![allow(clippy::seek_to_start_instead_of_rewind)]
+use binrw::{
+    io::{ChainSeek, Cursor, Read, Seek, SeekFrom},
+    BinRead,
+};
+
+#[test]
+fn chain_seek() {
+    let parts = vec![
+        Cursor::new(b"hello".to_vec()),
+        Cursor::new(Vec::new()),
+        Cursor::new(b"wor".to_vec()),
+        Cursor::new(b"ld".to_vec()),
+    ];
+    let mut stream = ChainSeek::new(parts).unwrap();
+    assert_eq!(stream.len(), 10);
+    assert!(!stream.is_empty());
+    assert_eq!(stream.get_ref().len(), 4);
+
+    let mut buf = Vec::new();
+    assert_eq!(stream.read_to_end(&mut buf).unwrap(), 10);
+    assert_eq!(buf, b"helloworld");
+    assert_eq!(stream.stream_position().unwrap(), 10);
+
+    // Reads spanning several parts
+    let mut buf = [0; 6];
+    assert_eq!(stream.seek(SeekFrom::Start(3)).unwrap(), 3);
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"loworl");
+    assert_eq!(stream.stream_position().unwrap(), 9);
+
+    // Relative and end seeks
+    assert_eq!(stream.seek(SeekFrom::Current(-3)).unwrap(), 6);
+    assert_eq!(stream.read(&mut buf[..1]).unwrap(), 1);
+    assert_eq!(buf[0], b'o');
+    assert_eq!(stream.seek(SeekFrom::End(-2)).unwrap(), 8);
+    assert_eq!(stream.read(&mut buf).unwrap(), 2);
+    assert_eq!(&buf[..2], b"ld");
+
+    // Seeking past the end
+    assert_eq!(stream.seek(SeekFrom::End(5)).unwrap(), 15);
+    assert_eq!(stream.read(&mut buf).unwrap(), 0);
+
+    // Invalid seek maintains correct stream position
+    stream.seek(SeekFrom::Current(-9001)).unwrap_err();
+    assert_eq!(stream.stream_position().unwrap(), 15);
+
+    let parts = stream.into_inner();
+    assert_eq!(parts.len(), 4);
+}
+
+#[test]
+fn chain_seek_empty() {
+    let mut stream = ChainSeek::new(Vec::<Cursor<Vec<u8>>>::new()).unwrap();
+    assert!(stream.is_empty());
+    assert_eq!(stream.read(&mut [0; 4]).unwrap(), 0);
+    assert_eq!(stream.seek(SeekFrom::Start(2)).unwrap(), 2);
+}
+
+#[test]
+fn chain_seek_parse_across_parts() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        offset: u16,
+        #[br(seek_before = SeekFrom::Start(offset.into()), restore_position)]
+        pointee: u32,
+        after: u16,
+    }
+
+    // Start at a non-zero position to make sure parts are rewound
+    let mut last = Cursor::new(b"\x05\x06\x07".to_vec());
+    last.seek(SeekFrom::End(0)).unwrap();
+    let parts = vec![
+        Cursor::new(b"\0\x04\x01".to_vec()),
+        Cursor::new(b"\x02\x03\x04".to_vec()),
+        last,
+    ];
+    let mut stream = ChainSeek::new(parts).unwrap();
+    assert_eq!(
+        Test::read(&mut stream).unwrap(),
+        Test {
+            offset: 4,
+            pointee: 0x0304_0506,
+            after: 0x0102,
+        }
+    );
+    assert_eq!(stream.stream_position().unwrap(), 4);
+}
@@ -0,0 +1,176 @@
+use binrw::{
+    helpers::{adler32, crc32},
+    io::{
+        Adler32, Checksum, ChecksumReader, ChecksumWriter, Crc32, Cursor, Read, Seek, SeekFrom,
+        Write,
+    },
+    BinRead,
+};
+
+#[test]
+fn crc32_known_values() {
+    assert_eq!(crc32(b""), 0);
+    assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+
+    let mut crc = Crc32::new();
+    crc.update(b"1234");
+    crc.update(b"56789");
+    assert_eq!(crc.finish(), 0xcbf4_3926);
+    crc.reset();
+    assert_eq!(crc, Crc32::default());
+}
+
+#[test]
+fn adler32_known_values() {
+    assert_eq!(adler32(b""), 1);
+    assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+
+    // Long enough to need reducing modulo 65521 in the middle
+    let data = vec![0xff; 100_000];
+    let mut adler = Adler32::new();
+    for chunk in data.chunks(7) {
+        adler.update(chunk);
+    }
+    assert_eq!(adler.finish(), adler32(&data));
+    assert_eq!(adler32(&data), 0x149a_302c);
+}
+
+#[test]
+fn checksum_writer() {
+    let mut writer = ChecksumWriter::new(Cursor::new(Vec::new()), Crc32::new());
+    assert!(writer.is_active());
+    writer.write_all(b"1234").unwrap();
+    writer.write_all(b"56789").unwrap();
+    assert_eq!(writer.value(), 0xcbf4_3926);
+    assert_eq!(writer.stop(), 0xcbf4_3926);
+    assert!(!writer.is_active());
+
+    // Bytes written while stopped are not checked
+    writer.write_all(b"xyz").unwrap();
+    assert_eq!(writer.value(), 0xcbf4_3926);
+
+    writer.start();
+    assert_eq!(writer.value(), 0);
+    writer.write_all(b"Wiki").unwrap();
+    writer.stop();
+    writer.write_all(b"!").unwrap();
+    writer.resume();
+    writer.write_all(b"pedia").unwrap();
+    assert_eq!(writer.value(), crc32(b"Wikipedia"));
+
+    // Seeking does not change the checksum
+    assert_eq!(writer.seek(SeekFrom::Start(0)).unwrap(), 0);
+    assert_eq!(writer.stream_position().unwrap(), 0);
+    assert_eq!(writer.value(), crc32(b"Wikipedia"));
+
+    let (inner, checksum) = writer.into_inner();
+    assert_eq!(inner.into_inner(), b"123456789xyzWiki!pedia");
+    assert_eq!(checksum.finish(), crc32(b"Wikipedia"));
+}
+
+#[test]
+fn checksum_writer_custom() {
+    #[derive(Default)]
+    struct Sum(u8);
+
+    impl Checksum for Sum {
+        type Output = u8;
+
+        fn update(&mut self, bytes: &[u8]) {
+            self.0 = bytes.iter().fold(self.0, |sum, &b| sum.wrapping_add(b));
+        }
+
+        fn finish(&self) -> u8 {
+            self.0
+        }
+
+        fn reset(&mut self) {
+            self.0 = 0;
+        }
+    }
+
+    let mut writer = ChecksumWriter::new(Vec::new(), Sum::default());
+    writer.write_all(&[0x80, 0x81, 3]).unwrap();
+    assert_eq!(writer.value(), 4);
+    assert_eq!(writer.checksum().0, 4);
+    assert_eq!(writer.get_ref().len(), 3);
+}
+
+#[test]
+fn checksum_reader() {
+    let mut reader = ChecksumReader::new(Cursor::new(b"123456789xyzWiki!pedia"), Crc32::new());
+    assert!(reader.is_active());
+    let mut buf = [0; 9];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(reader.stop(), 0xcbf4_3926);
+    assert!(!reader.is_active());
+
+    // Bytes read while stopped are not checked
+    reader.read_exact(&mut buf[..3]).unwrap();
+    assert_eq!(reader.value(), 0xcbf4_3926);
+
+    reader.start();
+    assert_eq!(reader.value(), 0);
+    reader.read_exact(&mut buf[..4]).unwrap();
+    reader.stop();
+    reader.read_exact(&mut buf[..1]).unwrap();
+    reader.resume();
+    reader.read_exact(&mut buf[..5]).unwrap();
+    assert_eq!(reader.value(), crc32(b"Wikipedia"));
+
+    // Bytes which are skipped over are not checked
+    reader.start();
+    reader.seek(SeekFrom::Start(3)).unwrap();
+    reader.read_exact(&mut buf[..6]).unwrap();
+    assert_eq!(reader.stream_position().unwrap(), 9);
+    assert_eq!(reader.value(), crc32(b"456789"));
+
+    let (inner, checksum) = reader.into_inner();
+    assert_eq!(inner.position(), 9);
+    assert_eq!(checksum.finish(), crc32(b"456789"));
+}
+
+#[test]
+fn checksum_reader_derive() {
+    #[derive(BinRead)]
+    #[br(big)]
+    struct Header {
+        kind: u8,
+        len: u16,
+    }
+
+    let mut reader = ChecksumReader::new(Cursor::new(b"\x01\x00\x02\xff"), Adler32::new());
+    let header = Header::read(&mut reader).unwrap();
+    assert_eq!(header.kind, 1);
+    assert_eq!(header.len, 2);
+    assert_eq!(reader.value(), adler32(b"\x01\x00\x02"));
+}
+
+#[cfg(feature = "digest")]
+#[test]
+fn digest_checksum() {
+    use binrw::io::DigestChecksum;
+    use md5::Md5;
+    use sha2::Sha256;
+
+    let mut reader = ChecksumReader::new(Cursor::new(b"abc"), DigestChecksum::<Sha256>::new());
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).unwrap();
+    assert_eq!(
+        reader.value()[..],
+        *b"\xba\x78\x16\xbf\x8f\x01\xcf\xea\x41\x41\x40\xde\x5d\xae\x22\x23\
+           \xb0\x03\x61\xa3\x96\x17\x7a\x9c\xb4\x10\xff\x61\xf2\x00\x15\xad"
+    );
+
+    let mut writer = ChecksumWriter::new(Vec::new(), DigestChecksum::<Md5>::new());
+    writer.write_all(b"abc").unwrap();
+    assert_eq!(
+        writer.value()[..],
+        *b"\x90\x01\x50\x98\x3c\xd2\x4f\xb0\xd6\x96\x3f\x7d\x28\xe1\x7f\x72"
+    );
+    writer.start();
+    assert_eq!(
+        writer.value()[..],
+        DigestChecksum::<Md5>::new().finish()[..]
+    );
+}
@@ -0,0 +1,157 @@
+use aes::{
+    cipher::{BlockEncrypt, KeyInit, KeyIvInit, StreamCipher},
+    Aes128,
+};
+use binrw::{
+    io::{CbcReader, Cursor, Read, Seek, SeekFrom, StreamCipherReader},
+    BinRead,
+};
+
+type Aes128Ctr = ctr::Ctr128BE<Aes128>;
+
+const KEY: [u8; 16] = [1; 16];
+const IV: [u8; 16] = [2; 16];
+
+fn plaintext() -> Vec<u8> {
+    (0..64).collect()
+}
+
+fn ctr_encrypt(data: &[u8]) -> Vec<u8> {
+    let mut data = data.to_vec();
+    Aes128Ctr::new(&KEY.into(), &IV.into()).apply_keystream(&mut data);
+    data
+}
+
+fn cbc_encrypt(data: &[u8]) -> Vec<u8> {
+    let cipher = Aes128::new(&KEY.into());
+    let mut prev = IV;
+    let mut out = Vec::new();
+    for chunk in data.chunks(16) {
+        let mut block = [0; 16];
+        for (i, byte) in chunk.iter().enumerate() {
+            block[i] = byte ^ prev[i];
+        }
+        cipher.encrypt_block((&mut block).into());
+        out.extend_from_slice(&block);
+        prev = block;
+    }
+    out
+}
+
+#[test]
+fn stream_cipher_reader() {
+    let mut data = vec![0xff; 3];
+    data.extend(ctr_encrypt(&plaintext()));
+    let mut data = Cursor::new(data);
+    data.seek(SeekFrom::Start(3)).unwrap();
+
+    let mut reader = StreamCipherReader::new(data, Aes128Ctr::new(&KEY.into(), &IV.into()));
+    let mut buf = [0; 5];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, [0, 1, 2, 3, 4]);
+
+    assert_eq!(reader.seek(SeekFrom::Current(40)).unwrap(), 48);
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, [45, 46, 47, 48, 49]);
+
+    assert_eq!(reader.seek(SeekFrom::Start(20)).unwrap(), 20);
+    assert_eq!(reader.stream_position().unwrap(), 20);
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, [17, 18, 19, 20, 21]);
+
+    assert_eq!(reader.seek(SeekFrom::End(-2)).unwrap(), 65);
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, [62, 63]);
+
+    // Seeking before the start of the keystream fails and keeps the position
+    reader.seek(SeekFrom::Start(2)).unwrap_err();
+    assert_eq!(reader.stream_position().unwrap(), 67);
+    let (inner, _) = reader.into_inner();
+    assert_eq!(inner.position(), 67);
+}
+
+#[test]
+fn cbc_reader() {
+    let mut data = vec![0xff; 3];
+    data.extend(cbc_encrypt(&plaintext()));
+    let mut data = Cursor::new(data);
+    data.seek(SeekFrom::Start(3)).unwrap();
+
+    let mut reader = CbcReader::new(data, Aes128::new(&KEY.into()), IV.into());
+    let mut buf = [0; 20];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf[..], plaintext()[..20]);
+    assert_eq!(reader.stream_position().unwrap(), 23);
+
+    // Seeking backwards and forwards across blocks
+    assert_eq!(reader.seek(SeekFrom::Start(50)).unwrap(), 50);
+    reader.read_exact(&mut buf[..3]).unwrap();
+    assert_eq!(buf[..3], [47, 48, 49]);
+    assert_eq!(reader.seek(SeekFrom::Current(-40)).unwrap(), 13);
+    reader.read_exact(&mut buf[..5]).unwrap();
+    assert_eq!(buf[..5], [10, 11, 12, 13, 14]);
+    assert_eq!(reader.seek(SeekFrom::End(-4)).unwrap(), 63);
+
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, [60, 61, 62, 63]);
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+
+    reader.seek(SeekFrom::Start(3)).unwrap();
+    rest.clear();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, plaintext());
+
+    // Seeking before the start of the encrypted data fails
+    reader.seek(SeekFrom::Start(2)).unwrap_err();
+    assert_eq!(reader.stream_position().unwrap(), 67);
+}
+
+#[test]
+fn cbc_reader_partial_block() {
+    let mut data = cbc_encrypt(&plaintext());
+    data.truncate(40);
+    let mut reader = CbcReader::new(Cursor::new(data), Aes128::new(&KEY.into()), IV.into());
+    let mut buf = Vec::new();
+    let err = reader.read_to_end(&mut buf).unwrap_err();
+    assert_eq!(err.kind(), binrw::io::ErrorKind::InvalidData);
+    assert_eq!(buf, plaintext()[..32]);
+}
+
+#[test]
+fn cipher_map_stream() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big, import(key: [u8; 16], iv: [u8; 16]))]
+    #[br(map_stream = |reader| StreamCipherReader::new(reader, Aes128Ctr::new(&key.into(), &iv.into())))]
+    struct Secret {
+        len: u8,
+        #[br(count = len)]
+        data: Vec<u8>,
+        #[br(seek_before = SeekFrom::Start(1), restore_position)]
+        first: u8,
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Container {
+        magic: u8,
+        #[br(args(KEY, IV))]
+        secret: Secret,
+    }
+
+    let mut data = vec![0xaa];
+    data.extend(ctr_encrypt(&[3, 4, 5, 6]));
+    let container = Container::read(&mut Cursor::new(data)).unwrap();
+    assert_eq!(
+        container,
+        Container {
+            magic: 0xaa,
+            secret: Secret {
+                len: 3,
+                data: vec![4, 5, 6],
+                first: 3,
+            },
+        }
+    );
+}
@@ -0,0 +1,125 @@
+#![allow(clippy::seek_to_start_instead_of_rewind)]
+use binrw::{
+    io::{CountingReader, CountingWriter, Cursor, Discard, Read, Seek, SeekFrom, Write},
+    BinWrite,
+};
+
+struct PositionCounter<T> {
+    inner: T,
+    queries: usize,
+}
+
+impl<T: Seek> Seek for PositionCounter<T> {
+    fn seek(&mut self, pos: SeekFrom) -> binrw::io::Result<u64> {
+        if pos == SeekFrom::Current(0) {
+            self.queries += 1;
+        }
+        self.inner.seek(pos)
+    }
+}
+
+impl<T: Read> Read for PositionCounter<T> {
+    fn read(&mut self, buf: &mut [u8]) -> binrw::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl<T: Write> Write for PositionCounter<T> {
+    fn write(&mut self, buf: &[u8]) -> binrw::io::Result<usize> {
+        self.inner.write(buf)
+    }
+
+    fn flush(&mut self) -> binrw::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[test]
+fn counting_reader() {
+    let mut cursor = Cursor::new(b"helloworld");
+    cursor.set_position(2);
+    let mut reader = CountingReader::new(PositionCounter {
+        inner: cursor,
+        queries: 0,
+    });
+    let mut buf = [0; 3];
+
+    assert_eq!(reader.stream_position().unwrap(), 2);
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"llo");
+    assert_eq!(reader.stream_position().unwrap(), 5);
+    assert_eq!(reader.bytes_read(), 3);
+    assert_eq!(reader.get_ref().queries, 1);
+
+    assert_eq!(reader.seek(SeekFrom::Current(2)).unwrap(), 7);
+    assert_eq!(reader.stream_position().unwrap(), 7);
+    assert_eq!(reader.read(&mut buf).unwrap(), 3);
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    assert_eq!(reader.stream_position().unwrap(), 10);
+    assert_eq!(reader.bytes_read(), 6);
+    assert_eq!(reader.get_ref().queries, 1);
+
+    reader.get_mut().inner.set_position(0);
+    assert_eq!(reader.stream_position().unwrap(), 0);
+    assert_eq!(reader.into_inner().queries, 2);
+}
+
+#[test]
+fn counting_writer() {
+    let mut writer = CountingWriter::new(PositionCounter {
+        inner: Cursor::new(Vec::new()),
+        queries: 0,
+    });
+
+    writer.write_all(b"hello").unwrap();
+    assert_eq!(writer.stream_position().unwrap(), 5);
+    0x0102_u16.write_be(&mut writer).unwrap();
+    assert_eq!(writer.stream_position().unwrap(), 7);
+    assert_eq!(writer.bytes_written(), 7);
+    assert_eq!(writer.get_ref().queries, 1);
+
+    assert_eq!(writer.seek(SeekFrom::Start(0)).unwrap(), 0);
+    writer.write_all(b"j").unwrap();
+    writer.flush().unwrap();
+    assert_eq!(writer.stream_position().unwrap(), 1);
+    assert_eq!(writer.bytes_written(), 8);
+    assert_eq!(writer.into_inner().inner.into_inner(), b"jello\x01\x02");
+}
+
+#[test]
+fn counting_writer_sink() {
+    #[derive(BinWrite)]
+    #[bw(little)]
+    struct Test {
+        a: u32,
+        #[bw(seek_before = SeekFrom::Current(4))]
+        b: u16,
+        #[bw(restore_position)]
+        c: u8,
+    }
+
+    let mut counter = CountingWriter::sink();
+    Test { a: 1, b: 2, c: 3 }.write(&mut counter).unwrap();
+    assert_eq!(counter.bytes_written(), 7);
+    assert_eq!(counter.stream_position().unwrap(), 10);
+    assert_eq!(counter.get_ref().len(), 11);
+    assert!(!counter.get_ref().is_empty());
+}
+
+#[test]
+fn discard() {
+    let mut discard = Discard::new();
+    assert!(discard.is_empty());
+    assert_eq!(discard.write(b"hello").unwrap(), 5);
+    assert_eq!(discard.stream_position().unwrap(), 5);
+    assert_eq!(discard.seek(SeekFrom::End(-1)).unwrap(), 4);
+    assert_eq!(discard.seek(SeekFrom::Current(6)).unwrap(), 10);
+    assert_eq!(discard.len(), 5);
+    discard.write_all(b"x").unwrap();
+    assert_eq!(discard.len(), 11);
+    discard.seek(SeekFrom::Current(-12)).unwrap_err();
+    discard.seek(SeekFrom::End(-12)).unwrap_err();
+    assert_eq!(discard.seek(SeekFrom::Start(0)).unwrap(), 0);
+    discard.flush().unwrap();
+    assert_eq!(discard.len(), 11);
+}
@@ -0,0 +1,47 @@
+#![allow(clippy::single_range_in_vec_init)]
+use binrw::io::{CoverageReader, Cursor, Read, Seek, SeekFrom};
+
+#[test]
+fn coverage_reader() {
+    let mut reader = CoverageReader::new(Cursor::new(b"0123456789"));
+    let mut buf = [0; 2];
+
+    reader.read_exact(&mut buf).unwrap();
+    reader.seek(SeekFrom::Start(6)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(reader.covered(), &[0..2, 6..8]);
+    assert_eq!(reader.gaps(10), [2..6, 8..10]);
+    assert_eq!(reader.gaps(4), [2..4]);
+
+    reader.seek(SeekFrom::Start(1)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    reader.seek(SeekFrom::Start(4)).unwrap();
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(reader.covered(), &[0..3, 4..8]);
+
+    reader.seek(SeekFrom::Start(3)).unwrap();
+    reader.read_exact(&mut [0; 1]).unwrap();
+    assert_eq!(reader.covered(), &[0..8]);
+
+    assert_eq!(reader.gaps_to_end().unwrap(), [8..10]);
+    assert_eq!(reader.stream_position().unwrap(), 4);
+
+    reader.seek(SeekFrom::Start(8)).unwrap();
+    assert_eq!(reader.read(&mut [0; 4]).unwrap(), 2);
+    assert_eq!(reader.read(&mut [0; 4]).unwrap(), 0);
+    assert!(reader.gaps_to_end().unwrap().is_empty());
+
+    reader.clear();
+    assert!(reader.covered().is_empty());
+    assert_eq!(reader.gaps(10), [0..10]);
+}
+
+#[test]
+fn coverage_reader_offset_start() {
+    let mut cursor = Cursor::new(b"0123456789");
+    cursor.set_position(5);
+    let mut reader = CoverageReader::new(cursor);
+    reader.read_exact(&mut [0; 2]).unwrap();
+    assert_eq!(reader.covered(), &[5..7]);
+    assert_eq!(reader.into_inner().position(), 7);
+}
@@ -0,0 +1,112 @@
+use binrw::{
+    io::{Cursor, DeadlineReader, ErrorKind, NoSeek, Read, ReadTimeout, Result, Seek, SeekFrom},
+    BinReaderExt,
+};
+use std::time::{Duration, Instant};
+
+/// Serves data until it runs out, then stalls.
+struct Stalling {
+    data: Cursor<&'static [u8]>,
+    timeouts: Vec<Option<Duration>>,
+}
+
+impl Stalling {
+    fn new(data: &'static [u8]) -> Self {
+        Self {
+            data: Cursor::new(data),
+            timeouts: Vec::new(),
+        }
+    }
+}
+
+impl Read for Stalling {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        match self.data.read(buf)? {
+            0 if self.timeouts.last().copied().flatten().is_some() => {
+                Err(ErrorKind::WouldBlock.into())
+            }
+            0 => panic!("read would block forever"),
+            n => Ok(n),
+        }
+    }
+}
+
+impl Seek for Stalling {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        self.data.seek(pos)
+    }
+}
+
+impl ReadTimeout for Stalling {
+    fn set_read_timeout(&mut self, timeout: Option<Duration>) -> Result<()> {
+        self.timeouts.push(timeout);
+        Ok(())
+    }
+}
+
+#[test]
+fn deadline_reader_read_timeout() {
+    let timeout = Duration::from_secs(5);
+    let mut reader = DeadlineReader::new(Stalling::new(b"\x01\x02\x03")).read_timeout(timeout);
+    assert_eq!(reader.read_be::<u16>().unwrap(), 0x102);
+    let err = reader.read_be::<u16>().unwrap_err();
+    assert!(matches!(err, binrw::Error::Timeout { pos: 3 }), "{err:?}");
+    assert_eq!(err.to_string(), "timed out at 0x3");
+    assert!(reader
+        .get_ref()
+        .timeouts
+        .iter()
+        .all(|t| *t == Some(timeout)));
+}
+
+#[test]
+fn deadline_reader_deadline() {
+    // The total deadline shortens the read timeout
+    let mut reader = DeadlineReader::new(Stalling::new(b"\x01"))
+        .read_timeout(Duration::from_secs(60))
+        .deadline_in(Duration::from_secs(5));
+    assert_eq!(reader.read_be::<u8>().unwrap(), 1);
+    let timeout = reader.get_ref().timeouts[0].unwrap();
+    assert!(timeout <= Duration::from_secs(5));
+
+    // An expired deadline fails without reading
+    let mut reader = DeadlineReader::new(Stalling::new(b"\x01\x02")).deadline(Instant::now());
+    reader.seek(SeekFrom::Start(1)).unwrap();
+    let err = reader.read_be::<u8>().unwrap_err();
+    assert!(matches!(err, binrw::Error::Timeout { pos: 1 }), "{err:?}");
+    assert!(reader.get_ref().timeouts.is_empty());
+    assert_eq!(reader.stream_position().unwrap(), 1);
+
+    // Timeouts from other sources are not converted
+    let err = binrw::Error::from(std::io::Error::from(ErrorKind::TimedOut));
+    assert!(matches!(err, binrw::Error::Io(_)));
+}
+
+#[test]
+fn deadline_reader_tcp() {
+    use std::{
+        io::Write,
+        net::{TcpListener, TcpStream},
+    };
+
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let addr = listener.local_addr().unwrap();
+    let peer = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        stream.write_all(b"\x01\x02").unwrap();
+        // Stall until the reader gives up and closes the connection
+        let mut buf = [0; 1];
+        let _ = std::io::Read::read(&mut stream, &mut buf);
+    });
+
+    let stream = TcpStream::connect(addr).unwrap();
+    let mut reader = NoSeek::new(
+        DeadlineReader::new(stream)
+            .read_timeout(Duration::from_millis(50))
+            .deadline_in(Duration::from_secs(10)),
+    );
+    let err = reader.read_be::<u32>().unwrap_err();
+    assert!(matches!(err, binrw::Error::Timeout { pos: 2 }), "{err:?}");
+    drop(reader);
+    peer.join().unwrap();
+}
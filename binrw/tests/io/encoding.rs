@@ -0,0 +1,215 @@
+use binrw::{
+    io::{
+        Base64Reader, Base64Writer, Cursor, ErrorKind, HexReader, HexWriter, Read, Seek, SeekFrom,
+        Write,
+    },
+    BinRead, BinWrite,
+};
+
+#[derive(BinRead, BinWrite, Debug, PartialEq)]
+#[brw(big)]
+struct Message {
+    kind: u8,
+    len: u8,
+    #[br(count = len)]
+    data: Vec<u8>,
+    #[brw(seek_before = SeekFrom::Start(1), restore_position)]
+    len_again: u8,
+}
+
+#[test]
+fn hex_reader() {
+    let mut data = Cursor::new(b"xx00010203040506070809FfeE".to_vec());
+    data.seek(SeekFrom::Start(2)).unwrap();
+    let mut reader = HexReader::new(data);
+
+    let mut buf = [0; 3];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(buf, [0, 1, 2]);
+    assert_eq!(reader.stream_position().unwrap(), 3);
+    assert_eq!(reader.seek(SeekFrom::End(-2)).unwrap(), 10);
+    reader.read_exact(&mut buf[..2]).unwrap();
+    assert_eq!(buf[..2], [0xff, 0xee]);
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    assert_eq!(reader.seek(SeekFrom::Current(-5)).unwrap(), 7);
+
+    let mut rest = Vec::new();
+    reader.read_to_end(&mut rest).unwrap();
+    assert_eq!(rest, [7, 8, 9, 0xff, 0xee]);
+
+    reader.seek(SeekFrom::Current(-13)).unwrap_err();
+    assert_eq!(reader.stream_position().unwrap(), 12);
+    assert_eq!(reader.into_inner().position(), 26);
+}
+
+#[test]
+fn hex_reader_invalid() {
+    let mut buf = Vec::new();
+    let err = HexReader::new(Cursor::new(b"0g"))
+        .read_to_end(&mut buf)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+    let err = HexReader::new(Cursor::new(b"012"))
+        .read_to_end(&mut buf)
+        .unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn hex_writer() {
+    let mut data = Cursor::new(b"xx".to_vec());
+    data.seek(SeekFrom::End(0)).unwrap();
+    let mut writer = HexWriter::new(&mut data);
+    writer.write_all(&[0, 0xab, 0x12, 0xff]).unwrap();
+    assert_eq!(writer.stream_position().unwrap(), 4);
+    assert_eq!(writer.seek(SeekFrom::Start(1)).unwrap(), 1);
+    writer.write_all(&[0xcd]).unwrap();
+    assert_eq!(writer.seek(SeekFrom::End(-1)).unwrap(), 3);
+    writer.write_all(&[0x7e; 40]).unwrap();
+    assert_eq!(writer.stream_position().unwrap(), 43);
+
+    let mut expected = b"xx00cd12".to_vec();
+    expected.extend(b"7e".repeat(40));
+    assert_eq!(data.into_inner(), expected);
+}
+
+#[test]
+fn hex_round_trip() {
+    let message = Message {
+        kind: 1,
+        len: 40,
+        data: (0..40).collect(),
+        len_again: 40,
+    };
+    let mut writer = HexWriter::new(Cursor::new(Vec::new()));
+    message.write(&mut writer).unwrap();
+    let data = writer.into_inner().into_inner();
+    assert_eq!(&data[..6], b"012800");
+    assert_eq!(
+        Message::read(&mut HexReader::new(Cursor::new(data))).unwrap(),
+        message
+    );
+}
+
+#[test]
+fn base64_reader() {
+    // "Many hands make light work."
+    let mut data = Cursor::new(b"xxTWFueSBoYW5kcyBtYWtlIGxpZ2h0IHdvcmsu".to_vec());
+    data.seek(SeekFrom::Start(2)).unwrap();
+    let mut reader = Base64Reader::new(data);
+
+    let mut buf = [0; 4];
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"Many");
+    assert_eq!(reader.seek(SeekFrom::End(-5)).unwrap(), 22);
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"work");
+    assert_eq!(reader.seek(SeekFrom::Start(11)).unwrap(), 11);
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"make");
+
+    reader.seek(SeekFrom::Start(0)).unwrap();
+    let mut all = Vec::new();
+    reader.read_to_end(&mut all).unwrap();
+    assert_eq!(all, b"Many hands make light work.");
+    assert_eq!(reader.stream_position().unwrap(), 27);
+}
+
+#[test]
+fn base64_reader_padding() {
+    for (encoded, decoded) in [
+        (&b""[..], &b""[..]),
+        (b"bA==", b"l"),
+        (b"bGk=", b"li"),
+        (b"bGln", b"lig"),
+        (b"bGlnaHQgdw==", b"light w"),
+    ] {
+        let mut reader = Base64Reader::new(Cursor::new(encoded));
+        let mut buf = Vec::new();
+        reader.read_to_end(&mut buf).unwrap();
+        assert_eq!(buf, decoded);
+        assert_eq!(reader.seek(SeekFrom::End(0)).unwrap(), decoded.len() as u64);
+        assert_eq!(reader.read(&mut [0; 4]).unwrap(), 0);
+    }
+}
+
+#[test]
+fn base64_reader_invalid() {
+    for encoded in [&b"bGk"[..], b"bG!n", b"b===", b"bA==bGln"] {
+        let mut buf = Vec::new();
+        let err = Base64Reader::new(Cursor::new(encoded))
+            .read_to_end(&mut buf)
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+}
+
+#[test]
+fn base64_writer() {
+    let mut data = Cursor::new(b"xx".to_vec());
+    data.seek(SeekFrom::End(0)).unwrap();
+    let mut writer = Base64Writer::new(&mut data);
+    writer.write_all(b"Many hands ").unwrap();
+    assert_eq!(writer.stream_position().unwrap(), 11);
+    assert_eq!(writer.buffer(), b"Many hands ");
+    assert_eq!(writer.get_ref().get_ref(), b"xx");
+    writer.flush().unwrap();
+    assert_eq!(writer.get_ref().get_ref(), b"xxTWFueSBoYW5kcyA=");
+
+    writer.write_all(b"mXke light work.").unwrap();
+    assert_eq!(writer.seek(SeekFrom::Current(-15)).unwrap(), 12);
+    writer.write_all(b"a").unwrap();
+    assert_eq!(writer.seek(SeekFrom::End(-1)).unwrap(), 26);
+    writer.write_all(b"!").unwrap();
+    assert_eq!(writer.buffer(), b"Many hands make light work!");
+
+    writer.into_inner().unwrap();
+    assert_eq!(data.into_inner(), b"xxTWFueSBoYW5kcyBtYWtlIGxpZ2h0IHdvcmsh");
+}
+
+#[test]
+fn base64_writer_padding() {
+    for (decoded, encoded) in [
+        (&b""[..], &b""[..]),
+        (b"l", b"bA=="),
+        (b"li", b"bGk="),
+        (b"lig", b"bGln"),
+        (b"light w", b"bGlnaHQgdw=="),
+    ] {
+        let mut data = Cursor::new(Vec::new());
+        {
+            let mut writer = Base64Writer::new(&mut data);
+            writer.write_all(decoded).unwrap();
+        }
+        assert_eq!(data.into_inner(), encoded);
+    }
+}
+
+#[test]
+fn base64_round_trip() {
+    #[derive(BinRead, BinWrite, Debug, PartialEq)]
+    #[brw(big)]
+    struct Record {
+        kind: u8,
+        len: u8,
+        #[br(count = len)]
+        data: Vec<u8>,
+        #[brw(seek_before = SeekFrom::Start(0), restore_position)]
+        kind_again: u8,
+    }
+
+    let record = Record {
+        kind: 1,
+        len: 1,
+        data: vec![2],
+        kind_again: 1,
+    };
+    let mut writer = Base64Writer::new(Cursor::new(Vec::new()));
+    record.write(&mut writer).unwrap();
+    let data = writer.into_inner().unwrap().into_inner();
+    assert_eq!(data, b"AQEC");
+    assert_eq!(
+        Record::read(&mut Base64Reader::new(Cursor::new(data))).unwrap(),
+        record
+    );
+}
@@ -0,0 +1,79 @@
+use binrw::{
+    io::{MmapReader, Read, Seek, SeekFrom},
+    BinRead, BinReadBorrowed,
+};
+use std::io::Write;
+
+struct TempFile(std::path::PathBuf);
+
+impl TempFile {
+    fn new(name: &str, data: &[u8]) -> Self {
+        let path = std::env::temp_dir().join(format!("binrw_{}_{}", name, std::process::id()));
+        std::fs::File::create(&path)
+            .unwrap()
+            .write_all(data)
+            .unwrap();
+        Self(path)
+    }
+
+    fn open(&self) -> std::fs::File {
+        std::fs::File::open(&self.0).unwrap()
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+#[test]
+fn mmap_reader() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Test {
+        offset: u8,
+        #[br(seek_before = SeekFrom::Start(offset.into()), restore_position)]
+        pointee: u16,
+        value: u16,
+    }
+
+    let file = TempFile::new("mmap_reader", b"\x03\x01\x02\x04\x05");
+    // SAFETY: The file is not modified while it is mapped.
+    let mut reader = unsafe { MmapReader::map(&file.open()) }.unwrap();
+    assert_eq!(
+        Test::read(&mut reader).unwrap(),
+        Test {
+            offset: 3,
+            pointee: 0x405,
+            value: 0x102,
+        }
+    );
+    assert_eq!(reader.stream_position().unwrap(), 3);
+
+    let mut buf = Vec::new();
+    assert_eq!(reader.read_to_end(&mut buf).unwrap(), 2);
+    assert_eq!(buf, b"\x04\x05");
+    assert_eq!(reader.as_slice().len(), 5);
+    assert_eq!(reader.into_inner().len(), 5);
+}
+
+#[test]
+fn mmap_reader_borrowed() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(little, borrow = 'a)]
+    struct Test<'a> {
+        len: u8,
+        #[br(count = len)]
+        name: &'a str,
+        #[br(count = 2)]
+        data: &'a [u8],
+    }
+
+    let file = TempFile::new("mmap_reader_borrowed", b"\x02hi\x01\x02");
+    // SAFETY: The file is not modified while it is mapped.
+    let reader = unsafe { MmapReader::map(&file.open()) }.unwrap();
+    let test = Test::read_borrowed(reader.as_ref()).unwrap();
+    assert_eq!(test.name, "hi");
+    assert_eq!(test.data, [1, 2]);
+}
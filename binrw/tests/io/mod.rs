@@ -1,6 +1,27 @@
+#[cfg(feature = "futures-io")]
+mod async_bridge;
+mod bits;
+#[cfg(feature = "bytes")]
+mod buf;
 #[cfg(feature = "std")]
 mod bufreader;
+mod bufwriter;
+mod cached_len;
+mod chain_seek;
+mod checksum;
+#[cfg(feature = "cipher")]
+mod cipher;
+mod counting;
+mod coverage;
+#[cfg(feature = "std")]
+mod deadline;
+mod encoding;
+#[cfg(feature = "mmap")]
+mod mmap;
 #[cfg(not(feature = "std"))]
 mod no_std;
+mod relative;
 mod seek;
 mod take_seek;
+mod tee;
+mod window;
@@ -1,5 +1,5 @@
 #![allow(clippy::seek_to_start_instead_of_rewind)]
-use binrw::io::{Cursor, Read, Seek, SeekFrom};
+use binrw::io::{Cursor, ErrorKind, Read, Seek, SeekFrom};
 use std::io::{Read as StdReadExt, Seek as StdSeekExt};
 
 #[test]
@@ -41,3 +41,75 @@ fn cursor_test() {
     assert_eq!(test.read(&mut buf).unwrap(), test2.read(&mut buf2).unwrap());
     assert_eq!(buf, buf2);
 }
+
+#[test]
+fn cursor_write_slice() {
+    use binrw::io::Write;
+    use std::io::Write as StdWriteExt;
+
+    let mut data = [0u8; 4];
+    let mut data2 = [0u8; 4];
+    let mut test = Cursor::new(&mut data[..]);
+    let mut test2 = std::io::Cursor::new(&mut data2[..]);
+
+    assert_eq!(test.write(b"ab").unwrap(), test2.write(b"ab").unwrap());
+    assert_eq!(
+        test.seek(SeekFrom::Start(1)).unwrap(),
+        test2.seek(std::io::SeekFrom::Start(1)).unwrap(),
+    );
+    // Writes overwrite and do not grow the buffer
+    assert_eq!(test.write(b"xyzw").unwrap(), test2.write(b"xyzw").unwrap());
+    assert_eq!(test.write(b"!").unwrap(), test2.write(b"!").unwrap());
+    assert_eq!(
+        test.write_all(b"!").unwrap_err().kind(),
+        ErrorKind::WriteZero
+    );
+    test.set_position(10);
+    test2.set_position(10);
+    assert_eq!(test.write(b"!").unwrap(), test2.write(b"!").unwrap());
+    assert_eq!(data, data2);
+    assert_eq!(&data, b"axyz");
+
+    let mut test = Cursor::new([0u8; 3]);
+    test.write_all(b"abc").unwrap();
+    test.write_all(b"d").unwrap_err();
+    assert_eq!(test.into_inner(), *b"abc");
+
+    let mut test = Cursor::new(vec![0u8; 2].into_boxed_slice());
+    test.write_all(b"ab").unwrap();
+    assert_eq!(&*test.into_inner(), b"ab");
+}
+
+#[test]
+fn cursor_write_vec() {
+    use binrw::io::Write;
+    use std::io::Write as StdWriteExt;
+
+    let mut test = Cursor::new(b"hello".to_vec());
+    let mut test2 = std::io::Cursor::new(b"hello".to_vec());
+
+    // Writes overwrite existing data, then extend the vector
+    test.set_position(3);
+    test2.set_position(3);
+    assert_eq!(test.write(b"p me").unwrap(), test2.write(b"p me").unwrap());
+    assert_eq!(test.get_ref(), test2.get_ref());
+
+    // Writes past the end fill the gap with zeroes
+    assert_eq!(
+        test.seek(SeekFrom::End(2)).unwrap(),
+        test2.seek(std::io::SeekFrom::End(2)).unwrap(),
+    );
+    assert_eq!(test.write(b"!").unwrap(), test2.write(b"!").unwrap());
+    assert_eq!(test.position(), test2.position());
+    assert_eq!(test.get_ref(), test2.get_ref());
+    assert_eq!(test.get_ref(), b"help me\0\0!");
+
+    let mut data = Vec::new();
+    let mut test = Cursor::new(&mut data);
+    test.write_all(b"abc").unwrap();
+    test.set_position(1);
+    test.write_all(b"X").unwrap();
+    assert_eq!(data, b"aXc");
+
+    assert_eq!(Cursor::new(b"ab".to_vec()), Cursor::new(b"ab".to_vec()));
+}
@@ -0,0 +1,83 @@
+use binrw::{
+    binwrite,
+    io::{Cursor, RelativeWriter, Seek, SeekFrom, Write},
+    BinWrite,
+};
+
+#[test]
+fn relative_writer() {
+    let mut data = Cursor::new(b"helloworld".to_vec());
+    let mut stream = RelativeWriter::with_offset(&mut data, 3).unwrap();
+    assert_eq!(stream.offset(), 3);
+    assert!(stream.is_empty());
+    assert_eq!(stream.stream_position().unwrap(), 0);
+    assert_eq!(stream.get_ref().position(), 3);
+
+    stream.write_all(b"LO").unwrap();
+    assert_eq!(stream.len(), 2);
+    assert_eq!(stream.stream_position().unwrap(), 2);
+    assert_eq!(stream.seek(SeekFrom::Start(4)).unwrap(), 4);
+    stream.write_all(b"R").unwrap();
+    assert_eq!(stream.len(), 5);
+
+    // The end is the furthest written position, not the end of the writer
+    assert_eq!(stream.seek(SeekFrom::End(-2)).unwrap(), 3);
+    assert_eq!(stream.get_ref().position(), 6);
+    stream.write_all(b"O").unwrap();
+    assert_eq!(stream.len(), 5);
+
+    // Invalid seek maintains correct stream position
+    stream.seek(SeekFrom::Current(-5)).unwrap_err();
+    assert_eq!(stream.stream_position().unwrap(), 4);
+
+    assert_eq!(data.into_inner(), b"helLOwORld");
+}
+
+#[test]
+fn relative_writer_new() {
+    let mut data = Cursor::new(Vec::new());
+    data.write_all(b"abc").unwrap();
+    let mut stream = RelativeWriter::new(&mut data).unwrap();
+    assert_eq!(stream.offset(), 3);
+    stream.write_all(b"d").unwrap();
+    assert_eq!(stream.stream_position().unwrap(), 1);
+    assert_eq!(stream.into_inner().position(), 4);
+}
+
+#[test]
+fn relative_writer_nested() {
+    #[binwrite]
+    #[bw(big)]
+    struct Inner {
+        #[bw(calc = 4)]
+        data_offset: u8,
+        #[bw(seek_before = SeekFrom::Start(data_offset.into()))]
+        data: u8,
+    }
+
+    fn write_inner<W: Write + Seek>(
+        value: &Inner,
+        writer: &mut W,
+        endian: binrw::Endian,
+        (): (),
+    ) -> binrw::BinResult<()> {
+        value.write_options(&mut RelativeWriter::new(writer)?, endian, ())
+    }
+
+    #[binwrite]
+    #[bw(big)]
+    struct Outer {
+        header: [u8; 2],
+        #[bw(write_with = write_inner)]
+        inner: Inner,
+    }
+
+    let mut data = Cursor::new(Vec::new());
+    Outer {
+        header: [0xff; 2],
+        inner: Inner { data: 7 },
+    }
+    .write(&mut data)
+    .unwrap();
+    assert_eq!(data.into_inner(), b"\xff\xff\x04\0\0\0\x07");
+}
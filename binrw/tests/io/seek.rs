@@ -114,3 +114,42 @@ fn write_vectored() {
     assert_eq!(stream.get_ref(), b"aaaaaaaaaa");
     assert_eq!(stream.stream_position().unwrap(), 10);
 }
+
+#[test]
+fn forward_reader() {
+    let data = (0..=255).cycle().take(1000).collect::<Vec<u8>>();
+    let mut stream = NoSeek::forward_reader(data.as_slice());
+    let mut buf = [0; 2];
+
+    assert_eq!(stream.seek(SeekFrom::Start(0)).unwrap(), 0);
+    assert_eq!(stream.seek(SeekFrom::Current(2)).unwrap(), 2);
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, &[2, 3]);
+    assert_eq!(stream.seek(SeekFrom::Start(600)).unwrap(), 600);
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, &[88, 89]);
+    assert_eq!(stream.stream_position().unwrap(), 602);
+
+    stream.seek(SeekFrom::Start(601)).unwrap_err();
+    stream.seek(SeekFrom::Current(-1)).unwrap_err();
+    stream.seek(SeekFrom::End(0)).unwrap_err();
+    stream.seek(SeekFrom::Start(1001)).unwrap_err();
+}
+
+#[test]
+fn forward_writer() {
+    let mut stream = NoSeek::forward_writer(Vec::new());
+    stream.write_all(b"ab").unwrap();
+    assert_eq!(stream.seek(SeekFrom::Current(3)).unwrap(), 5);
+    stream.write_all(b"c").unwrap();
+    assert_eq!(stream.seek(SeekFrom::Start(300)).unwrap(), 300);
+    assert_eq!(stream.stream_position().unwrap(), 300);
+
+    stream.seek(SeekFrom::Start(0)).unwrap_err();
+    stream.seek(SeekFrom::End(0)).unwrap_err();
+
+    let data = stream.into_inner();
+    assert_eq!(data.len(), 300);
+    assert_eq!(&data[..6], b"ab\0\0\0c");
+    assert!(data[6..].iter().all(|&b| b == 0));
+}
@@ -52,3 +52,33 @@ fn take_seek_ref() {
     assert_eq!(data.take_seek(5).read(&mut buf).unwrap(), 1);
     assert_eq!(&buf, b"dworl");
 }
+
+#[test]
+fn take_seek_nested_parser() {
+    use binrw::{helpers::until_eof, BinRead, BinReaderExt};
+
+    #[derive(BinRead)]
+    #[br(big)]
+    struct Section {
+        kind: u8,
+        #[br(parse_with = until_eof)]
+        values: Vec<u16>,
+    }
+
+    let data = &mut Cursor::new(b"\x01\0\x02\0\x03\xff\xff");
+    let mut section = data.take_seek(5);
+    let parsed: Section = section.read_be().unwrap();
+    assert_eq!(parsed.kind, 1);
+    assert_eq!(parsed.values, [2, 3]);
+
+    // Seeking back within the window and parsing again reads the same bytes
+    section.seek(SeekFrom::Start(1)).unwrap();
+    section.set_limit(4);
+    let values: [u16; 2] = section.read_be().unwrap();
+    assert_eq!(values, [2, 3]);
+    assert_eq!(section.read(&mut [0]).unwrap(), 0);
+
+    assert_eq!(data.position(), 5);
+    let rest: u16 = data.read_be().unwrap();
+    assert_eq!(rest, 0xffff);
+}
@@ -0,0 +1,53 @@
+#![allow(clippy::seek_to_start_instead_of_rewind)]
+use binrw::{
+    io::{Cursor, Read, Seek, SeekFrom, TeeReader},
+    BinRead, BinWrite,
+};
+
+#[test]
+fn tee_reader() {
+    let mut reader = TeeReader::new(Cursor::new(b"helloworld"), Vec::new());
+    let mut buf = [0; 5];
+
+    reader.read_exact(&mut buf).unwrap();
+    assert_eq!(reader.sink(), b"hello");
+    assert_eq!(reader.stream_position().unwrap(), 5);
+
+    assert_eq!(reader.seek(SeekFrom::Current(2)).unwrap(), 7);
+    assert_eq!(reader.read(&mut buf).unwrap(), 3);
+    assert_eq!(reader.sink(), b"hellorld");
+
+    assert_eq!(reader.seek(SeekFrom::Start(0)).unwrap(), 0);
+    assert_eq!(reader.read(&mut buf[..1]).unwrap(), 1);
+    assert_eq!(reader.sink(), b"hellorldh");
+
+    reader.sink_mut().clear();
+    reader.get_mut().set_position(9);
+    assert_eq!(reader.read(&mut buf).unwrap(), 1);
+    assert_eq!(reader.read(&mut buf).unwrap(), 0);
+    assert_eq!(reader.get_ref().position(), 10);
+
+    let (inner, sink) = reader.into_inner();
+    assert_eq!(inner.position(), 10);
+    assert_eq!(sink, b"d");
+}
+
+#[test]
+fn tee_reader_round_trip() {
+    #[derive(BinRead, BinWrite)]
+    #[brw(little)]
+    struct Record {
+        kind: u8,
+        #[br(count = kind)]
+        values: Vec<u16>,
+    }
+
+    let data = b"\x02\x01\x02\x03\x04\xff";
+    let mut reader = TeeReader::new(Cursor::new(data), Cursor::new(Vec::new()));
+    let record = Record::read(&mut reader).unwrap();
+    let (_, raw) = reader.into_inner();
+
+    let mut out = Cursor::new(Vec::new());
+    record.write(&mut out).unwrap();
+    assert_eq!(out.into_inner(), raw.into_inner());
+}
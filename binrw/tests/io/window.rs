@@ -0,0 +1,90 @@
+#![allow(clippy::seek_to_start_instead_of_rewind)]
+use binrw::{
+    io::{Cursor, Read, Seek, SeekFrom, Window},
+    BinRead,
+};
+
+#[test]
+fn window() {
+    let mut data = Cursor::new(b"helloworld");
+    let mut stream = Window::new(&mut data, 3, 5).unwrap();
+    assert_eq!(stream.offset(), 3);
+    assert_eq!(stream.len(), 5);
+    assert!(!stream.is_empty());
+    assert_eq!(stream.stream_position().unwrap(), 0);
+    assert_eq!(stream.get_ref().position(), 3);
+
+    let mut buf = Vec::new();
+    assert_eq!(stream.read_to_end(&mut buf).unwrap(), 5);
+    assert_eq!(buf, b"lowor");
+    assert_eq!(stream.stream_position().unwrap(), 5);
+
+    let mut buf = [0; 3];
+    assert_eq!(stream.seek(SeekFrom::Start(1)).unwrap(), 1);
+    stream.read_exact(&mut buf).unwrap();
+    assert_eq!(&buf, b"owo");
+    assert_eq!(stream.seek(SeekFrom::Current(-4)).unwrap(), 0);
+    assert_eq!(stream.read(&mut buf[..1]).unwrap(), 1);
+    assert_eq!(buf[0], b'l');
+    assert_eq!(stream.seek(SeekFrom::End(-1)).unwrap(), 4);
+    assert_eq!(stream.read(&mut buf).unwrap(), 1);
+    assert_eq!(buf[0], b'r');
+
+    // Seeking past the end of the window does not read past it
+    assert_eq!(stream.seek(SeekFrom::End(1)).unwrap(), 6);
+    assert_eq!(stream.read(&mut buf).unwrap(), 0);
+
+    // Invalid seek maintains correct stream position
+    stream.seek(SeekFrom::Current(-7)).unwrap_err();
+    assert_eq!(stream.stream_position().unwrap(), 6);
+
+    assert_eq!(data.position(), 9);
+    Window::new(&mut data, u64::MAX, 1).unwrap_err();
+}
+
+#[test]
+fn window_nested_parser() {
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Inner {
+        offset: u8,
+        #[br(seek_before = SeekFrom::Start(offset.into()), restore_position)]
+        value: u16,
+        #[br(parse_with = binrw::helpers::until_eof)]
+        rest: Vec<u8>,
+    }
+
+    fn parse_window<R: Read + Seek>(
+        reader: &mut R,
+        endian: binrw::Endian,
+        (len,): (u8,),
+    ) -> binrw::BinResult<Inner> {
+        let pos = reader.stream_position()?;
+        let inner =
+            Inner::read_options(&mut Window::new(&mut *reader, pos, len.into())?, endian, ())?;
+        reader.seek(SeekFrom::Start(pos + u64::from(len)))?;
+        Ok(inner)
+    }
+
+    #[derive(BinRead, Debug, PartialEq)]
+    #[br(big)]
+    struct Outer {
+        len: u8,
+        #[br(parse_with = parse_window, args(len))]
+        inner: Inner,
+        trailer: u8,
+    }
+
+    assert_eq!(
+        Outer::read(&mut Cursor::new(b"\x04\x02\0\x01\x02\xff")).unwrap(),
+        Outer {
+            len: 4,
+            inner: Inner {
+                offset: 2,
+                value: 0x102,
+                rest: vec![0, 1, 2],
+            },
+            trailer: 0xff,
+        }
+    );
+}
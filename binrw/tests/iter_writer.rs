@@ -0,0 +1,40 @@
+use binrw::{io::Cursor, BinWrite, Endian, IterWriter};
+
+#[test]
+fn iter_writer() {
+    let values = [1_u16, 2, 3];
+    let writer = IterWriter::new(values.iter().map(|value| value * 2));
+
+    let mut output = Cursor::new(Vec::new());
+    writer.write_options(&mut output, Endian::Big, ()).unwrap();
+    // Writing again restarts the iterator
+    writer
+        .write_options(&mut output, Endian::Little, ())
+        .unwrap();
+    assert_eq!(output.into_inner(), [0, 2, 0, 4, 0, 6, 2, 0, 4, 0, 6, 0]);
+}
+
+#[test]
+fn iter_writer_borrowed_items() {
+    #[derive(BinWrite)]
+    #[bw(big)]
+    struct Record {
+        id: u8,
+    }
+
+    #[derive(BinWrite)]
+    #[bw(big)]
+    struct Export<'a> {
+        values: IterWriter<core::iter::Rev<core::slice::Iter<'a, Record>>>,
+    }
+
+    let records = [Record { id: 1 }, Record { id: 2 }];
+    let export = Export {
+        values: records.iter().rev().into(),
+    };
+
+    let mut output = Cursor::new(Vec::new());
+    export.write(&mut output).unwrap();
+    assert_eq!(output.into_inner(), [2, 1]);
+    assert_eq!(format!("{:?}", export.values), "IterWriter { .. }");
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "std")]
+
+use binrw::{
+    error::{DefaultMessages, MessageFormatter},
+    io::Cursor,
+    BinRead, Error,
+};
+use core::fmt;
+
+struct Product;
+
+impl MessageFormatter for Product {
+    fn bad_magic(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+        pos: u64,
+        found: &dyn fmt::Debug,
+    ) -> fmt::Result {
+        write!(f, "E001: wrong file signature {found:?} at offset {pos}")
+    }
+
+    fn assert_fail(&self, f: &mut fmt::Formatter<'_>, pos: u64, message: &str) -> fmt::Result {
+        write!(f, "E002: {message} (offset {pos})")
+    }
+
+    fn assertion_failed(&self, f: &mut fmt::Formatter<'_>, condition: &str) -> fmt::Result {
+        write!(f, "check `{condition}` did not hold")
+    }
+
+    fn no_variant_match(&self, f: &mut fmt::Formatter<'_>, pos: u64) -> fmt::Result {
+        write!(f, "E003: unknown record at offset {pos}")
+    }
+}
+
+#[allow(dead_code)]
+#[derive(BinRead, Debug)]
+#[br(big, magic = b"AB")]
+struct Header {
+    #[br(assert(version == 1))]
+    version: u8,
+}
+
+#[allow(dead_code)]
+#[derive(BinRead, Debug)]
+#[br(big)]
+enum Record {
+    #[br(magic = 1u8)]
+    One(u8),
+    #[br(magic = 2u8)]
+    Two(u8),
+}
+
+#[test]
+fn display_with() {
+    let err = Header::read(&mut Cursor::new(b"ZZ\x01")).unwrap_err();
+    assert_eq!(
+        err.display_with(&Product).to_string(),
+        "E001: wrong file signature [90, 90] at offset 0"
+    );
+    assert_eq!(err.to_string(), "bad magic at 0x0: [90, 90]");
+
+    let err = Header::read(&mut Cursor::new(b"AB\x02")).unwrap_err();
+    assert_eq!(
+        err.display_with(&Product).to_string(),
+        "E002: assertion failed: `version == 1` (offset 0)"
+    );
+
+    let err = Record::read(&mut Cursor::new(b"\x03")).unwrap_err();
+    assert!(err
+        .display_with(&Product)
+        .to_string()
+        .starts_with("E003: unknown record at offset 0:\n"));
+    assert_eq!(
+        Error::NoVariantMatch { pos: 5 }
+            .display_with(&Product)
+            .to_string(),
+        "E003: unknown record at offset 5"
+    );
+}
+
+#[test]
+fn with_message_formatter() {
+    let (err, message) = binrw::error::with_message_formatter(&Product, || {
+        let err = Header::read(&mut Cursor::new(b"AB\x02")).unwrap_err();
+        let message = err.to_string();
+        (err, message)
+    });
+    assert_eq!(
+        message,
+        "E002: check `version == 1` did not hold (offset 0)"
+    );
+    assert_eq!(err.to_string(), "check `version == 1` did not hold at 0x0");
+
+    let err = Header::read(&mut Cursor::new(b"AB\x02")).unwrap_err();
+    assert_eq!(err.to_string(), "assertion failed: `version == 1` at 0x0");
+    assert_eq!(
+        err.display_with(&DefaultMessages).to_string(),
+        "assertion failed: `version == 1` at 0x0"
+    );
+}
@@ -1,4 +1,4 @@
-use binrw::{binwrite, BinReaderExt, BinWriterExt, VecArgs};
+use binrw::{binread, binwrite, io::Seek, BinReaderExt, BinWriterExt, Endian, VecArgs};
 
 #[test]
 #[allow(non_snake_case)]
@@ -36,6 +36,41 @@ fn BinReaderExt() {
     );
 }
 
+#[test]
+#[allow(non_snake_case)]
+fn BinReaderExt_peek() {
+    #[binread]
+    #[derive(Debug)]
+    #[br(big, magic = 1u8)]
+    struct Tagged(u16);
+
+    let mut data = binrw::io::Cursor::new(b"\x01\x02\x03\x04");
+    assert_eq!(data.peek::<Tagged>().unwrap().0, 0x203);
+    assert_eq!(data.peek_be::<u16>().unwrap(), 0x102);
+    assert_eq!(data.peek_le::<u16>().unwrap(), 0x201);
+    #[cfg(target_endian = "little")]
+    assert_eq!(data.peek_ne::<u16>().unwrap(), 0x201);
+    #[cfg(target_endian = "big")]
+    assert_eq!(data.peek_ne::<u16>().unwrap(), 0x102);
+    assert_eq!(data.peek_type::<u32>(Endian::Big).unwrap(), 0x0102_0304);
+    assert_eq!(
+        data.peek_type_args::<Vec<u8>>(Endian::Big, VecArgs::builder().count(3).finalize())
+            .unwrap(),
+        [1, 2, 3]
+    );
+    assert_eq!(data.peek_bytes(2).unwrap(), [1, 2]);
+    assert_eq!(data.stream_position().unwrap(), 0);
+
+    // Failed peeks restore the position and report where the error occurred
+    data.set_position(1);
+    let error = data.peek::<Tagged>().unwrap_err();
+    assert!(matches!(error, binrw::Error::BadMagic { pos: 1, .. }));
+    data.peek_bytes(4).unwrap_err();
+    data.peek_be::<u32>().unwrap_err();
+    assert_eq!(data.stream_position().unwrap(), 1);
+    assert_eq!(data.read_be::<u16>().unwrap(), 0x203);
+}
+
 #[test]
 #[allow(non_snake_case)]
 fn BinWriterExt() {
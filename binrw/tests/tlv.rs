@@ -0,0 +1,63 @@
+use binrw::{
+    io::{Cursor, Seek, SeekFrom},
+    tlv::{self, Tlv, TlvIter},
+    BinRead, BinWrite, Endian, Error,
+};
+
+#[test]
+fn tlv_round_trip() {
+    let record = Tlv::<u16, u8>::new(0x9f02, b"\x01\x02\x03".to_vec()).unwrap();
+    let mut x = Cursor::new(Vec::new());
+    record.write_be(&mut x).unwrap();
+    assert_eq!(x.get_ref(), b"\x9f\x02\x03\x01\x02\x03");
+
+    x.seek(SeekFrom::Start(0)).unwrap();
+    assert_eq!(Tlv::<u16, u8>::read_be(&mut x).unwrap(), record);
+}
+
+#[test]
+fn tlv_new_len_overflow() {
+    assert!(Tlv::<u8, u8>::new(0, vec![0; 256]).is_err());
+}
+
+#[test]
+fn tlv_iter_overrun() {
+    let mut x = Cursor::new(b"\x01\x01a\x02\x03bcd");
+    let mut iter = TlvIter::<_, u8, u8>::new(&mut x, Endian::Big, 6).unwrap();
+    assert_eq!(iter.next().unwrap().unwrap().value, b"a");
+    assert!(matches!(
+        iter.next(),
+        Some(Err(Error::AssertFail { pos: 3, .. }))
+    ));
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn tlv_iter_starts_at_current_position() {
+    let mut x = Cursor::new(b"\xff\xff\x01\x00\x02\x00");
+    x.seek(SeekFrom::Start(2)).unwrap();
+    let tags = TlvIter::<_, u8, u8>::new(&mut x, Endian::Big, 4)
+        .unwrap()
+        .map(|record| record.unwrap().tag)
+        .collect::<Vec<_>>();
+    assert_eq!(tags, [1, 2]);
+}
+
+#[test]
+fn tlv_dispatch_skips_unread_value() {
+    let mut x = Cursor::new(b"\x01\x03\x00\x05\xff\x02");
+    let read = tlv::dispatch::<u8, u8, _, _, _>(|tag, value, endian| {
+        assert_eq!(tag, 1);
+        u16::read_options(value, endian, ())
+    });
+    assert_eq!(read(&mut x, Endian::Big, ()).unwrap(), 5);
+    assert_eq!(x.position(), 5);
+}
+
+#[test]
+fn tlv_dispatch_value_is_bounded() {
+    let mut x = Cursor::new(b"\x01\x01\x00\x05");
+    let read =
+        tlv::dispatch::<u8, u8, _, _, _>(|_, value, endian| u16::read_options(value, endian, ()));
+    assert!(read(&mut x, Endian::Big, ()).unwrap_err().is_eof());
+}
@@ -0,0 +1,7 @@
+use binrw::BinRead;
+
+#[derive(BinRead)]
+#[br(pre_assert(true, |_, _| "oops"))]
+struct Foo;
+
+fn main() {}
@@ -0,0 +1,8 @@
+use binrw::BinRead;
+
+#[derive(BinRead)]
+union Foo {
+    a: i32,
+}
+
+fn main() {}
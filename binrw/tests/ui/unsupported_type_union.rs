@@ -1,13 +0,0 @@
-use binrw::{BinRead, binread};
-
-#[derive(BinRead)]
-union Foo {
-    a: i32,
-}
-
-#[binread]
-union Bar {
-    a: i32,
-}
-
-fn main() {}
@@ -101,6 +101,8 @@ impl BacktraceFrame {
 
         if let Some(line_highlights) = self.syntax_info.lines.get(&line_num) {
             let line_len = line.len() + start_col;
+            // ranges of multi-line spans may extend past either end of the line
+            let clamp = |column: usize| column.clamp(start_col, line_len);
 
             // syntax highlighting on this line
             let highlights = &line_highlights.highlights;
@@ -110,18 +112,16 @@ impl BacktraceFrame {
                 .filter(|&(i, highlight)| {
                     i == 0 || !highlights[i - 1].0.contains(&highlight.0.start)
                 })
-                .map(|(_, (range, color))| {
-                    (range.start.min(line_len)..range.end.min(line_len), color)
-                });
+                .map(|(_, (range, color))| (clamp(range.start)..clamp(range.end), color));
             let highlights_next_start = line_highlights
                 .highlights
                 .iter()
                 .skip(1)
-                .map(|x| x.0.start)
+                .map(|x| clamp(x.0.start))
                 .chain(core::iter::once(start_col + line.len()));
 
             if let Some((first_range, _)) = line_highlights.highlights.first() {
-                let component = &line[..first_range.start - start_col];
+                let component = &line[..clamp(first_range.start) - start_col];
 
                 write!(f, "{}", conditional_bold(&component, should_highlight))?;
             } else {
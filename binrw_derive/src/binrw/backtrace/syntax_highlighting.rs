@@ -1,4 +1,4 @@
-use super::{end, start};
+use super::{end, start, LineColumn};
 use crate::binrw::parser::{
     AssertionError, CondEndian, Condition, ErrContext, FieldMode, Map, PassedArgs, StructField,
 };
@@ -37,7 +37,10 @@ impl Color {
     pub(crate) fn into_owo(self) -> owo_colors::XtermColors {
         match self {
             Self::String => XtermColors::DollyYellow,
-            Self::Char | Self::Number => XtermColors::Heliotrope,
+            // Char and Number use different colors so that the binrw runtime
+            // can tell them apart when applying a theme
+            Self::Char => XtermColors::LightHeliotrope,
+            Self::Number => XtermColors::Heliotrope,
             Self::Keyword => XtermColors::DarkRose,
             Self::Function => XtermColors::RioGrandeGreen,
             Self::Unary => XtermColors::MalibuBlue,
@@ -82,16 +85,39 @@ struct Visitor {
 
 impl SyntaxInfo {
     fn highlight_color(&mut self, span: Span, color: Color) {
-        let start = start(span);
-        let end = end(span);
-
-        let line = self.lines.entry(start.line()).or_default();
-
-        assert_eq!(start.line(), end.line());
-        line.highlights.push((start.column()..end.column(), color));
+        for (line, range) in line_ranges(&start(span), &end(span)) {
+            self.lines
+                .entry(line)
+                .or_default()
+                .highlights
+                .push((range, color));
+        }
     }
 }
 
+/// Splits the span from `start` to `end` into the range of columns it covers on
+/// each line.
+///
+/// Ranges which continue onto the next line extend to `usize::MAX`, and ranges
+/// which continue from the previous line start at column 0; they are clamped to
+/// the text of each line when it is written.
+fn line_ranges(
+    start: &LineColumn,
+    end: &LineColumn,
+) -> impl Iterator<Item = (usize, Range<usize>)> {
+    let (start_line, start_column) = (start.line(), start.column());
+    let (end_line, end_column) = (end.line(), end.column());
+    (start_line..=end_line).map(move |line| {
+        let from = if line == start_line { start_column } else { 0 };
+        let to = if line == end_line {
+            end_column
+        } else {
+            usize::MAX
+        };
+        (line, from..to)
+    })
+}
+
 pub(super) fn get_syntax_highlights(field: &StructField) -> SyntaxInfo {
     let mut visit = Visitor::default();
 
@@ -251,7 +277,9 @@ fn visit_expr_attributes(field: &StructField, visitor: &mut Visitor) {
     for assert in &field.assertions {
         visit!(assert.condition.clone());
 
-        let (AssertionError::Message(err) | AssertionError::Error(err)) = assert.consequent.clone();
+        let (AssertionError::Message(err)
+        | AssertionError::Error(err)
+        | AssertionError::Closure(err)) = assert.consequent.clone();
         visit!(err);
     }
 
@@ -278,23 +306,14 @@ impl Parse for ArgList {
 
 impl<'ast> Visit<'ast> for Visitor {
     fn visit_lit(&mut self, lit: &'ast syn::Lit) {
-        let start = start(lit.span());
-        let end = end(lit.span());
-
-        // syntax highlighting for multi-line spans isn't supported yet (sorry)
-        if start.line() == end.line() {
-            let lines = self.syntax_info.lines.entry(start.line()).or_default();
-
-            lines.highlights.push((
-                start.column()..end.column(),
-                match lit {
-                    Lit::Str(_) | Lit::ByteStr(_) => Color::String,
-                    Lit::Byte(_) | Lit::Char(_) => Color::Char,
-                    Lit::Int(_) | Lit::Float(_) | Lit::Bool(_) => Color::Number,
-                    Lit::Verbatim(_) => return,
-                },
-            ));
-        }
+        let color = match lit {
+            Lit::Str(_) | Lit::ByteStr(_) => Color::String,
+            Lit::Byte(_) | Lit::Char(_) => Color::Char,
+            Lit::Int(_) | Lit::Float(_) | Lit::Bool(_) => Color::Number,
+            Lit::Verbatim(_) => return,
+        };
+
+        self.syntax_info.highlight_color(lit.span(), color);
     }
 
     fn visit_ident(&mut self, ident: &'ast proc_macro2::Ident) {
@@ -329,6 +348,8 @@ impl<'ast> Visit<'ast> for Visitor {
 
     fn visit_expr_call(&mut self, call: &'ast syn::ExprCall) {
         if let syn::Expr::Path(path) = &*call.func {
+            // the segments before the called function are highlighted by
+            // `visit_path` when the function path is walked below
             if let Some(ident) = path.path.segments.last() {
                 let ident = &ident.ident;
                 let start = start(ident.span());
@@ -363,14 +384,44 @@ impl<'ast> Visit<'ast> for Visitor {
     }
 
     fn visit_path(&mut self, path: &'ast syn::Path) {
-        if path.segments.len() > 1 {
-            if let Some(first_segment) = path.segments.iter().next() {
+        // std::mem::size_of::<T>
+        // ^^^  ^^^
+        // |____|______ every segment before the last is a crate, module, or type
+        //
+        for segment in path.segments.iter().rev().skip(1) {
+            self.syntax_info
+                .highlight_color(segment.ident.span(), Color::Keyword);
+        }
+
+        visit::visit_path(self, path);
+    }
+
+    fn visit_type_path(&mut self, ty: &'ast syn::TypePath) {
+        // size_of::<crate::Header>()
+        //                  ^^^^^^
+        //                  |______ the last segment of a type path is the type
+        //
+        if let Some(segment) = ty.path.segments.last() {
+            self.syntax_info
+                .highlight_color(segment.ident.span(), Color::Keyword);
+        }
+
+        visit::visit_type_path(self, ty);
+    }
+
+    fn visit_expr_field(&mut self, field: &'ast syn::ExprField) {
+        // self.header.items.0
+        // ^^^^
+        // |___ the base of a field access chain
+        //
+        if let syn::Expr::Path(base) = &*field.base {
+            if base.path.is_ident("self") {
                 self.syntax_info
-                    .highlight_color(first_segment.ident.span(), Color::Keyword);
+                    .highlight_color(base.path.span(), Color::Keyword);
             }
         }
 
-        visit::visit_path(self, path);
+        visit::visit_expr_field(self, field);
     }
 }
 
@@ -427,3 +478,61 @@ impl Parse for FieldValue {
         Ok(Self { ident, expr })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn highlight_colors(expr: &str) -> Vec<Color> {
+        let mut visitor = Visitor::default();
+        visit::visit_expr(&mut visitor, &syn::parse_str(expr).unwrap());
+        visitor
+            .syntax_info
+            .lines
+            .into_values()
+            .flat_map(|line| line.highlights)
+            .map(|(_, color)| color)
+            .collect()
+    }
+
+    #[test]
+    fn highlight_full_paths() {
+        let colors = highlight_colors("std::mem::size_of::<crate::Header>()");
+        let count = |color| colors.iter().filter(|&&c| c == color).count();
+        // std, mem, crate, Header
+        assert_eq!(count(Color::Keyword), 4);
+        // size_of
+        assert_eq!(count(Color::Function), 1);
+    }
+
+    #[test]
+    fn highlight_field_chains() {
+        let colors = highlight_colors("self.header.items.0 == Kind::Empty");
+        let count = |color| colors.iter().filter(|&&c| c == color).count();
+        // self, ==, Kind
+        assert_eq!(count(Color::Keyword), 3);
+        // 0
+        assert_eq!(count(Color::Number), 1);
+    }
+
+    #[test]
+    fn line_ranges_single_line() {
+        let ranges = line_ranges(
+            &LineColumn { line: 3, column: 4 },
+            &LineColumn { line: 3, column: 9 },
+        );
+        assert_eq!(ranges.collect::<Vec<_>>(), [(3, 4..9)]);
+    }
+
+    #[test]
+    fn line_ranges_multi_line() {
+        let ranges = line_ranges(
+            &LineColumn { line: 3, column: 4 },
+            &LineColumn { line: 5, column: 2 },
+        );
+        assert_eq!(
+            ranges.collect::<Vec<_>>(),
+            [(3, 4..usize::MAX), (4, 0..usize::MAX), (5, 0..2)]
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use crate::binrw::parser::Struct;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned, ToTokens};
+use syn::spanned::Spanned;
+
+/// Generates compile-time assertions that the in-memory layout of a struct
+/// matches its binary layout.
+///
+/// The binary layout of each field is the size of its type, with fields
+/// placed one after another in declaration order, so this only holds for
+/// `#[repr(C, packed)]` structs whose field types have no padding of their
+/// own.
+pub(super) fn generate(st: &Struct, derive_input: &syn::DeriveInput) -> TokenStream {
+    let name = &derive_input.ident;
+
+    if !derive_input.generics.params.is_empty() {
+        return syn::Error::new(
+            derive_input.generics.span(),
+            "`assert_layout` is not supported on generic types",
+        )
+        .into_compile_error();
+    }
+
+    let mut offset = quote! { 0 };
+    let fields = st.fields.iter().enumerate().map(|(index, field)| {
+        let ty = &field.ty;
+        let member = field.field.ident.as_ref().map_or_else(
+            || syn::Index::from(index).into_token_stream(),
+            ToTokens::to_token_stream,
+        );
+        let message = format!(
+            "binary layout of `{name}` does not match its memory layout at field `{member}`"
+        );
+        let expected = offset.clone();
+        offset = quote! { #offset + ::core::mem::size_of::<#ty>() };
+
+        quote_spanned! {field.field.span()=>
+            let field = unsafe { ::core::ptr::addr_of!((*base).#member) };
+            let actual = unsafe { field.cast::<u8>().offset_from(base.cast::<u8>()) };
+            assert!(actual as usize == #expected, #message);
+        }
+    });
+
+    let message = format!("binary size of `{name}` does not match its memory size");
+
+    quote! {
+        #[allow(clippy::all, unused_unsafe, unused_variables)]
+        const _: () = {
+            let value = ::core::mem::MaybeUninit::<#name>::uninit();
+            let base = value.as_ptr();
+            #(#fields)*
+            assert!(::core::mem::size_of::<#name>() == #offset, #message);
+        };
+    }
+}
@@ -10,16 +10,17 @@ pub(crate) fn generate<const WRITE: bool>(
     let name = &derive_input.ident;
     let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
 
-    let magic = input.magic().as_ref().map(|magic| {
+    // Magic computed at runtime has no constant value to expose
+    let magic = input.magic().as_ref().and_then(|magic| {
         let magic_meta = if WRITE { WRITE_MAGIC } else { READ_MAGIC };
-        let ty = TokenStream::from(magic.kind());
+        let ty = magic.kind().ty()?;
         let val = magic.deref_value();
-        quote! {
+        Some(quote! {
             impl #impl_generics #magic_meta for #name #ty_generics #where_clause {
                 type MagicType = #ty;
                 const MAGIC: Self::MagicType = #val;
             }
-        }
+        })
     });
 
     let endian_meta = if WRITE { WRITE_ENDIAN } else { READ_ENDIAN };
@@ -34,7 +35,11 @@ pub(crate) fn generate<const WRITE: bool>(
             Map::Map(_) | Map::Try(_) => Some(quote! {
                 #META_ENDIAN_KIND::None
             }),
-            Map::Repr(repr) => ["i8", "u8"].contains(&repr.to_string().as_str()).then(|| {
+            // the variants of a keyed repr enum may contain data of any
+            // byte order
+            Map::Repr(repr) => (["i8", "u8"].contains(&repr.to_string().as_str())
+                && !matches!(input, Input::Enum(e) if e.is_keyed_repr()))
+            .then(|| {
                 quote! { <(#repr) as #endian_meta>::ENDIAN }
             }),
         },
@@ -1,3 +1,4 @@
+mod layout;
 mod meta;
 mod read_options;
 pub(crate) mod sanitization;
@@ -6,30 +7,40 @@ mod write_options;
 use crate::{
     binrw::parser::{
         Assert, AssertionError, CondEndian, Imports, Input, ParseResult, PassedArgs, StructField,
+        Union,
     },
+    meta_types::IdentTypeMaybeDefault,
     named_args::{arg_type_name, derive_from_imports},
-    util::{quote_spanned_any, IdentStr},
+    util::{contains_ident, quote_spanned_any, IdentStr},
 };
+use alloc::borrow::Cow;
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, quote_spanned, ToTokens};
 use sanitization::{
-    ARGS, ARGS_LIFETIME, ARGS_MACRO, ASSERT, ASSERT_ERROR_FN, BINREAD_TRAIT, BINWRITE_TRAIT,
-    BIN_ERROR, BIN_RESULT, ENDIAN_ENUM, OPT, POS, READER, READ_TRAIT, SEEK_TRAIT, TEMP, WRITER,
-    WRITE_TRAIT,
+    ARGS, ARGS_LIFETIME, ARGS_MACRO, ASSERT, ASSERT_ERROR_FN, ASSERT_ERROR_WITH,
+    BINREAD_BORROWED_TRAIT, BINREAD_TRAIT, BINWRITE_TRAIT, BIN_ERROR, BIN_RESULT, CURSOR,
+    DBG_EPRINTLN, ENDIAN_ENUM, OPT, POS, READER, READ_TRAIT, SEEK_TRAIT, TEMP, WRITER, WRITE_TRAIT,
 };
-use syn::{spanned::Spanned, DeriveInput, Ident, Type};
+use syn::{spanned::Spanned, DeriveInput, Generics, Ident, Type};
 
 pub(crate) fn generate_impl<const WRITE: bool>(
     derive_input: &DeriveInput,
     binrw_input: &ParseResult<Input>,
 ) -> TokenStream {
     let (arg_type, arg_type_declaration) = match binrw_input {
-        ParseResult::Ok(binrw_input) | ParseResult::Partial(binrw_input, _) => generate_imports(
-            binrw_input.imports(),
-            &derive_input.ident,
-            &derive_input.vis,
-            WRITE,
-        ),
+        ParseResult::Ok(binrw_input) | ParseResult::Partial(binrw_input, _) => {
+            if let Some(repr) = binrw_input.repr_args() {
+                (generate_repr_args(repr, WRITE), None)
+            } else {
+                generate_imports(
+                    binrw_input.imports(),
+                    &derive_input.ident,
+                    &derive_input.vis,
+                    &derive_input.generics,
+                    WRITE,
+                )
+            }
+        }
         ParseResult::Err(_) => (quote! { () }, None),
     };
 
@@ -42,17 +53,36 @@ pub(crate) fn generate_impl<const WRITE: bool>(
         ParseResult::Err(_) => None,
     };
 
+    let layout_assertions = match binrw_input {
+        ParseResult::Ok(binrw_input) => binrw_input
+            .assert_layout()
+            .map(|st| layout::generate(st, derive_input)),
+        ParseResult::Partial(..) | ParseResult::Err(_) => None,
+    };
+
     quote! {
         #trait_impl
         #meta_impls
+        #layout_assertions
         #arg_type_declaration
     }
 }
 
+fn generate_repr_args(repr: &TokenStream, is_write: bool) -> TokenStream {
+    let trait_name = if is_write {
+        BINWRITE_TRAIT
+    } else {
+        BINREAD_TRAIT
+    };
+    let lifetime = get_args_lifetime(repr.span());
+    quote! { <#repr as #trait_name>::Args<#lifetime> }
+}
+
 fn generate_imports(
     imports: &Imports,
     type_name: &Ident,
     ty_vis: &syn::Visibility,
+    ty_generics: &Generics,
     is_write: bool,
 ) -> (TokenStream, Option<TokenStream>) {
     use syn::fold::Fold;
@@ -112,38 +142,84 @@ fn generate_imports(
                 .iter()
                 .any(|arg| has_elided_lifetime(&arg.ty))
                 .then(|| get_args_lifetime(type_name.span()));
+
+            let generics = get_import_generics(ty_generics, lifetime, args);
+
             let defs = derive_from_imports(
                 type_name,
                 is_write,
                 &name,
                 ty_vis,
-                lifetime.clone(),
+                generics.params.iter().cloned(),
                 args.iter().map(|arg| {
                     let mut arg = arg.clone();
                     arg.ty = ExpandLifetimes.fold_type(arg.ty);
                     arg
                 }),
             );
+            let (_, ty_generics, _) = generics.split_for_impl();
             (
-                if let Some(lifetime) = lifetime {
-                    quote_spanned! { type_name.span()=> #name<#lifetime> }
-                } else {
-                    name.into_token_stream()
-                },
+                quote_spanned! { type_name.span()=> #name #ty_generics },
                 Some(defs),
             )
         }
     }
 }
 
+/// Returns the generic parameters for a named arguments type. Type and const
+/// parameters of the deriving type are only in scope in the generated arguments
+/// type if it declares them itself.
+fn get_import_generics(
+    ty_generics: &Generics,
+    lifetime: Option<syn::Lifetime>,
+    args: &[IdentTypeMaybeDefault],
+) -> Generics {
+    let uses = |ident: &Ident| {
+        args.iter()
+            .any(|arg| contains_ident(arg.ty.to_token_stream(), ident))
+    };
+
+    Generics {
+        params: lifetime
+            .map(|lifetime| syn::GenericParam::Lifetime(syn::LifetimeDef::new(lifetime)))
+            .into_iter()
+            .chain(ty_generics.params.iter().filter_map(|param| match param {
+                syn::GenericParam::Type(param) if uses(&param.ident) => {
+                    Some(syn::GenericParam::Type(syn::TypeParam {
+                        eq_token: None,
+                        default: None,
+                        ..param.clone()
+                    }))
+                }
+                syn::GenericParam::Const(param) if uses(&param.ident) => {
+                    Some(syn::GenericParam::Const(syn::ConstParam {
+                        eq_token: None,
+                        default: None,
+                        ..param.clone()
+                    }))
+                }
+                _ => None,
+            }))
+            .collect(),
+        ..<_>::default()
+    }
+}
+
 fn generate_trait_impl<const WRITE: bool>(
     binrw_input: &ParseResult<Input>,
     derive_input: &DeriveInput,
     arg_type: &TokenStream,
 ) -> TokenStream {
+    let borrow = match binrw_input {
+        ParseResult::Ok(binrw_input) | ParseResult::Partial(binrw_input, _) if !WRITE => {
+            binrw_input.borrow()
+        }
+        _ => None,
+    };
+
     let (trait_name, fn_sig) = if WRITE {
         (
-            BINWRITE_TRAIT,
+            BINWRITE_TRAIT.to_token_stream(),
             quote! {
                 fn write_options<W: #WRITE_TRAIT + #SEEK_TRAIT>(
                     &self,
@@ -153,9 +229,18 @@ fn generate_trait_impl<const WRITE: bool>(
                 ) -> #BIN_RESULT<()>
             },
         )
+    } else if let Some(borrow) = borrow {
+        (
+            quote! { #BINREAD_BORROWED_TRAIT<#borrow> },
+            quote! {
+                fn read_borrowed_options
+                    (#READER: &mut #CURSOR<&#borrow [u8]>, #OPT: #ENDIAN_ENUM, #ARGS: Self::Args<'_>)
+                    -> #BIN_RESULT<Self>
+            },
+        )
     } else {
         (
-            BINREAD_TRAIT,
+            BINREAD_TRAIT.to_token_stream(),
             quote! {
                 fn read_options<R: #READ_TRAIT + #SEEK_TRAIT>
                     (#READER: &mut R, #OPT: #ENDIAN_ENUM, #ARGS: Self::Args<'_>)
@@ -180,7 +265,8 @@ fn generate_trait_impl<const WRITE: bool>(
     };
 
     let name = &derive_input.ident;
-    let (impl_generics, ty_generics, where_clause) = derive_input.generics.split_for_impl();
+    let generics = with_bound(binrw_input, &derive_input.generics);
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
 
     let args_lifetime = get_args_lifetime(Span::call_site());
     quote! {
@@ -197,18 +283,43 @@ fn generate_trait_impl<const WRITE: bool>(
     }
 }
 
+fn with_bound<'a>(binrw_input: &ParseResult<Input>, generics: &'a Generics) -> Cow<'a, Generics> {
+    let bound = match binrw_input {
+        ParseResult::Ok(binrw_input) | ParseResult::Partial(binrw_input, _) => binrw_input.bound(),
+        ParseResult::Err(_) => None,
+    };
+
+    match bound {
+        Some(bound) => {
+            let mut generics = generics.clone();
+            generics
+                .make_where_clause()
+                .predicates
+                .extend(bound.iter().cloned());
+            Cow::Owned(generics)
+        }
+        None => Cow::Borrowed(generics),
+    }
+}
+
 fn get_args_lifetime(span: proc_macro2::Span) -> syn::Lifetime {
     syn::Lifetime::new(&format!("'{ARGS_LIFETIME}"), span)
 }
 
-fn get_assertions(assertions: &[Assert]) -> impl Iterator<Item = TokenStream> + '_ {
+/// Generates the checks for `assertions`. `value` is a reference to the
+/// asserted value which is passed to closure consequents, and is `None` only
+/// for pre-assertions, which do not allow them.
+fn get_assertions(
+    assertions: &[Assert],
+    value: Option<TokenStream>,
+) -> impl Iterator<Item = TokenStream> + '_ {
     assertions.iter().map(
-        |Assert {
-             kw_span,
-             condition,
-             consequent,
-             ..
-         }| {
+        move |Assert {
+                  kw_span,
+                  condition,
+                  consequent,
+                  ..
+              }| {
             let error_fn = match &consequent {
                 AssertionError::Message(message) => {
                     quote! { #ASSERT_ERROR_FN::<_, fn() -> !>::Message(|| { #message }) }
@@ -216,6 +327,16 @@ fn get_assertions(assertions: &[Assert]) -> impl Iterator<Item = TokenStream> +
                 AssertionError::Error(error) => {
                     quote! { #ASSERT_ERROR_FN::Error::<fn() -> &'static str, _>(|| { #error }) }
                 }
+                AssertionError::Closure(closure) => {
+                    let value = value
+                        .as_ref()
+                        .expect("pre-assertions cannot have closure consequents");
+                    quote! {
+                        #ASSERT_ERROR_FN::Error::<fn() -> &'static str, _>(|| {
+                            #ASSERT_ERROR_WITH(#value, #POS, #closure)
+                        })
+                    }
+                }
             };
 
             quote_spanned_any! {*kw_span=>
@@ -306,6 +427,100 @@ fn get_passed_args(field: &StructField, stream: &TokenStream) -> Option<TokenStr
     }
 }
 
+/// Returns code which evaluates the `tag` or `selector` of a union and runs
+/// the body for the matching field, or `no_match` if no field matches.
+fn get_union_dispatch(
+    un: &Union,
+    body: impl Fn(&StructField) -> TokenStream,
+    no_match: &TokenStream,
+) -> TokenStream {
+    if let Some(tag) = &un.tag {
+        let clauses = un.fields.iter().map(|field| {
+            let value = &field.tag;
+            let body = body(field);
+            quote! {
+                if *#TEMP == #value {
+                    #body
+                }
+            }
+        });
+
+        quote! {
+            let #TEMP = &(#tag);
+            #(#clauses else)* {
+                #no_match
+            }
+        }
+    } else {
+        let selector = &un.selector;
+        let arms = un.selector_arms.iter().flatten().map(|arm| {
+            let pat = &arm.pat;
+            let guard = arm.guard.as_ref().map(|guard| quote! { if #guard });
+            let field = un
+                .fields
+                .iter()
+                .find(|field| field.ident == arm.ident)
+                .unwrap();
+            let body = body(field);
+            quote! {
+                #pat #guard => {
+                    #body
+                }
+            }
+        });
+
+        quote! {
+            let #TEMP = &(#selector);
+            match *#TEMP {
+                #(#arms,)*
+                _ => {
+                    #no_match
+                }
+            }
+        }
+    }
+}
+
+/// Returns the line number printed by the `dbg` directive for the given field.
+fn dbg_line(ident: &Ident) -> TokenStream {
+    // Unwrapping the proc-macro2 Span is undesirable but necessary until its API
+    // is updated to allow retrieving line/column again. Using a separate function
+    // to unwrap just to make it clearer what needs to be undone later.
+    // <https://github.com/dtolnay/proc-macro2/pull/383>
+    #[cfg(all(feature = "verbose-backtrace", nightly, proc_macro))]
+    fn start_line(span: proc_macro2::Span) -> usize {
+        span.unwrap().start().line()
+    }
+    #[cfg(not(all(feature = "verbose-backtrace", nightly, proc_macro)))]
+    fn start_line(_: proc_macro2::Span) -> usize {
+        0
+    }
+
+    let start_line = start_line(ident.span());
+    if start_line == 0 {
+        quote!(::core::line!())
+    } else {
+        start_line.to_token_stream()
+    }
+}
+
+/// Prints the amount of padding or alignment added by a directive for the
+/// `dbg` directive.
+fn dbg_space(
+    name: &'static str,
+    at: &TokenStream,
+    which: Option<&TokenStream>,
+) -> Option<TokenStream> {
+    which.map(|space| {
+        quote_spanned! {space.span()=> {
+            #DBG_EPRINTLN!(
+                ::core::concat!("[{}:{} | ", #name, " {:#x}]"),
+                ::core::file!(), #at, #space
+            );
+        }}
+    })
+}
+
 fn get_try_calc(pos: IdentStr, ty: &Type, calc: &TokenStream) -> TokenStream {
     let map_err = get_map_err(pos, calc.span());
     quote_spanned! {ty.span()=> {
@@ -319,6 +534,30 @@ fn directives_to_args(field: &StructField, stream: &TokenStream) -> TokenStream
         .count
         .as_ref()
         .map(|count| {
+            let max_count = field.max_count.as_ref().map(|max_count| {
+                let name = field.ident.to_string();
+                quote_spanned_any! {max_count.span()=>
+                    .and_then(|#TEMP: usize| {
+                        let max_count: usize = #max_count;
+                        if #TEMP > max_count {
+                            extern crate alloc;
+                            Err(#BIN_ERROR::AssertFail {
+                                pos: #SEEK_TRAIT::stream_position(#stream)
+                                    .unwrap_or_default(),
+                                message: alloc::format!(
+                                    "count {} of `{}` exceeds max_count {}",
+                                    #TEMP,
+                                    #name,
+                                    max_count
+                                )
+                            })
+                        } else {
+                            Ok(#TEMP)
+                        }
+                    })
+                }
+            });
+
             quote_spanned_any! {count.span()=>
                 count: {
                     let #TEMP = #count;
@@ -335,7 +574,7 @@ fn directives_to_args(field: &StructField, stream: &TokenStream) -> TokenStream
                             // using a bogus type with `count`
                             message: alloc::format!("count {:?} out of range of usize", #TEMP)
                         }
-                    })?
+                    })#max_count?
                 }
             }
         })
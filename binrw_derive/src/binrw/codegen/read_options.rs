@@ -1,6 +1,7 @@
 mod r#enum;
 mod map;
 mod r#struct;
+mod union;
 
 use super::{get_assertions, get_destructured_imports};
 use crate::{
@@ -8,19 +9,20 @@ use crate::{
         codegen::{
             get_endian,
             sanitization::{
-                ARGS, ASSERT_MAGIC, MAP_READER_TYPE_HINT, OPT, POS, READER, RESTORE_POSITION,
-                SEEK_TRAIT,
+                ARGS, ASSERT_MAGIC, BACKTRACE_FRAME, MAP_READER_TYPE_HINT, OPT, POS, READER,
+                RESTORE_POSITION, SEEK_TRAIT, WITH_CONTEXT,
             },
         },
-        parser::{Input, Magic, Map},
+        parser::{ErrContext, Input, Magic, Map},
     },
     util::quote_spanned_any,
 };
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
-use r#enum::{generate_data_enum, generate_unit_enum};
+use quote::{quote, quote_spanned, ToTokens};
+use r#enum::{generate_data_enum, generate_repr_enum, generate_unit_enum};
 use r#struct::{generate_struct, generate_unit_struct};
-use syn::{spanned::Spanned, Ident};
+use syn::{spanned::Spanned, Expr, Ident, LitStr};
+use union::generate_union;
 
 pub(crate) fn generate(input: &Input, derive_input: &syn::DeriveInput) -> TokenStream {
     let name = Some(&derive_input.ident);
@@ -28,16 +30,21 @@ pub(crate) fn generate(input: &Input, derive_input: &syn::DeriveInput) -> TokenS
         Map::None => match input {
             Input::UnitStruct(_) => (generate_unit_struct(input, name, None), false),
             Input::Struct(s) => (generate_struct(input, name, s), true),
-            Input::Enum(e) => (generate_data_enum(input, name, e), false),
+            Input::Enum(e) => (
+                generate_data_enum(input, name, e),
+                e.tag.is_some() || e.selector.is_some(),
+            ),
             Input::UnitOnlyEnum(e) => (
                 generate_unit_enum(input, name, e),
                 e.map.as_repr().is_some(),
             ),
+            Input::Union(u) => (generate_union(input, name, u), true),
         },
         Map::Try(map) => (map::generate_try_map(input, name, map), true),
         Map::Map(map) => (map::generate_map(input, name, map), true),
         Map::Repr(ty) => match input {
             Input::UnitOnlyEnum(e) => (generate_unit_enum(input, name, e), true),
+            Input::Enum(e) if e.is_keyed_repr() => (generate_repr_enum(input, name, e, ty), true),
             _ => (
                 map::generate_try_map(
                     input,
@@ -57,12 +64,64 @@ pub(crate) fn generate(input: &Input, derive_input: &syn::DeriveInput) -> TokenS
         }
     });
 
+    // Imports are read outside of the closure when there is a context so that
+    // they can be used by the context expression
+    let (imports, map_err) = match input.err_context() {
+        Some(err_context) => (
+            get_destructured_imports(input.imports(), name, false)
+                .map(|imports| quote! { let #imports = #ARGS; }),
+            Some(get_type_err_context(err_context)),
+        ),
+        None => (None, None),
+    };
+
     quote! {
         let #reader_var = #READER;
         let #POS = #SEEK_TRAIT::stream_position(#reader_var)?;
+        #imports
         (|| {
             #inner
-        })()#rewind
+        })()#map_err #rewind
+    }
+}
+
+/// Generates the message of an `err_context` directive with a format string.
+fn get_err_context_message(fmt: &LitStr, exprs: &[Expr]) -> TokenStream {
+    if exprs.is_empty() {
+        quote! { (#fmt) }
+    } else {
+        quote! {
+            {
+                extern crate alloc;
+                alloc::format!(#fmt, #(#exprs),*)
+            }
+        }
+    }
+}
+
+/// Generates the error mapping for an `err_context` directive on a type or
+/// enum variant, which adds a frame to any error from reading it.
+fn get_type_err_context(err_context: &ErrContext) -> TokenStream {
+    let backtrace = match err_context {
+        ErrContext::Context(expr) => quote_spanned! {expr.span()=>
+            #BACKTRACE_FRAME::Custom(Box::new(#expr) as _)
+        },
+        ErrContext::Format(fmt, exprs) => {
+            let message = get_err_context_message(fmt, exprs);
+            quote_spanned! {fmt.span()=>
+                #BACKTRACE_FRAME::Full {
+                    message: #message.into(),
+                    line: ::core::line!(),
+                    file: ::core::file!(),
+                    code: None,
+                    pos: Some(#POS),
+                }
+            }
+        }
+    };
+
+    quote! {
+        .map_err(|err| #WITH_CONTEXT(err, #backtrace))
     }
 }
 
@@ -87,6 +146,11 @@ impl<'input> PreludeGenerator<'input> {
     }
 
     fn add_imports(mut self, name: Option<&Ident>) -> Self {
+        // Imports are read by `generate` instead when there is a context
+        if self.input.err_context().is_some() {
+            return self;
+        }
+
         if let Some(imports) = get_destructured_imports(self.input.imports(), name, false) {
             let head = self.out;
             self.out = quote! {
@@ -111,7 +175,7 @@ impl<'input> PreludeGenerator<'input> {
     fn add_magic_pre_assertion(mut self) -> Self {
         let head = self.out;
         let magic = get_magic(self.input.magic(), &self.reader_var, OPT);
-        let pre_assertions = get_assertions(self.input.pre_assertions());
+        let pre_assertions = get_assertions(self.input.pre_assertions(), None);
         self.out = quote! {
             #head
             #magic
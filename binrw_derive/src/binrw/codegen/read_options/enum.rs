@@ -1,13 +1,21 @@
 use super::{
+    get_type_err_context,
     r#struct::{generate_unit_struct, StructGenerator},
     PreludeGenerator,
 };
-use crate::binrw::{
-    codegen::sanitization::{
-        BACKTRACE_FRAME, BIN_ERROR, ERROR_BASKET, OPT, POS, READER, READ_METHOD,
-        RESTORE_POSITION_VARIANT, TEMP, WITH_CONTEXT,
+use crate::{
+    binrw::{
+        codegen::sanitization::{
+            BACKTRACE_FRAME, BIN_ERROR, ERROR_BASKET, MAGIC_VALUE, OPT, POS, READER,
+            READ_MAGIC_VALUE, READ_METHOD, RESTORE_POSITION, RESTORE_POSITION_VARIANT, TEMP,
+            WITH_CONTEXT,
+        },
+        parser::{
+            Enum, EnumErrorMode, EnumVariant, Input, MagicKind as Kind, Struct, UnitEnumField,
+            UnitOnlyEnum,
+        },
     },
-    parser::{Enum, EnumErrorMode, EnumVariant, Input, UnitEnumField, UnitOnlyEnum},
+    meta_types::MatchArm,
 };
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -40,6 +48,7 @@ fn generate_unit_enum_repr(
     repr: &TokenStream,
     variants: &[UnitEnumField],
 ) -> TokenStream {
+    let fallback = variants.iter().find(|variant| variant.fallback.is_some());
     let clauses = variants.iter().map(|variant| {
         let ident = &variant.ident;
         let pre_assertions = variant
@@ -54,9 +63,11 @@ fn generate_unit_enum_repr(
         }
     });
 
-    quote! {
-        let #TEMP: #repr = #READ_METHOD(#reader_var, #OPT, ())?;
-        #(#clauses else)* {
+    let no_match = if let Some(fallback) = fallback {
+        let ident = &fallback.ident;
+        quote! { Ok(Self::#ident) }
+    } else {
+        quote! {
             Err(#WITH_CONTEXT(
                 #BIN_ERROR::NoVariantMatch {
                     pos: #POS,
@@ -67,48 +78,71 @@ fn generate_unit_enum_repr(
                 })
             ))
         }
+    };
+
+    quote! {
+        let #TEMP: #repr = #READ_METHOD(#reader_var, #OPT, ())?;
+        #(#clauses else)* {
+            #no_match
+        }
     }
 }
 
 fn generate_unit_enum_magic(reader_var: &TokenStream, variants: &[UnitEnumField]) -> TokenStream {
+    // the fallback variant is always tried last, and if it has no magic, it
+    // matches without reading anything
+    let (fallback, variants) = variants
+        .iter()
+        .partition::<Vec<_>, _>(|field| field.fallback.is_some());
+    let fallback = fallback.first();
+
     // group fields by the type (Kind) of their magic value, preserve the order
-    let group_by_magic_type = variants.iter().fold(
-        Vec::new(),
-        |mut group_by_magic_type: Vec<(_, Vec<_>)>, field| {
-            let kind = field.magic.as_ref().map(|magic| magic.kind());
-            let last = group_by_magic_type.last_mut();
-            match last {
-                // if the current field's magic kind is the same as the previous one
-                // then add the current field to the same group
-                // if the magic kind is none then it's a wildcard, just add it to the previous group
-                Some((last_kind, last_vec)) if kind.is_none() || *last_kind == kind => {
-                    last_vec.push(field);
+    let group_by_magic_type = variants
+        .into_iter()
+        .chain(fallback.filter(|field| field.magic.is_some()).copied())
+        .fold(
+            Vec::new(),
+            |mut group_by_magic_type: Vec<(_, Vec<_>)>, field| {
+                let kind = field.magic.as_ref().map(|magic| magic.kind());
+                let last = group_by_magic_type.last_mut();
+                match last {
+                    // if the current field's magic kind is the same as the previous one
+                    // then add the current field to the same group
+                    // if the magic kind is none then it's a wildcard, just add it to the previous group
+                    // runtime magic values may each have a different type, so are never grouped
+                    Some((last_kind, last_vec))
+                        if kind.is_none() || (*last_kind == kind && kind != Some(&Kind::Expr)) =>
+                    {
+                        last_vec.push(field);
+                    }
+                    // otherwise if the vector is empty
+                    // or the last field's magic kind is different
+                    // then create a new group
+                    _ => group_by_magic_type.push((kind, vec![field])),
                 }
-                // otherwise if the vector is empty
-                // or the last field's magic kind is different
-                // then create a new group
-                _ => group_by_magic_type.push((kind, vec![field])),
-            }
 
-            group_by_magic_type
-        },
-    );
+                group_by_magic_type
+            },
+        );
 
     // for each type (Kind), read and try to match the magic of each field
-    let try_each_magic_type = group_by_magic_type.into_iter().map(|(_kind, fields)| {
+    let try_each_magic_type = group_by_magic_type.into_iter().map(|(kind, fields)| {
         let amp = fields[0].magic.as_ref().map(|magic| magic.add_ref());
 
         let matches = fields.iter().map(|field| {
             let ident = &field.ident;
 
             if let Some(magic) = &field.magic {
-                let magic = magic.match_value();
-                let condition = if field.pre_assertions.is_empty() {
-                    quote! { #magic }
+                let pre_assertions = field.pre_assertions.iter().map(|assert| &assert.condition);
+                let condition = if magic.is_expr() {
+                    quote! { #MAGIC_VALUE if #MAGIC_VALUE == #TEMP #(&& (#pre_assertions))* }
                 } else {
-                    let pre_assertions =
-                        field.pre_assertions.iter().map(|assert| &assert.condition);
-                    quote! { #magic if true #(&& (#pre_assertions))* }
+                    let magic = magic.match_value();
+                    if field.pre_assertions.is_empty() {
+                        quote! { #magic }
+                    } else {
+                        quote! { #magic if true #(&& (#pre_assertions))* }
+                    }
                 };
 
                 quote! { #condition => Ok(Self::#ident) }
@@ -117,10 +151,21 @@ fn generate_unit_enum_magic(reader_var: &TokenStream, variants: &[UnitEnumField]
             }
         });
 
-        let body = quote! {
-            match #amp #READ_METHOD(#reader_var, #OPT, ())? {
-                #(#matches,)*
-                _ => Err(#BIN_ERROR::NoVariantMatch { pos: #POS })
+        let body = if kind == Some(&Kind::Expr) {
+            let magic = fields[0].magic.as_ref().unwrap().deref_value();
+            quote! {
+                let #TEMP = #magic;
+                match #READ_MAGIC_VALUE(#reader_var, &#TEMP, #OPT)? {
+                    #(#matches,)*
+                    _ => Err(#BIN_ERROR::NoVariantMatch { pos: #POS })
+                }
+            }
+        } else {
+            quote! {
+                match #amp #READ_METHOD(#reader_var, #OPT, ())? {
+                    #(#matches,)*
+                    _ => Err(#BIN_ERROR::NoVariantMatch { pos: #POS })
+                }
             }
         };
 
@@ -134,10 +179,15 @@ fn generate_unit_enum_magic(reader_var: &TokenStream, variants: &[UnitEnumField]
         }
     });
 
-    let return_error = quote! {
-        Err(#BIN_ERROR::NoVariantMatch {
-            pos: #POS
-        })
+    let return_error = if let Some(fallback) = fallback.filter(|field| field.magic.is_none()) {
+        let ident = &fallback.ident;
+        quote! { Ok(Self::#ident) }
+    } else {
+        quote! {
+            Err(#BIN_ERROR::NoVariantMatch {
+                pos: #POS
+            })
+        }
     };
 
     quote! {
@@ -146,6 +196,69 @@ fn generate_unit_enum_magic(reader_var: &TokenStream, variants: &[UnitEnumField]
     }
 }
 
+pub(super) fn generate_repr_enum(
+    input: &Input,
+    name: Option<&Ident>,
+    en: &Enum,
+    repr: &TokenStream,
+) -> TokenStream {
+    let prelude = PreludeGenerator::new(input)
+        .add_imports(name)
+        .add_endian()
+        .add_magic_pre_assertion()
+        .finish();
+
+    let reader_var = input.stream_ident_or(READER);
+
+    let mut fallback = None;
+    let clauses = en
+        .variant_discriminants()
+        .filter_map(|(variant, discriminant)| {
+            if variant.fallback().is_some() {
+                fallback = Some(variant.ident());
+                return None;
+            }
+
+            let mut options = Struct::from(variant.clone());
+            let pre_assertions = core::mem::take(&mut options.pre_assertions);
+            let pre_assertions = pre_assertions.iter().map(|assert| &assert.condition);
+            let body = generate_variant_impl_from(en, variant, options);
+
+            Some(quote! {
+                if #TEMP == #discriminant #(&& (#pre_assertions))* {
+                    (|| {
+                        #body
+                    })()
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let no_match = if let Some(fallback) = fallback {
+        quote! { Ok(Self::#fallback(#TEMP)) }
+    } else {
+        quote! {
+            Err(#WITH_CONTEXT(
+                #BIN_ERROR::NoVariantMatch {
+                    pos: #POS,
+                },
+                #BACKTRACE_FRAME::Message({
+                    extern crate alloc;
+                    alloc::format!("Unexpected value for enum: {:?}", #TEMP).into()
+                })
+            ))
+        }
+    };
+
+    quote! {
+        #prelude
+        let #TEMP: #repr = #READ_METHOD(#reader_var, #OPT, ())?;
+        #(#clauses else)* {
+            #no_match
+        }
+    }
+}
+
 pub(super) fn generate_data_enum(input: &Input, name: Option<&Ident>, en: &Enum) -> TokenStream {
     let return_all_errors = en.error_mode != EnumErrorMode::ReturnUnexpectedError;
 
@@ -182,18 +295,22 @@ pub(super) fn generate_data_enum(input: &Input, name: Option<&Ident>, en: &Enum)
 
     let reader_var = input.stream_ident_or(READER);
 
-    let try_each_variant = en.variants.iter().map(|variant| {
-        let body = generate_variant_impl(en, variant);
+    // the fallback variant is only tried once all other variants have failed
+    let (fallback, variants) = en
+        .variants
+        .iter()
+        .partition::<Vec<_>, _>(|variant| variant.fallback().is_some());
 
-        let handle_error = if return_all_errors {
-            let name = variant.ident().to_string();
-            quote! {
-                #ERROR_BASKET.push((#name, #TEMP));
-            }
-        } else {
-            TokenStream::new()
+    let select_variant = generate_select_variant(en, &variants, fallback.first());
+
+    if let Some(select_variant) = select_variant {
+        return quote! {
+            #prelude
+            #select_variant
         };
+    }
 
+    let try_variant = |body: TokenStream, handle_error: TokenStream| {
         quote! {
             match (|| {
                 #body
@@ -206,21 +323,229 @@ pub(super) fn generate_data_enum(input: &Input, name: Option<&Ident>, en: &Enum)
                 }
             }
         }
+    };
+
+    // when every variant starts with a magic of the same type, the magic is
+    // read once and used to choose the variant instead of trying each one
+    let (try_shared_magic, variants, fallback) = if en.has_shared_magic() {
+        let (variants, fallback) = match fallback.first() {
+            Some(fallback) if fallback.magic().is_some() => {
+                (variants.into_iter().chain(Some(*fallback)).collect(), None)
+            }
+            fallback => (variants, fallback.copied()),
+        };
+        let try_shared_magic =
+            generate_magic_variants(en, &reader_var, &variants, return_all_errors, try_variant);
+        (Some(try_shared_magic), Vec::new(), fallback)
+    } else {
+        (None, variants, fallback.first().copied())
+    };
+
+    let try_each_variant = variants.into_iter().chain(fallback).map(|variant| {
+        let body = generate_variant_impl(en, variant);
+
+        let handle_error = if return_all_errors {
+            let name = variant.ident().to_string();
+            quote! {
+                #ERROR_BASKET.push((#name, #TEMP));
+            }
+        } else {
+            TokenStream::new()
+        };
+
+        try_variant(body, handle_error)
     });
 
     quote! {
         #prelude
         #create_error_basket
+        #try_shared_magic
         #(#try_each_variant)*
         #return_error
     }
 }
 
+fn generate_select_variant(
+    en: &Enum,
+    variants: &[&EnumVariant],
+    fallback: Option<&&EnumVariant>,
+) -> Option<TokenStream> {
+    if let Some(tag) = &en.tag {
+        Some(generate_tagged_variants(en, tag, variants, fallback))
+    } else {
+        en.selector
+            .as_ref()
+            .zip(en.selector_arms.as_ref())
+            .map(|(selector, arms)| generate_selected_variants(en, selector, arms, fallback))
+    }
+}
+
+fn generate_magic_variants(
+    en: &Enum,
+    reader_var: &TokenStream,
+    variants: &[&EnumVariant],
+    return_all_errors: bool,
+    try_variant: impl Fn(TokenStream, TokenStream) -> TokenStream,
+) -> TokenStream {
+    let magics = variants
+        .iter()
+        .map(|variant| variant.magic().as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let amp = magics[0].add_ref();
+
+    let arms = variants.iter().zip(&magics).map(|(variant, magic)| {
+        let value = magic.match_value();
+        let mut options = Struct::from((*variant).clone());
+        options.magic = None;
+        let body = generate_variant_impl_from(en, variant, options);
+
+        quote! {
+            #value => (|| {
+                #body
+            })()
+        }
+    });
+
+    let body = quote! {
+        match #amp #MAGIC_VALUE {
+            #(#arms,)*
+            _ => Err(#BIN_ERROR::NoVariantMatch {
+                pos: #POS
+            })
+        }
+    };
+
+    // variants whose magic did not match get the same error they would have
+    // had if they were tried individually
+    let handle_error = if return_all_errors {
+        let push_errors = variants.iter().zip(&magics).map(|(variant, magic)| {
+            let name = variant.ident().to_string();
+            let value = magic.match_value();
+            quote! {
+                #ERROR_BASKET.push((#name, if matches!(#amp #MAGIC_VALUE, #value) {
+                    #TEMP.take().unwrap()
+                } else {
+                    #BIN_ERROR::BadMagic {
+                        pos: #POS,
+                        found: alloc::boxed::Box::new(#MAGIC_VALUE) as _,
+                    }
+                }));
+            }
+        });
+
+        quote! {
+            let mut #TEMP = Some(#TEMP);
+            #(#push_errors)*
+        }
+    } else {
+        TokenStream::new()
+    };
+
+    let try_variants = try_variant(body, handle_error);
+
+    quote! {
+        let #MAGIC_VALUE = #READ_METHOD(#reader_var, #OPT, ())
+            .or_else(#RESTORE_POSITION::<#BIN_ERROR, _, _>(#reader_var, #POS))?;
+        #try_variants
+    }
+}
+
+fn generate_selected_variants(
+    en: &Enum,
+    selector: &TokenStream,
+    arms: &[MatchArm],
+    fallback: Option<&&EnumVariant>,
+) -> TokenStream {
+    let arms = arms.iter().map(|arm| {
+        let pat = &arm.pat;
+        let guard = arm.guard.as_ref().map(|guard| quote! { if #guard });
+        let variant = en
+            .variants
+            .iter()
+            .find(|variant| *variant.ident() == arm.ident)
+            .unwrap();
+        let body = generate_variant_impl(en, variant);
+        quote! {
+            #pat #guard => (|| {
+                #body
+            })()
+        }
+    });
+
+    let no_match = generate_unselected_variant(en, fallback);
+
+    quote! {
+        let #TEMP = &(#selector);
+        match *#TEMP {
+            #(#arms,)*
+            _ => #no_match
+        }
+    }
+}
+
+fn generate_unselected_variant(en: &Enum, fallback: Option<&&EnumVariant>) -> TokenStream {
+    if let Some(fallback) = fallback {
+        let body = generate_variant_impl(en, fallback);
+        quote! {
+            (|| {
+                #body
+            })()
+        }
+    } else {
+        quote! {
+            Err(#BIN_ERROR::NoVariantMatch {
+                pos: #POS
+            })
+        }
+    }
+}
+
+fn generate_tagged_variants(
+    en: &Enum,
+    tag: &TokenStream,
+    variants: &[&EnumVariant],
+    fallback: Option<&&EnumVariant>,
+) -> TokenStream {
+    let clauses = variants.iter().map(|variant| {
+        let value = variant.tag();
+        let body = generate_variant_impl(en, variant);
+        quote! {
+            if *#TEMP == #value {
+                (|| {
+                    #body
+                })()
+            }
+        }
+    });
+
+    let no_match = generate_unselected_variant(en, fallback);
+
+    quote! {
+        let #TEMP = &(#tag);
+        #(#clauses else)* {
+            #no_match
+        }
+    }
+}
+
 fn generate_variant_impl(en: &Enum, variant: &EnumVariant) -> TokenStream {
-    let input = Input::Struct(variant.clone().into());
+    generate_variant_impl_from(en, variant, Struct::from(variant.clone()))
+}
+
+fn generate_variant_impl_from(
+    en: &Enum,
+    variant: &EnumVariant,
+    mut options: Struct,
+) -> TokenStream {
+    // variants must read from the same stream as the enum, which is not
+    // necessarily the original reader if the enum uses `stream`
+    if options.stream_ident.is_none() {
+        options.stream_ident.clone_from(&en.stream_ident);
+    }
+    let input = Input::Struct(options);
 
-    match variant {
-        EnumVariant::Variant { ident, options } => StructGenerator::new(&input, options)
+    let body = match variant {
+        EnumVariant::Variant { ident, options, .. } => StructGenerator::new(&input, options)
             .read_fields(
                 None,
                 Some(&format!("{}::{}", en.ident.as_ref().unwrap(), &ident)),
@@ -230,5 +555,17 @@ fn generate_variant_impl(en: &Enum, variant: &EnumVariant) -> TokenStream {
             .finish(),
 
         EnumVariant::Unit(options) => generate_unit_struct(&input, None, Some(&options.ident)),
+    };
+
+    match input.err_context() {
+        Some(err_context) => {
+            let map_err = get_type_err_context(err_context);
+            quote! {
+                (|| {
+                    #body
+                })()#map_err
+            }
+        }
+        None => body,
     }
 }
@@ -18,7 +18,8 @@ pub(crate) fn generate_map(input: &Input, name: Option<&Ident>, map: &TokenStrea
         .finish();
 
     let destructure_ref = destructure_ref(input);
-    let assertions = field_asserts(input).chain(get_assertions(input.assertions()));
+    let assertions =
+        field_asserts(input).chain(get_assertions(input.assertions(), Some(quote! { &#THIS })));
     let reader_var = input.stream_ident_or(READER);
 
     // TODO: replace args with top-level arguments and only
@@ -55,7 +56,8 @@ pub(crate) fn generate_try_map(
         .finish();
 
     let destructure_ref = destructure_ref(input);
-    let assertions = field_asserts(input).chain(get_assertions(input.assertions()));
+    let assertions =
+        field_asserts(input).chain(get_assertions(input.assertions(), Some(quote! { &#THIS })));
     let reader_var = input.stream_ident_or(READER);
 
     // TODO: replace args with top-level arguments and only
@@ -102,12 +104,10 @@ fn destructure_ref(input: &Input) -> Option<TokenStream> {
 
 fn field_asserts(input: &Input) -> impl Iterator<Item = TokenStream> + '_ {
     match input {
-        Input::Struct(input) => either::Left(
-            input
-                .fields
-                .iter()
-                .flat_map(|field| get_assertions(&field.assertions)),
-        ),
+        Input::Struct(input) => either::Left(input.fields.iter().flat_map(|field| {
+            let ident = &field.ident;
+            get_assertions(&field.assertions, Some(quote! { #ident }))
+        })),
         _ => either::Right(core::iter::empty()),
     }
 }
@@ -1,16 +1,18 @@
-use super::{get_magic, PreludeGenerator};
+use super::{get_err_context_message, get_magic, PreludeGenerator};
 #[cfg(feature = "verbose-backtrace")]
 use crate::binrw::backtrace::BacktraceFrame;
 use crate::binrw::parser::Assert;
 use crate::{
     binrw::{
         codegen::{
-            get_assertions, get_endian, get_map_err, get_passed_args, get_try_calc,
+            dbg_line, dbg_space, get_assertions, get_endian, get_map_err, get_passed_args,
+            get_try_calc,
             sanitization::{
-                make_ident, ARGS_TYPE_HINT, BACKTRACE_FRAME, BINREAD_TRAIT, COERCE_FN,
-                DBG_EPRINTLN, MAP_ARGS_TYPE_HINT, MAP_READER_TYPE_HINT, OPT, PARSE_FN_TYPE_HINT,
-                POS, READER, READ_FUNCTION, READ_METHOD, REQUIRED_ARG_TRAIT, SAVED_POSITION,
-                SEEK_FROM, SEEK_TRAIT, TEMP, THIS, WITH_CONTEXT,
+                make_ident, ARGS_TYPE_HINT, BACKTRACE_FRAME, BINREAD_BORROWED_TRAIT, BINREAD_TRAIT,
+                COERCE_FN, DBG_EPRINTLN, MAP_ARGS_TYPE_HINT, MAP_READER_TYPE_HINT, OPT,
+                PARSE_FN_TYPE_HINT, POS, READER, READ_BORROWED_METHOD, READ_FUNCTION, READ_METHOD,
+                REQUIRED_ARG_TRAIT, SAVED_POSITION, SEEK_FROM, SEEK_TRAIT, TEMP, THIS,
+                VERIFY_CHECKSUM, WITH_CONTEXT,
             },
         },
         parser::{ErrContext, FieldMode, Input, Map, Struct, StructField},
@@ -85,8 +87,9 @@ impl<'input> StructGenerator<'input> {
     }
 
     fn add_assertions(mut self, extra_assertions: &[Assert]) -> Self {
-        let assertions =
-            get_assertions(&self.st.assertions).chain(get_assertions(extra_assertions));
+        let this = Some(quote! { &#THIS });
+        let assertions = get_assertions(&self.st.assertions, this.clone())
+            .chain(get_assertions(extra_assertions, this));
         let head = self.out;
         self.out = quote! {
             #head
@@ -98,11 +101,17 @@ impl<'input> StructGenerator<'input> {
 
     pub(super) fn read_fields(mut self, name: Option<&Ident>, variant_name: Option<&str>) -> Self {
         let prelude = get_prelude(self.input, name);
-        let read_fields = self
-            .st
-            .fields
-            .iter()
-            .map(|field| generate_field(self.input, field, name, variant_name));
+        let read_order = self.read_order();
+        let read_fields = read_order.iter().enumerate().map(|(index, field)| {
+            let read_field = generate_field(self.input, field, name, variant_name);
+            let before = self.checksum_before(&read_order, index);
+            let after = self.checksum_after(&read_order, index);
+            quote! {
+                #before
+                #read_field
+                #after
+            }
+        });
         self.out = quote! {
             #prelude
             #(#read_fields)*
@@ -111,6 +120,79 @@ impl<'input> StructGenerator<'input> {
         self
     }
 
+    fn checksum_before(
+        &self,
+        read_order: &[Cow<'input, StructField>],
+        index: usize,
+    ) -> TokenStream {
+        let reader_var = self.input.stream_ident_or(READER);
+        let starts = checksums(read_order)
+            .filter(|(_, _, first, _)| *first == index)
+            .map(|(field, ..)| {
+                let start = make_ident(&field.ident, "checksum_start");
+                quote! {
+                    let #start = #SEEK_TRAIT::stream_position(#reader_var)?;
+                }
+            });
+        quote! { #(#starts)* }
+    }
+
+    fn checksum_after(&self, read_order: &[Cow<'input, StructField>], index: usize) -> TokenStream {
+        let reader_var = self.input.stream_ident_or(READER);
+        let ends = checksums(read_order)
+            .filter(|(_, _, _, last)| *last == index)
+            .map(|(field, ..)| {
+                let end = make_ident(&field.ident, "checksum_end");
+                quote! {
+                    let #end = #SEEK_TRAIT::stream_position(#reader_var)?;
+                }
+            });
+
+        // The checksum can only be verified once it and every field it covers
+        // have been read
+        let verifications = checksums(read_order)
+            .filter(|(_, field_index, _, last)| *field_index.max(last) == index)
+            .filter_map(|(field, ..)| {
+                let checksum = field.checksum.as_ref()?;
+                let ident = &field.ident;
+                let start = make_ident(ident, "checksum_start");
+                let end = make_ident(ident, "checksum_end");
+                let algorithm = &checksum.algorithm;
+                Some(quote! {
+                    #VERIFY_CHECKSUM(#reader_var, #start, #end, #algorithm, &#ident)?;
+                })
+            });
+
+        quote! {
+            #(#ends)*
+            #(#verifications)*
+        }
+    }
+
+    /// Returns the fields in the order they are read.
+    ///
+    /// A lookahead field is read out of order, immediately after the last
+    /// earlier field that it refers to, so that the fields in between can use
+    /// its value.
+    fn read_order(&self) -> Vec<Cow<'input, StructField>> {
+        let mut fields = Vec::<Cow<'input, StructField>>::with_capacity(self.st.fields.len());
+        for field in &self.st.fields {
+            if field.lookahead.is_some() {
+                let index = fields
+                    .iter()
+                    .rposition(|earlier| field.reads_ident(&earlier.ident))
+                    .map_or(0, |index| index + 1);
+                let mut field = field.clone();
+                field.seek_before = field.lookahead.take();
+                field.restore_position = Some(());
+                fields.insert(index, Cow::Owned(field));
+            } else {
+                fields.push(Cow::Borrowed(field));
+            }
+        }
+        fields
+    }
+
     fn init_value(mut self, variant_ident: Option<&Ident>) -> Self {
         let out_names = self.st.iter_permanent_idents();
         let return_type = get_return_type(variant_ident);
@@ -141,6 +223,26 @@ impl<'input> StructGenerator<'input> {
     }
 }
 
+/// Returns the `checksum` fields along with the positions of the field
+/// and the first and last checked fields in the read order.
+fn checksums<'a>(
+    read_order: &'a [Cow<'_, StructField>],
+) -> impl Iterator<Item = (&'a StructField, usize, usize, usize)> + 'a {
+    let position = |ident: &Ident| read_order.iter().position(|field| field.ident == *ident);
+    read_order
+        .iter()
+        .enumerate()
+        .filter_map(move |(index, field)| {
+            let checksum = field.checksum.as_ref()?;
+            Some((
+                &**field,
+                index,
+                position(&checksum.first)?,
+                position(&checksum.last)?,
+            ))
+        })
+}
+
 fn generate_field(
     input: &Input,
     field: &StructField,
@@ -148,7 +250,7 @@ fn generate_field(
     variant_name: Option<&str>,
 ) -> TokenStream {
     // temp + ignore == just don't bother
-    if field.is_temp(false) && matches!(field.field_mode, FieldMode::Default) {
+    if field.is_temp(false) && matches!(field.field_mode, FieldMode::Default(None)) {
         return TokenStream::new();
     }
 
@@ -172,6 +274,7 @@ fn generate_field(
 
 struct FieldGenerator<'field> {
     field: &'field StructField,
+    borrow: bool,
     out: TokenStream,
     outer_reader_var: TokenStream,
     reader_var: TokenStream,
@@ -185,6 +288,7 @@ impl<'field> FieldGenerator<'field> {
 
         Self {
             field,
+            borrow: input.borrow().is_some(),
             out: TokenStream::new(),
             outer_reader_var: input.stream_ident_or(READER),
             reader_var,
@@ -194,44 +298,11 @@ impl<'field> FieldGenerator<'field> {
     }
 
     fn wrap_debug(mut self) -> Self {
-        // Unwrapping the proc-macro2 Span is undesirable but necessary until its API
-        // is updated to allow retrieving line/column again. Using a separate function
-        // to unwrap just to make it clearer what needs to be undone later.
-        // <https://github.com/dtolnay/proc-macro2/pull/383>
-        #[cfg(all(feature = "verbose-backtrace", nightly, proc_macro))]
-        fn start_line(span: proc_macro2::Span) -> usize {
-            span.unwrap().start().line()
-        }
-        #[cfg(not(all(feature = "verbose-backtrace", nightly, proc_macro)))]
-        fn start_line(_: proc_macro2::Span) -> usize {
-            0
-        }
-
         if self.field.debug.is_some() {
-            fn dbg_space(
-                name: &'static str,
-                at: &TokenStream,
-                which: Option<&TokenStream>,
-            ) -> Option<TokenStream> {
-                which.map(|space| {
-                    quote_spanned! {space.span()=> {
-                        #DBG_EPRINTLN!(
-                            ::core::concat!("[{}:{} | ", #name, " {:#x}]"),
-                            ::core::file!(), #at, #space
-                        );
-                    }}
-                })
-            }
-
             let head = self.out;
             let reader_var = &self.outer_reader_var;
             let ident = &self.field.ident;
-            let start_line = start_line(ident.span());
-            let at = if start_line == 0 {
-                quote!(::core::line!())
-            } else {
-                start_line.to_token_stream()
-            };
+            let at = dbg_line(ident);
 
             let dbg_pad_before = dbg_space("pad_before", &at, self.field.pad_before.as_ref());
             let dbg_align_before = dbg_space("align_before", &at, self.field.align_before.as_ref());
@@ -259,7 +330,8 @@ impl<'field> FieldGenerator<'field> {
     }
 
     fn append_assertions(mut self) -> Self {
-        let assertions = get_assertions(&self.field.assertions);
+        let ident = &self.field.ident;
+        let assertions = get_assertions(&self.field.assertions, Some(quote! { &#ident }));
         let head = self.out;
         self.out = quote! {
             #head
@@ -364,6 +436,14 @@ impl<'field> FieldGenerator<'field> {
                     let #READ_FUNCTION = #PARSE_FN_TYPE_HINT(#parser);
                 }
             }
+            FieldMode::Normal if self.borrow => quote! {
+                let #READ_FUNCTION = #READ_BORROWED_METHOD;
+            },
+            // This is also used for `Vec<u8>` fields with `count`, since the
+            // `Vec` implementation already picks the bulk read of
+            // `helpers::count_bytes` by the type of the items. Choosing it here
+            // from the tokens of the type would misfire on other types named
+            // `Vec`.
             FieldMode::Normal => quote! {
                 let #READ_FUNCTION = #READ_METHOD;
             },
@@ -396,6 +476,11 @@ impl<'field> FieldGenerator<'field> {
                             let #args_var = #MAP_ARGS_TYPE_HINT(&#map_func, #args);
                         }
                     }
+                    Map::None if self.borrow => {
+                        quote_spanned! {ty.span()=>
+                            let #args_var: <#ty as #BINREAD_BORROWED_TRAIT<'_>>::Args<'_> = #args;
+                        }
+                    }
                     Map::None => {
                         quote_spanned! {ty.span()=>
                             let #args_var: <#ty as #BINREAD_TRAIT>::Args<'_> = #args;
@@ -437,7 +522,8 @@ impl<'field> FieldGenerator<'field> {
 
     fn read_value(mut self) -> Self {
         self.out = match &self.field.field_mode {
-            FieldMode::Default => quote! { <_>::default() },
+            FieldMode::Default(None) => quote! { <_>::default() },
+            FieldMode::Default(Some(default)) => quote! { #default },
             FieldMode::Calc(calc) => quote! { #calc },
             FieldMode::TryCalc(calc) => get_try_calc(POS, &self.field.ty, calc),
             read_mode @ (FieldMode::Normal | FieldMode::Function(_)) => {
@@ -487,7 +573,8 @@ impl<'field> FieldGenerator<'field> {
                     _ => result.span(),
                 };
 
-                let map_err = get_err_context(self.field, name, variant_name);
+                let map_err =
+                    get_err_context(&self.outer_reader_var, self.field, name, variant_name);
                 quote_spanned! {span=> #result #map_err ? }
             };
         }
@@ -541,6 +628,7 @@ impl<'field> FieldGenerator<'field> {
 }
 
 fn get_err_context(
+    reader_var: &TokenStream,
     field: &StructField,
     name: Option<&Ident>,
     variant_name: Option<&str>,
@@ -563,16 +651,7 @@ fn get_err_context(
         let code = quote!(None);
 
         let message = if let Some(ErrContext::Format(fmt, exprs)) = &field.err_context {
-            if exprs.is_empty() {
-                quote! { (#fmt) }
-            } else {
-                quote! {
-                    {
-                        extern crate alloc;
-                        alloc::format!(#fmt, #(#exprs),*)
-                    }
-                }
-            }
+            get_err_context_message(fmt, exprs)
         } else {
             format!(
                 "While parsing field '{}' in {}",
@@ -588,6 +667,7 @@ fn get_err_context(
                 line: ::core::line!(),
                 file: ::core::file!(),
                 code: #code,
+                pos: #SEEK_TRAIT::stream_position(#reader_var).ok(),
             }
         }
     };
@@ -0,0 +1,56 @@
+use super::{r#struct::StructGenerator, PreludeGenerator};
+use crate::binrw::{
+    codegen::{
+        get_union_dispatch,
+        sanitization::{BIN_ERROR, POS},
+    },
+    parser::{Input, Struct, StructField, Union},
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+pub(super) fn generate_union(input: &Input, name: Option<&Ident>, un: &Union) -> TokenStream {
+    let prelude = PreludeGenerator::new(input)
+        .add_imports(name)
+        .add_endian()
+        .add_magic_pre_assertion()
+        .add_map_stream()
+        .reset_position_after_magic()
+        .finish();
+
+    let read_field = |field: &StructField| {
+        // each field is read as though it were the only field of a struct,
+        // which also gives the union initialiser syntax `Self { field }`
+        let options = Struct {
+            stream_ident: un.stream_ident.clone(),
+            fields: vec![field.clone()],
+            ..<_>::default()
+        };
+        let input = Input::Struct(options.clone());
+        let body = StructGenerator::new(&input, &options)
+            .read_fields(name, None)
+            .initialize_value_with_assertions(None, &[])
+            .return_value()
+            .finish();
+
+        quote! {
+            (|| {
+                #body
+            })()
+        }
+    };
+
+    let no_match = quote! {
+        Err(#BIN_ERROR::NoVariantMatch {
+            pos: #POS
+        })
+    };
+
+    let read_active_field = get_union_dispatch(un, read_field, &no_match);
+
+    quote! {
+        #prelude
+        #read_active_field
+    }
+}
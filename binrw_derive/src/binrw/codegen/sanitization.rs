@@ -33,6 +33,9 @@ ident_str! {
     pub(crate) BIN_RESULT = from_crate!(BinResult);
     pub(crate) ENDIAN_ENUM = from_crate!(Endian);
     pub(crate) READ_METHOD = from_read_trait!(read_options);
+    pub(crate) BINREAD_BORROWED_TRAIT = from_crate!(BinReadBorrowed);
+    pub(crate) READ_BORROWED_METHOD = from_crate!(BinReadBorrowed::read_borrowed_options);
+    pub(crate) CURSOR = from_crate!(io::Cursor);
     pub(crate) WRITE_METHOD = from_write_trait!(write_options);
     pub(crate) READER = "__binrw_generated_var_reader";
     pub(crate) WRITER = "__binrw_generated_var_writer";
@@ -40,16 +43,23 @@ ident_str! {
     pub(crate) ARGS = "__binrw_generated_var_arguments";
     pub(crate) SAVED_POSITION = "__binrw_generated_saved_position";
     pub(crate) ASSERT_MAGIC = from_crate!(__private::magic);
+    pub(crate) READ_MAGIC_VALUE = from_crate!(__private::read_magic_value);
     pub(crate) ASSERT = from_crate!(__private::assert);
     pub(crate) ASSERT_ERROR_FN = from_crate!(__private::AssertErrorFn);
+    pub(crate) ASSERT_ERROR_WITH = from_crate!(__private::assert_error_with);
+    pub(crate) ASSERTION_FAILED_MESSAGE = from_crate!(__private::assertion_failed_message);
     pub(crate) COERCE_FN = from_crate!(__private::coerce_fn);
+    pub(crate) ARGS_ITER = from_crate!(helpers::args_iter);
     pub(crate) ARGS_TYPE_HINT = from_crate!(__private::parse_function_args_type_hint);
     pub(crate) MAP_ARGS_TYPE_HINT = from_crate!(__private::map_args_type_hint);
     pub(crate) REQUIRED_ARG_TRAIT = from_crate!(__private::Required);
     pub(crate) MAP_READER_TYPE_HINT = from_crate!(__private::map_reader_type_hint);
     pub(crate) MAP_WRITER_TYPE_HINT = from_crate!(__private::map_writer_type_hint);
+    pub(crate) MAP_FIELD_WRITER_TYPE_HINT = from_crate!(__private::map_field_writer_type_hint);
     pub(crate) PARSE_FN_TYPE_HINT = from_crate!(__private::parse_fn_type_hint);
     pub(crate) WRITE_FN_TYPE_HINT = from_crate!(__private::write_fn_type_hint);
+    pub(crate) WRITE_HOOK_TYPE_HINT = from_crate!(__private::write_hook_type_hint);
+    pub(crate) WRITE_FN_WRITER_TYPE_HINT = from_crate!(__private::write_fn_writer_type_hint);
     pub(crate) WRITE_ARGS_TYPE_HINT = from_crate!(__private::write_function_args_type_hint);
     pub(crate) WRITE_MAP_ARGS_TYPE_HINT = from_crate!(__private::write_map_args_type_hint);
     pub(crate) WRITE_TRY_MAP_ARGS_TYPE_HINT = from_crate!(__private::write_try_map_args_type_hint);
@@ -58,7 +68,16 @@ ident_str! {
     pub(crate) WRITE_FN_TRY_MAP_OUTPUT_TYPE_HINT = from_crate!(__private::write_fn_try_map_output_type_hint);
     pub(crate) RESTORE_POSITION = from_crate!(__private::restore_position);
     pub(crate) RESTORE_POSITION_VARIANT = from_crate!(__private::restore_position_variant);
-    pub(crate) WRITE_ZEROES = from_crate!(__private::write_zeroes);
+    pub(crate) WRITE_PADDING = from_crate!(__private::write_padding);
+    pub(crate) WRITE_ALIGNMENT = from_crate!(__private::write_alignment);
+    pub(crate) WRITE_PATCH = from_crate!(__private::write_patch);
+    pub(crate) CHECKED_REPR = from_crate!(__private::checked_repr);
+    pub(crate) OFFSET_OF_VALUE = from_crate!(__private::offset_of_value);
+    pub(crate) CHECKSUM_BUFFER = from_crate!(__private::ChecksumBuffer);
+    pub(crate) TRUNCATING_WRITER = from_crate!(__private::TruncatingWriter);
+    pub(crate) PAD_SIZE_OVERFLOW = from_crate!(__private::pad_size_overflow);
+    pub(crate) WRITE_CHECKSUM = from_crate!(__private::write_checksum);
+    pub(crate) VERIFY_CHECKSUM = from_crate!(__private::verify_checksum);
     pub(crate) ARGS_MACRO = from_crate!(args);
     pub(crate) META_ENDIAN_KIND = from_crate!(meta::EndianKind);
     pub(crate) READ_ENDIAN = from_crate!(meta::ReadEndian);
@@ -70,10 +89,13 @@ ident_str! {
     pub(crate) TEMP = "__binrw_temp";
     pub(crate) THIS = "__binrw_this";
     pub(crate) POS = "__binrw_generated_position_temp";
+    pub(crate) MAGIC_VALUE = "__binrw_generated_magic_value";
     pub(crate) ERROR_BASKET = "__binrw_generated_error_basket";
     pub(crate) READ_FUNCTION = "__binrw_generated_read_function";
     pub(crate) WRITE_FUNCTION = "__binrw_generated_write_function";
     pub(crate) BEFORE_POS = "__binrw_generated_before_pos";
+    pub(crate) WRITE_POSITION = "write_position";
+    pub(crate) STRUCT_POSITION = "struct_position";
     pub(crate) DBG_EPRINTLN = from_crate!(__private::eprintln);
 }
 
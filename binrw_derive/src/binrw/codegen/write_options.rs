@@ -1,18 +1,21 @@
 mod r#enum;
+mod patch;
 mod prelude;
 mod r#struct;
 mod struct_field;
+mod union;
 
 use super::get_map_err;
 use crate::binrw::{
-    codegen::sanitization::{OPT, POS, SEEK_TRAIT, WRITER, WRITE_METHOD},
+    codegen::sanitization::{ARGS, OPT, POS, SEEK_TRAIT, WRITER, WRITE_METHOD},
     parser::{Input, Map},
 };
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens};
 use r#enum::{generate_data_enum, generate_unit_enum};
 use r#struct::generate_struct;
 use syn::{spanned::Spanned, Ident};
+use union::generate_union;
 
 pub(crate) fn generate(input: &Input, derive_input: &syn::DeriveInput) -> TokenStream {
     let name = Some(&derive_input.ident);
@@ -21,10 +24,12 @@ pub(crate) fn generate(input: &Input, derive_input: &syn::DeriveInput) -> TokenS
             Input::UnitStruct(s) | Input::Struct(s) => generate_struct(input, name, s),
             Input::Enum(e) => generate_data_enum(input, name, e),
             Input::UnitOnlyEnum(e) => generate_unit_enum(input, name, e),
+            Input::Union(u) => generate_union(input, name, u),
         },
         Map::Try(map) | Map::Map(map) => generate_map(input, name, map),
         Map::Repr(map) => match input {
             Input::UnitOnlyEnum(e) => generate_unit_enum(input, name, e),
+            Input::Enum(e) if e.is_keyed_repr() => generate_data_enum(input, name, e),
             _ => generate_map(input, name, map),
         },
     };
@@ -51,12 +56,17 @@ fn generate_map(input: &Input, name: Option<&Ident>, map: &TokenStream) -> Token
         map.clone()
     };
     let writer_var = input.stream_ident_or(WRITER);
+    let args = if input.repr_args().is_some() {
+        ARGS.to_token_stream()
+    } else {
+        quote! { () }
+    };
     let write_data = quote! {
         #WRITE_METHOD(
             &((#map)(self) #map_try),
             #writer_var,
             #OPT,
-            ()
+            #args
         )?;
     };
 
@@ -64,6 +74,7 @@ fn generate_map(input: &Input, name: Option<&Ident>, map: &TokenStream) -> Token
     let endian = input.endian();
     prelude::PreludeGenerator::new(write_data, input, name, &writer_var)
         .prefix_magic(magic)
+        .wrap_hooks()
         .prefix_assertions()
         .prefix_endian(endian)
         .prefix_imports()
@@ -1,6 +1,6 @@
 use super::{prelude::PreludeGenerator, r#struct::StructGenerator};
 use crate::binrw::{
-    codegen::sanitization::{OPT, WRITER, WRITE_METHOD},
+    codegen::sanitization::{CHECKED_REPR, OPT, POS, TEMP, THIS, WRITER, WRITE_METHOD},
     parser::{Enum, EnumVariant, Input, UnitEnumField, UnitOnlyEnum},
 };
 use proc_macro2::{Ident, TokenStream};
@@ -13,6 +13,9 @@ pub(crate) fn generate_unit_enum(
 ) -> TokenStream {
     let writer_var = input.stream_ident_or(WRITER);
     let write = match en.map.as_repr() {
+        Some(repr) if en.checked_repr.is_some() => {
+            generate_unit_enum_checked_repr(&writer_var, name, repr, &en.fields)
+        }
         Some(repr) => generate_unit_enum_repr(&writer_var, repr, &en.fields),
         None => generate_unit_enum_magic(&writer_var, &en.fields),
     };
@@ -20,6 +23,7 @@ pub(crate) fn generate_unit_enum(
     PreludeGenerator::new(write, input, name, &writer_var)
         .prefix_map_stream()
         .prefix_magic(&en.magic)
+        .wrap_hooks()
         .prefix_assertions()
         .prefix_endian(&en.endian)
         .prefix_imports()
@@ -58,46 +62,79 @@ impl<'a> EnumGenerator<'a> {
     }
 
     fn write_variants(mut self) -> Self {
-        let variants = self.en.variants.iter().map(|variant| {
-            let name = variant.ident();
-            let fields = match variant {
-                EnumVariant::Variant { options, .. } => Some(options.fields_pattern()),
-                EnumVariant::Unit(_) => None,
-            };
-
-            let writer_var = &self.writer_var;
-            let writing = match variant {
-                EnumVariant::Variant { options, .. } => {
-                    let input = Input::Struct(variant.clone().into());
-
-                    StructGenerator::new(&input, options, None, &self.writer_var)
-                        .write_fields()
-                        .prefix_prelude()
-                        .finish()
-                }
-                EnumVariant::Unit(variant) => variant
-                    .magic
-                    .as_ref()
-                    .map(|magic| {
-                        let magic = magic.match_value();
-                        quote! {
+        // in a keyed `repr` enum, each variant is prefixed by its discriminant
+        let repr = self.en.map.as_repr().filter(|_| self.en.is_keyed_repr());
+        let variants = self
+            .en
+            .variant_discriminants()
+            .map(|(variant, discriminant)| {
+                let name = variant.ident();
+                let writer_var = &self.writer_var;
+
+                if let (Some(repr), Some(_)) = (repr, variant.fallback()) {
+                    return quote! {
+                        Self::#name(#THIS) => {
+                            let #TEMP: #repr = *#THIS;
                             #WRITE_METHOD (
-                                &#magic,
+                                &#TEMP,
                                 #writer_var,
                                 #OPT,
                                 ()
                             )?;
                         }
-                    })
-                    .unwrap_or_default(),
-            };
+                    };
+                }
 
-            quote! {
-                Self::#name #fields => {
-                    #writing
+                let fields = match variant {
+                    EnumVariant::Variant { options, .. } => Some(options.fields_pattern()),
+                    EnumVariant::Unit(_) => None,
+                };
+
+                let discriminant = repr.map(|repr| {
+                    quote! {
+                        let #TEMP: #repr = #discriminant;
+                        #WRITE_METHOD (
+                            &#TEMP,
+                            #writer_var,
+                            #OPT,
+                            ()
+                        )?;
+                    }
+                });
+
+                let writing = match variant {
+                    EnumVariant::Variant { options, .. } => {
+                        let input = Input::Struct(variant.clone().into());
+
+                        StructGenerator::new(&input, options, None, &self.writer_var)
+                            .write_fields()
+                            .prefix_prelude()
+                            .finish()
+                    }
+                    EnumVariant::Unit(variant) => variant
+                        .magic
+                        .as_ref()
+                        .map(|magic| {
+                            let magic = magic.ref_value();
+                            quote! {
+                                #WRITE_METHOD (
+                                    #magic,
+                                    #writer_var,
+                                    #OPT,
+                                    ()
+                                )?;
+                            }
+                        })
+                        .unwrap_or_default(),
+                };
+
+                quote! {
+                    Self::#name #fields => {
+                        #discriminant
+                        #writing
+                    }
                 }
-            }
-        });
+            });
 
         self.out = quote! {
             match self {
@@ -114,6 +151,7 @@ impl<'a> EnumGenerator<'a> {
         self.out = PreludeGenerator::new(out, self.input, self.name, &self.writer_var)
             .prefix_map_stream()
             .prefix_magic(&self.en.magic)
+            .wrap_hooks()
             .prefix_assertions()
             .prefix_endian(&self.en.endian)
             .prefix_imports()
@@ -151,15 +189,45 @@ fn generate_unit_enum_repr(
     }
 }
 
+fn generate_unit_enum_checked_repr(
+    writer_var: &TokenStream,
+    name: Option<&Ident>,
+    repr: &TokenStream,
+    variants: &[UnitEnumField],
+) -> TokenStream {
+    let branches = variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let message = format!(
+            "discriminant of `{}::{ident}` does not fit in `{repr}`",
+            name.map_or_else(|| "Self".to_owned(), ToString::to_string),
+        );
+        quote! {
+            Self::#ident => #CHECKED_REPR(Self::#ident as i128, #POS, #message)?
+        }
+    });
+
+    quote! {
+        let #TEMP: #repr = match self {
+            #(#branches),*
+        };
+        #WRITE_METHOD (
+            &#TEMP,
+            #writer_var,
+            #OPT,
+            (),
+        )?;
+    }
+}
+
 fn generate_unit_enum_magic(writer_var: &TokenStream, variants: &[UnitEnumField]) -> TokenStream {
     let branches = variants.iter().map(|variant| {
         let name = &variant.ident;
         let magic = variant.magic.as_ref().map(|magic| {
-            let magic = magic.match_value();
+            let magic = magic.ref_value();
 
             quote! {
                 #WRITE_METHOD (
-                    &#magic,
+                    #magic,
                     #writer_var,
                     #OPT,
                     (),
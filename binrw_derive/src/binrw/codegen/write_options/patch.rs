@@ -0,0 +1,243 @@
+use crate::binrw::{
+    codegen::{
+        get_endian,
+        sanitization::{
+            make_ident, CHECKSUM_BUFFER, OFFSET_OF_VALUE, SEEK_TRAIT, WRITE_CHECKSUM, WRITE_PATCH,
+        },
+    },
+    parser::{Struct, StructField},
+};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+/// Generates the code which replaces the placeholders written for `size_of`,
+/// `offset_of`, and `checksum` fields once their values are known.
+pub(super) struct Patches<'input> {
+    st: &'input Struct,
+    writer_var: &'input TokenStream,
+    sizes: Vec<Option<(usize, usize)>>,
+    offsets: Vec<Option<(Option<usize>, usize)>>,
+    checksums: Vec<Option<(usize, usize)>>,
+}
+
+impl<'input> Patches<'input> {
+    pub(super) fn new(st: &'input Struct, writer_var: &'input TokenStream) -> Self {
+        Self {
+            st,
+            writer_var,
+            sizes: st
+                .fields
+                .iter()
+                .map(|field| st.size_of_range(field))
+                .collect(),
+            offsets: st
+                .fields
+                .iter()
+                .map(|field| st.offset_of_fields(field))
+                .collect(),
+            checksums: st
+                .fields
+                .iter()
+                .map(|field| st.checksum_range(field))
+                .collect(),
+        }
+    }
+
+    /// Returns true if any bytes need to be kept for computing checksums.
+    pub(super) fn needs_checksum_buffer(&self) -> bool {
+        self.checksums.iter().any(Option::is_some)
+    }
+
+    /// Returns true if the position of the field at `index` is needed by an
+    /// `offset_of` field or as an `align_relative` anchor.
+    pub(super) fn needs_position(&self, index: usize) -> bool {
+        let ident = &self.st.fields[index].ident;
+        self.offsets
+            .iter()
+            .flatten()
+            .any(|(base, target)| *target == index || *base == Some(index))
+            || self.st.fields.iter().any(|field| {
+                field
+                    .align_relative
+                    .as_ref()
+                    .and_then(|align_relative| align_relative.anchor.as_ref())
+                    == Some(ident)
+            })
+    }
+
+    /// Returns the code to run before writing the field at `index`.
+    pub(super) fn before(&self, index: usize) -> TokenStream {
+        let writer_var = self.writer_var;
+        let starts = self
+            .range_fields()
+            .filter(|(_, (first, _), _)| *first == index)
+            .map(|(field, _, kind)| {
+                let start = make_ident(&field.ident, &format!("{kind}_start"));
+                if kind == "checksum" {
+                    // The checksum buffer keeps the bytes from here until the
+                    // checksum is written
+                    quote! {
+                        let #start = #CHECKSUM_BUFFER::begin(#writer_var);
+                    }
+                } else {
+                    quote! {
+                        let #start = #SEEK_TRAIT::stream_position(#writer_var)?;
+                    }
+                }
+            });
+
+        // The field may not be written at all if it is conditional
+        let position = self.needs_position(index).then(|| {
+            let position = position_ident(&self.st.fields[index].ident);
+            quote! {
+                let mut #position = ::core::option::Option::<u64>::None;
+            }
+        });
+
+        quote! {
+            #(#starts)*
+            #position
+        }
+    }
+
+    /// Returns the code to run after writing the field at `index`.
+    pub(super) fn after(&self, index: usize) -> TokenStream {
+        let writer_var = self.writer_var;
+        let ends = self
+            .range_fields()
+            .filter(|(_, (_, last), _)| *last == index)
+            .map(|(field, _, kind)| {
+                let end = make_ident(&field.ident, &format!("{kind}_end"));
+                quote! {
+                    let #end = #SEEK_TRAIT::stream_position(#writer_var)?;
+                }
+            });
+
+        // A placeholder can only be replaced once it and every field it
+        // depends on have been written
+        let sizes = self
+            .st
+            .fields
+            .iter()
+            .zip(&self.sizes)
+            .enumerate()
+            .filter_map(|(field_index, (field, range))| {
+                let (_, last) = (*range)?;
+                (field_index.max(last) == index).then(|| {
+                    let start = make_ident(&field.ident, "size_of_start");
+                    let end = make_ident(&field.ident, "size_of_end");
+                    self.patch(field, &quote! { #end.checked_sub(#start) }, "size")
+                })
+            });
+
+        let offsets = self
+            .st
+            .fields
+            .iter()
+            .zip(&self.offsets)
+            .enumerate()
+            .filter_map(|(field_index, (field, fields))| {
+                let (base, target) = (*fields)?;
+                (field_index.max(target).max(base.unwrap_or(0)) == index).then(|| {
+                    let target = position_ident(&self.st.fields[target].ident);
+                    let base = base.map_or_else(
+                        || quote! { ::core::option::Option::Some(0) },
+                        |base| position_ident(&self.st.fields[base].ident).into_token_stream(),
+                    );
+                    self.patch(
+                        field,
+                        &quote! { #OFFSET_OF_VALUE(#target, #base) },
+                        "offset",
+                    )
+                })
+            });
+
+        let checksums = self
+            .st
+            .fields
+            .iter()
+            .zip(&self.checksums)
+            .enumerate()
+            .filter_map(|(field_index, (field, range))| {
+                let (_, last) = (*range)?;
+                let checksum = field.checksum.as_ref()?;
+                (field_index.max(last) == index).then(|| {
+                    let writer_var = self.writer_var;
+                    let ty = &field.ty;
+                    let endian = get_endian(&field.endian);
+                    let slot = slot_ident(field);
+                    let start = make_ident(&field.ident, "checksum_start");
+                    let end = make_ident(&field.ident, "checksum_end");
+                    let algorithm = &checksum.algorithm;
+                    quote! {
+                        #WRITE_CHECKSUM::<#ty, _, _>(
+                            #writer_var,
+                            #slot,
+                            #start,
+                            #end,
+                            #algorithm,
+                            #endian,
+                        )?;
+                    }
+                })
+            });
+
+        quote! {
+            #(#ends)*
+            #(#sizes)*
+            #(#offsets)*
+            #(#checksums)*
+        }
+    }
+
+    fn patch(&self, field: &StructField, value: &TokenStream, kind: &str) -> TokenStream {
+        let writer_var = self.writer_var;
+        let ty = &field.ty;
+        let endian = get_endian(&field.endian);
+        let slot = slot_ident(field);
+        let message = format!("{kind} does not fit in `{}`", field.ident);
+        quote! {
+            #WRITE_PATCH::<#ty, _>(
+                #writer_var,
+                #slot,
+                #value,
+                #endian,
+                #message,
+            )?;
+        }
+    }
+
+    /// Returns the fields which depend on the positions where a range of
+    /// fields starts and ends, along with the prefix used for their position
+    /// variables.
+    fn range_fields(
+        &self,
+    ) -> impl Iterator<Item = (&StructField, (usize, usize), &'static str)> + '_ {
+        let sizes = self
+            .st
+            .fields
+            .iter()
+            .zip(&self.sizes)
+            .filter_map(|(field, range)| Some((field, (*range)?, "size_of")));
+        let checksums = self
+            .st
+            .fields
+            .iter()
+            .zip(&self.checksums)
+            .filter_map(|(field, range)| Some((field, (*range)?, "checksum")));
+        sizes.chain(checksums)
+    }
+}
+
+/// Returns the variable which holds the position where the placeholder of a
+/// `size_of`, `offset_of`, or `checksum` field was written.
+pub(super) fn slot_ident(field: &StructField) -> syn::Ident {
+    make_ident(&field.ident, "patch_slot")
+}
+
+/// Returns the variable which holds the position where a field referenced by
+/// an `offset_of` or `align_relative` directive was written, if it was
+/// written.
+pub(super) fn position_ident(ident: &syn::Ident) -> syn::Ident {
+    make_ident(ident, "position")
+}
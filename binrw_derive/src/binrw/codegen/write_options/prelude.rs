@@ -2,7 +2,10 @@ use crate::{
     binrw::{
         codegen::{
             get_assertions, get_destructured_imports, get_endian,
-            sanitization::{ARGS, MAP_WRITER_TYPE_HINT, OPT, WRITER, WRITE_METHOD},
+            sanitization::{
+                ARGS, MAP_WRITER_TYPE_HINT, OPT, THIS, WRITER, WRITE_HOOK_TYPE_HINT, WRITE_METHOD,
+                WRITE_TRAIT,
+            },
         },
         parser::{CondEndian, Input, Magic},
     },
@@ -35,7 +38,7 @@ impl<'a> PreludeGenerator<'a> {
     }
 
     pub(super) fn prefix_assertions(mut self) -> Self {
-        let assertions = get_assertions(self.input.assertions());
+        let assertions = get_assertions(self.input.assertions(), Some(quote! { #THIS }));
         let out = self.out;
         self.out = quote! {
             #(#assertions)*
@@ -60,11 +63,11 @@ impl<'a> PreludeGenerator<'a> {
     pub(crate) fn prefix_magic(mut self, magic: &Magic) -> Self {
         if let Some(magic) = magic {
             let writer_var = &self.writer_var;
-            let magic = magic.match_value();
+            let magic = magic.ref_value();
             let out = self.out;
             self.out = quote! {
                 #WRITE_METHOD (
-                    &#magic,
+                    #magic,
                     #writer_var,
                     #OPT,
                     ()
@@ -96,12 +99,44 @@ impl<'a> PreludeGenerator<'a> {
             self.out = quote_spanned_any! { map_stream.span()=>
                 let #inner_writer = &mut #MAP_WRITER_TYPE_HINT::<W, _, _>(#map_stream)(#outer_writer);
                 #tail
+                #WRITE_TRAIT::flush(#inner_writer)?;
             };
         }
 
         self
     }
 
+    /// Wraps the code which writes the object with calls to its `pre_write`
+    /// and `post_write` hooks.
+    pub(crate) fn wrap_hooks(mut self) -> Self {
+        let (pre_write, post_write) = (self.input.pre_write(), self.input.post_write());
+        if pre_write.is_none() && post_write.is_none() {
+            return self;
+        }
+
+        let writer_var = self.writer_var;
+        let call = |hook: &TokenStream| {
+            quote_spanned_any! { hook.span()=>
+                #WRITE_HOOK_TYPE_HINT::<Self, _, _>(&*#writer_var, #hook)(self, #writer_var, #OPT)?;
+            }
+        };
+        let pre_write = pre_write.map(call);
+        let post_write = post_write.map(call);
+
+        // The object is written in its own scope since a mapped stream
+        // shadows the writer
+        let out = self.out;
+        self.out = quote! {
+            #pre_write
+            {
+                #out
+            }
+            #post_write
+        };
+
+        self
+    }
+
     pub(crate) fn finish(self) -> TokenStream {
         self.out
     }
@@ -1,9 +1,19 @@
-use super::{prelude::PreludeGenerator, struct_field::write_field};
+use super::{
+    patch::Patches,
+    prelude::PreludeGenerator,
+    struct_field::{calc_value, write_struct_field},
+};
 use crate::binrw::{
-    codegen::sanitization::{THIS, WRITER},
-    parser::{Input, Struct},
+    codegen::{
+        get_assertions,
+        sanitization::{
+            CHECKSUM_BUFFER, POS, SEEK_TRAIT, STRUCT_POSITION, THIS, WRITER, WRITE_POSITION,
+        },
+    },
+    parser::{Input, Struct, StructField},
 };
-use proc_macro2::TokenStream;
+use alloc::borrow::Cow;
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
 use syn::Ident;
 
@@ -49,9 +59,30 @@ impl<'input> StructGenerator<'input> {
     }
 
     pub(super) fn prefix_prelude(mut self) -> Self {
-        self.out = PreludeGenerator::new(self.out, self.input, self.name, self.writer_var)
+        // Field assertions are checked before anything is written so that a
+        // failed assertion does not leave a partially written object behind
+        let field_assertions = self
+            .st
+            .fields
+            .iter()
+            .filter(|field| self.has_early_assertions(field))
+            .flat_map(|field| {
+                let ident = &field.ident;
+                get_assertions(&field.assertions, Some(quote! { #ident }))
+            })
+            .collect::<Vec<_>>();
+
+        let out = PreludeGenerator::new(self.out, self.input, self.name, self.writer_var)
             .prefix_map_stream()
             .prefix_magic(&self.st.magic)
+            .wrap_hooks()
+            .finish();
+        let out = quote! {
+            #(#field_assertions)*
+            #out
+        };
+
+        self.out = PreludeGenerator::new(out, self.input, self.name, self.writer_var)
             .prefix_endian(&self.st.endian)
             .prefix_assertions()
             .finish();
@@ -59,14 +90,94 @@ impl<'input> StructGenerator<'input> {
         self
     }
 
+    /// Returns true if the assertions of the field can be checked before
+    /// anything is written.
+    ///
+    /// Assertions which depend on calculated values or on the state of the
+    /// stream are checked when the field is written instead.
+    fn has_early_assertions(&self, field: &StructField) -> bool {
+        !field.is_calc()
+            && !self.st.field_uses_position(field)
+            && !self
+                .st
+                .fields
+                .iter()
+                .any(|other| other.is_calc() && field.writes_ident(&other.ident))
+            && self
+                .input
+                .stream_ident()
+                .map_or(true, |stream| !field.writes_ident(stream))
+    }
+
     pub(super) fn write_fields(mut self) -> Self {
+        let forward_calc_fields = self
+            .st
+            .forward_calc_fields()
+            .unwrap_or_else(|_| vec![Vec::new(); self.st.fields.len()]);
+        let precomputed = forward_calc_fields.iter().flatten().collect::<Vec<_>>();
+        let patches = Patches::new(self.st, self.writer_var);
+        let write_position = WRITE_POSITION.to_ident(Span::call_site());
+        let uses_write_position = self.st.uses_implicit_position(&write_position);
+
         let write_fields = self
             .st
             .fields
             .iter()
-            .map(|field| write_field(self.writer_var, field));
+            .zip(&forward_calc_fields)
+            .enumerate()
+            .map(|(index, (field, forward))| {
+                let field = if self.has_early_assertions(field) {
+                    let mut field = field.clone();
+                    field.assertions.clear();
+                    Cow::Owned(field)
+                } else {
+                    Cow::Borrowed(field)
+                };
+                let forward = forward
+                    .iter()
+                    .filter_map(|&index| calc_value(&self.st.fields[index]));
+                let write = write_struct_field(
+                    self.writer_var,
+                    &field,
+                    precomputed.contains(&&index),
+                    patches.needs_position(index),
+                );
+                let before = patches.before(index);
+                let after = patches.after(index);
+                let position =
+                    (uses_write_position && field.writes_ident(&write_position)).then(|| {
+                        let writer_var = self.writer_var;
+                        quote! {
+                            let #write_position = #SEEK_TRAIT::stream_position(#writer_var)?;
+                        }
+                    });
+                quote! {
+                    #(#forward)*
+                    #position
+                    #before
+                    #write
+                    #after
+                }
+            });
+
+        // Checksums are computed from a copy of the bytes written while a
+        // checksum is pending since the writer cannot be read back
+        let checksum_buffer = patches.needs_checksum_buffer().then(|| {
+            let writer_var = self.writer_var;
+            quote! {
+                let #writer_var = &mut #CHECKSUM_BUFFER::new(#writer_var)?;
+            }
+        });
+
+        let struct_position = STRUCT_POSITION.to_ident(Span::call_site());
+        let struct_position = self
+            .st
+            .uses_implicit_position(&struct_position)
+            .then(|| quote! { let #struct_position = #POS; });
 
         self.out = quote! {
+            #struct_position
+            #checksum_buffer
             #(#write_fields)*
         };
 
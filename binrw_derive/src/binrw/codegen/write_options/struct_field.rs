@@ -1,17 +1,22 @@
+use super::patch::{position_ident, slot_ident};
 use crate::{
     binrw::{
         codegen::{
-            get_assertions, get_endian, get_map_err, get_passed_args, get_try_calc,
+            dbg_line, dbg_space, get_assertions, get_endian, get_map_err, get_passed_args,
+            get_try_calc,
             sanitization::{
-                make_ident, BEFORE_POS, BINWRITE_TRAIT, MAP_WRITER_TYPE_HINT, POS,
-                REQUIRED_ARG_TRAIT, SAVED_POSITION, SEEK_FROM, SEEK_TRAIT, WRITE_ARGS_TYPE_HINT,
+                make_ident, BEFORE_POS, BINWRITE_TRAIT, DBG_EPRINTLN, MAP_FIELD_WRITER_TYPE_HINT,
+                PAD_SIZE_OVERFLOW, POS, REQUIRED_ARG_TRAIT, SAVED_POSITION, SEEK_FROM, SEEK_TRAIT,
+                TRUNCATING_WRITER, WRITE_ALIGNMENT, WRITE_ARGS_TYPE_HINT,
                 WRITE_FN_MAP_OUTPUT_TYPE_HINT, WRITE_FN_TRY_MAP_OUTPUT_TYPE_HINT,
-                WRITE_FN_TYPE_HINT, WRITE_FUNCTION, WRITE_MAP_ARGS_TYPE_HINT,
-                WRITE_MAP_INPUT_TYPE_HINT, WRITE_METHOD, WRITE_TRY_MAP_ARGS_TYPE_HINT,
-                WRITE_ZEROES,
+                WRITE_FN_TYPE_HINT, WRITE_FN_WRITER_TYPE_HINT, WRITE_FUNCTION,
+                WRITE_MAP_ARGS_TYPE_HINT, WRITE_MAP_INPUT_TYPE_HINT, WRITE_METHOD, WRITE_PADDING,
+                WRITE_TRAIT, WRITE_TRY_MAP_ARGS_TYPE_HINT,
             },
         },
-        parser::{FieldMode, Map, StructField},
+        parser::{
+            AlignRelative, FieldMode, Map, PadSizeOverflow, PadSizeOverflowMode, StructField,
+        },
     },
     util::quote_spanned_any,
 };
@@ -22,11 +27,49 @@ use quote::{quote, quote_spanned, ToTokens};
 use syn::{spanned::Spanned, Ident};
 
 pub(crate) fn write_field(writer_var: &TokenStream, field: &StructField) -> TokenStream {
-    StructFieldGenerator::new(field, writer_var)
+    write_struct_field(writer_var, field, false, false)
+}
+
+/// Computes the value of a `calc` field.
+pub(crate) fn calc_value(field: &StructField) -> Option<TokenStream> {
+    let name = &field.ident;
+    let ty = &field.ty;
+    let expr = match &field.field_mode {
+        FieldMode::Calc(expr) => expr.clone(),
+        FieldMode::TryCalc(expr) => get_try_calc(POS, &field.ty, expr),
+        _ => return None,
+    };
+
+    Some(quote! {
+        let #name: #ty = #expr;
+    })
+}
+
+/// Writes a field of a struct.
+///
+/// If `precomputed` is true, the value of a `calc` field was already computed
+/// by [`calc_value`] because an earlier field refers to it. If
+/// `record_position` is true, the position of the field is stored for an
+/// `offset_of` field.
+pub(super) fn write_struct_field(
+    writer_var: &TokenStream,
+    field: &StructField,
+    precomputed: bool,
+    record_position: bool,
+) -> TokenStream {
+    let generator = StructFieldGenerator::new(field, writer_var)
         .write_field()
         .wrap_map_stream()
-        .prefix_map_value()
-        .prefix_calc_value()
+        .wrap_debug()
+        .prefix_position(record_position)
+        .prefix_map_value();
+    let generator = if precomputed {
+        generator
+    } else {
+        generator.prefix_calc_value()
+    };
+    generator
+        .wrap_truncate()
         .wrap_padding()
         .prefix_magic()
         .wrap_condition()
@@ -59,7 +102,8 @@ impl<'a> StructFieldGenerator<'a> {
     }
 
     fn prefix_assertions(mut self) -> Self {
-        let assertions = get_assertions(&self.field.assertions);
+        let ident = &self.field.ident;
+        let assertions = get_assertions(&self.field.assertions, Some(quote! { #ident }));
 
         let out = self.out;
         self.out = quote! {
@@ -75,15 +119,56 @@ impl<'a> StructFieldGenerator<'a> {
             let rest = self.out;
             let writer_var = &self.writer_var;
             let outer_writer_var = self.outer_writer_var;
+            // The mapped stream is flushed so that any buffered output is
+            // written before the positions of later fields are measured
             self.out = quote_spanned_any! { map_stream.span()=> {
-                let #writer_var = &mut #MAP_WRITER_TYPE_HINT::<W, _, _>(#map_stream)(#outer_writer_var);
+                let #writer_var = &mut #MAP_FIELD_WRITER_TYPE_HINT(&*#outer_writer_var, #map_stream)(#outer_writer_var);
                 #rest
+                #WRITE_TRAIT::flush(#writer_var)?;
             }};
         }
 
         self
     }
 
+    fn wrap_debug(mut self) -> Self {
+        if self.field.debug.is_none() || !self.field.is_written() {
+            return self;
+        }
+
+        let out = self.out;
+        let writer_var = self.outer_writer_var;
+        let ident = &self.field.ident;
+        let at = dbg_line(ident);
+
+        let dbg_pad_before = dbg_space("pad_before", &at, self.field.pad_before.as_ref());
+        let dbg_align_before = dbg_space("align_before", &at, self.field.align_before.as_ref());
+        let dbg_pad_size_to = dbg_space("pad_size_to", &at, self.field.pad_size_to.as_ref());
+        let dbg_pad_after = dbg_space("pad_after", &at, self.field.pad_after.as_ref());
+        let dbg_align_after = dbg_space("align_after", &at, self.field.align_after.as_ref());
+
+        self.out = quote! {{
+            #dbg_pad_before
+            #dbg_align_before
+            let #SAVED_POSITION = #SEEK_TRAIT::stream_position(#writer_var)?;
+            #out
+            {
+                let end = #SEEK_TRAIT::stream_position(#writer_var)?;
+                #DBG_EPRINTLN!(
+                    "[{}:{} | offset {:#x}..{:#x}, {} bytes] {} = {:#x?}",
+                    ::core::file!(), #at, #SAVED_POSITION, end,
+                    end.saturating_sub(#SAVED_POSITION),
+                    ::core::stringify!(#ident), &#ident
+                );
+            }
+            #dbg_pad_size_to
+            #dbg_pad_after
+            #dbg_align_after
+        }};
+
+        self
+    }
+
     fn prefix_write_function(mut self) -> Self {
         if !self.field.is_written() {
             return self;
@@ -94,7 +179,7 @@ impl<'a> StructFieldGenerator<'a> {
                 quote! { #WRITE_METHOD }
             }
             FieldMode::Function(write_fn) => write_fn.clone(),
-            FieldMode::Default => unreachable!("Ignored fields are not written"),
+            FieldMode::Default(_) => unreachable!("Ignored fields are not written"),
         };
 
         let write_fn = if self.field.map.is_some() {
@@ -106,7 +191,14 @@ impl<'a> StructFieldGenerator<'a> {
             }
         } else {
             let ty = &self.field.ty;
-            quote! { #WRITE_FN_TYPE_HINT::<#ty, _, _, _>(#write_fn) }
+            if self.field.map_stream.is_some() || self.field.truncates() {
+                quote! { #WRITE_FN_TYPE_HINT::<#ty, _, _, _>(#write_fn) }
+            } else {
+                // Tying the writer type to the writer lets closures call its
+                // methods without a type annotation
+                let writer_var = self.outer_writer_var;
+                quote! { #WRITE_FN_WRITER_TYPE_HINT::<#ty, _, _, _>(&*#writer_var, #write_fn) }
+            }
         };
 
         let out = self.out;
@@ -139,17 +231,11 @@ impl<'a> StructFieldGenerator<'a> {
     }
 
     fn prefix_calc_value(mut self) -> Self {
-        let name = &self.field.ident;
-        let ty = &self.field.ty;
-        let expr = match &self.field.field_mode {
-            FieldMode::Calc(expr) => expr.clone(),
-            FieldMode::TryCalc(expr) => get_try_calc(POS, &self.field.ty, expr),
-            _ => return self,
-        };
+        let calc_value = calc_value(self.field);
 
         let rest = self.out;
         self.out = quote! {
-            let #name: #ty = #expr;
+            #calc_value
             #rest
         };
 
@@ -214,7 +300,15 @@ impl<'a> StructFieldGenerator<'a> {
             })
             .unwrap_or_else(|| quote_spanned! { name.span()=> &#name });
 
+        let patch_slot = self.field.is_patched().then(|| {
+            let slot = slot_ident(self.field);
+            quote! {
+                let #slot = #SEEK_TRAIT::stream_position(#writer_var)?;
+            }
+        });
+
         self.out = quote! {
+            #patch_slot
             #WRITE_FUNCTION(
                 #name,
                 #writer_var,
@@ -226,15 +320,40 @@ impl<'a> StructFieldGenerator<'a> {
         self
     }
 
+    fn prefix_position(mut self, record_position: bool) -> Self {
+        if record_position {
+            let position = position_ident(&self.field.ident);
+            let outer_writer_var = self.outer_writer_var;
+            let rest = self.out;
+            self.out = quote! {
+                #position = ::core::option::Option::Some(
+                    #SEEK_TRAIT::stream_position(#outer_writer_var)?
+                );
+                #rest
+            };
+        }
+
+        self
+    }
+
     fn wrap_condition(mut self) -> Self {
         if let Some(cond) = &self.field.if_cond {
             if cond.alternate.is_none() {
                 let condition = &cond.condition;
                 let consequent = self.out;
+                let alternate = self.field.else_pad.as_ref().map(|padding| {
+                    let outer_writer_var = self.outer_writer_var;
+                    let pad_byte = pad_byte(self.field);
+                    quote! {
+                        else {
+                            #WRITE_PADDING(#outer_writer_var, (#padding) as u64, #pad_byte)?;
+                        }
+                    }
+                });
                 self.out = quote! {
                     if #condition {
                         #consequent
-                    }
+                    } #alternate
                 };
             }
         }
@@ -242,6 +361,27 @@ impl<'a> StructFieldGenerator<'a> {
         self
     }
 
+    fn wrap_truncate(mut self) -> Self {
+        if !self.field.truncates() {
+            return self;
+        }
+
+        let out = self.out;
+        let outer_writer_var = self.outer_writer_var;
+        let size = &self.field.pad_size_to;
+        self.out = quote! {
+            {
+                let #outer_writer_var = &mut #TRUNCATING_WRITER::new(
+                    &mut *#outer_writer_var,
+                    (#size) as u64,
+                )?;
+                #out
+            }
+        };
+
+        self
+    }
+
     fn wrap_padding(mut self) -> Self {
         let out = self.out;
 
@@ -297,12 +437,12 @@ impl<'a> StructFieldGenerator<'a> {
                 let ty = &self.field.ty;
                 quote! {
                     let #args = #WRITE_ARGS_TYPE_HINT::<#ty, _, _, _>(
-                        #WRITE_FUNCTION, #args_val
+                        &#WRITE_FUNCTION, #args_val
                     );
                     #out
                 }
             }
-            FieldMode::Default => unreachable!("Ignored fields are not written"),
+            FieldMode::Default(_) => unreachable!("Ignored fields are not written"),
         };
 
         self
@@ -310,13 +450,13 @@ impl<'a> StructFieldGenerator<'a> {
 
     fn prefix_magic(mut self) -> Self {
         if let Some(magic) = &self.field.magic {
-            let magic = magic.match_value();
+            let magic = magic.ref_value();
             let endian = get_endian(&self.field.endian);
             let writer_var = self.outer_writer_var;
             let out = self.out;
             self.out = quote! {
                 #WRITE_METHOD (
-                    &#magic,
+                    #magic,
                     #writer_var,
                     #endian,
                     ()
@@ -350,32 +490,64 @@ fn map_func_ident(ident: &Ident) -> Ident {
     make_ident(ident, "map_func")
 }
 
+/// Returns the byte used to fill padding and alignment gaps.
+fn pad_byte(field: &StructField) -> TokenStream {
+    field
+        .pad_byte
+        .as_ref()
+        .map_or_else(|| quote! { 0 }, ToTokens::to_token_stream)
+}
+
+/// Returns the position that alignment is relative to.
+fn align_base(field: &StructField) -> TokenStream {
+    match &field.align_relative {
+        None => quote! { ::core::option::Option::Some(0) },
+        Some(AlignRelative { anchor: None, .. }) => quote! { ::core::option::Option::Some(#POS) },
+        Some(AlignRelative {
+            anchor: Some(anchor),
+            ..
+        }) => position_ident(anchor).into_token_stream(),
+    }
+}
+
 fn pad_after(writer_var: &TokenStream, field: &StructField) -> TokenStream {
+    let pad_byte = pad_byte(field);
+    let align_base = align_base(field);
     let pad_size_to = field.pad_size_to.as_ref().map(|size| {
+        let overflow = matches!(
+            field.pad_size_overflow,
+            Some(PadSizeOverflow {
+                mode: PadSizeOverflowMode::Error,
+                ..
+            })
+        )
+        .then(|| {
+            let message = format!("`{}`", field.ident);
+            quote! {
+                else {
+                    return Err(#PAD_SIZE_OVERFLOW(#BEFORE_POS, size, pad_to_size, #message));
+                }
+            }
+        });
         quote! {{
             let pad_to_size = (#size) as u64;
             let after_pos = #SEEK_TRAIT::stream_position(#writer_var)?;
             if let Some(size) = after_pos.checked_sub(#BEFORE_POS) {
                 if let Some(padding) = pad_to_size.checked_sub(size) {
-                    #WRITE_ZEROES(#writer_var, padding)?;
-                }
+                    #WRITE_PADDING(#writer_var, padding, #pad_byte)?;
+                } #overflow
             }
         }}
     });
     let pad_after = field.pad_after.as_ref().map(|padding| {
         quote! {
-            #WRITE_ZEROES(#writer_var, (#padding) as u64)?;
+            #WRITE_PADDING(#writer_var, (#padding) as u64, #pad_byte)?;
         }
     });
     let align_after = field.align_after.as_ref().map(|alignment| {
-        quote! {{
-            let pos = #SEEK_TRAIT::stream_position(#writer_var)?;
-            let align = ((#alignment) as u64);
-            let rem = pos % align;
-            if rem != 0 {
-                #WRITE_ZEROES(#writer_var, align - rem)?;
-            }
-        }}
+        quote! {
+            #WRITE_ALIGNMENT(#writer_var, (#alignment) as u64, #align_base, #pad_byte)?;
+        }
     });
     let restore_position = field.restore_position.map(|()| {
         quote! {
@@ -392,6 +564,8 @@ fn pad_after(writer_var: &TokenStream, field: &StructField) -> TokenStream {
 }
 
 fn pad_before(writer_var: &TokenStream, field: &StructField) -> TokenStream {
+    let pad_byte = pad_byte(field);
+    let align_base = align_base(field);
     let seek_before = field.seek_before.as_ref().map(|seek| {
         quote! {
             #SEEK_TRAIT::seek(
@@ -402,18 +576,13 @@ fn pad_before(writer_var: &TokenStream, field: &StructField) -> TokenStream {
     });
     let pad_before = field.pad_before.as_ref().map(|padding| {
         quote! {
-            #WRITE_ZEROES(#writer_var, (#padding) as u64)?;
+            #WRITE_PADDING(#writer_var, (#padding) as u64, #pad_byte)?;
         }
     });
     let align_before = field.align_before.as_ref().map(|alignment| {
-        quote! {{
-            let pos = #SEEK_TRAIT::stream_position(#writer_var)?;
-            let align = ((#alignment) as u64);
-            let rem = pos % align;
-            if rem != 0 {
-                #WRITE_ZEROES(#writer_var, align - rem)?;
-            }
-        }}
+        quote! {
+            #WRITE_ALIGNMENT(#writer_var, (#alignment) as u64, #align_base, #pad_byte)?;
+        }
     });
     let pad_size_to_before = field.pad_size_to.as_ref().map(|_| {
         quote! {
@@ -0,0 +1,45 @@
+use super::{prelude::PreludeGenerator, struct_field::write_field};
+use crate::binrw::{
+    codegen::{
+        get_union_dispatch,
+        sanitization::{BIN_ERROR, POS, WRITER},
+    },
+    parser::{Input, StructField, Union},
+};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::Ident;
+
+pub(super) fn generate_union(input: &Input, name: Option<&Ident>, un: &Union) -> TokenStream {
+    let writer_var = input.stream_ident_or(WRITER);
+
+    let write_active_field = |field: &StructField| {
+        let ident = &field.ident;
+        let write = write_field(&writer_var, field);
+
+        quote! {
+            // SAFETY: A union does not record which of its fields was last
+            // written, so the union opts in with `unsafe_assume_active`,
+            // which promises that its `tag` or `selector` always names the
+            // active field.
+            let #ident = unsafe { &self.#ident };
+            #write
+        }
+    };
+
+    let no_match = quote! {
+        return Err(#BIN_ERROR::NoVariantMatch {
+            pos: #POS
+        });
+    };
+
+    let write = get_union_dispatch(un, write_active_field, &no_match);
+
+    PreludeGenerator::new(write, input, name, &writer_var)
+        .prefix_map_stream()
+        .prefix_magic(&un.magic)
+        .wrap_hooks()
+        .prefix_endian(&un.endian)
+        .prefix_imports()
+        .finish()
+}
@@ -1,12 +1,13 @@
 use crate::{
     binrw::{
         codegen::generate_impl,
-        parser::{Enum, EnumVariant, Input, ParseResult, Struct, StructField},
+        parser::{Enum, EnumVariant, FieldMode, Input, ParseResult, Struct, StructField},
         Options,
     },
     combine_error,
 };
-use quote::quote;
+use proc_macro2::TokenStream;
+use quote::{quote, quote_spanned};
 use std::collections::HashSet;
 use syn::{spanned::Spanned, DeriveInput};
 
@@ -66,7 +67,8 @@ fn apply_temp_crossover(
         }
         // These don't have temp fields.
         (Input::UnitStruct(_), Input::UnitStruct(_))
-        | (Input::UnitOnlyEnum(_), Input::UnitOnlyEnum(_)) => None,
+        | (Input::UnitOnlyEnum(_), Input::UnitOnlyEnum(_))
+        | (Input::Union(_), Input::Union(_)) => None,
         _ => unreachable!("read and write input should always be the same kind"),
     }
 }
@@ -107,6 +109,8 @@ fn apply_temp_crossover_struct(
     binread_struct: &mut Struct,
     binwrite_struct: &mut Struct,
 ) -> Option<syn::Error> {
+    synchronise_count_fields(binread_struct, binwrite_struct);
+
     // Index temporary fields
     let read_temporary = extract_temporary_field_names(&binread_struct.fields, false);
     let write_temporary = extract_temporary_field_names(&binwrite_struct.fields, true);
@@ -121,6 +125,48 @@ fn apply_temp_crossover_struct(
     None
 }
 
+/// Makes each temporary field which is only used as the `count` of another
+/// field write the length of that field, unless the field already has a
+/// write-side value.
+fn synchronise_count_fields(binread_struct: &Struct, binwrite_struct: &mut Struct) {
+    for read_field in binread_struct.fields.iter().filter(|f| f.is_temp(false)) {
+        let Some(counted) = binread_struct.fields.iter().find(|field| {
+            field
+                .count
+                .as_ref()
+                .and_then(count_ident)
+                .map_or(false, |ident| ident == read_field.ident)
+        }) else {
+            continue;
+        };
+
+        let counted = &counted.ident;
+        if let Some(write_field) = binwrite_struct
+            .fields
+            .iter_mut()
+            .find(|field| field.ident == read_field.ident && !field.generated_value())
+        {
+            write_field.field_mode = FieldMode::TryCalc(quote_spanned! {write_field.ty.span()=>
+                ::core::convert::TryFrom::try_from(#counted.len())
+            });
+        }
+    }
+}
+
+/// Returns the field used by a `count` expression which is just the field,
+/// optionally converted with `as`.
+fn count_ident(count: &TokenStream) -> Option<syn::Ident> {
+    let mut expr = syn::parse2::<syn::Expr>(count.clone()).ok()?;
+    loop {
+        expr = match expr {
+            syn::Expr::Cast(cast) => *cast.expr,
+            syn::Expr::Paren(paren) => *paren.expr,
+            syn::Expr::Path(path) => return path.path.get_ident().cloned(),
+            _ => return None,
+        };
+    }
+}
+
 fn validate_fields_temporary(
     fields: &[StructField],
     read_temporary: &HashSet<syn::Ident>,
@@ -1,10 +1,13 @@
 use super::{
     attr_struct,
     top_level_attrs::StructAttr,
-    types::{Assert, CondEndian, Condition, ErrContext, FieldMode, Magic, Map, PassedArgs},
+    types::{
+        AlignRelative, Assert, Checksum, CondEndian, Condition, ErrContext, FieldMode, Magic, Map,
+        OffsetOf, PadSizeOverflow, PadSizeOverflowMode, PassedArgs, SizeOf,
+    },
     FromAttrs, FromField, FromInput, ParseResult, SpannedValue, Struct, TrySet,
 };
-use crate::{binrw::Options, combine_error};
+use crate::{binrw::Options, combine_error, util::contains_ident};
 use proc_macro2::TokenStream;
 use syn::spanned::Spanned;
 
@@ -26,10 +29,12 @@ attr_struct! {
         pub(crate) magic: Magic,
         #[from(RW:Args, RW:ArgsRaw)]
         pub(crate) args: PassedArgs,
-        #[from(RW:Calc, RW:TryCalc, RO:Default, RW:Ignore, RO:ParseWith, WO:WriteWith)]
+        #[from(RW:Calc, RW:TryCalc, RO:DefaultValue, RW:Ignore, RO:ParseWith, RO:ArgsIter, RO:Switch, WO:WriteWith)]
         pub(crate) field_mode: FieldMode,
         #[from(RO:Count)]
         pub(crate) count: Option<TokenStream>,
+        #[from(RO:MaxCount)]
+        pub(crate) max_count: Option<TokenStream>,
         #[from(RO:Offset)]
         pub(crate) offset: Option<TokenStream>,
         #[from(RW:If)]
@@ -38,8 +43,8 @@ attr_struct! {
         pub(crate) restore_position: Option<()>,
         #[from(RO:Try)]
         pub(crate) do_try: Option<SpannedValue<()>>,
-        #[from(RO:Temp)]
-        pub(crate) temp: Option<()>,
+        #[from(RW:Temp)]
+        pub(crate) temp: Option<SpannedValue<Option<TokenStream>>>,
         #[from(RW:Assert)]
         pub(crate) assertions: Vec<Assert>,
         #[from(RO:ErrContext)]
@@ -52,22 +57,45 @@ attr_struct! {
         pub(crate) align_before: Option<TokenStream>,
         #[from(RW:AlignAfter)]
         pub(crate) align_after: Option<TokenStream>,
+        #[from(WO:AlignRelative)]
+        pub(crate) align_relative: Option<AlignRelative>,
         #[from(RW:SeekBefore)]
         pub(crate) seek_before: Option<TokenStream>,
+        #[from(RO:Lookahead)]
+        pub(crate) lookahead: Option<TokenStream>,
         #[from(RW:PadSizeTo)]
         pub(crate) pad_size_to: Option<TokenStream>,
-        #[from(RO:Debug)] // TODO is this really RO?
+        #[from(WO:PadSizeOverflow)]
+        pub(crate) pad_size_overflow: Option<PadSizeOverflow>,
+        #[from(WO:PadByte)]
+        pub(crate) pad_byte: Option<TokenStream>,
+        #[from(WO:ElsePad)]
+        pub(crate) else_pad: Option<TokenStream>,
+        #[from(WO:SizeOf)]
+        pub(crate) size_of: Option<SizeOf>,
+        #[from(WO:OffsetOf)]
+        pub(crate) offset_of: Option<OffsetOf>,
+        #[from(RW:Checksum)]
+        pub(crate) checksum: Option<Checksum>,
+        #[from(RW:Tag)]
+        pub(crate) tag: Option<TokenStream>,
+        #[from(RW:Debug)]
         pub(crate) debug: Option<()>,
     }
 }
 
+/// Types which are read without any arguments.
+const PRIMITIVES: &[&str] = &[
+    "u8", "u16", "u32", "u64", "u128", "i8", "i16", "i32", "i64", "i128", "f32", "f64",
+];
+
 impl StructField {
     /// Returns true if this field is generated using a calculated value instead
     /// of a parser.
     pub(crate) fn generated_value(&self) -> bool {
         matches!(
             self.field_mode,
-            FieldMode::TryCalc(_) | FieldMode::Calc(_) | FieldMode::Default
+            FieldMode::TryCalc(_) | FieldMode::Calc(_) | FieldMode::Default(_)
         )
     }
 
@@ -80,7 +108,126 @@ impl StructField {
 
     /// Returns true if the field is actually written.
     pub(crate) fn is_written(&self) -> bool {
-        !matches!(self.field_mode, FieldMode::Default)
+        !matches!(self.field_mode, FieldMode::Default(_))
+    }
+
+    /// Returns true if the field is a `PhantomData` marker.
+    pub(crate) fn is_phantom_data(&self) -> bool {
+        matches!(&self.ty, syn::Type::Path(ty) if ty.qself.is_none() && ty
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "PhantomData"))
+    }
+
+    /// Returns true if the field is written as a placeholder which is
+    /// replaced once the value is known.
+    pub(crate) fn is_patched(&self) -> bool {
+        self.size_of.is_some() || self.offset_of.is_some() || self.checksum.is_some()
+    }
+
+    /// Validates that a `temp` field has a value to write.
+    fn validate_temp(&self, options: Options, all_errors: &mut Option<syn::Error>) {
+        if let (true, Some(temp)) = (options.write, &self.temp) {
+            if temp.is_none() {
+                combine_error(
+                    all_errors,
+                    syn::Error::new(
+                        temp.span(),
+                        "`temp` requires a value to write, like `#[brw(temp = $expr)]`",
+                    ),
+                );
+            } else if !matches!(self.field_mode, FieldMode::Normal) {
+                combine_error(
+                    all_errors,
+                    syn::Error::new(
+                        temp.span(),
+                        "`temp` with a value is incompatible with `calc`, `try_calc`, `ignore`, and `write_with`",
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Returns true if bytes written past the `pad_size_to` size of the field
+    /// are discarded.
+    pub(crate) fn truncates(&self) -> bool {
+        matches!(
+            self.pad_size_overflow,
+            Some(PadSizeOverflow {
+                mode: PadSizeOverflowMode::Truncate,
+                ..
+            })
+        )
+    }
+
+    /// Validates the directives which write a placeholder for the field.
+    fn validate_placeholders(&self, all_errors: &mut Option<syn::Error>) {
+        let is_plain = matches!(self.field_mode, FieldMode::Normal)
+            && matches!(self.map, Map::None)
+            && self.map_stream.is_none()
+            && self.if_cond.is_none();
+
+        if let (true, Some(overflow)) = (self.truncates(), &self.pad_size_overflow) {
+            if self.is_patched() {
+                combine_error(
+                    all_errors,
+                    syn::Error::new(
+                        overflow.span,
+                        "`pad_size_overflow = truncate` is incompatible with `size_of`, `offset_of`, and `checksum`",
+                    ),
+                );
+            }
+        }
+
+        if let (Some(size_of), Some(_)) = (&self.size_of, &self.offset_of) {
+            combine_error(
+                all_errors,
+                syn::Error::new(size_of.span, "`size_of` is incompatible with `offset_of`"),
+            );
+        }
+
+        for (span, name) in [
+            (self.size_of.as_ref().map(|size_of| size_of.span), "size_of"),
+            (
+                self.offset_of.as_ref().map(|offset_of| offset_of.span),
+                "offset_of",
+            ),
+        ] {
+            if let Some(span) = span {
+                if !is_plain {
+                    combine_error(
+                        all_errors,
+                        syn::Error::new(
+                            span,
+                            format!("`{name}` is incompatible with `calc`, `try_calc`, `ignore`, `write_with`, `map`, `map_stream`, and `if`"),
+                        ),
+                    );
+                }
+            }
+        }
+
+        if let Some(checksum) = &self.checksum {
+            if self.size_of.is_some() || self.offset_of.is_some() {
+                combine_error(
+                    all_errors,
+                    syn::Error::new(
+                        checksum.span,
+                        "`checksum` is incompatible with `size_of` and `offset_of`",
+                    ),
+                );
+            }
+
+            if !is_plain {
+                combine_error(
+                    all_errors,
+                    syn::Error::new(
+                        checksum.span,
+                        "`checksum` is incompatible with `calc`, `try_calc`, `default`, `ignore`, `parse_with`, `write_with`, `map`, `map_stream`, and `if`",
+                    ),
+                );
+            }
+        }
     }
 
     /// Returns true if the field requires arguments.
@@ -88,6 +235,32 @@ impl StructField {
         self.args.is_some() || self.count.is_some() || self.offset.is_some()
     }
 
+    /// Returns true if any of the `br` or `brw` directives on the field refer
+    /// to the given identifier.
+    pub(crate) fn reads_ident(&self, ident: &syn::Ident) -> bool {
+        self.field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("br") || attr.path.is_ident("brw"))
+            .any(|attr| contains_ident(attr.tokens.clone(), ident))
+    }
+
+    /// Returns true if any of the `bw` or `brw` directives on the field refer
+    /// to the given identifier.
+    pub(crate) fn writes_ident(&self, ident: &syn::Ident) -> bool {
+        self.field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident("bw") || attr.path.is_ident("brw"))
+            .any(|attr| contains_ident(attr.tokens.clone(), ident))
+    }
+
+    /// Returns true if the field value is computed by a `calc` or `try_calc`
+    /// directive.
+    pub(crate) fn is_calc(&self) -> bool {
+        matches!(self.field_mode, FieldMode::Calc(_) | FieldMode::TryCalc(_))
+    }
+
     /// Returns true if the field overrides endianness.
     pub(crate) fn needs_endian(&self) -> bool {
         !matches!(self.endian, CondEndian::Inherited)
@@ -114,9 +287,10 @@ impl StructField {
         matches!(self.endian, CondEndian::Inherited)
             && matches!(self.map, Map::None)
             && matches!(self.args, PassedArgs::None)
-            && matches!(self.field_mode, FieldMode::Normal)
+            && (matches!(self.field_mode, FieldMode::Normal) || self.is_phantom_data())
             && all_fields_none!(
                 count,
+                max_count,
                 offset,
                 if_cond,
                 restore_position,
@@ -127,11 +301,34 @@ impl StructField {
                 align_before,
                 align_after,
                 seek_before,
+                lookahead,
                 pad_size_to,
-                magic
+                magic,
+                tag
             )
     }
 
+    /// Returns true if the field has directives which make its binary layout
+    /// differ from the in-memory layout of its type.
+    pub(crate) fn changes_layout(&self) -> bool {
+        !matches!(self.map, Map::None)
+            || self.map_stream.is_some()
+            || !(matches!(self.field_mode, FieldMode::Normal) || self.is_phantom_data())
+            || self.count.is_some()
+            || self.if_cond.is_some()
+            || self.restore_position.is_some()
+            || self.do_try.is_some()
+            || self.temp.is_some()
+            || self.pad_before.is_some()
+            || self.pad_after.is_some()
+            || self.align_before.is_some()
+            || self.align_after.is_some()
+            || self.seek_before.is_some()
+            || self.pad_size_to.is_some()
+            || self.magic.is_some()
+            || self.tag.is_some()
+    }
+
     /// Forces the field to be treated as a temporary variable even if it was
     /// not explicitly specified by a directive.
     ///
@@ -139,12 +336,77 @@ impl StructField {
     /// type, a field specified as temporary on one side is treated as a
     /// temporary on both sides.
     pub(crate) fn force_temp(&mut self) {
-        self.temp = Some(());
+        self.temp = Some(SpannedValue::new(None, proc_macro2::Span::call_site()));
     }
 
-    fn validate(&self, _: Options) -> syn::Result<()> {
+    /// Reports arguments which are known to be missing or unused from the
+    /// type of the field alone, since the errors from the compiler about
+    /// unsatisfied argument types are hard to understand.
+    fn validate_args(&self, all_errors: &mut Option<syn::Error>) {
+        let Some(type_name) = self.type_name() else {
+            return;
+        };
+
+        let field_name = if self.generated_ident {
+            format!(
+                "field {}",
+                self.ident.to_string().trim_start_matches("self_")
+            )
+        } else {
+            format!("field `{}`", self.ident)
+        };
+
+        if type_name == "Vec" && !self.args.is_some() && self.count.is_none() {
+            combine_error(
+                all_errors,
+                syn::Error::new(
+                    self.ty.span(),
+                    format!(
+                        "{field_name} is a `Vec`, which needs to know how many items to read; add `#[br(count = ...)]` or `#[br(args {{ count: ..., inner: ... }})]`"
+                    ),
+                ),
+            );
+        }
+
+        if PRIMITIVES.contains(&type_name.as_str()) {
+            let passed = if let Some(span) = self.args.span() {
+                Some((span, "args"))
+            } else {
+                self.count.as_ref().map(|count| (count.span(), "count"))
+            };
+
+            if let Some((span, directive)) = passed {
+                combine_error(
+                    all_errors,
+                    syn::Error::new(
+                        span,
+                        format!(
+                            "{field_name} has type `{type_name}`, which does not take arguments; remove `{directive}`"
+                        ),
+                    ),
+                );
+            }
+        }
+    }
+
+    /// Returns the name of the last segment of the type of the field, if it is
+    /// a plain path.
+    fn type_name(&self) -> Option<String> {
+        match &self.ty {
+            syn::Type::Path(ty) if ty.qself.is_none() => ty
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident.to_string()),
+            _ => None,
+        }
+    }
+
+    fn validate(&self, options: Options) -> syn::Result<()> {
         let mut all_errors = None::<syn::Error>;
 
+        self.validate_temp(options, &mut all_errors);
+
         if self.do_try.is_some() && self.generated_value() {
             //TODO: join with span of read mode somehow
             let span = self.do_try.as_ref().unwrap().span();
@@ -170,6 +432,57 @@ impl StructField {
             );
         }
 
+        self.validate_placeholders(&mut all_errors);
+
+        if let Some(else_pad) = &self.else_pad {
+            match &self.if_cond {
+                None => combine_error(
+                    &mut all_errors,
+                    syn::Error::new(else_pad.span(), "`else_pad` requires `if`"),
+                ),
+                Some(Condition {
+                    alternate: Some(_), ..
+                }) => combine_error(
+                    &mut all_errors,
+                    syn::Error::new(
+                        else_pad.span(),
+                        "`else_pad` is incompatible with an `if` alternate",
+                    ),
+                ),
+                Some(_) => {}
+            }
+        }
+
+        if let (Some(overflow), None) = (&self.pad_size_overflow, &self.pad_size_to) {
+            combine_error(
+                &mut all_errors,
+                syn::Error::new(overflow.span, "`pad_size_overflow` requires `pad_size_to`"),
+            );
+        }
+
+        if let (Some(max_count), None) = (&self.max_count, &self.count) {
+            combine_error(
+                &mut all_errors,
+                syn::Error::new(max_count.span(), "`max_count` requires `count`"),
+            );
+        }
+
+        if !options.write && matches!(self.field_mode, FieldMode::Normal) && self.map.is_none() {
+            self.validate_args(&mut all_errors);
+        }
+
+        if let Some(lookahead) = &self.lookahead {
+            if self.seek_before.is_some() || self.restore_position.is_some() {
+                combine_error(
+                    &mut all_errors,
+                    syn::Error::new(
+                        lookahead.span(),
+                        "`lookahead` is incompatible with `seek_before` and `restore_position`",
+                    ),
+                );
+            }
+        }
+
         if self.has_named_arg_directives()
             && !matches!(self.args, PassedArgs::None | PassedArgs::Named(..))
         {
@@ -226,6 +539,7 @@ impl FromField for StructField {
             args: <_>::default(),
             field_mode: <_>::default(),
             count: <_>::default(),
+            max_count: <_>::default(),
             offset: <_>::default(),
             if_cond: <_>::default(),
             restore_position: <_>::default(),
@@ -236,8 +550,17 @@ impl FromField for StructField {
             pad_after: <_>::default(),
             align_before: <_>::default(),
             align_after: <_>::default(),
+            align_relative: <_>::default(),
             seek_before: <_>::default(),
+            lookahead: <_>::default(),
             pad_size_to: <_>::default(),
+            pad_size_overflow: <_>::default(),
+            pad_byte: <_>::default(),
+            else_pad: <_>::default(),
+            size_of: <_>::default(),
+            offset_of: <_>::default(),
+            checksum: <_>::default(),
+            tag: <_>::default(),
             #[cfg(feature = "verbose-backtrace")]
             keyword_spans: <_>::default(),
             err_context: <_>::default(),
@@ -248,7 +571,15 @@ impl FromField for StructField {
             <Self as FromAttrs<StructFieldAttr<true>>>::set_from_attrs(this, &field.attrs, options)
         } else {
             <Self as FromAttrs<StructFieldAttr<false>>>::set_from_attrs(this, &field.attrs, options)
-        };
+        }
+        .map(|mut this| {
+            // Markers have nothing to read or write, so they are ignored
+            // unless some other read mode was requested
+            if matches!(this.field_mode, FieldMode::Normal) && this.is_phantom_data() {
+                this.field_mode = FieldMode::Default(None);
+            }
+            this
+        });
 
         match result {
             ParseResult::Ok(this) => {
@@ -266,6 +597,24 @@ impl FromField for StructField {
             }
             ParseResult::Err(error) => ParseResult::Err(error),
         }
+        .map(|mut this| {
+            // A placeholder is written for size, offset, and checksum fields
+            // and is replaced with the real value once it is known
+            if options.write && this.is_patched() {
+                let ty = &this.ty;
+                this.field_mode = FieldMode::Calc(quote::quote! {
+                    <#ty as ::core::default::Default>::default()
+                });
+            }
+
+            // A temporary field with a value is written as if it were `calc`
+            if let (true, Some(Some(calc))) = (options.write, this.temp.as_deref()) {
+                if matches!(this.field_mode, FieldMode::Normal) {
+                    this.field_mode = FieldMode::Calc(calc.clone());
+                }
+            }
+            this
+        })
     }
 }
 
@@ -274,10 +623,15 @@ attr_struct! {
     #[derive(Clone, Debug)]
     pub(crate) struct UnitEnumField {
         pub(crate) ident: syn::Ident,
+        pub(crate) discriminant: Option<syn::Expr>,
         #[from(RW:Magic)]
         pub(crate) magic: Magic,
         #[from(RO:PreAssert)]
         pub(crate) pre_assertions: Vec<Assert>,
+        #[from(RW:Default)]
+        pub(crate) fallback: Option<SpannedValue<()>>,
+        #[from(RO:Tag)]
+        pub(crate) tag: Option<TokenStream>,
     }
 }
 
@@ -286,6 +640,8 @@ impl From<UnitEnumField> for Struct {
         Self {
             magic: value.magic,
             pre_assertions: value.pre_assertions,
+            fallback: value.fallback,
+            tag: value.tag,
             ..<_>::default()
         }
     }
@@ -297,8 +653,11 @@ impl FromField for UnitEnumField {
     fn from_field(field: &Self::In, _: usize, options: Options) -> ParseResult<Self> {
         let this = Self {
             ident: field.ident.clone(),
+            discriminant: field.discriminant.as_ref().map(|(_, expr)| expr.clone()),
             magic: <_>::default(),
             pre_assertions: <_>::default(),
+            fallback: <_>::default(),
+            tag: <_>::default(),
             #[cfg(feature = "verbose-backtrace")]
             keyword_spans: <_>::default(),
         };
@@ -323,6 +682,7 @@ impl FromField for UnitEnumField {
 pub(crate) enum EnumVariant {
     Variant {
         ident: syn::Ident,
+        discriminant: Option<syn::Expr>,
         options: Box<Struct>,
     },
     Unit(UnitEnumField),
@@ -342,6 +702,36 @@ impl EnumVariant {
             Self::Unit(_) => true,
         }
     }
+
+    pub(crate) fn discriminant(&self) -> Option<&syn::Expr> {
+        match self {
+            EnumVariant::Variant { discriminant, .. } => discriminant.as_ref(),
+            EnumVariant::Unit(field) => field.discriminant.as_ref(),
+        }
+    }
+
+    pub(crate) fn magic(&self) -> &Magic {
+        match self {
+            Self::Variant { options, .. } => &options.magic,
+            Self::Unit(field) => &field.magic,
+        }
+    }
+
+    pub(crate) fn tag(&self) -> Option<&TokenStream> {
+        match self {
+            Self::Variant { options, .. } => options.tag.as_ref(),
+            Self::Unit(field) => field.tag.as_ref(),
+        }
+    }
+
+    /// Returns the span of the `default` directive if this is the fallback
+    /// variant of its enum.
+    pub(crate) fn fallback(&self) -> Option<&SpannedValue<()>> {
+        match self {
+            Self::Variant { options, .. } => options.fallback.as_ref(),
+            Self::Unit(field) => field.fallback.as_ref(),
+        }
+    }
 }
 
 impl From<EnumVariant> for Struct {
@@ -373,6 +763,7 @@ impl FromField for EnumVariant {
             }
             .map(|options| Self::Variant {
                 ident: variant.ident.clone(),
+                discriminant: variant.discriminant.as_ref().map(|(_, expr)| expr.clone()),
                 options: Box::new(options),
             }),
             syn::Fields::Unit => UnitEnumField::from_field(variant, index, options).map(Self::Unit),
@@ -9,19 +9,27 @@ macro_rules! define_keywords {
 define_keywords! {
     align_after,
     align_before,
+    align_relative,
     args,
+    args_iter,
     args_raw,
     assert,
+    assert_layout,
     big,
     binread,
     br,
     brw,
     binwrite,
     bw,
+    borrow,
+    bound,
     calc,
+    checked_repr,
+    checksum,
     count,
     dbg,
     default,
+    else_pad,
     err_context,
     ignore,
     import,
@@ -29,23 +37,35 @@ define_keywords! {
     is_big,
     is_little,
     little,
+    lookahead,
     magic,
     map,
     map_stream,
+    max_count,
     offset,
+    offset_of,
     pad_after,
     pad_before,
+    pad_byte,
+    pad_size_overflow,
     pad_size_to,
     parse_with,
+    post_write,
     pre_assert,
+    pre_write,
     repr,
     restore_position,
     return_all_errors,
     return_unexpected_error,
     seek_before,
+    size_of,
+    selector,
     stream,
+    switch,
+    tag,
     temp,
     try_calc,
     try_map,
+    unsafe_assume_active,
     write_with,
 }
@@ -6,6 +6,9 @@ macro_rules! parse_any {
         ),*
         $(,)?
     }) => {
+        // Lint: These are short-lived values which are consumed immediately
+        // after parsing.
+        #[allow(clippy::large_enum_variant)]
         $vis enum $enum<const WRITE: bool> {
             $(
                 $variant($ty)
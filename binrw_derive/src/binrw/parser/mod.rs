@@ -13,7 +13,7 @@ use crate::{
 };
 pub(crate) use field_level_attrs::{EnumVariant, StructField, UnitEnumField};
 use macros::attr_struct;
-pub(crate) use top_level_attrs::{Enum, Input, Struct, UnitOnlyEnum};
+pub(crate) use top_level_attrs::{Enum, Input, Struct, Union, UnitOnlyEnum};
 use try_set::TrySet;
 pub(crate) use types::*;
 
@@ -130,11 +130,16 @@ mod tests {
 
     #[cfg_attr(coverage_nightly, coverage(off))]
     fn try_input(input: TokenStream) -> ParseResult<Input> {
+        try_input_with(input, false)
+    }
+
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn try_input_with(input: TokenStream, write: bool) -> ParseResult<Input> {
         Input::from_input(
             &syn::parse2::<DeriveInput>(input).unwrap(),
             Options {
                 derive: false,
-                write: false,
+                write,
             },
         )
     }
@@ -148,6 +153,14 @@ mod tests {
                 try_input(quote::quote! $tt).unwrap();
             }
         };
+        (write $name:ident: $message:literal $tt:tt) => {
+            #[test]
+            #[cfg_attr(coverage_nightly, coverage(off))]
+            #[should_panic(expected = $message)]
+            fn $name() {
+                try_input_with(quote::quote! $tt, true).unwrap();
+            }
+        };
     );
 
     try_error!(args_calc_conflict: "`args` is incompatible" {
@@ -157,6 +170,44 @@ mod tests {
         }
     });
 
+    try_error!(max_count_without_count: "`max_count` requires `count`" {
+        struct Foo {
+            #[br(max_count = 10)]
+            a: Vec<u8>,
+        }
+    });
+
+    try_error!(vec_without_count: "field `a` is a `Vec`, which needs to know how many items to read; add `#[br(count = ...)]`" {
+        struct Foo {
+            a: Vec<u8>,
+        }
+    });
+
+    try_error!(tuple_vec_without_count: "field 1 is a `Vec`" {
+        struct Foo(u8, alloc::vec::Vec<u8>);
+    });
+
+    try_error!(primitive_with_args: "field `a` has type `u32`, which does not take arguments; remove `args`" {
+        struct Foo {
+            #[br(args(1))]
+            a: u32,
+        }
+    });
+
+    try_error!(primitive_with_count: "field `a` has type `u8`, which does not take arguments; remove `count`" {
+        struct Foo {
+            #[br(count = 4)]
+            a: u8,
+        }
+    });
+
+    try_error!(lookahead_seek_before: "`lookahead` is incompatible" {
+        struct Foo {
+            #[br(lookahead = SeekFrom::End(-1), seek_before = SeekFrom::Start(0))]
+            a: u8,
+        }
+    });
+
     try_error!(conflicting_keyword_bool: "conflicting `restore_position` keyword" {
         struct Foo {
             #[br(restore_position, restore_position)]
@@ -217,6 +268,177 @@ mod tests {
         }
     });
 
+    try_error!(borrow_map_stream: "`borrow` is incompatible with `map_stream`" {
+        #[br(borrow = 'a)]
+        struct Foo<'a> {
+            #[br(map_stream = Wrapper::new)]
+            a: &'a [u8],
+        }
+    });
+
+    try_error!(assert_layout_padding: "`assert_layout` requires every field" {
+        #[br(assert_layout)]
+        struct Foo {
+            #[br(pad_before = 2)]
+            a: u32,
+        }
+    });
+
+    try_error!(write calc_cycle: "`calc` on `b` forms a cycle with `a`" {
+        #[bw(big)]
+        struct Foo {
+            #[bw(calc = b + 1)]
+            a: u8,
+            #[bw(calc = a + 1)]
+            b: u8,
+        }
+    });
+
+    try_error!(write else_pad_without_if: "`else_pad` requires `if`" {
+        struct Foo {
+            #[bw(else_pad = 4)]
+            a: u32,
+        }
+    });
+
+    try_error!(write else_pad_alternate: "`else_pad` is incompatible" {
+        struct Foo {
+            #[bw(if(true, 0), else_pad = 4)]
+            a: u32,
+        }
+    });
+
+    try_error!(write align_relative_unknown_field: "`align_relative` refers to unknown field `header`" {
+        struct Foo {
+            #[bw(align_relative = "header", align_after = 4)]
+            a: u32,
+        }
+    });
+
+    try_error!(write align_relative_later_field: "`align_relative` must refer to an earlier field" {
+        struct Foo {
+            #[bw(align_relative = "b", align_after = 4)]
+            a: u32,
+            b: u32,
+        }
+    });
+
+    try_error!(write align_relative_struct_anchor: "`align_relative` on a struct cannot refer to a field" {
+        #[bw(align_relative = "a")]
+        struct Foo {
+            a: u32,
+        }
+    });
+
+    try_error!(write temp_without_value: "`temp` requires a value to write" {
+        struct Foo {
+            #[brw(temp)]
+            a: u32,
+        }
+    });
+
+    try_error!(write temp_calc: "`temp` with a value is incompatible" {
+        struct Foo {
+            #[brw(temp = 1)]
+            #[bw(calc = 2)]
+            a: u32,
+        }
+    });
+
+    try_error!(write checked_repr_without_repr: "`checked_repr` requires `repr`" {
+        #[bw(checked_repr)]
+        enum Foo {
+            #[bw(magic = 0u8)]
+            A,
+        }
+    });
+
+    try_error!(write pad_size_overflow_without_pad_size_to: "`pad_size_overflow` requires `pad_size_to`" {
+        struct Foo {
+            #[bw(pad_size_overflow = error)]
+            a: u32,
+        }
+    });
+    try_error!(write pad_size_overflow_unknown_mode: "expected one of: `grow`, `error`, `truncate`" {
+        struct Foo {
+            #[bw(pad_size_to = 4, pad_size_overflow = wrap)]
+            a: u32,
+        }
+    });
+    try_error!(write pad_size_overflow_truncate_size_of: "`pad_size_overflow = truncate` is incompatible with `size_of`, `offset_of`, and `checksum`" {
+        struct Foo {
+            #[bw(pad_size_to = 4, pad_size_overflow = truncate, size_of = "b")]
+            a: u32,
+            b: u32,
+        }
+    });
+    try_error!(write write_position_forward_calc: "`write_position` cannot be used by a `calc` field which an earlier field refers to" {
+        struct Foo {
+            #[bw(calc = b + 1)]
+            a: u32,
+            #[bw(calc = write_position as u32)]
+            b: u32,
+        }
+    });
+    try_error!(write size_of_unknown_field: "`size_of` refers to unknown field `body`" {
+        struct Foo {
+            #[bw(size_of = "body")]
+            len: u8,
+        }
+    });
+
+    try_error!(write size_of_calc: "`size_of` is incompatible" {
+        struct Foo {
+            #[bw(size_of = "body", calc = 1)]
+            len: u8,
+            body: u8,
+        }
+    });
+
+    try_error!(write offset_of_unknown_field: "`offset_of` refers to unknown field `body`" {
+        struct Foo {
+            #[bw(offset_of = "header..body")]
+            offset: u8,
+            header: u8,
+        }
+    });
+
+    try_error!(write offset_of_size_of: "`size_of` is incompatible with `offset_of`" {
+        struct Foo {
+            #[bw(size_of = "body", offset_of = "body")]
+            len: u8,
+            body: u8,
+        }
+    });
+
+    try_error!(checksum_unknown_field: "`checksum` refers to unknown field `body`" {
+        struct Foo {
+            #[br(checksum(crc32, over = "body"))]
+            crc: u32,
+        }
+    });
+
+    try_error!(checksum_contains_self: "`checksum` range must not contain the checksum field" {
+        struct Foo {
+            a: u8,
+            #[br(checksum(crc32, over = "a..=b"))]
+            crc: u32,
+            b: u8,
+        }
+    });
+
+    try_error!(checksum_missing_over: "`checksum` requires an `over` range" {
+        struct Foo {
+            #[br(checksum(crc32))]
+            crc: u32,
+        }
+    });
+
+    try_error!(bound_invalid: "expected `:`" {
+        #[br(bound = "T")]
+        struct Foo<T>(T);
+    });
+
     try_error!(conflicting_keyword_option: "conflicting `magic` keyword" {
         #[br(magic = 0u8, magic = 0u8)]
         struct Foo;
@@ -230,6 +452,20 @@ mod tests {
         }
     });
 
+    try_error!(conflicting_keyword_args_iter: "conflicting read mode keyword" {
+        struct Foo {
+            #[br(args_iter = [(1,)], parse_with = u8)]
+            a: Vec<i32>,
+        }
+    });
+
+    try_error!(conflicting_keyword_switch: "conflicting read mode keyword" {
+        struct Foo {
+            #[br(switch(a, _ => u8), calc(1))]
+            a: i32,
+        }
+    });
+
     try_error!(conflicting_keyword_read_mode: "conflicting read mode keyword" {
         struct Foo {
             #[br(calc(1), default, ignore, parse_with = u8)]
@@ -237,6 +473,88 @@ mod tests {
         }
     });
 
+    try_error!(default_variant_multiple: "only one variant" {
+        enum Foo {
+            #[br(default)]
+            A(u8),
+            #[br(default)]
+            B(u16),
+        }
+    });
+
+    try_error!(repr_keyed_tag: "cannot also use `tag`" {
+        #[br(repr = u8, import(kind: u8), tag = kind)]
+        enum Foo {
+            #[br(tag = 1)]
+            A(u8) = 1,
+        }
+    });
+
+    try_error!(default_variant_repr_fields: "tuple variant with one field" {
+        #[br(repr = u8)]
+        enum Foo {
+            A,
+            #[br(default)]
+            B(u8, u8),
+        }
+    });
+
+    try_error!(tag_missing_enum: "requires a `tag` on the enum" {
+        enum Foo {
+            #[br(tag = 1)]
+            A(u8),
+            B(u16),
+        }
+    });
+
+    try_error!(tag_missing_variant: "needs a `tag`" {
+        #[br(tag = 1)]
+        enum Foo {
+            #[br(tag = 1)]
+            A(u8),
+            B(u16),
+        }
+    });
+
+    try_error!(tag_struct: "only allowed on enum variants" {
+        #[br(tag = 1)]
+        struct Foo;
+    });
+
+    try_error!(selector_missing_match: "requires a `match`" {
+        #[br(selector = 1)]
+        enum Foo {
+            A(u8),
+        }
+    });
+
+    try_error!(selector_missing_selector: "requires a `selector`" {
+        #[br(match(1 => A))]
+        enum Foo {
+            A(u8),
+        }
+    });
+
+    try_error!(selector_missing_variant: "no variant named `B`" {
+        #[br(selector = 1, match(1 => A, 2 => B))]
+        enum Foo {
+            A(u8),
+        }
+    });
+
+    try_error!(selector_tag: "mutually exclusive" {
+        #[br(tag = 1, selector = 1, match(1 => A))]
+        enum Foo {
+            #[br(tag = 1)]
+            A(u8),
+        }
+    });
+
+    try_error!(default_variant_struct: "only allowed on enum variants" {
+        #[br(default)]
+        struct Foo;
+    });
+
     try_error!(enum_missing_magic_repr: "requires either" {
         enum UnitEnum {
             A,
@@ -384,9 +702,49 @@ mod tests {
         enum Foo {}
     });
 
-    try_error!(unsupported_type_union: "unions are not supported" {
+    try_error!(union_missing_selector: "unions require either a `tag` or a `selector`" {
         union Bar {
             a: i32,
         }
     });
+
+    try_error!(union_missing_field_tag: "every field needs a `tag`" {
+        #[br(import(kind: u8), tag = kind)]
+        union Bar {
+            #[br(tag = 1)]
+            a: i32,
+            b: u32,
+        }
+    });
+
+    try_error!(union_missing_field: "no field named `c`" {
+        #[br(import(kind: u8), selector = kind, match(1 => a, 2 => c))]
+        union Bar {
+            a: i32,
+            b: u32,
+        }
+    });
+
+    try_error!(union_temp_field: "unions cannot have temporary fields" {
+        #[br(import(kind: u8), selector = kind, match(1 => a))]
+        union Bar {
+            #[br(temp)]
+            a: i32,
+        }
+    });
+
+    try_error!(write union_without_opt_in: "add `#[bw(unsafe_assume_active)]`" {
+        #[bw(import(kind: u8), tag = kind)]
+        union Bar {
+            #[bw(tag = 1)]
+            a: i32,
+        }
+    });
+
+    try_error!(tag_field: "`tag` on a field is only allowed in unions" {
+        struct Foo {
+            #[br(tag = 1)]
+            a: i32,
+        }
+    });
 }
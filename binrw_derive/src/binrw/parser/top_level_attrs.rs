@@ -1,12 +1,18 @@
 use super::{
     attr_struct,
-    types::{Assert, CondEndian, EnumErrorMode, Imports, Magic, Map},
-    EnumVariant, FromInput, ParseResult, StructField, TrySet, UnitEnumField,
+    types::{AlignRelative, Assert, CondEndian, EnumErrorMode, ErrContext, Imports, Magic, Map},
+    EnumVariant, FromInput, ParseResult, SpannedValue, StructField, TrySet, UnitEnumField,
 };
-use crate::binrw::Options;
-use proc_macro2::TokenStream;
+use crate::{
+    binrw::{
+        codegen::sanitization::{STRUCT_POSITION, WRITE_POSITION},
+        Options,
+    },
+    meta_types::MatchArm,
+};
+use proc_macro2::{Span, TokenStream};
 use quote::ToTokens;
-use syn::{spanned::Spanned, Ident};
+use syn::{spanned::Spanned, Ident, WherePredicate};
 
 /// The parsed representation of binrw attributes on a data structure.
 pub(crate) enum Input {
@@ -18,6 +24,8 @@ pub(crate) enum Input {
     Enum(Enum),
     /// An enum containing only unit variants.
     UnitOnlyEnum(UnitOnlyEnum),
+    /// A union whose active field is chosen by a tag or selector.
+    Union(Union),
 }
 
 impl Input {
@@ -41,6 +49,20 @@ impl Input {
                     )
                 };
 
+                let read_struct = match read_struct {
+                    ParseResult::Ok(st) => match st.validate_top_level() {
+                        Ok(()) => ParseResult::Ok(st),
+                        Err(error) => ParseResult::Partial(st, error),
+                    },
+                    ParseResult::Partial(st, mut error) => {
+                        if let Err(top_level_error) = st.validate_top_level() {
+                            error.combine(top_level_error);
+                        }
+                        ParseResult::Partial(st, error)
+                    }
+                    ParseResult::Err(error) => ParseResult::Err(error),
+                };
+
                 if matches!(st.fields, syn::Fields::Unit) {
                     read_struct.map(Self::UnitStruct)
                 } else {
@@ -92,9 +114,20 @@ impl Input {
                     })
                 }
             }
-            syn::Data::Union(_) => {
-                ParseResult::Err(syn::Error::new(input.span(), "unions are not supported"))
+            syn::Data::Union(union) => if options.write {
+                <Union as FromInput<UnionAttr<true>>>::from_input(
+                    attrs,
+                    union.fields.named.iter(),
+                    options,
+                )
+            } else {
+                <Union as FromInput<UnionAttr<false>>>::from_input(
+                    attrs,
+                    union.fields.named.iter(),
+                    options,
+                )
             }
+            .map(Self::Union),
         }
     }
 
@@ -103,6 +136,7 @@ impl Input {
             Input::Struct(s) | Input::UnitStruct(s) => &s.endian,
             Input::Enum(e) => &e.endian,
             Input::UnitOnlyEnum(e) => &e.endian,
+            Input::Union(u) => &u.endian,
         }
     }
 
@@ -111,6 +145,54 @@ impl Input {
             Input::Struct(s) | Input::UnitStruct(s) => &s.imports,
             Input::Enum(e) => &e.imports,
             Input::UnitOnlyEnum(e) => &e.imports,
+            Input::Union(u) => &u.imports,
+        }
+    }
+
+    pub(crate) fn err_context(&self) -> Option<&ErrContext> {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.err_context.as_ref(),
+            Input::Enum(e) => e.err_context.as_ref(),
+            Input::UnitOnlyEnum(e) => e.err_context.as_ref(),
+            Input::Union(u) => u.err_context.as_ref(),
+        }
+    }
+
+    /// Returns the raw type whose arguments are passed through unchanged, if
+    /// this is a struct stored as a `repr` type and has no imports of its own.
+    pub(crate) fn repr_args(&self) -> Option<&TokenStream> {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) if matches!(s.imports, Imports::None) => {
+                s.map.as_repr()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the lifetime of the data borrowed from the reader, if this
+    /// type is read with `BinReadBorrowed` instead of `BinRead`.
+    pub(crate) fn borrow(&self) -> Option<&syn::Lifetime> {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.borrow.as_ref(),
+            Input::Enum(_) | Input::UnitOnlyEnum(_) | Input::Union(_) => None,
+        }
+    }
+
+    /// Returns the struct if its binary layout should be checked against its
+    /// in-memory layout.
+    pub(crate) fn assert_layout(&self) -> Option<&Struct> {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) if s.assert_layout.is_some() => Some(s),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn bound(&self) -> Option<&[WherePredicate]> {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.bound.as_deref(),
+            Input::Enum(e) => e.bound.as_deref(),
+            Input::UnitOnlyEnum(e) => e.bound.as_deref(),
+            Input::Union(u) => u.bound.as_deref(),
         }
     }
 
@@ -119,7 +201,7 @@ impl Input {
             Input::Struct(s) => s.fields.is_empty() && s.magic.is_none(),
             Input::UnitStruct(_) => true,
             Input::Enum(e) => e.variants.is_empty() && e.magic.is_none(),
-            Input::UnitOnlyEnum(_) => false,
+            Input::UnitOnlyEnum(_) | Input::Union(_) => false,
         }
     }
 
@@ -139,7 +221,7 @@ impl Input {
                     false
                 }
             }),
-            Input::UnitStruct(_) | Input::UnitOnlyEnum(_) => false,
+            Input::UnitStruct(_) | Input::UnitOnlyEnum(_) | Input::Union(_) => false,
         }
     }
 
@@ -148,6 +230,7 @@ impl Input {
             Input::Struct(s) | Input::UnitStruct(s) => &s.map,
             Input::Enum(e) => &e.map,
             Input::UnitOnlyEnum(e) => &e.map,
+            Input::Union(u) => &u.map,
         }
     }
 
@@ -156,6 +239,7 @@ impl Input {
             Input::Struct(s) | Input::UnitStruct(s) => &s.magic,
             Input::Enum(e) => &e.magic,
             Input::UnitOnlyEnum(e) => &e.magic,
+            Input::Union(u) => &u.magic,
         }
     }
 
@@ -164,6 +248,25 @@ impl Input {
             Input::Struct(s) | Input::UnitStruct(s) => s.map_stream.as_ref(),
             Input::Enum(en) => en.map_stream.as_ref(),
             Input::UnitOnlyEnum(en) => en.map_stream.as_ref(),
+            Input::Union(u) => u.map_stream.as_ref(),
+        }
+    }
+
+    pub(crate) fn pre_write(&self) -> Option<&TokenStream> {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.pre_write.as_ref(),
+            Input::Enum(e) => e.pre_write.as_ref(),
+            Input::UnitOnlyEnum(e) => e.pre_write.as_ref(),
+            Input::Union(u) => u.pre_write.as_ref(),
+        }
+    }
+
+    pub(crate) fn post_write(&self) -> Option<&TokenStream> {
+        match self {
+            Input::Struct(s) | Input::UnitStruct(s) => s.post_write.as_ref(),
+            Input::Enum(e) => e.post_write.as_ref(),
+            Input::UnitOnlyEnum(e) => e.post_write.as_ref(),
+            Input::Union(u) => u.post_write.as_ref(),
         }
     }
 
@@ -171,6 +274,7 @@ impl Input {
         match self {
             Input::Struct(s) | Input::UnitStruct(s) => &s.pre_assertions,
             Input::Enum(e) => &e.pre_assertions,
+            Input::Union(u) => &u.pre_assertions,
             Input::UnitOnlyEnum(_) => &[],
         }
     }
@@ -180,6 +284,7 @@ impl Input {
             Input::Struct(s) | Input::UnitStruct(s) => s.stream_ident.as_ref(),
             Input::Enum(en) => en.stream_ident.as_ref(),
             Input::UnitOnlyEnum(en) => en.stream_ident.as_ref(),
+            Input::Union(u) => u.stream_ident.as_ref(),
         }
     }
 
@@ -192,7 +297,7 @@ impl Input {
         match self {
             Input::Struct(s) | Input::UnitStruct(s) => &s.assertions,
             Input::Enum(e) => &e.assertions,
-            Input::UnitOnlyEnum(_) => &[],
+            Input::UnitOnlyEnum(_) | Input::Union(_) => &[],
         }
     }
 }
@@ -213,10 +318,30 @@ attr_struct! {
         pub(crate) magic: Magic,
         #[from(RW:Import, RW:ImportRaw)]
         pub(crate) imports: Imports,
+        #[from(RO:ErrContext)]
+        pub(crate) err_context: Option<ErrContext>,
+        #[from(RO:Borrow)]
+        pub(crate) borrow: Option<syn::Lifetime>,
+        #[from(RW:Bound)]
+        pub(crate) bound: Option<Vec<WherePredicate>>,
         #[from(RW:Assert)]
         pub(crate) assertions: Vec<Assert>,
         #[from(RO:PreAssert)]
         pub(crate) pre_assertions: Vec<Assert>,
+        #[from(RW:AssertLayout)]
+        pub(crate) assert_layout: Option<SpannedValue<()>>,
+        #[from(WO:PadByte)]
+        pub(crate) pad_byte: Option<TokenStream>,
+        #[from(WO:AlignRelative)]
+        pub(crate) align_relative: Option<AlignRelative>,
+        #[from(WO:PreWrite)]
+        pub(crate) pre_write: Option<TokenStream>,
+        #[from(WO:PostWrite)]
+        pub(crate) post_write: Option<TokenStream>,
+        #[from(RW:Default)]
+        pub(crate) fallback: Option<SpannedValue<()>>,
+        #[from(RO:Tag)]
+        pub(crate) tag: Option<TokenStream>,
         pub(crate) fields: Vec<StructField>,
         pub(crate) for_write: bool,
     }
@@ -244,9 +369,316 @@ impl Struct {
             && matches!(self.map, Map::None)
             && self.magic.is_none()
             && matches!(self.imports, Imports::None)
+            && self.fallback.is_none()
+            && self.tag.is_none()
             && self.fields.iter().all(StructField::has_no_attrs)
     }
 
+    /// Validates directives which are only allowed when the struct is an enum
+    /// variant.
+    fn validate_top_level(&self) -> syn::Result<()> {
+        if let Some(fallback) = &self.fallback {
+            Err(syn::Error::new(
+                fallback.span(),
+                "`default` is only allowed on enum variants",
+            ))
+        } else if let Some(tag) = &self.tag {
+            Err(syn::Error::new(
+                tag.span(),
+                "`tag` on a struct is only allowed on enum variants",
+            ))
+        } else if let (Some(_), Some(map_stream)) = (
+            &self.borrow,
+            self.map_stream.as_ref().or_else(|| {
+                self.fields
+                    .iter()
+                    .find_map(|field| field.map_stream.as_ref())
+            }),
+        ) {
+            Err(syn::Error::new(
+                map_stream.span(),
+                "`borrow` is incompatible with `map_stream`",
+            ))
+        } else if let Some(assert_layout) = &self.assert_layout {
+            self.validate_layout(assert_layout.span())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Validates that the binary layout of the struct is fully described by
+    /// the types of its fields, so it can be compared against the in-memory
+    /// layout.
+    fn validate_layout(&self, span: proc_macro2::Span) -> syn::Result<()> {
+        if !matches!(self.map, Map::None) || self.map_stream.is_some() || self.magic.is_some() {
+            Err(syn::Error::new(
+                span,
+                "`assert_layout` cannot be combined with `magic`, `map`, or `map_stream`",
+            ))
+        } else if let Some(field) = self.fields.iter().find(|field| field.changes_layout()) {
+            Err(syn::Error::new(
+                field.field.span(),
+                "`assert_layout` requires every field to be read and written as its own type without padding, seeking, or alternate parsers",
+            ))
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Returns true if the implicit position variable `ident` is referred to
+    /// by any field and is not shadowed by a field of the same name.
+    pub(crate) fn uses_implicit_position(&self, ident: &Ident) -> bool {
+        self.fields.iter().all(|field| field.ident != *ident)
+            && self.fields.iter().any(|field| field.writes_ident(ident))
+    }
+
+    /// Returns true if the field refers to either of the implicit position
+    /// variables.
+    pub(crate) fn field_uses_position(&self, field: &StructField) -> bool {
+        [WRITE_POSITION, STRUCT_POSITION].iter().any(|name| {
+            let ident = name.to_ident(Span::call_site());
+            self.uses_implicit_position(&ident) && field.writes_ident(&ident)
+        })
+    }
+
+    /// Validates that no `calc` field which must be computed before it is
+    /// written refers to `write_position`, since its position is not known
+    /// yet.
+    fn validate_write_position(&self) -> syn::Result<()> {
+        let write_position = WRITE_POSITION.to_ident(Span::call_site());
+        if !self.uses_implicit_position(&write_position) {
+            return Ok(());
+        }
+
+        let forward = self.forward_calc_fields()?;
+        match forward
+            .iter()
+            .flatten()
+            .map(|&index| &self.fields[index])
+            .find(|field| field.writes_ident(&write_position))
+        {
+            Some(field) => Err(syn::Error::new(
+                field.field.span(),
+                "`write_position` cannot be used by a `calc` field which an earlier field refers to",
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns, for each field, the indexes of the later `calc` fields which
+    /// must be computed before that field is written because it refers to
+    /// them, in the order they must be computed.
+    pub(crate) fn forward_calc_fields(&self) -> syn::Result<Vec<Vec<usize>>> {
+        let mut computed = vec![false; self.fields.len()];
+        let mut order = Vec::with_capacity(self.fields.len());
+        for (index, field) in self.fields.iter().enumerate() {
+            let mut forward = Vec::new();
+            let mut pending = if field.is_calc() {
+                vec![index]
+            } else {
+                Vec::new()
+            };
+            self.visit_calc_fields(index, index, &mut computed, &mut pending, &mut forward)?;
+            computed[index] = true;
+            order.push(forward);
+        }
+        Ok(order)
+    }
+
+    fn visit_calc_fields(
+        &self,
+        from: usize,
+        position: usize,
+        computed: &mut [bool],
+        pending: &mut Vec<usize>,
+        forward: &mut Vec<usize>,
+    ) -> syn::Result<()> {
+        for (index, field) in self.fields.iter().enumerate().skip(position) {
+            if index == from
+                || computed[index]
+                || !field.is_calc()
+                || !self.fields[from].writes_ident(&field.ident)
+            {
+                continue;
+            }
+
+            if pending.contains(&index) {
+                return Err(syn::Error::new(
+                    self.fields[from].field.span(),
+                    format!(
+                        "`calc` on `{}` forms a cycle with `{}`",
+                        self.fields[from].ident, field.ident
+                    ),
+                ));
+            }
+
+            pending.push(index);
+            self.visit_calc_fields(index, position, computed, pending, forward)?;
+            pending.pop();
+            computed[index] = true;
+            forward.push(index);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the indexes of the first and last fields measured by a
+    /// `size_of` field.
+    pub(crate) fn size_of_range(&self, field: &StructField) -> Option<(usize, usize)> {
+        let size_of = field.size_of.as_ref()?;
+        Some((
+            self.field_index(&size_of.first)?,
+            self.field_index(&size_of.last)?,
+        ))
+    }
+
+    /// Returns the indexes of the first and last fields checked by a
+    /// `checksum` field.
+    pub(crate) fn checksum_range(&self, field: &StructField) -> Option<(usize, usize)> {
+        let checksum = field.checksum.as_ref()?;
+        Some((
+            self.field_index(&checksum.first)?,
+            self.field_index(&checksum.last)?,
+        ))
+    }
+
+    /// Returns the indexes of the base and target fields of an `offset_of`
+    /// field.
+    pub(crate) fn offset_of_fields(&self, field: &StructField) -> Option<(Option<usize>, usize)> {
+        let offset_of = field.offset_of.as_ref()?;
+        let base = match &offset_of.base {
+            Some(base) => Some(self.field_index(base)?),
+            None => None,
+        };
+        Some((base, self.field_index(&offset_of.target)?))
+    }
+
+    fn field_index(&self, ident: &Ident) -> Option<usize> {
+        self.fields.iter().position(|field| field.ident == *ident)
+    }
+
+    fn validate_checksum(&self) -> syn::Result<()> {
+        for (index, (field, checksum)) in self
+            .fields
+            .iter()
+            .enumerate()
+            .filter_map(|(index, field)| Some((index, (field, field.checksum.as_ref()?))))
+        {
+            for ident in [&checksum.first, &checksum.last] {
+                if self.field_index(ident).is_none() {
+                    return Err(syn::Error::new(
+                        checksum.span,
+                        format!("`checksum` refers to unknown field `{ident}`"),
+                    ));
+                }
+            }
+
+            if let Some((first, last)) = self.checksum_range(field) {
+                if first > last {
+                    return Err(syn::Error::new(
+                        checksum.span,
+                        "`checksum` range must start before it ends",
+                    ));
+                }
+
+                if (first..=last).contains(&index) {
+                    return Err(syn::Error::new(
+                        checksum.span,
+                        "`checksum` range must not contain the checksum field",
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_offset_of(&self) -> syn::Result<()> {
+        for offset_of in self
+            .fields
+            .iter()
+            .filter_map(|field| field.offset_of.as_ref())
+        {
+            for ident in offset_of.base.iter().chain([&offset_of.target]) {
+                if self.field_index(ident).is_none() {
+                    return Err(syn::Error::new(
+                        offset_of.span,
+                        format!("`offset_of` refers to unknown field `{ident}`"),
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_align_relative(&self) -> syn::Result<()> {
+        if let Some(AlignRelative {
+            anchor: Some(_),
+            span,
+        }) = &self.align_relative
+        {
+            return Err(syn::Error::new(
+                *span,
+                "`align_relative` on a struct cannot refer to a field",
+            ));
+        }
+
+        for (index, field) in self.fields.iter().enumerate() {
+            if let Some(AlignRelative {
+                anchor: Some(anchor),
+                span,
+            }) = &field.align_relative
+            {
+                match self.field_index(anchor) {
+                    None => {
+                        return Err(syn::Error::new(
+                            *span,
+                            format!("`align_relative` refers to unknown field `{anchor}`"),
+                        ))
+                    }
+                    Some(anchor_index) if anchor_index >= index => {
+                        return Err(syn::Error::new(
+                            *span,
+                            "`align_relative` must refer to an earlier field",
+                        ))
+                    }
+                    Some(_) => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn validate_size_of(&self) -> syn::Result<()> {
+        for (field, size_of) in self
+            .fields
+            .iter()
+            .filter_map(|field| Some((field, field.size_of.as_ref()?)))
+        {
+            for ident in [&size_of.first, &size_of.last] {
+                if self.field_index(ident).is_none() {
+                    return Err(syn::Error::new(
+                        size_of.span,
+                        format!("`size_of` refers to unknown field `{ident}`"),
+                    ));
+                }
+            }
+
+            if let Some((first, last)) = self.size_of_range(field) {
+                if first > last {
+                    return Err(syn::Error::new(
+                        size_of.span,
+                        "`size_of` range must start before it ends",
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn fields_pattern(&self) -> TokenStream {
         let fields = self.iter_permanent_idents();
 
@@ -265,7 +697,13 @@ impl Struct {
 impl<const WRITE: bool> FromInput<StructAttr<WRITE>> for Struct {
     type Field = StructField;
 
-    fn push_field(&mut self, field: Self::Field) -> syn::Result<()> {
+    fn push_field(&mut self, mut field: Self::Field) -> syn::Result<()> {
+        if field.pad_byte.is_none() {
+            field.pad_byte.clone_from(&self.pad_byte);
+        }
+        if field.align_relative.is_none() {
+            field.align_relative.clone_from(&self.align_relative);
+        }
         self.fields.push(field);
         Ok(())
     }
@@ -275,6 +713,23 @@ impl<const WRITE: bool> FromInput<StructAttr<WRITE>> for Struct {
     }
 
     fn validate(&self, options: Options) -> syn::Result<()> {
+        if let Some(tag) = self.fields.iter().find_map(|field| field.tag.as_ref()) {
+            return Err(syn::Error::new(
+                tag.span(),
+                "`tag` on a field is only allowed in unions",
+            ));
+        }
+
+        self.validate_checksum()?;
+
+        if options.write {
+            self.forward_calc_fields()?;
+            self.validate_write_position()?;
+            self.validate_size_of()?;
+            self.validate_offset_of()?;
+            self.validate_align_relative()?;
+        }
+
         if self.map.is_none() && !options.derive {
             return Ok(());
         }
@@ -320,16 +775,147 @@ attr_struct! {
         pub(crate) magic: Magic,
         #[from(RW:Import, RW:ImportRaw)]
         pub(crate) imports: Imports,
+        #[from(RO:ErrContext)]
+        pub(crate) err_context: Option<ErrContext>,
+        #[from(RW:Bound)]
+        pub(crate) bound: Option<Vec<WherePredicate>>,
         #[from(RW:Assert)]
         pub(crate) assertions: Vec<Assert>,
         #[from(RO:PreAssert)]
         pub(crate) pre_assertions: Vec<Assert>,
+        #[from(WO:PreWrite)]
+        pub(crate) pre_write: Option<TokenStream>,
+        #[from(WO:PostWrite)]
+        pub(crate) post_write: Option<TokenStream>,
         #[from(RO:ReturnAllErrors, RO:ReturnUnexpectedError)]
         pub(crate) error_mode: EnumErrorMode,
+        #[from(RO:Tag)]
+        pub(crate) tag: Option<TokenStream>,
+        #[from(RO:Selector)]
+        pub(crate) selector: Option<TokenStream>,
+        #[from(RO:Match)]
+        pub(crate) selector_arms: Option<Vec<MatchArm>>,
         pub(crate) variants: Vec<EnumVariant>,
     }
 }
 
+impl Enum {
+    /// Returns `true` if this is a `repr` enum whose variants are selected by
+    /// their discriminants, rather than one which is mapped from the `repr`
+    /// type as a whole.
+    ///
+    /// An enum is keyed by its discriminants if any variant has an explicit
+    /// discriminant or is the `default` variant.
+    pub(crate) fn is_keyed_repr(&self) -> bool {
+        self.map.as_repr().is_some()
+            && self
+                .variants
+                .iter()
+                .any(|variant| variant.discriminant().is_some() || variant.fallback().is_some())
+    }
+
+    /// Returns the variant which holds unrecognised discriminants if this is a
+    /// keyed `repr` enum with a `default` variant.
+    pub(crate) fn repr_fallback(&self) -> Option<&EnumVariant> {
+        if self.is_keyed_repr() {
+            self.variants
+                .iter()
+                .find(|variant| variant.fallback().is_some())
+        } else {
+            None
+        }
+    }
+
+    /// Returns each variant along with an expression for its discriminant,
+    /// using the same implicit numbering as the compiler.
+    pub(crate) fn variant_discriminants(
+        &self,
+    ) -> impl Iterator<Item = (&EnumVariant, TokenStream)> + '_ {
+        let mut base = None;
+        let mut offset = 0_usize;
+        self.variants.iter().map(move |variant| {
+            if let Some(discriminant) = variant.discriminant() {
+                base = Some(discriminant);
+                offset = 0;
+            }
+
+            let value = proc_macro2::Literal::usize_unsuffixed(offset);
+            let discriminant = match base {
+                Some(base) if offset == 0 => quote::quote! { (#base) },
+                Some(base) => quote::quote! { ((#base) + #value) },
+                None => value.to_token_stream(),
+            };
+            offset += 1;
+
+            (variant, discriminant)
+        })
+    }
+
+    /// Returns `true` if the variant of the enum can be selected by reading a
+    /// single magic value which is shared by every variant.
+    pub(crate) fn has_shared_magic(&self) -> bool {
+        if self.tag.is_some() || self.selector.is_some() {
+            return false;
+        }
+
+        let mut kind = None;
+        let mut values = Vec::new();
+        for variant in &self.variants {
+            let plain = match variant {
+                EnumVariant::Variant { options, .. } => {
+                    matches!(options.endian, CondEndian::Inherited)
+                        && options.map_stream.is_none()
+                        && options.pre_assertions.is_empty()
+                }
+                EnumVariant::Unit(field) => field.pre_assertions.is_empty(),
+            };
+            if !plain {
+                return false;
+            }
+
+            match variant.magic() {
+                Some(magic)
+                    if !magic.is_expr() && *kind.get_or_insert(magic.kind()) == magic.kind() =>
+                {
+                    let value = magic.match_value().to_string();
+                    if values.contains(&value) {
+                        return false;
+                    }
+                    values.push(value);
+                }
+                None if variant.fallback().is_some() => {}
+                _ => return false,
+            }
+        }
+
+        kind.is_some()
+    }
+
+    fn validate_keyed_repr(&self) -> syn::Result<()> {
+        if let Some(selector) = self.tag.as_ref().or(self.selector.as_ref()) {
+            return Err(syn::Error::new(
+                selector.span(),
+                "`repr` enums select variants by discriminant, so cannot also use `tag` or `selector`",
+            ));
+        }
+
+        match self.repr_fallback() {
+            Some(EnumVariant::Variant { options, .. })
+                if options.is_tuple()
+                    && options.fields.len() == 1
+                    && options.fields[0].has_no_attrs() =>
+            {
+                Ok(())
+            }
+            Some(fallback) => Err(syn::Error::new(
+                fallback.ident().span(),
+                "the `default` variant of a `repr` enum must be a tuple variant with one field to hold the unknown value",
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
 impl<const WRITE: bool> FromInput<EnumAttr<WRITE>> for Enum {
     type Field = EnumVariant;
 
@@ -339,7 +925,17 @@ impl<const WRITE: bool> FromInput<EnumAttr<WRITE>> for Enum {
     }
 
     fn validate(&self, _: Options) -> syn::Result<()> {
-        if self.map.is_some() {
+        validate_selector(
+            self.tag.as_ref(),
+            self.selector.as_ref(),
+            self.selector_arms.as_deref(),
+            "variant",
+            |ident| self.variants.iter().any(|variant| variant.ident() == ident),
+        )?;
+
+        if self.is_keyed_repr() {
+            self.validate_keyed_repr()?;
+        } else if self.map.is_some() {
             if let Some(variant) = self.variants.iter().find(|variant| !variant.has_no_attrs()) {
                 return Err(syn::Error::new(
                     variant.ident().span(),
@@ -347,7 +943,26 @@ impl<const WRITE: bool> FromInput<EnumAttr<WRITE>> for Enum {
                 ));
             }
         }
-        Ok(())
+
+        if self.tag.is_some() {
+            if let Some(variant) = self
+                .variants
+                .iter()
+                .find(|variant| variant.tag().is_none() && variant.fallback().is_none())
+            {
+                return Err(syn::Error::new(
+                    variant.ident().span(),
+                    "every variant except the `default` variant needs a `tag` when the enum has a `tag`",
+                ));
+            }
+        } else if let Some(tag) = self.variants.iter().find_map(EnumVariant::tag) {
+            return Err(syn::Error::new(
+                tag.span(),
+                "`tag` on a variant requires a `tag` on the enum",
+            ));
+        }
+
+        validate_single_fallback(self.variants.iter().filter_map(EnumVariant::fallback))
     }
 }
 
@@ -361,12 +976,22 @@ attr_struct! {
         pub(crate) endian: CondEndian,
         #[from(RW:Map, RW:TryMap, RW:Repr)]
         pub(crate) map: Map,
+        #[from(WO:CheckedRepr)]
+        pub(crate) checked_repr: Option<SpannedValue<()>>,
         #[from(RW:MapStream)]
         pub(crate) map_stream: Option<TokenStream>,
         #[from(RW:Magic)]
         pub(crate) magic: Magic,
         #[from(RW:Import, RW:ImportRaw)]
         pub(crate) imports: Imports,
+        #[from(RO:ErrContext)]
+        pub(crate) err_context: Option<ErrContext>,
+        #[from(RW:Bound)]
+        pub(crate) bound: Option<Vec<WherePredicate>>,
+        #[from(WO:PreWrite)]
+        pub(crate) pre_write: Option<TokenStream>,
+        #[from(WO:PostWrite)]
+        pub(crate) post_write: Option<TokenStream>,
         pub(crate) fields: Vec<UnitEnumField>,
         pub(crate) is_magic_enum: bool,
     }
@@ -397,6 +1022,26 @@ impl<const WRITE: bool> FromInput<UnitEnumAttr<WRITE>> for UnitOnlyEnum {
     }
 
     fn validate(&self, options: Options) -> syn::Result<()> {
+        if let Some(tag) = self.fields.iter().find_map(|field| field.tag.as_ref()) {
+            return Err(syn::Error::new(
+                tag.span(),
+                "`tag` on a variant requires a `tag` on the enum",
+            ));
+        }
+
+        validate_single_fallback(
+            self.fields
+                .iter()
+                .filter_map(|field| field.fallback.as_ref()),
+        )?;
+
+        if let (Some(checked_repr), None) = (&self.checked_repr, self.map.as_repr()) {
+            return Err(syn::Error::new(
+                checked_repr.span(),
+                "`checked_repr` requires `repr`",
+            ));
+        }
+
         if self.map.as_repr().is_some() || self.is_magic_enum() {
             Ok(())
         } else if options.write {
@@ -406,3 +1051,182 @@ impl<const WRITE: bool> FromInput<UnitEnumAttr<WRITE>> for UnitOnlyEnum {
         }
     }
 }
+
+attr_struct! {
+    #[from(UnionAttr)]
+    #[derive(Clone, Debug, Default)]
+    pub(crate) struct Union {
+        #[from(RW:Stream)]
+        pub(crate) stream_ident: Option<Ident>,
+        #[from(RW:Big, RW:Little, RW:IsBig, RW:IsLittle)]
+        pub(crate) endian: CondEndian,
+        #[from(RW:Map, RW:TryMap)]
+        pub(crate) map: Map,
+        #[from(RW:MapStream)]
+        pub(crate) map_stream: Option<TokenStream>,
+        #[from(RW:Magic)]
+        pub(crate) magic: Magic,
+        #[from(RW:Import, RW:ImportRaw)]
+        pub(crate) imports: Imports,
+        #[from(RO:ErrContext)]
+        pub(crate) err_context: Option<ErrContext>,
+        #[from(RW:Bound)]
+        pub(crate) bound: Option<Vec<WherePredicate>>,
+        #[from(RO:PreAssert)]
+        pub(crate) pre_assertions: Vec<Assert>,
+        #[from(WO:PreWrite)]
+        pub(crate) pre_write: Option<TokenStream>,
+        #[from(WO:PostWrite)]
+        pub(crate) post_write: Option<TokenStream>,
+        #[from(RW:Tag)]
+        pub(crate) tag: Option<TokenStream>,
+        #[from(RW:Selector)]
+        pub(crate) selector: Option<TokenStream>,
+        #[from(RW:Match)]
+        pub(crate) selector_arms: Option<Vec<MatchArm>>,
+        #[from(WO:UnsafeAssumeActive)]
+        pub(crate) unsafe_assume_active: Option<SpannedValue<()>>,
+        pub(crate) fields: Vec<StructField>,
+        pub(crate) for_write: bool,
+    }
+}
+
+impl<const WRITE: bool> FromInput<UnionAttr<WRITE>> for Union {
+    type Field = StructField;
+
+    fn push_field(&mut self, field: Self::Field) -> syn::Result<()> {
+        self.fields.push(field);
+        Ok(())
+    }
+
+    fn set_options(&mut self, options: Options) {
+        self.for_write = options.write;
+    }
+
+    fn validate(&self, _: Options) -> syn::Result<()> {
+        if self.map.is_some() {
+            if let Some(field) = self.fields.iter().find(|field| !field.has_no_attrs()) {
+                return Err(syn::Error::new(
+                    field.field.span(),
+                    "cannot use attributes on fields inside a union with a union-level `map`",
+                ));
+            }
+
+            return Ok(());
+        }
+
+        validate_selector(
+            self.tag.as_ref(),
+            self.selector.as_ref(),
+            self.selector_arms.as_deref(),
+            "field",
+            |ident| self.fields.iter().any(|field| field.ident == *ident),
+        )?;
+
+        if let Some(AlignRelative { span, .. }) = self
+            .fields
+            .iter()
+            .filter_map(|field| field.align_relative.as_ref())
+            .find(|align_relative| align_relative.anchor.is_some())
+        {
+            return Err(syn::Error::new(
+                *span,
+                "`align_relative` cannot refer to a field in a union",
+            ));
+        }
+
+        if let Some(field) = self
+            .fields
+            .iter()
+            .find(|field| field.is_temp(self.for_write))
+        {
+            return Err(syn::Error::new(
+                field.field.span(),
+                "unions cannot have temporary fields",
+            ));
+        }
+
+        if self.tag.is_some() {
+            if let Some(field) = self.fields.iter().find(|field| field.tag.is_none()) {
+                return Err(syn::Error::new(
+                    field.ident.span(),
+                    "every field needs a `tag` when the union has a `tag`",
+                ));
+            }
+        } else if let Some(tag) = self.fields.iter().find_map(|field| field.tag.as_ref()) {
+            return Err(syn::Error::new(
+                tag.span(),
+                "`tag` on a field requires a `tag` on the union",
+            ));
+        } else if self.selector.is_none() {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "unions require either a `tag` or a `selector` to choose which field is active",
+            ));
+        }
+
+        // Writing reads the chosen field without knowing that it is active,
+        // so the user has to promise that it always is
+        if self.for_write && self.unsafe_assume_active.is_none() {
+            return Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "writing a union reads the field chosen by its `tag` or `selector` without checking that it is active; add `#[bw(unsafe_assume_active)]` to promise that it always is",
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_selector(
+    tag: Option<&TokenStream>,
+    selector: Option<&TokenStream>,
+    arms: Option<&[MatchArm]>,
+    kind: &str,
+    has_ident: impl Fn(&Ident) -> bool,
+) -> syn::Result<()> {
+    match (selector, arms) {
+        (Some(selector), Some(arms)) => {
+            if let Some(tag) = tag {
+                let tag_span = tag.span();
+                let span = tag_span.join(selector.span()).unwrap_or(tag_span);
+                return Err(syn::Error::new(
+                    span,
+                    "`tag` and `selector` are mutually exclusive",
+                ));
+            }
+
+            for arm in arms {
+                if !has_ident(&arm.ident) {
+                    return Err(syn::Error::new(
+                        arm.ident.span(),
+                        format!("no {} named `{}`", kind, arm.ident),
+                    ));
+                }
+            }
+
+            Ok(())
+        }
+        (Some(selector), None) => Err(syn::Error::new(
+            selector.span(),
+            format!("`selector` requires a `match` list of {kind}s"),
+        )),
+        (None, Some(arms)) => Err(syn::Error::new(
+            arms.first()
+                .map_or_else(proc_macro2::Span::call_site, |arm| arm.ident.span()),
+            "`match` requires a `selector`",
+        )),
+        (None, None) => Ok(()),
+    }
+}
+
+fn validate_single_fallback<'a>(
+    mut fallbacks: impl Iterator<Item = &'a SpannedValue<()>>,
+) -> syn::Result<()> {
+    fallbacks.nth(1).map_or(Ok(()), |fallback| {
+        Err(syn::Error::new(
+            fallback.span(),
+            "only one variant can be the `default` variant",
+        ))
+    })
+}
@@ -0,0 +1,31 @@
+use crate::{binrw::parser::keywords, meta_types::MetaOptionalValue};
+use proc_macro2::Span;
+use syn::Ident;
+
+/// The position that `align_before` and `align_after` align to when writing.
+#[derive(Debug, Clone)]
+pub(crate) struct AlignRelative {
+    /// The field whose position alignment is relative to, or `None` if
+    /// alignment is relative to the start of the struct.
+    pub(crate) anchor: Option<Ident>,
+    pub(crate) span: Span,
+}
+
+impl TryFrom<MetaOptionalValue<keywords::align_relative, syn::LitStr>> for AlignRelative {
+    type Error = syn::Error;
+
+    fn try_from(
+        value: MetaOptionalValue<keywords::align_relative, syn::LitStr>,
+    ) -> syn::Result<Self> {
+        match value.value {
+            Some(anchor) => Ok(Self {
+                span: anchor.span(),
+                anchor: Some(anchor.parse()?),
+            }),
+            None => Ok(Self {
+                anchor: None,
+                span: value.ident.span,
+            }),
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{
-    binrw::{codegen::sanitization::THIS, parser::attrs},
+    binrw::{
+        codegen::sanitization::{ASSERTION_FAILED_MESSAGE, THIS},
+        parser::{attrs, keywords},
+    },
     meta_types::KeywordToken,
 };
 use proc_macro2::{Ident, Span, TokenStream};
@@ -7,10 +10,15 @@ use quote::{quote, ToTokens};
 use syn::fold::Fold;
 use syn::{parse::Parse, spanned::Spanned, token::Token, Expr, ExprLit, Lit};
 
+// Lint: Renaming `Error` would be a larger change than it is worth
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone)]
 pub(crate) enum Error {
     Message(TokenStream),
     Error(TokenStream),
+    /// A closure which receives the asserted value and the stream position
+    /// and returns the error.
+    Closure(TokenStream),
 }
 
 #[derive(Debug, Clone)]
@@ -48,16 +56,26 @@ impl<K: Parse + Spanned + Token> TryFrom<attrs::AssertLike<K>> for Assert {
                 extern crate alloc;
                 alloc::format!(#message #(, #args)*)
             }),
+            Some(Expr::Closure(closure)) => {
+                super::assert_all_args_consumed(args, value.keyword_span())?;
+                if K::display() == <keywords::pre_assert as Token>::display() {
+                    return Err(Self::Error::new(
+                        closure.span(),
+                        format!(
+                            "{} cannot use a closure since there is no value to pass to it",
+                            value.dyn_display()
+                        ),
+                    ));
+                }
+                Error::Closure(closure.to_token_stream())
+            }
             Some(error) => {
                 super::assert_all_args_consumed(args, value.keyword_span())?;
                 Error::Error(error.to_token_stream())
             }
             None => Error::Message({
                 let condition = condition.to_token_stream().to_string();
-                quote! {
-                    extern crate alloc;
-                    alloc::format!("assertion failed: `{}`", #condition)
-                }
+                quote! { #ASSERTION_FAILED_MESSAGE(#condition) }
             }),
         };
 
@@ -0,0 +1,68 @@
+use super::size_of::parse_range;
+use crate::{binrw::parser::attrs, meta_types::KeywordToken};
+use proc_macro2::{Span, TokenStream};
+use quote::ToTokens;
+use syn::{spanned::Spanned, Expr, ExprAssign, ExprLit, Lit};
+
+/// The algorithm and the fields whose serialised bytes are checked by a
+/// `checksum` field.
+#[derive(Debug, Clone)]
+pub(crate) struct Checksum {
+    /// The function which computes the checksum from a byte slice.
+    pub(crate) algorithm: TokenStream,
+    /// The first field which is checked.
+    pub(crate) first: syn::Ident,
+    /// The last field which is checked.
+    pub(crate) last: syn::Ident,
+    pub(crate) span: Span,
+}
+
+impl TryFrom<attrs::Checksum> for Checksum {
+    type Error = syn::Error;
+
+    fn try_from(value: attrs::Checksum) -> syn::Result<Self> {
+        let kw_span = value.keyword_span();
+        let mut args = value.fields.into_iter();
+
+        let algorithm = args.next().ok_or_else(|| {
+            syn::Error::new(
+                kw_span,
+                "`checksum` requires an algorithm and an `over` range",
+            )
+        })?;
+
+        let over = match args.next() {
+            Some(Expr::Assign(ExprAssign { left, right, .. })) if matches!(&*left, Expr::Path(path) if path.path.is_ident("over")) => {
+                match *right {
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(over),
+                        ..
+                    }) => over,
+                    right => {
+                        return Err(syn::Error::new(
+                            right.span(),
+                            "expected a string literal containing a field name or range",
+                        ))
+                    }
+                }
+            }
+            Some(arg) => return Err(syn::Error::new(arg.span(), "expected `over = \"...\"`")),
+            None => {
+                return Err(syn::Error::new(
+                    kw_span,
+                    "`checksum` requires an `over` range",
+                ))
+            }
+        };
+
+        super::assert_all_args_consumed(args, kw_span)?;
+
+        let (first, last) = over.parse_with(parse_range)?;
+        Ok(Self {
+            algorithm: algorithm.into_token_stream(),
+            first,
+            last,
+            span: over.span(),
+        })
+    }
+}
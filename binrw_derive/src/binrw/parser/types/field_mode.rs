@@ -1,14 +1,17 @@
 use crate::{
-    binrw::parser::{attrs, TrySet},
+    binrw::{
+        codegen::sanitization::{ARGS, ARGS_ITER, OPT, PARSE_FN_TYPE_HINT, READER},
+        parser::{attrs, TrySet},
+    },
     meta_types::KeywordToken,
 };
 use proc_macro2::TokenStream;
-use quote::ToTokens;
+use quote::{quote, ToTokens};
 
 #[derive(Clone, Debug)]
 pub(crate) enum FieldMode {
     Normal,
-    Default,
+    Default(Option<TokenStream>),
     Calc(TokenStream),
     TryCalc(TokenStream),
     Function(TokenStream),
@@ -21,14 +24,14 @@ impl Default for FieldMode {
 }
 
 impl From<attrs::Ignore> for FieldMode {
-    fn from(_: attrs::Ignore) -> Self {
-        Self::Default
+    fn from(ignore: attrs::Ignore) -> Self {
+        Self::Default(ignore.value.map(ToTokens::into_token_stream))
     }
 }
 
-impl From<attrs::Default> for FieldMode {
-    fn from(_: attrs::Default) -> Self {
-        Self::Default
+impl From<attrs::DefaultValue> for FieldMode {
+    fn from(default: attrs::DefaultValue) -> Self {
+        Self::Default(default.value.map(ToTokens::into_token_stream))
     }
 }
 
@@ -50,6 +53,33 @@ impl From<attrs::ParseWith> for FieldMode {
     }
 }
 
+impl From<attrs::ArgsIter> for FieldMode {
+    fn from(args_iter: attrs::ArgsIter) -> Self {
+        let args_iter = args_iter.into_token_stream();
+        Self::Function(quote! { #ARGS_ITER(#args_iter) })
+    }
+}
+
+impl From<attrs::Switch> for FieldMode {
+    fn from(switch: attrs::Switch) -> Self {
+        let selector = switch.selector;
+        let arms = switch.arms.into_iter().map(|arm| {
+            let pat = arm.pat.into_iter();
+            let guard = arm.guard.map(|guard| quote! { if #guard });
+            let parser = arm.expr;
+            quote! {
+                #(#pat)|* #guard => #PARSE_FN_TYPE_HINT(#parser)(#READER, #OPT, #ARGS)
+            }
+        });
+
+        Self::Function(quote! {
+            |#READER, #OPT, #ARGS| match #selector {
+                #(#arms,)*
+            }
+        })
+    }
+}
+
 impl From<attrs::WriteWith> for FieldMode {
     fn from(write_with: attrs::WriteWith) -> Self {
         Self::Function(write_with.into_token_stream())
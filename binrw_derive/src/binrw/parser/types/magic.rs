@@ -2,21 +2,28 @@ use super::SpannedValue;
 use crate::{binrw::parser::attrs, meta_types::KeywordToken};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens};
-use syn::Lit;
+use syn::{Expr, ExprLit, Lit};
 
 #[derive(PartialEq, Eq, Hash, Clone, Debug, PartialOrd, Ord)]
 pub(crate) enum Kind {
     Numeric(String),
     ByteStr(String),
+    /// A magic value computed at runtime from an arbitrary expression. The
+    /// type of the value is inferred from the expression, so it is not known
+    /// to the macro.
+    Expr,
 }
 
-impl From<&Kind> for TokenStream {
-    fn from(kind: &Kind) -> Self {
-        match kind {
+impl Kind {
+    /// Returns the type of the magic value, if it is known at macro expansion
+    /// time.
+    pub(crate) fn ty(&self) -> Option<TokenStream> {
+        match self {
             Kind::ByteStr(ty) | Kind::Numeric(ty) => {
                 let ty: TokenStream = ty.parse().unwrap();
-                quote! { #ty }
+                Some(quote! { #ty })
             }
+            Kind::Expr => None,
         }
     }
 }
@@ -30,7 +37,7 @@ impl Inner {
     pub(crate) fn add_ref(&self) -> TokenStream {
         match &self.0 {
             Kind::ByteStr(_) => quote! { & },
-            Kind::Numeric(_) => TokenStream::new(),
+            Kind::Numeric(_) | Kind::Expr => TokenStream::new(),
         }
     }
 
@@ -40,10 +47,24 @@ impl Inner {
                 let value = &self.1;
                 quote! { *#value }
             }
-            Kind::Numeric(_) => self.1.clone(),
+            Kind::Numeric(_) | Kind::Expr => self.1.clone(),
         }
     }
 
+    /// Returns a reference to the magic value suitable for passing to a
+    /// writer.
+    pub(crate) fn ref_value(&self) -> TokenStream {
+        let value = &self.1;
+        match self.0 {
+            Kind::Expr => quote! { &(#value) },
+            Kind::ByteStr(_) | Kind::Numeric(_) => quote! { &#value },
+        }
+    }
+
+    pub(crate) fn is_expr(&self) -> bool {
+        matches!(self.0, Kind::Expr)
+    }
+
     pub(crate) fn kind(&self) -> &Kind {
         &self.0
     }
@@ -62,7 +83,15 @@ impl TryFrom<attrs::Magic> for SpannedValue<Inner> {
     type Error = syn::Error;
 
     fn try_from(magic: attrs::Magic) -> Result<Self, Self::Error> {
-        let value = &magic.value;
+        let value = match &magic.value {
+            Expr::Lit(ExprLit { lit, .. }) => lit,
+            expr => {
+                return Ok(Self::new(
+                    Inner(Kind::Expr, expr.to_token_stream()),
+                    magic.keyword_span(),
+                ))
+            }
+        };
 
         let kind = match &value {
             Lit::ByteStr(bytes) => Kind::ByteStr(format!("[u8; {}]", bytes.value().len())),
@@ -1,4 +1,6 @@
+mod align_relative;
 mod assert;
+mod checksum;
 mod cond_endian;
 mod condition;
 mod enum_error_mode;
@@ -7,19 +9,27 @@ mod field_mode;
 mod imports;
 mod magic;
 mod map;
+mod offset_of;
+mod pad_size_overflow;
 mod passed_args;
+mod size_of;
 mod spanned_value;
 
+pub(crate) use align_relative::AlignRelative;
 pub(crate) use assert::{Assert, Error as AssertionError};
+pub(crate) use checksum::Checksum;
 pub(crate) use cond_endian::CondEndian;
 pub(crate) use condition::Condition;
 pub(crate) use enum_error_mode::EnumErrorMode;
 pub(crate) use err_context::ErrContext;
 pub(crate) use field_mode::FieldMode;
 pub(crate) use imports::Imports;
-pub(crate) use magic::Magic;
+pub(crate) use magic::{Kind as MagicKind, Magic};
 pub(crate) use map::Map;
+pub(crate) use offset_of::OffsetOf;
+pub(crate) use pad_size_overflow::{PadSizeOverflow, PadSizeOverflowMode};
 pub(crate) use passed_args::PassedArgs;
+pub(crate) use size_of::SizeOf;
 pub(crate) use spanned_value::SpannedValue;
 
 fn assert_all_args_consumed<Iter, IterItem>(
@@ -0,0 +1,39 @@
+use crate::{binrw::parser::keywords, meta_types::MetaValue};
+use proc_macro2::Span;
+use syn::{parse::ParseStream, Ident, Token};
+
+/// The field whose position is written into an `offset_of` field.
+#[derive(Debug, Clone)]
+pub(crate) struct OffsetOf {
+    /// The field the position is relative to, or `None` if the position is
+    /// absolute.
+    pub(crate) base: Option<Ident>,
+    /// The field whose position is written.
+    pub(crate) target: Ident,
+    pub(crate) span: Span,
+}
+
+impl TryFrom<MetaValue<keywords::offset_of, syn::LitStr>> for OffsetOf {
+    type Error = syn::Error;
+
+    fn try_from(value: MetaValue<keywords::offset_of, syn::LitStr>) -> syn::Result<Self> {
+        let span = value.value.span();
+        value.value.parse_with(|input: ParseStream<'_>| {
+            let first = input.parse::<Ident>()?;
+            if input.is_empty() {
+                Ok(Self {
+                    base: None,
+                    target: first,
+                    span,
+                })
+            } else {
+                input.parse::<Token![..]>()?;
+                Ok(Self {
+                    base: Some(first),
+                    target: input.parse::<Ident>()?,
+                    span,
+                })
+            }
+        })
+    }
+}
@@ -0,0 +1,41 @@
+use crate::binrw::parser::attrs;
+use proc_macro2::Span;
+
+/// What to do when a field written with `pad_size_to` is larger than the
+/// padded size.
+#[derive(Debug, Clone)]
+pub(crate) struct PadSizeOverflow {
+    pub(crate) mode: PadSizeOverflowMode,
+    pub(crate) span: Span,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum PadSizeOverflowMode {
+    /// The whole field is written, making it larger than the padded size.
+    Grow,
+    /// An error is returned.
+    Error,
+    /// Bytes past the padded size are discarded.
+    Truncate,
+}
+
+impl TryFrom<attrs::PadSizeOverflow> for PadSizeOverflow {
+    type Error = syn::Error;
+
+    fn try_from(value: attrs::PadSizeOverflow) -> syn::Result<Self> {
+        let span = value.value.span();
+        let mode = match value.value.to_string().as_str() {
+            "grow" => PadSizeOverflowMode::Grow,
+            "error" => PadSizeOverflowMode::Error,
+            "truncate" => PadSizeOverflowMode::Truncate,
+            _ => {
+                return Err(syn::Error::new(
+                    span,
+                    "expected one of: `grow`, `error`, `truncate`",
+                ))
+            }
+        };
+
+        Ok(Self { mode, span })
+    }
+}
@@ -0,0 +1,36 @@
+use crate::{binrw::parser::keywords, meta_types::MetaValue};
+use proc_macro2::Span;
+use syn::{parse::ParseStream, Ident, Token};
+
+/// The fields whose combined serialised size is written into a `size_of`
+/// field.
+#[derive(Debug, Clone)]
+pub(crate) struct SizeOf {
+    /// The first field which is measured.
+    pub(crate) first: Ident,
+    /// The last field which is measured.
+    pub(crate) last: Ident,
+    pub(crate) span: Span,
+}
+
+impl TryFrom<MetaValue<keywords::size_of, syn::LitStr>> for SizeOf {
+    type Error = syn::Error;
+
+    fn try_from(value: MetaValue<keywords::size_of, syn::LitStr>) -> syn::Result<Self> {
+        let span = value.value.span();
+        let (first, last) = value.value.parse_with(parse_range)?;
+        Ok(Self { first, last, span })
+    }
+}
+
+/// Parses a single field name or an inclusive range of field names.
+pub(super) fn parse_range(input: ParseStream<'_>) -> syn::Result<(Ident, Ident)> {
+    let first = input.parse::<Ident>()?;
+    let last = if input.is_empty() {
+        first.clone()
+    } else {
+        input.parse::<Token![..=]>()?;
+        input.parse::<Ident>()?
+    };
+    Ok((first, last))
+}
@@ -6,7 +6,7 @@ use syn::{
     punctuated::Punctuated,
     spanned::Spanned,
     token::{self, Token},
-    Expr, Ident, Lit, Token, Type,
+    Expr, Ident, Token, Type,
 };
 
 pub(crate) trait KeywordToken {
@@ -46,13 +46,6 @@ pub(crate) type MetaType<Keyword> = MetaValue<Keyword, Type>;
 /// both are always allowed
 pub(crate) type MetaIdent<Keyword> = MetaValue<Keyword, Ident>;
 
-/// `MetaLit` represents a key/lit pair
-/// Takes two forms:
-/// * ident(lit)
-/// * ident = lit
-/// both are always allowed
-pub(crate) type MetaLit<Keyword> = MetaValue<Keyword, Lit>;
-
 #[derive(Debug, Clone)]
 pub(crate) struct MetaValue<Keyword, Value> {
     pub(crate) ident: Keyword,
@@ -89,12 +82,30 @@ impl<Keyword, Value: ToTokens> From<MetaValue<Keyword, Value>> for TokenStream {
     }
 }
 
+impl<Keyword> TryFrom<MetaValue<Keyword, syn::LitStr>> for Vec<syn::WherePredicate> {
+    type Error = syn::Error;
+
+    fn try_from(value: MetaValue<Keyword, syn::LitStr>) -> syn::Result<Self> {
+        Ok(value
+            .value
+            .parse_with(Punctuated::<syn::WherePredicate, Token![,]>::parse_terminated)?
+            .into_iter()
+            .collect())
+    }
+}
+
 impl<Keyword> From<MetaValue<Keyword, Ident>> for Ident {
     fn from(value: MetaValue<Keyword, Ident>) -> Self {
         value.value
     }
 }
 
+impl<Keyword> From<MetaValue<Keyword, syn::Lifetime>> for syn::Lifetime {
+    fn from(value: MetaValue<Keyword, syn::Lifetime>) -> Self {
+        value.value
+    }
+}
+
 impl<Keyword, Value: ToTokens> ToTokens for MetaValue<Keyword, Value> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         self.value.to_tokens(tokens);
@@ -109,6 +120,51 @@ impl<Keyword: Token + Spanned, Value> KeywordToken for MetaValue<Keyword, Value>
     }
 }
 
+/// `MetaOptionalExpr` represents a key with an optional expr
+/// Takes three forms:
+/// * ident
+/// * ident(expr)
+/// * ident = expr
+pub(crate) type MetaOptionalExpr<Keyword> = MetaOptionalValue<Keyword, Expr>;
+
+#[derive(Debug, Clone)]
+pub(crate) struct MetaOptionalValue<Keyword, Value> {
+    pub(crate) ident: Keyword,
+    pub(crate) value: Option<Value>,
+}
+
+impl<Keyword: Parse, Value: Parse> Parse for MetaOptionalValue<Keyword, Value> {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        let value = if input.peek(token::Paren) {
+            let content;
+            parenthesized!(content in input);
+            Some(content.parse()?)
+        } else if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(MetaOptionalValue { ident, value })
+    }
+}
+
+impl<Keyword, Value: ToTokens> From<MetaOptionalValue<Keyword, Value>> for Option<TokenStream> {
+    fn from(value: MetaOptionalValue<Keyword, Value>) -> Self {
+        value.value.map(ToTokens::into_token_stream)
+    }
+}
+
+impl<Keyword: Token + Spanned, Value> KeywordToken for MetaOptionalValue<Keyword, Value> {
+    type Token = Keyword;
+
+    fn keyword_span(&self) -> Span {
+        self.ident.span()
+    }
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct MetaVoid<Keyword> {
     pub(crate) ident: Keyword,
@@ -254,6 +310,100 @@ impl Parse for IdentTypeMaybeDefault {
     }
 }
 
+// This is like `syn::Arm` except:
+// (1) Implements `Parse`;
+// (2) No attributes;
+// (3) Only allows an ident on the RHS instead of any `syn::Expr`.
+#[derive(Debug, Clone)]
+pub(crate) struct MatchArm {
+    pub(crate) pat: Punctuated<syn::Pat, Token![|]>,
+    pub(crate) guard: Option<Box<syn::Expr>>,
+    pub(crate) ident: syn::Ident,
+}
+
+impl Parse for MatchArm {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        input.parse::<Option<Token![|]>>()?;
+        let pat = Punctuated::parse_separated_nonempty(input)?;
+        let guard = if input.peek(Token![if]) {
+            input.parse::<Token![if]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        input.parse::<Token![=>]>()?;
+        let ident = input.parse()?;
+        Ok(Self { pat, guard, ident })
+    }
+}
+
+impl<Keyword> From<MetaList<Keyword, MatchArm>> for Vec<MatchArm> {
+    fn from(value: MetaList<Keyword, MatchArm>) -> Self {
+        value.fields.into_iter().collect()
+    }
+}
+
+/// `MetaSwitch` represents a keyword followed by a parenthesised selector
+/// expression and a list of arms:
+/// * ident(expr, pat => expr, ...)
+#[derive(Debug, Clone)]
+pub(crate) struct MetaSwitch<Keyword> {
+    pub(crate) ident: Keyword,
+    pub(crate) selector: Expr,
+    pub(crate) arms: Fields<SwitchArm>,
+}
+
+impl<Keyword: Parse> Parse for MetaSwitch<Keyword> {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let ident = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+        let selector = content.parse()?;
+        content.parse::<Token![,]>()?;
+        let arms = Fields::parse_terminated(&content)?;
+        if arms.is_empty() {
+            return Err(content.error("expected at least one arm"));
+        }
+        Ok(Self {
+            ident,
+            selector,
+            arms,
+        })
+    }
+}
+
+impl<Keyword: Token + Spanned> KeywordToken for MetaSwitch<Keyword> {
+    type Token = Keyword;
+
+    fn keyword_span(&self) -> Span {
+        self.ident.span()
+    }
+}
+
+// This is like `MatchArm` except that it allows any `syn::Expr` on the RHS.
+#[derive(Debug, Clone)]
+pub(crate) struct SwitchArm {
+    pub(crate) pat: Punctuated<syn::Pat, Token![|]>,
+    pub(crate) guard: Option<Box<syn::Expr>>,
+    pub(crate) expr: syn::Expr,
+}
+
+impl Parse for SwitchArm {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        input.parse::<Option<Token![|]>>()?;
+        let pat = Punctuated::parse_separated_nonempty(input)?;
+        let guard = if input.peek(Token![if]) {
+            input.parse::<Token![if]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        input.parse::<Token![=>]>()?;
+        let expr = input.parse()?;
+        Ok(Self { pat, guard, expr })
+    }
+}
+
 pub(crate) struct MetaAttrList<P>(Fields<P>);
 
 impl<P> MetaAttrList<P> {
@@ -273,17 +423,21 @@ impl<P: Parse> Parse for MetaAttrList<P> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use syn::Lit;
 
     mod kw {
         syn::custom_keyword!(test);
         syn::custom_keyword!(test_list);
         syn::custom_keyword!(test_enclosed_list);
+        syn::custom_keyword!(test_switch);
     }
 
     type MetaValueTest = MetaValue<kw::test, Lit>;
     type MetaListTest = MetaList<kw::test_list, Lit>;
+    type MetaOptionalValueTest = MetaOptionalValue<kw::test, Lit>;
     type MetaAttrListTest = MetaAttrList<Lit>;
     type MetaEnclosedListTest = MetaEnclosedList<kw::test_enclosed_list, Lit, Lit>;
+    type MetaSwitchTest = MetaSwitch<kw::test_switch>;
 
     macro_rules! try_parse {
         ($name:ident, $ty:ty, $tt:tt) => {
@@ -454,6 +608,38 @@ mod tests {
     try_parse_fail!(ident_type_missing_colon, "expected `:`", IdentTypeMaybeDefault, { foo u8 });
     try_parse_fail!(ident_type_missing_ident, "expected identifier", IdentTypeMaybeDefault, { :u8 });
 
+    try_parse!(match_arm, MatchArm, { 0 => Foo });
+    try_parse!(match_arm_multiple, MatchArm, { | 0 | 2..=5 => Foo });
+    try_parse!(match_arm_guard, MatchArm, { x if x > 5 => Foo });
+    try_parse_fail!(match_arm_missing_arrow, "expected `=>`", MatchArm, { 0 Foo });
+    try_parse_fail!(match_arm_not_ident, "expected identifier", MatchArm, { 0 => 1 });
+
+    try_parse!(switch_arm, SwitchArm, { 0 | 1 => parse_a });
+    try_parse!(switch_arm_guard, SwitchArm, { x if x > 5 => helpers::until_eof });
+    try_parse_fail!(switch_arm_missing_arrow, "expected `=>`", SwitchArm, { 0 parse_a });
+
+    try_parse!(meta_switch, MetaSwitchTest, { test_switch(mode, 0 => parse_a, _ => parse_b) });
+    try_parse_fail!(
+        meta_switch_no_arms,
+        "expected at least one arm",
+        MetaSwitchTest,
+        { test_switch(mode,) }
+    );
+
+    try_parse!(meta_optional_value_void, MetaOptionalValueTest, { test });
+    try_parse!(meta_optional_value_assign, MetaOptionalValueTest, {
+        test = 3u8
+    });
+    try_parse!(meta_optional_value_paren, MetaOptionalValueTest, {
+        test(3u8)
+    });
+    try_parse_fail!(
+        meta_optional_value_missing_keyword,
+        "expected `test`",
+        MetaOptionalValueTest,
+        { = 3u8 }
+    );
+
     try_parse!(meta_attr_list, MetaAttrListTest, { (1u8, 2u8, 3u8) });
     try_parse!(meta_attr_list_empty, MetaAttrListTest, { () });
     try_parse_fail!(
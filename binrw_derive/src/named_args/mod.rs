@@ -23,7 +23,7 @@ pub(crate) fn derive_from_imports(
     is_write: bool,
     result_name: &Ident,
     vis: &Visibility,
-    lifetime: Option<syn::Lifetime>,
+    generics: impl Iterator<Item = syn::GenericParam>,
     args: impl Iterator<Item = IdentTypeMaybeDefault>,
 ) -> TokenStream {
     let builder_name = &if is_write {
@@ -38,10 +38,7 @@ pub(crate) fn derive_from_imports(
         builder_name,
         result_name,
         fields: &args.map(Into::into).collect::<Vec<_>>(),
-        generics: lifetime
-            .map(|lifetime| [syn::GenericParam::Lifetime(syn::LifetimeDef::new(lifetime))])
-            .as_ref()
-            .map_or(&[], |generics| generics.as_slice()),
+        generics: &generics.collect::<Vec<_>>(),
         vis,
     }
     .generate(true)
@@ -23,6 +23,32 @@ macro_rules! from_crate {
 }
 pub(crate) use from_crate;
 
+/// Returns true if the given identifier appears anywhere in the token stream,
+/// other than as the name of a field or method accessed with `.`.
+pub(crate) fn contains_ident(tokens: TokenStream, ident: &Ident) -> bool {
+    use proc_macro2::{Spacing, TokenTree};
+
+    // A `.` which is not part of a `..` range operator
+    let mut after_dot = false;
+    let mut last_punct = None;
+    tokens.into_iter().any(|token| {
+        let found = match &token {
+            TokenTree::Ident(token) => !after_dot && token == ident,
+            TokenTree::Group(group) => contains_ident(group.stream(), ident),
+            TokenTree::Punct(_) | TokenTree::Literal(_) => false,
+        };
+        after_dot = matches!(&token, TokenTree::Punct(punct)
+            if punct.as_char() == '.'
+                && punct.spacing() == Spacing::Alone
+                && last_punct != Some(('.', Spacing::Joint)));
+        last_punct = match &token {
+            TokenTree::Punct(punct) => Some((punct.as_char(), punct.spacing())),
+            _ => None,
+        };
+        found
+    })
+}
+
 pub(crate) trait ToSpannedTokens {
     fn to_spanned_tokens(&self, tokens: &mut TokenStream, span: Span);
 }